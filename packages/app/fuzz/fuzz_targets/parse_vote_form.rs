@@ -0,0 +1,29 @@
+#![no_main]
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use bytes::Bytes;
+use hyperchad::router::{RequestInfo, RouteRequest};
+use libfuzzer_sys::fuzz_target;
+use switchy::http::models::Method;
+
+fuzz_target!(|data: &[u8]| {
+    let mut headers = BTreeMap::new();
+    headers.insert(
+        "content-type".to_string(),
+        "multipart/form-data; boundary=fuzz".to_string(),
+    );
+
+    let req = RouteRequest {
+        path: "/api/games/00000000-0000-0000-0000-000000000000/vote".to_string(),
+        method: Method::Post,
+        query: BTreeMap::new(),
+        headers,
+        cookies: BTreeMap::new(),
+        info: RequestInfo::default(),
+        body: Some(Arc::new(Bytes::copy_from_slice(data))),
+    };
+
+    // Any input must produce either a parsed form or a RouteError, never a panic
+    let _ = planning_poker_app::parse_vote_form(&req);
+});
@@ -0,0 +1,251 @@
+//! Coalescing of the partial updates pushed to a game's clients
+//!
+//! A burst of votes used to re-render the same targets once per vote. Request
+//! handlers now mark which targets changed, and a task per game renders each
+//! of them once when its window closes, from the data as it is by then.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use uuid::Uuid;
+
+/// How long a game's changes are gathered before they are rendered
+pub const FLUSH_INTERVAL: Duration = Duration::from_millis(150);
+
+/// A part of the game page that is re-rendered as a whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UpdateTarget {
+    PlayersList,
+    VoteResults,
+    GameActions,
+}
+
+type FlushFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type Flush = Arc<dyn Fn(Uuid, Vec<UpdateTarget>) -> FlushFuture + Send + Sync>;
+type Pending = Arc<Mutex<HashMap<Uuid, BTreeSet<UpdateTarget>>>>;
+
+/// Dirty targets per game, rendered by `flush` at most once per `interval`
+///
+/// A static coalescer is never dropped, so [`flush_all`](Self::flush_all) has
+/// to be awaited on the way out for the last changes to be rendered. Dropping
+/// one only starts the renders of what's pending without waiting for them.
+pub struct UpdateCoalescer {
+    interval: Duration,
+    pending: Pending,
+    flush: Flush,
+}
+
+impl UpdateCoalescer {
+    /// A coalescer that gathers each game's changes for `interval` before
+    /// handing them to `flush`
+    pub fn new<F, Fut>(interval: Duration, flush: F) -> Self
+    where
+        F: Fn(Uuid, Vec<UpdateTarget>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            interval,
+            pending: Arc::default(),
+            flush: Arc::new(move |game_id, targets| Box::pin(flush(game_id, targets))),
+        }
+    }
+
+    /// Marks `targets` of the game as changed, opening a window that renders
+    /// them once it closes if none is open yet
+    pub fn mark_dirty(&self, game_id: Uuid, targets: &[UpdateTarget]) {
+        let opens_window = {
+            let mut pending = lock(&self.pending);
+            let window_open = pending.contains_key(&game_id);
+            pending
+                .entry(game_id)
+                .or_default()
+                .extend(targets.iter().copied());
+            !window_open
+        };
+
+        if opens_window {
+            let pending = self.pending.clone();
+            let flush = self.flush.clone();
+            let interval = self.interval;
+            switchy::unsync::task::spawn(async move {
+                switchy::unsync::time::sleep(interval).await;
+                if let Some(targets) = take(&pending, game_id) {
+                    flush(game_id, targets).await;
+                }
+            });
+        }
+    }
+
+    /// Renders whatever is pending for the game now, for events like a kick
+    /// that shouldn't wait for the window to close
+    pub async fn flush_now(&self, game_id: Uuid) {
+        if let Some(targets) = take(&self.pending, game_id) {
+            (self.flush)(game_id, targets).await;
+        }
+    }
+
+    /// Renders whatever is pending for every game and waits for the renders
+    /// to finish, so nothing is lost when the app shuts down
+    pub async fn flush_all(&self) {
+        let pending = std::mem::take(&mut *lock(&self.pending));
+        for (game_id, targets) in pending {
+            (self.flush)(game_id, targets.into_iter().collect()).await;
+        }
+    }
+
+    /// Forgets what is pending for the game, for callers that are about to
+    /// re-render every target themselves
+    pub fn discard(&self, game_id: Uuid) {
+        lock(&self.pending).remove(&game_id);
+    }
+}
+
+impl Drop for UpdateCoalescer {
+    fn drop(&mut self) {
+        let pending = std::mem::take(&mut *lock(&self.pending));
+        for (game_id, targets) in pending {
+            switchy::unsync::task::spawn((self.flush)(game_id, targets.into_iter().collect()));
+        }
+    }
+}
+
+fn lock(pending: &Pending) -> MutexGuard<'_, HashMap<Uuid, BTreeSet<UpdateTarget>>> {
+    pending.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn take(pending: &Pending, game_id: Uuid) -> Option<Vec<UpdateTarget>> {
+    lock(pending)
+        .remove(&game_id)
+        .map(|targets| targets.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Renders = Arc<Mutex<Vec<(Uuid, UpdateTarget)>>>;
+
+    fn recording_coalescer(interval: Duration) -> (UpdateCoalescer, Renders) {
+        let renders = Renders::default();
+        let recorded = renders.clone();
+        let coalescer = UpdateCoalescer::new(interval, move |game_id, targets| {
+            let recorded = recorded.clone();
+            async move {
+                let mut recorded = recorded.lock().unwrap();
+                recorded.extend(targets.into_iter().map(|target| (game_id, target)));
+            }
+        });
+        (coalescer, renders)
+    }
+
+    fn render_count(renders: &Renders, target: UpdateTarget) -> usize {
+        renders
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, rendered)| *rendered == target)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_vote_burst_renders_results_far_fewer_times_than_votes() {
+        const VOTES: usize = 8;
+        let (coalescer, renders) = recording_coalescer(FLUSH_INTERVAL);
+        let game_id = Uuid::new_v4();
+
+        for _ in 0..VOTES {
+            coalescer.mark_dirty(
+                game_id,
+                &[UpdateTarget::VoteResults, UpdateTarget::GameActions],
+            );
+            switchy::unsync::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(render_count(&renders, UpdateTarget::VoteResults), 0);
+
+        switchy::unsync::time::sleep(FLUSH_INTERVAL * 2).await;
+
+        let results_renders = render_count(&renders, UpdateTarget::VoteResults);
+        assert_eq!(
+            results_renders, 1,
+            "{VOTES} votes rendered {results_renders} times"
+        );
+        assert_eq!(render_count(&renders, UpdateTarget::GameActions), 1);
+        assert_eq!(render_count(&renders, UpdateTarget::PlayersList), 0);
+    }
+
+    #[tokio::test]
+    async fn test_flush_now_renders_pending_targets_without_waiting() {
+        let (coalescer, renders) = recording_coalescer(Duration::from_millis(20));
+        let (game_id, other_game_id) = (Uuid::new_v4(), Uuid::new_v4());
+        coalescer.mark_dirty(game_id, &[UpdateTarget::PlayersList]);
+        coalescer.mark_dirty(other_game_id, &[UpdateTarget::VoteResults]);
+
+        coalescer.flush_now(game_id).await;
+        assert_eq!(
+            *renders.lock().unwrap(),
+            [(game_id, UpdateTarget::PlayersList)]
+        );
+
+        // The window closing later doesn't render the flushed game again
+        switchy::unsync::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(
+            *renders.lock().unwrap(),
+            [
+                (game_id, UpdateTarget::PlayersList),
+                (other_game_id, UpdateTarget::VoteResults),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_all_renders_every_game_before_returning() {
+        let (coalescer, renders) = recording_coalescer(FLUSH_INTERVAL * 1000);
+        let (game_id, other_game_id) = (Uuid::new_v4(), Uuid::new_v4());
+        coalescer.mark_dirty(game_id, &[UpdateTarget::PlayersList]);
+        coalescer.mark_dirty(other_game_id, &[UpdateTarget::VoteResults]);
+
+        coalescer.flush_all().await;
+
+        let mut rendered = renders.lock().unwrap().clone();
+        rendered.sort();
+        let mut expected = vec![
+            (game_id, UpdateTarget::PlayersList),
+            (other_game_id, UpdateTarget::VoteResults),
+        ];
+        expected.sort();
+        assert_eq!(rendered, expected);
+    }
+
+    #[tokio::test]
+    async fn test_dropping_flushes_what_is_still_pending() {
+        let (coalescer, renders) = recording_coalescer(FLUSH_INTERVAL * 1000);
+        let game_id = Uuid::new_v4();
+        coalescer.mark_dirty(game_id, &[UpdateTarget::VoteResults]);
+
+        drop(coalescer);
+        switchy::unsync::time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(
+            *renders.lock().unwrap(),
+            [(game_id, UpdateTarget::VoteResults)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_discarded_targets_are_not_rendered() {
+        let (coalescer, renders) = recording_coalescer(Duration::from_millis(20));
+        let game_id = Uuid::new_v4();
+        coalescer.mark_dirty(game_id, &[UpdateTarget::VoteResults]);
+
+        coalescer.discard(game_id);
+        drop(coalescer);
+        switchy::unsync::time::sleep(Duration::from_millis(60)).await;
+
+        assert!(renders.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,268 @@
+//! The game and action named by an `/api/games/{id}/...` path
+//!
+//! Routes used to split the path by hand and pick out its fourth segment,
+//! which grabbed the wrong one as soon as the mount path moved and let an
+//! unknown action fall through to reading the game.
+
+use uuid::Uuid;
+
+use crate::RouteError;
+
+/// Where the game API is mounted
+pub const GAMES_API_PREFIX: &str = "/api/games/";
+
+/// What a request does to the game its path names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameAction {
+    Join,
+    Vote,
+    Reveal,
+    StartVoting,
+    Reset,
+    Revote,
+    Archive,
+    Complete,
+    Story,
+    ImportStories,
+    /// `/stories/batch`, a JSON list of stories
+    BatchStories,
+    Finalize,
+    TransferOwnership,
+    /// `/votes/{player_id}/discard`
+    DiscardVote(Uuid),
+    /// `/players/{player_id}/role`
+    SetPlayerRole(Uuid),
+    /// `/players/{player_id}/kick`, or `/kick` with the player in the form
+    Kick(Option<Uuid>),
+    /// `/players/{player_id}`
+    UpdatePlayer(Uuid),
+    Leave,
+    Export,
+    Stats,
+    Rounds,
+    AuditLog,
+}
+
+/// A parsed `/api/games/{id}` path, with the action after the id if any
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GamePath {
+    pub game_id: Uuid,
+    pub action: Option<GameAction>,
+}
+
+impl GamePath {
+    /// Read the game id and action from a request path
+    ///
+    /// # Errors
+    ///
+    /// * If the path isn't under `/api/games/` or names an unknown action
+    /// * If the game or player id is not a valid UUID
+    pub fn parse(path: &str) -> Result<Self, RouteError> {
+        let rest = path
+            .strip_prefix(GAMES_API_PREFIX)
+            .ok_or_else(|| RouteError::NotFound("Page not found".to_string()))?;
+        let mut segments = rest.trim_end_matches('/').split('/');
+        let game_id = Uuid::parse_str(segments.next().unwrap_or_default())?;
+        let player_id = |id: &str| Uuid::parse_str(id).map_err(RouteError::from);
+
+        let action = match segments.collect::<Vec<_>>().as_slice() {
+            [] => None,
+            ["join"] => Some(GameAction::Join),
+            ["vote"] => Some(GameAction::Vote),
+            ["reveal"] => Some(GameAction::Reveal),
+            ["start-voting"] => Some(GameAction::StartVoting),
+            ["reset"] => Some(GameAction::Reset),
+            ["revote"] => Some(GameAction::Revote),
+            ["archive"] => Some(GameAction::Archive),
+            ["complete"] => Some(GameAction::Complete),
+            ["story"] => Some(GameAction::Story),
+            ["stories", "import"] => Some(GameAction::ImportStories),
+            ["stories", "batch"] => Some(GameAction::BatchStories),
+            ["finalize"] => Some(GameAction::Finalize),
+            ["transfer-ownership"] => Some(GameAction::TransferOwnership),
+            ["votes", id, "discard"] => Some(GameAction::DiscardVote(player_id(id)?)),
+            ["players", id, "role"] => Some(GameAction::SetPlayerRole(player_id(id)?)),
+            ["players", id, "kick"] => Some(GameAction::Kick(Some(player_id(id)?))),
+            ["kick"] => Some(GameAction::Kick(None)),
+            ["players", id] => Some(GameAction::UpdatePlayer(player_id(id)?)),
+            ["leave"] => Some(GameAction::Leave),
+            ["export"] => Some(GameAction::Export),
+            ["stats"] => Some(GameAction::Stats),
+            ["rounds"] => Some(GameAction::Rounds),
+            ["audit-log"] => Some(GameAction::AuditLog),
+            action => {
+                return Err(RouteError::NotFound(format!(
+                    "Unknown game action '{}'",
+                    action.join("/")
+                )))
+            }
+        };
+
+        Ok(Self { game_id, action })
+    }
+
+    /// The methods the path answers to, as listed in an `Allow` header
+    ///
+    /// Reading a game or one of its reports is a `GET`, editing a player a
+    /// `PATCH` and every other action a `POST`.
+    #[must_use]
+    pub const fn allowed_methods(&self) -> &'static str {
+        match self.action {
+            None
+            | Some(
+                GameAction::Export | GameAction::Stats | GameAction::Rounds | GameAction::AuditLog,
+            ) => "GET",
+            Some(GameAction::UpdatePlayer(_)) => "PATCH",
+            Some(_) => "POST",
+        }
+    }
+
+    /// The player the path names, for actions on one player
+    #[must_use]
+    pub const fn player_id(&self) -> Option<Uuid> {
+        match self.action {
+            Some(
+                GameAction::DiscardVote(player_id)
+                | GameAction::SetPlayerRole(player_id)
+                | GameAction::Kick(Some(player_id))
+                | GameAction::UpdatePlayer(player_id),
+            ) => Some(player_id),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_action_is_parsed() {
+        let game_id = Uuid::new_v4();
+        let player_id = Uuid::new_v4();
+        let cases = [
+            (String::new(), None),
+            ("/".to_string(), None),
+            ("/join".to_string(), Some(GameAction::Join)),
+            ("/vote".to_string(), Some(GameAction::Vote)),
+            ("/reveal".to_string(), Some(GameAction::Reveal)),
+            ("/start-voting".to_string(), Some(GameAction::StartVoting)),
+            ("/reset".to_string(), Some(GameAction::Reset)),
+            ("/revote".to_string(), Some(GameAction::Revote)),
+            ("/archive".to_string(), Some(GameAction::Archive)),
+            ("/complete".to_string(), Some(GameAction::Complete)),
+            ("/story".to_string(), Some(GameAction::Story)),
+            (
+                "/stories/import".to_string(),
+                Some(GameAction::ImportStories),
+            ),
+            ("/stories/batch".to_string(), Some(GameAction::BatchStories)),
+            ("/finalize".to_string(), Some(GameAction::Finalize)),
+            (
+                "/transfer-ownership".to_string(),
+                Some(GameAction::TransferOwnership),
+            ),
+            (
+                format!("/votes/{player_id}/discard"),
+                Some(GameAction::DiscardVote(player_id)),
+            ),
+            (
+                format!("/players/{player_id}/role"),
+                Some(GameAction::SetPlayerRole(player_id)),
+            ),
+            (
+                format!("/players/{player_id}/kick"),
+                Some(GameAction::Kick(Some(player_id))),
+            ),
+            ("/kick".to_string(), Some(GameAction::Kick(None))),
+            (
+                format!("/players/{player_id}"),
+                Some(GameAction::UpdatePlayer(player_id)),
+            ),
+            ("/leave".to_string(), Some(GameAction::Leave)),
+            ("/export".to_string(), Some(GameAction::Export)),
+            ("/stats".to_string(), Some(GameAction::Stats)),
+            ("/rounds".to_string(), Some(GameAction::Rounds)),
+            ("/audit-log".to_string(), Some(GameAction::AuditLog)),
+        ];
+
+        for (suffix, action) in cases {
+            let path = format!("/api/games/{game_id}{suffix}");
+            assert_eq!(
+                GamePath::parse(&path).unwrap(),
+                GamePath { game_id, action },
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_player_id_comes_from_player_actions_only() {
+        let game_id = Uuid::new_v4();
+        let player_id = Uuid::new_v4();
+
+        let path = GamePath::parse(&format!("/api/games/{game_id}/players/{player_id}/kick"));
+        assert_eq!(path.unwrap().player_id(), Some(player_id));
+        let path = GamePath::parse(&format!("/api/games/{game_id}/kick"));
+        assert_eq!(path.unwrap().player_id(), None);
+        let path = GamePath::parse(&format!("/api/games/{game_id}/vote"));
+        assert_eq!(path.unwrap().player_id(), None);
+    }
+
+    #[test]
+    fn test_reads_are_get_and_changes_are_post_or_patch() {
+        let game_id = Uuid::new_v4();
+        let player_id = Uuid::new_v4();
+        let cases = [
+            (String::new(), "GET"),
+            ("/export".to_string(), "GET"),
+            ("/stats".to_string(), "GET"),
+            ("/rounds".to_string(), "GET"),
+            ("/audit-log".to_string(), "GET"),
+            ("/vote".to_string(), "POST"),
+            ("/stories/import".to_string(), "POST"),
+            (format!("/players/{player_id}/kick"), "POST"),
+            (format!("/players/{player_id}"), "PATCH"),
+        ];
+
+        for (suffix, allowed) in cases {
+            let path = format!("/api/games/{game_id}{suffix}");
+            assert_eq!(
+                GamePath::parse(&path).unwrap().allowed_methods(),
+                allowed,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_paths_are_rejected() {
+        let game_id = Uuid::new_v4();
+
+        for path in [
+            format!("/api/games/{game_id}/unknown-thing"),
+            format!("/api/games/{game_id}/vote/extra"),
+            format!("/api/games/{game_id}/stories"),
+            format!("/api/games/{game_id}/players/{game_id}/unknown-thing"),
+            format!("/api/v2/games/{game_id}/vote"),
+            format!("/games/{game_id}"),
+        ] {
+            assert!(
+                matches!(GamePath::parse(&path), Err(RouteError::NotFound(_))),
+                "{path}"
+            );
+        }
+
+        for path in [
+            "/api/games/".to_string(),
+            "/api/games/not-a-uuid/vote".to_string(),
+            format!("/api/games/{game_id}/players/not-a-uuid/role"),
+            format!("/api/games/{game_id}/votes//discard"),
+        ] {
+            assert!(
+                matches!(GamePath::parse(&path), Err(RouteError::InvalidUuid(_))),
+                "{path}"
+            );
+        }
+    }
+}
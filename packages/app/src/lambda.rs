@@ -2,9 +2,11 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
-use planning_poker_app::{build_app, create_app_router, init, set_renderer};
+use planning_poker_app::{
+    build_app, create_app_router, init, init_config, init_logging, set_renderer,
+};
 use std::sync::{Arc, LazyLock};
-use tracing::info;
+use tracing::{error, info};
 
 static RUNTIME: LazyLock<Arc<switchy::unsync::runtime::Runtime>> = LazyLock::new(|| {
     let runtime = switchy::unsync::runtime::Builder::new()
@@ -16,13 +18,16 @@ static RUNTIME: LazyLock<Arc<switchy::unsync::runtime::Runtime>> = LazyLock::new
 
 #[allow(clippy::cognitive_complexity)]
 fn main() -> Result<(), hyperchad::app::Error> {
-    // Initialize tracing - respect RUST_LOG environment variable
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
+    // Log in the configured format; RUST_LOG still overrides the level
+    init_logging();
 
     info!("Starting Planning Poker Lambda");
 
+    if let Err(e) = init_config() {
+        error!("Invalid configuration: {e}");
+        std::process::exit(1);
+    }
+
     // Initialize app builder (synchronous like MoosicBox)
     let app_builder = init().with_runtime_handle(RUNTIME.handle().clone());
 
@@ -2,7 +2,15 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
-use chrono::Utc;
+mod coalescer;
+mod game_path;
+pub mod logging;
+pub mod shutdown;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use coalescer::{UpdateCoalescer, UpdateTarget, FLUSH_INTERVAL};
+use game_path::{GameAction, GamePath};
 use hyperchad::{
     app::{renderer::DefaultRenderer, App, AppBuilder},
     renderer::{Content, PartialView, Renderer},
@@ -10,11 +18,27 @@ use hyperchad::{
     template::{self as hyperchad_template, container, Containers},
     transformer::html::ParseError as HtmlParseError,
 };
-use planning_poker_models::{GameState, Player, Vote};
+use planning_poker_auth::{AuthError, AuthToken};
+use planning_poker_config::{AuthConfig, Config, ConfigError, VotingConfig};
+use planning_poker_metrics::Metric;
+use planning_poker_models::{
+    Confidence, Game, GameHistory, GameSettings, GameState, NewStory, PaginationParams, Player,
+    PlayerRole, RoundSummary, RoundTiming, StoryInput, StoryInputError, TeamRole, Vote,
+};
+use planning_poker_poker::VotingSystem;
+use planning_poker_session::NameTakenError;
 use planning_poker_state::PlanningPokerState;
+use planning_poker_ui::ToastKind;
 use serde::Deserialize;
-use std::sync::{Arc, LazyLock, OnceLock};
+use shutdown::InFlight;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::{Arc, LazyLock, OnceLock},
+    time::Duration,
+};
 use switchy::http::models::Method;
+use tracing::Instrument as _;
 
 use uuid::Uuid;
 
@@ -23,6 +47,43 @@ static RENDERER: OnceLock<Arc<dyn Renderer>> = OnceLock::new();
 // Global lazy state - initialized on first access
 static STATE: LazyLock<PlanningPokerState> = LazyLock::new(PlanningPokerState::new);
 
+/// Partial updates for bursty events like votes and joins, rendered at most
+/// once per [`FLUSH_INTERVAL`] for each game
+static UPDATES: LazyLock<UpdateCoalescer> =
+    LazyLock::new(|| UpdateCoalescer::new(FLUSH_INTERVAL, flush_game_updates));
+
+/// API requests being handled, drained before the app shuts down
+static IN_FLIGHT: InFlight = InFlight::new();
+
+/// Completed once the graceful shutdown has run, so a second caller waits
+/// for the first instead of repeating it
+static SHUT_DOWN: tokio::sync::OnceCell<()> = tokio::sync::OnceCell::const_new();
+
+/// Cookie carrying the player's auth token
+const AUTH_COOKIE: &str = "planning_poker_token";
+
+/// Key auth tokens are signed with
+///
+/// Debug builds fall back to a random key when none is configured, so tokens
+/// then only stay valid for the life of the process; [`init_config`] refuses
+/// to start a release build without one.
+static AUTH_SECRET: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    STATE
+        .config()
+        .ok()
+        .and_then(|config| config.auth.secret.clone())
+        .map_or_else(
+            || {
+                tracing::warn!("No auth secret configured, generating one for this process");
+                [Uuid::new_v4(), Uuid::new_v4()]
+                    .into_iter()
+                    .flat_map(Uuid::into_bytes)
+                    .collect()
+            },
+            String::into_bytes,
+        )
+});
+
 #[cfg(feature = "assets")]
 pub mod assets {
     use hyperchad::renderer;
@@ -68,14 +129,55 @@ pub enum RouteError {
     MissingFormData,
     #[error("Unsupported method")]
     UnsupportedMethod,
+    /// A known path asked for with a method it doesn't answer to
+    #[error("Method {method} not allowed, expected {allowed}")]
+    MethodNotAllowed {
+        method: String,
+        /// The methods the path does answer to, as listed in an `Allow` header
+        allowed: &'static str,
+    },
     #[error("Failed to parse body")]
     ParseBody(#[from] ParseError),
     #[error("Failed to parse HTML")]
     ParseHtml(#[from] HtmlParseError),
     #[error("Invalid UUID")]
     InvalidUuid(#[from] uuid::Error),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Invalid or missing auth token")]
+    InvalidToken,
+    #[error("Auth token expired")]
+    ExpiredToken,
+    #[error("The name '{0}' is already taken in this game, please pick another one")]
+    NameTaken(String),
+    #[error("Invalid {field}: {message}")]
+    Validation { field: String, message: String },
+    /// A story batch with invalid stories, none of which were added
+    #[error("{} of the stories are invalid", .0.len())]
+    InvalidStories(Vec<StoryInputError>),
+    #[error("Failed to render page: {0}")]
+    Render(String),
+    /// A change the game turned down, like a second vote in a locked round;
+    /// the acting player is told why with an error toast
+    #[error("{0}")]
+    Rejected(String),
     #[error("Route failed: {0}")]
     RouteFailed(String),
+    #[error("The server is shutting down")]
+    ShuttingDown,
+}
+
+impl RouteError {
+    /// A problem with the submitted `field`, reported back next to that field
+    #[must_use]
+    pub fn validation(field: &str, message: &str) -> Self {
+        Self::Validation {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -83,12 +185,139 @@ pub enum RouteError {
 pub struct JoinGameForm {
     pub game_id: String,
     pub player_name: String,
+    #[serde(default)]
+    pub role: Option<String>,
+    /// What the player does on the team; blank for none
+    #[serde(default)]
+    pub team_role: Option<String>,
+}
+
+impl JoinGameForm {
+    /// The role picked on the form, defaulting to a regular voter
+    ///
+    /// # Errors
+    ///
+    /// * If the role is not a known role
+    pub fn role(&self) -> Result<PlayerRole, RouteError> {
+        parse_role(self.role.as_deref().unwrap_or_default())
+    }
+
+    /// The team role picked on the form, where any name not offered is kept
+    /// as an `Other` role
+    #[must_use]
+    pub fn team_role(&self) -> TeamRole {
+        self.team_role
+            .clone()
+            .map(TeamRole::from)
+            .unwrap_or_default()
+    }
+}
+
+/// The form a spectator fills in to watch a game without voting
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ObserveGameForm {
+    pub game_id: String,
+    pub observer_name: String,
+}
+
+/// Parse a submitted role, treating a blank value as a regular voter
+fn parse_role(role: &str) -> Result<PlayerRole, RouteError> {
+    if role.trim().is_empty() {
+        return Ok(PlayerRole::Voter);
+    }
+    role.parse().map_err(RouteError::RouteFailed)
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CreateGameForm {
     pub name: String,
     pub voting_system: String,
+    /// Present when the "first answer counts" box is checked
+    #[serde(default)]
+    pub lock_votes: Option<String>,
+    /// Comma-separated cards of a custom deck; overrides `voting_system` when filled in
+    #[serde(default)]
+    pub custom_deck: Option<String>,
+    /// Hours a revealed game is kept before it is archived; blank to keep it
+    #[serde(default)]
+    pub auto_archive_hours: Option<String>,
+    /// URL that receives each round's results; blank for none
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+impl CreateGameForm {
+    /// The custom deck entered on the form, if any
+    ///
+    /// # Errors
+    ///
+    /// * If the custom deck field is filled in but lists no cards
+    pub fn custom_deck(&self) -> Result<Option<VotingSystem>, RouteError> {
+        let Some(list) = self
+            .custom_deck
+            .as_deref()
+            .filter(|list| !list.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+
+        VotingSystem::custom_from_list(list)
+            .map(Some)
+            .ok_or_else(|| {
+                RouteError::RouteFailed("Custom deck needs at least one card".to_string())
+            })
+    }
+
+    /// The game settings selected on the form
+    ///
+    /// # Errors
+    ///
+    /// * If the auto-archive delay is filled in but is not a whole number of hours
+    /// * If the webhook URL is filled in but isn't HTTP(S)
+    pub fn settings(&self) -> Result<GameSettings, RouteError> {
+        let auto_archive_after = self
+            .auto_archive_hours
+            .as_deref()
+            .map(str::trim)
+            .filter(|hours| !hours.is_empty())
+            .map(|hours| {
+                hours
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|hours| *hours > 0)
+                    .and_then(|hours| hours.checked_mul(60 * 60))
+                    .map(Duration::from_secs)
+                    .ok_or_else(|| {
+                        RouteError::RouteFailed(
+                            "Auto-archive delay must be a positive number of hours".to_string(),
+                        )
+                    })
+            })
+            .transpose()?;
+
+        let webhook_url = self
+            .webhook_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| {
+                if url.starts_with("http://") || url.starts_with("https://") {
+                    Ok(url.to_string())
+                } else {
+                    Err(RouteError::RouteFailed(
+                        "Webhook URL must start with http:// or https://".to_string(),
+                    ))
+                }
+            })
+            .transpose()?;
+
+        Ok(GameSettings {
+            allow_vote_change: self.lock_votes.is_none(),
+            auto_archive_after,
+            webhook_url,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -100,6 +329,10 @@ pub struct CreateGameRequest {
 #[derive(Debug, Deserialize)]
 pub struct JoinGameRequest {
     pub player_name: String,
+    #[serde(default)]
+    pub role: PlayerRole,
+    #[serde(default)]
+    pub team_role: TeamRole,
 }
 
 #[derive(Debug, Deserialize)]
@@ -111,105 +344,332 @@ pub struct VoteRequest {
 #[derive(Debug, Deserialize)]
 pub struct VoteForm {
     pub vote: String,
+    #[serde(default)]
+    pub confidence: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct StartVotingForm {
+    #[serde(default)]
     pub story: String,
+    /// Seconds until the round reveals itself; blank for an untimed round
+    #[serde(default)]
+    pub timer_seconds: Option<String>,
 }
 
-// SSE Partial Update Helper Functions
-#[allow(clippy::cognitive_complexity)]
-async fn send_partial_update(target: &str, content: Containers) {
-    let Some(renderer) = RENDERER.get() else {
-        tracing::warn!("RENDERER not initialized, cannot send partial update");
-        return;
-    };
+#[derive(Debug, Deserialize)]
+pub struct UpdateStoryForm {
+    /// The new story text; blank clears the story
+    #[serde(default)]
+    pub story: String,
+}
 
-    tracing::info!(
-        "Sending partial update to target: {} with content length: {}",
-        target,
-        format!("{content:?}").len()
-    );
+/// Story stored for a round started without one
+const UNTITLED_STORY: &str = "Untitled Story";
 
-    let partial = PartialView {
-        target: target.to_string(),
-        container: content.into(),
-    };
+/// Longest story, in characters, a round can be started with
+const MAX_STORY_LENGTH: usize = 200;
 
-    if let Err(e) = renderer.render_partial(partial).await {
-        tracing::error!("Failed to render_partial for target {}: {e:?}", target);
-    } else {
-        tracing::info!("Successfully sent partial update to target: {}", target);
+/// Longest timer, in seconds, a round can be started with
+const MAX_VOTING_TIMER_SECS: u64 = 60 * 60;
+
+/// How often a timed round broadcasts the time it has left
+const TIMER_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Most stories one import may add, so a huge paste is turned away up front
+const MAX_IMPORTED_STORIES: usize = 200;
+
+/// How many completed rounds one page of a game's history lists
+const ROUND_HISTORY_PAGE_SIZE: usize = 50;
+
+/// How many players one page of a game lists when only `players_page` is given
+const DEFAULT_PLAYERS_PAGE_SIZE: u32 = 20;
+
+/// Largest `players_page_size` a request may ask for
+const MAX_PLAYERS_PAGE_SIZE: u32 = 100;
+
+/// Fewest characters a game search may look for, so a single letter can't
+/// list every game
+const MIN_SEARCH_QUERY_LENGTH: usize = 2;
+
+/// How many games one page of search results lists when no `page_size` is given
+const DEFAULT_SEARCH_PAGE_SIZE: u32 = 20;
+
+/// Games on a page of the games list when no `page_size` is given
+const DEFAULT_GAMES_LIST_PAGE_SIZE: u32 = 25;
+
+/// Largest search or games list `page_size` a request may ask for
+const MAX_SEARCH_PAGE_SIZE: u32 = 100;
+
+/// The query parameters of a game search, read by [`SearchGamesRequest::from_query`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchGamesRequest {
+    /// Text the game's name must contain, ignoring case
+    pub q: String,
+    pub state: Option<GameState>,
+    pub voting_system: Option<String>,
+    pub owner_id: Option<Uuid>,
+    pub page: PaginationParams,
+}
+
+impl SearchGamesRequest {
+    /// Read a search from `q`, `state`, `voting_system`, `owner_id`, `page`
+    /// and `page_size` query parameters; only `q` is required
+    ///
+    /// # Errors
+    ///
+    /// * If `q` is shorter than `MIN_SEARCH_QUERY_LENGTH` characters
+    /// * If `state` is not a game state or `owner_id` is not a UUID
+    /// * If `page` or `page_size` is not a whole number of at least 1, or the
+    ///   page size is over `MAX_SEARCH_PAGE_SIZE`
+    pub fn from_query(query: &BTreeMap<String, String>) -> Result<Self, RouteError> {
+        let q = query.get("q").map_or("", |q| q.trim());
+        if q.chars().count() < MIN_SEARCH_QUERY_LENGTH {
+            return Err(RouteError::validation(
+                "q",
+                &format!("Search text must be at least {MIN_SEARCH_QUERY_LENGTH} characters"),
+            ));
+        }
+
+        let owner_id = query
+            .get("owner_id")
+            .map(|owner_id| Uuid::parse_str(owner_id.trim()))
+            .transpose()?;
+
+        Ok(Self {
+            q: q.to_string(),
+            state: state_from_query(query)?,
+            voting_system: query
+                .get("voting_system")
+                .map(|voting_system| voting_system.trim().to_string())
+                .filter(|voting_system| !voting_system.is_empty()),
+            owner_id,
+            page: page_from_query(query, DEFAULT_SEARCH_PAGE_SIZE)?,
+        })
     }
 }
 
-async fn update_game_status(_game_id: &str, status: &str) {
-    let content = planning_poker_ui::game_status_content(status);
-    send_partial_update("game-status", content).await;
+/// The query parameters of the games list page, read by
+/// [`GamesListRequest::from_query`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GamesListRequest {
+    pub state: Option<GameState>,
+    /// Whether completed games are listed when no state is picked
+    pub include_completed: bool,
+    pub page: PaginationParams,
 }
 
-async fn update_players_list(_game_id: &str, players: Vec<Player>) {
-    let content = planning_poker_ui::players_list_content(&players);
-    send_partial_update("players-list", content).await;
+impl GamesListRequest {
+    /// Read the list from optional `state`, `completed`, `page` and
+    /// `page_size` query parameters, where any `completed` value but a blank
+    /// one or `0` lists the completed games too
+    ///
+    /// # Errors
+    ///
+    /// * If `state` is not a game state
+    /// * If `page` or `page_size` is not a whole number of at least 1, or the
+    ///   page size is over `MAX_SEARCH_PAGE_SIZE`
+    pub fn from_query(query: &BTreeMap<String, String>) -> Result<Self, RouteError> {
+        Ok(Self {
+            state: state_from_query(query)?,
+            include_completed: query
+                .get("completed")
+                .is_some_and(|completed| !matches!(completed.trim(), "" | "0")),
+            page: page_from_query(query, DEFAULT_GAMES_LIST_PAGE_SIZE)?,
+        })
+    }
 }
 
-#[allow(clippy::cognitive_complexity)]
-async fn update_vote_buttons(game_id: &str, voting_active: bool) {
-    tracing::info!(
-        "VOTE BUTTONS: update_vote_buttons called for game {}, voting_active: {}",
-        game_id,
-        voting_active
-    );
+/// The game state a `state` query parameter names, if it is given
+fn state_from_query(query: &BTreeMap<String, String>) -> Result<Option<GameState>, RouteError> {
+    query
+        .get("state")
+        .map(|state| state.parse::<GameState>())
+        .transpose()
+        .map_err(|e| RouteError::validation("state", &e))
+}
 
-    let content = if voting_active {
-        tracing::info!("VOTE BUTTONS: Voting is active, using simple test content");
-        container! {
-            div {
-                "VOTING IS ACTIVE - TEST MESSAGE"
-            }
+/// The page named by `page` and `page_size` query parameters, each of which
+/// defaults when it is left out
+fn page_from_query(
+    query: &BTreeMap<String, String>,
+    default_page_size: u32,
+) -> Result<PaginationParams, RouteError> {
+    let parse = |name: &str, default: u32| {
+        query
+            .get(name)
+            .map_or(Ok(default), |value| value.trim().parse::<u32>())
+            .ok()
+            .filter(|value| *value >= 1)
+            .ok_or_else(|| RouteError::validation(name, "Must be a whole number of at least 1"))
+    };
+    let page = parse("page", 1)?;
+    let page_size = parse("page_size", default_page_size)?;
+    if page_size > MAX_SEARCH_PAGE_SIZE {
+        return Err(RouteError::validation(
+            "page_size",
+            &format!("Must be at most {MAX_SEARCH_PAGE_SIZE}"),
+        ));
+    }
+
+    Ok(PaginationParams { page, page_size })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KickPlayerForm {
+    pub player_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FinalizeEstimateForm {
+    pub value: String,
+}
+
+/// The JSON body of a story batch, like
+/// `{"stories": [{"title": "Login page", "description": "..."}]}`
+#[derive(Debug, Deserialize)]
+pub struct StoryBatchRequest {
+    pub stories: Vec<StoryInput>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportStoriesForm {
+    /// One story per line, each either `title` or `KEY-123 | title`
+    #[serde(default)]
+    pub stories: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransferOwnershipForm {
+    pub new_owner_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlayerRoleForm {
+    pub role: String,
+}
+
+/// A partial update that never reached the clients
+#[derive(Debug, thiserror::Error)]
+enum PartialUpdateError {
+    #[error("Renderer is not initialized")]
+    NoRenderer,
+    #[error("Failed to load the game to update: {0}")]
+    Load(String),
+    #[error("Failed to render partial {target}: {message}")]
+    Render { target: String, message: String },
+}
+
+/// The outcome of a route's partial updates, so its response can say when
+/// other players may not have seen the change
+#[derive(Debug, Default)]
+struct Delivery {
+    failure: Option<PartialUpdateError>,
+}
+
+impl Delivery {
+    /// Keep the first failure, logging every one
+    fn record(&mut self, result: Result<(), PartialUpdateError>) {
+        if let Err(e) = result {
+            tracing::warn!("Partial update was not delivered: {}", e);
+            self.failure.get_or_insert(e);
         }
-    } else {
-        tracing::info!("VOTE BUTTONS: Voting is not active, showing inactive message");
+    }
+
+    /// A warning to append to the route's response, empty when everything was sent
+    fn warning(&self) -> Containers {
+        let Some(e) = &self.failure else {
+            return Containers::new();
+        };
+
         container! {
-            div color="#666" {
-                "Voting not active. Click 'Start Voting' to begin."
+            div id="broadcast-warning" margin-top=5 color="#856404" {
+                (format!("Saved, but other players may not see it until they reload: {e}"))
             }
         }
-    };
+    }
+}
 
-    tracing::info!("VOTE BUTTONS: About to send partial update to vote-buttons target");
-    send_partial_update("vote-buttons", content).await;
+// SSE Partial Update Helper Functions
+async fn send_partial_update(target: &str, content: Containers) -> Result<(), PartialUpdateError> {
+    let renderer = RENDERER.get().ok_or(PartialUpdateError::NoRenderer)?;
+    deliver_partial(target, content, |partial| renderer.render_partial(partial)).await
 }
 
-async fn update_entire_voting_section(
-    game_id: &str,
-    game: &planning_poker_models::Game,
-    voting_active: bool,
-) {
-    tracing::info!(
-        "VOTING SECTION: Updating entire voting section for game {}, voting_active: {}",
-        game_id,
-        voting_active
+/// Hand `content` for `target` to `render`, counting failures in the
+/// `partial_updates_failed_total` metric
+async fn deliver_partial<F, Fut, E>(
+    target: &str,
+    content: Containers,
+    render: F,
+) -> Result<(), PartialUpdateError>
+where
+    F: FnOnce(PartialView) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: std::fmt::Debug,
+{
+    tracing::debug!(
+        "Sending partial update to target {} with {} containers",
+        target,
+        content.len()
     );
 
-    let content = planning_poker_ui::voting_section(game_id, game, voting_active);
-    send_partial_update("voting-section", content).await;
+    let partial = PartialView {
+        target: target.to_string(),
+        container: content.into(),
+    };
+
+    render(partial).await.map_err(|e| {
+        STATE.metrics().increment(Metric::PartialUpdatesFailed);
+        PartialUpdateError::Render {
+            target: target.to_string(),
+            message: format!("{e:?}"),
+        }
+    })?;
+
+    tracing::debug!("Sent partial update to target {}", target);
+    Ok(())
+}
+
+async fn update_game_status(_game_id: &str, status: &str) -> Result<(), PartialUpdateError> {
+    let content = planning_poker_ui::game_status_content(status);
+    send_partial_update("game-status", content).await
+}
+
+async fn update_players_list(
+    _game_id: &str,
+    players: Vec<Player>,
+) -> Result<(), PartialUpdateError> {
+    let content = planning_poker_ui::players_list_content(&players);
+    send_partial_update("players-list", content).await
 }
 
-async fn update_story_input(game_id: &str, voting_active: bool, current_story: Option<&String>) {
+async fn update_story_input(
+    game_id: &str,
+    voting_active: bool,
+    current_story: Option<&String>,
+) -> Result<(), PartialUpdateError> {
     let content =
         planning_poker_ui::story_input_content(game_id, voting_active, &current_story.cloned());
-    send_partial_update("story-input", content).await;
+    send_partial_update("story-input", content).await
 }
 
-async fn update_current_story(current_story: Option<&String>, voting_active: bool) {
+async fn update_current_story(
+    current_story: Option<&String>,
+    voting_active: bool,
+) -> Result<(), PartialUpdateError> {
     let content = planning_poker_ui::current_story_section(&current_story.cloned(), voting_active);
-    send_partial_update("current-story", content).await;
+    send_partial_update("current-story", content).await
 }
 
 #[allow(clippy::cognitive_complexity)]
-async fn update_vote_results(_game_id: &str, votes: Vec<Vote>, revealed: bool) {
+async fn update_vote_results(
+    _game_id: &str,
+    votes: Vec<Vote>,
+    players: &[Player],
+    revealed: bool,
+    voting_system: &VotingSystem,
+) -> Result<(), PartialUpdateError> {
     tracing::info!(
         "Updating vote results: {} votes, revealed: {}",
         votes.len(),
@@ -236,47 +696,49 @@ async fn update_vote_results(_game_id: &str, votes: Vec<Vote>, revealed: bool) {
         tracing::info!("Votes are hidden - will show vote count only");
     }
 
-    let content = planning_poker_ui::vote_results_content(&votes, revealed);
-    send_partial_update("vote-results", content).await;
+    let content = planning_poker_ui::vote_results_content(&votes, players, revealed, voting_system);
+    send_partial_update("vote-results", content).await
 }
 
-async fn update_game_actions(game_id: &str, game_state: GameState) {
+async fn update_game_actions(
+    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
+    game_id: &str,
+    game: &Game,
+) -> Result<(), PartialUpdateError> {
     tracing::info!(
         "GAME ACTIONS: Updating game actions for game {}, state: {:?}",
         game_id,
-        game_state
+        game.state
     );
 
-    let reveal_url = format!("/api/games/{game_id}/reveal");
-    let reset_url = format!("/api/games/{game_id}/reset");
-
-    let content = container! {
-        @if matches!(game_state, GameState::Revealed) {
-            button hx-post=(reveal_url) margin=5 padding=10 background="#6c757d" color="#fff" border="none" border-radius=5 disabled {
-                "Votes Revealed"
-            }
-            button hx-post=(reset_url) margin=5 padding=10 background="#ffc107" color="#000" border="none" border-radius=5 {
-                "Reset Voting"
-            }
-        } @else if matches!(game_state, GameState::Voting) {
-            button hx-post=(reveal_url) margin=5 padding=10 background="#dc3545" color="#fff" border="none" border-radius=5 {
-                "Reveal Votes"
-            }
-            button hx-post=(reset_url) margin=5 padding=10 background="#ffc107" color="#000" border="none" border-radius=5 {
-                "Reset Voting"
-            }
-        } @else {
-            // Waiting state - no votes to reveal yet, no need for reset
-            div color="#666" {
-                "Start voting to see action buttons"
-            }
-        }
+    // Only an open round needs the count, to hold back the reveal until someone voted
+    let vote_count = if game.state == GameState::Voting {
+        session_manager
+            .get_game_votes(game.id)
+            .await
+            .map(|votes| votes.len())
+            .unwrap_or_default()
+    } else {
+        0
     };
 
-    send_partial_update("game-actions", content).await;
+    let content = planning_poker_ui::game_actions_content(
+        game_id,
+        &game.state,
+        &VotingSystem::from_string(&game.voting_system),
+        vote_count,
+    );
+    send_partial_update("game-actions", content).await
 }
 
-async fn update_entire_results_section(game_id: &str, votes: Vec<Vote>, votes_revealed: bool) {
+async fn update_entire_results_section(
+    game_id: &str,
+    votes: Vec<Vote>,
+    players: &[Player],
+    votes_revealed: bool,
+    voting_system: &VotingSystem,
+    timing: Option<&RoundTiming>,
+) -> Result<(), PartialUpdateError> {
     tracing::info!(
         "RESULTS SECTION: Updating entire results section for game {}, {} votes, revealed: {}",
         game_id,
@@ -284,8 +746,9 @@ async fn update_entire_results_section(game_id: &str, votes: Vec<Vote>, votes_re
         votes_revealed
     );
 
-    let content = planning_poker_ui::results_section(game_id, &votes, votes_revealed);
-    send_partial_update("results-section", content).await;
+    let content =
+        planning_poker_ui::results_section(&votes, players, votes_revealed, voting_system, timing);
+    send_partial_update("results-section", content).await
 }
 
 pub fn set_renderer(renderer: Arc<dyn Renderer>) {
@@ -297,6 +760,50 @@ pub fn set_renderer(renderer: Arc<dyn Renderer>) {
     }
 }
 
+/// How long requests already running get to finish on shutdown
+#[must_use]
+pub fn shutdown_timeout() -> Duration {
+    let seconds = STATE.config().map_or_else(
+        |_| Config::default().server.shutdown_timeout_seconds,
+        |config| config.server.shutdown_timeout_seconds,
+    );
+    Duration::from_secs(seconds)
+}
+
+/// Shuts the app down gracefully
+///
+/// New API requests are turned away, every open page is told the server is
+/// going away, the requests already running get up to `timeout` to finish
+/// and the partial updates the coalescer is still holding back are rendered.
+/// Calling it again waits for the first call to finish.
+pub async fn graceful_shutdown(timeout: Duration) {
+    SHUT_DOWN
+        .get_or_init(|| async {
+            tracing::info!(
+                "Shutting down, waiting up to {}s for requests in flight",
+                timeout.as_secs()
+            );
+
+            let notice = planning_poker_ui::shutdown_notice(timeout.as_secs());
+            if let Err(e) = send_partial_update("toast-area", notice).await {
+                tracing::warn!("Failed to tell open pages about the shutdown: {e}");
+            }
+
+            if !IN_FLIGHT.drain(timeout).await {
+                tracing::warn!(
+                    "{} requests still running after {}s, shutting down anyway",
+                    IN_FLIGHT.active(),
+                    timeout.as_secs()
+                );
+            }
+
+            // The coalescer is a static that is never dropped
+            UPDATES.flush_all().await;
+            tracing::info!("Shutdown complete");
+        })
+        .await;
+}
+
 /// Initialize the app with common configuration (synchronous like `MoosicBox`)
 ///
 /// # Panics
@@ -336,40 +843,52 @@ pub fn build_app(
 }
 
 pub fn create_app_router() -> Router {
-    let router = planning_poker_ui::create_router()
-        .with_route("/health", health_route)
-        .with_route_result("/join-game", join_game_route)
-        .with_route_result(
-            hyperchad::router::RoutePath::LiteralPrefix("/game/".to_string()),
-            game_page_route,
+    let router = Router::new()
+        .with_route(
+            "/",
+            |req| async move { with_error_pages(home_route(req).await) },
         )
-        .with_route_result("/api/games", |req| async move {
-            // Handle both POST /api/games (create) and GET /api/games/uuid (get)
-            if req.path == "/api/games" {
-                create_game_route(req).await
-            } else {
-                get_game_route(req).await
-            }
+        .with_route("/home", |req| async move {
+            with_error_pages(home_route(req).await)
         })
-        .with_route_result(
-            hyperchad::router::RoutePath::LiteralPrefix("/api/games/".to_string()),
+        .with_route("/health", health_route)
+        .with_route("/metrics", metrics_route)
+        .with_route("/api/voting-systems", |req| async move {
+            with_error_pages(voting_systems_route(req).await)
+        })
+        .with_route("/games", |req| async move {
+            with_error_pages(games_list_route(req).await)
+        })
+        .with_route("/join-game", |req| async move {
+            with_error_pages(traced_route(req, join_game_route).await)
+        })
+        .with_route(
+            hyperchad::router::RoutePath::LiteralPrefix("/join/".to_string()),
+            |req| async move { with_error_pages(join_prompt_route(req).await) },
+        )
+        .with_route(
+            hyperchad::router::RoutePath::LiteralPrefix("/game/".to_string()),
             |req| async move {
-                // Route based on the path suffix
-                if req.path.ends_with("/join") {
-                    join_game_api_route(req).await
-                } else if req.path.ends_with("/vote") {
-                    vote_route(req).await
-                } else if req.path.ends_with("/reveal") {
-                    reveal_votes_route(req).await
-                } else if req.path.ends_with("/start-voting") {
-                    start_voting_route(req).await
-                } else if req.path.ends_with("/reset") {
-                    reset_voting_route(req).await
+                let result = if req.path.ends_with("/summary") {
+                    session_summary_route(req).await
+                } else if req.path.ends_with("/snapshot") {
+                    game_snapshot_route(req).await
+                } else if req.path.ends_with("/observe") {
+                    observe_game_route(req).await
+                } else if req.path.ends_with("/present") {
+                    present_game_route(req).await
                 } else {
-                    // Default to get_game_route for paths like /api/games/uuid
-                    get_game_route(req).await
-                }
+                    game_page_route(req).await
+                };
+                with_error_pages(result)
             },
+        )
+        .with_route("/api/games", |req| async move {
+            with_error_pages(traced_route(req, games_collection_route).await)
+        })
+        .with_route(
+            hyperchad::router::RoutePath::LiteralPrefix("/api/games/".to_string()),
+            |req| async move { with_error_pages(traced_route(req, games_api_route).await) },
         );
 
     #[cfg(feature = "lambda")]
@@ -378,299 +897,646 @@ pub fn create_app_router() -> Router {
     router
 }
 
-/// Handles the health check route
-pub async fn health_route(_req: RouteRequest) -> Content {
-    Content::Json(serde_json::json!({
-        "success": true
-    }))
+/// Header a client or proxy can set to have its own ID used for a request
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The request's `X-Request-ID` if it holds a UUID, otherwise a fresh one
+fn request_id(req: &RouteRequest) -> Uuid {
+    req.headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|id| Uuid::parse_str(id.trim()).ok())
+        .unwrap_or_else(Uuid::new_v4)
 }
 
-/// Handles the join game route
-///
-/// # Errors
-///
-/// * If method is not POST
-/// * If game ID is not a valid UUID
-/// * If game ID is not found
-/// * If adding player to game fails
-/// * If getting game players fails
-///
-/// # Panics
+/// Runs `handler` inside a `route` span carrying the request ID and path
 ///
-/// * Infallible
-pub async fn join_game_route(req: RouteRequest) -> Result<Content, RouteError> {
-    if !matches!(req.method, Method::Post) {
-        return Err(RouteError::UnsupportedMethod);
+/// Everything the handler logs, database calls included, is tagged with the
+/// same `request_id`, and the request's start and outcome are logged at
+/// `INFO`. The request counts as in flight until it finishes, and once a
+/// shutdown has begun it is refused instead of started.
+async fn traced_route<F, Fut>(req: RouteRequest, handler: F) -> Result<Content, RouteError>
+where
+    F: FnOnce(RouteRequest) -> Fut,
+    Fut: Future<Output = Result<Content, RouteError>>,
+{
+    let request_id = request_id(&req);
+    let span = tracing::info_span!("route", request_id = %request_id, path = %req.path);
+
+    async move {
+        let Some(_in_flight) = IN_FLIGHT.start() else {
+            tracing::info!("Request refused: shutting down");
+            return Err(RouteError::ShuttingDown);
+        };
+        tracing::info!("Request started: {:?}", req.method);
+        let result = handler(req).await;
+        match &result {
+            Ok(_) => tracing::info!("Request finished: success"),
+            Err(e) => tracing::info!("Request finished: error: {e}"),
+        }
+        result
     }
+    .instrument(span)
+    .await
+}
 
-    let form_data = req.parse_form::<JoinGameForm>()?;
+/// Dispatches `/api/games/{game_id}/...` requests to the route for the
+/// action their path names; unknown actions are a 404
+///
+/// The method is checked against the path before any handler runs, so a
+/// wrong one is a 405 naming the methods the path does answer to. Actions
+/// that change the game answer their recoverable errors with a toast, see
+/// [`toast_errors`].
+async fn games_api_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if req.path == "/api/games/search" {
+        require_method(&req, "GET")?;
+        return search_games_route(req).await;
+    }
 
-    // Validate form data
-    if form_data.game_id.trim().is_empty() {
-        return Err(RouteError::RouteFailed("Game ID is required".to_string()));
+    let path = GamePath::parse(&req.path)?;
+    require_method(&req, path.allowed_methods())?;
+    let Some(action) = path.action else {
+        return get_game_route(req).await;
+    };
+    match action {
+        GameAction::Join => join_game_api_route(req).await,
+        GameAction::Vote => toast_errors(vote_route(req).await),
+        GameAction::Reveal => toast_errors(reveal_votes_route(req).await),
+        GameAction::StartVoting => toast_errors(start_voting_route(req).await),
+        GameAction::Reset => toast_errors(reset_voting_route(req).await),
+        GameAction::Revote => toast_errors(revote_route(req).await),
+        GameAction::Archive => toast_errors(archive_game_route(req).await),
+        GameAction::Complete => toast_errors(complete_game_route(req).await),
+        GameAction::Story => toast_errors(update_story_route(req).await),
+        GameAction::ImportStories => toast_errors(import_stories_route(req).await),
+        GameAction::BatchStories => batch_stories_route(req).await,
+        GameAction::Finalize => toast_errors(finalize_estimate_route(req).await),
+        GameAction::TransferOwnership => toast_errors(transfer_ownership_route(req).await),
+        GameAction::DiscardVote(_) => toast_errors(discard_vote_route(req).await),
+        GameAction::SetPlayerRole(_) => toast_errors(set_player_role_route(req).await),
+        GameAction::Kick(_) => toast_errors(kick_player_route(req).await),
+        GameAction::UpdatePlayer(_) => toast_errors(update_player_route(req).await),
+        GameAction::Leave => toast_errors(leave_game_route(req).await),
+        GameAction::Export => export_game_route(req).await,
+        GameAction::Stats => player_stats_route(req).await,
+        GameAction::Rounds => round_history_route(req).await,
+        GameAction::AuditLog => audit_log_route(req).await,
     }
+}
 
-    if form_data.player_name.trim().is_empty() {
-        return Err(RouteError::RouteFailed(
-            "Player name is required".to_string(),
-        ));
+/// Routes `/api/games` itself: `GET` lists the games and `POST` creates one
+async fn games_collection_route(req: RouteRequest) -> Result<Content, RouteError> {
+    match method_name(&req.method).as_str() {
+        "GET" => list_games_api_route(req).await,
+        "POST" => create_game_route(req).await,
+        method => Err(RouteError::MethodNotAllowed {
+            method: method.to_string(),
+            allowed: "GET, POST",
+        }),
     }
+}
 
-    // Parse game ID as UUID
-    let game_id = Uuid::parse_str(&form_data.game_id)?;
+/// The upper-case name of `method`, as it appears in a request line
+fn method_name(method: &Method) -> String {
+    format!("{method:?}").to_uppercase()
+}
 
-    // Get session manager from global state
-    let session_manager = STATE
-        .get_session_manager()
-        .await
-        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+/// Fails with a 405 unless the request's method is one of `allowed`, a
+/// comma-separated list like `"GET, POST"`
+fn require_method(req: &RouteRequest, allowed: &'static str) -> Result<(), RouteError> {
+    let method = method_name(&req.method);
+    if allowed.split(", ").any(|allowed| allowed == method) {
+        Ok(())
+    } else {
+        Err(RouteError::MethodNotAllowed { method, allowed })
+    }
+}
 
-    // Check if game exists
-    match session_manager.get_game(game_id).await {
-        Ok(Some(_)) => {
-            // Join the game directly via database
-            let player = Player {
-                id: Uuid::new_v4(),
-                name: form_data.player_name.clone(),
-                is_observer: false,
-                joined_at: Utc::now(),
-            };
-            if let Err(e) = session_manager.add_player_to_game(game_id, player).await {
-                return Err(RouteError::RouteFailed(format!("Failed to join game: {e}")));
+/// Renders a route's error as the page its user sees
+///
+/// This is the one place route errors are turned into pages: missing
+/// resources get a 404 page, malformed links and forms a 400, wrong methods
+/// a 405 and anything unexpected a generic 500 page that keeps the internal
+/// message in the logs.
+#[must_use]
+pub fn with_error_pages(result: Result<Content, RouteError>) -> Content {
+    result.unwrap_or_else(|error| {
+        let content = error_page_content(&error);
+        Content::try_view(planning_poker_ui::page_layout(&content)).unwrap_or_else(|e| {
+            tracing::error!("Failed to render the error page for {error}: {e}");
+            Content::Raw {
+                data: Bytes::from(error.to_string()),
+                content_type: "text/plain".to_string(),
             }
+        })
+    })
+}
 
-            // Return success message with redirect to game page
-            tracing::info!("Join game success: game_id = {}", form_data.game_id);
-            let content = container! {
-                h2 { "Success!" }
-                div {
-                    (format!("Successfully joined game {} as {}", form_data.game_id, form_data.player_name))
-                }
-                div margin-top=20 {
-                    anchor href=(format!("/game/{}", form_data.game_id)) margin=10 padding=10 background="#007bff" color="#fff" text-decoration="none" border-radius=5 {
-                        "Go to Game"
-                    }
-                    anchor href="/" margin=10 padding=10 background="#6c757d" color="#fff" text-decoration="none" border-radius=5 {
-                        "Back to Home"
-                    }
-                }
-            };
-            let success_content = planning_poker_ui::page_layout(&content);
-
-            Ok(Content::try_view(success_content).unwrap())
+/// The page shown for `error`
+fn error_page_content(error: &RouteError) -> Containers {
+    tracing::info!("Rendering error page: {}", error);
+    match error {
+        RouteError::NotFound(resource) => planning_poker_ui::page_not_found_content(resource),
+        RouteError::Forbidden(reason) => planning_poker_ui::forbidden_content(reason),
+        RouteError::Validation { field, message } => {
+            planning_poker_ui::validation_error_content(field, message)
+        }
+        RouteError::InvalidUuid(_) => planning_poker_ui::error_page(
+            "400 - Bad Request",
+            "That game link doesn't look right",
+            "/",
+        ),
+        RouteError::MissingFormData | RouteError::ParseBody(_) | RouteError::ParseHtml(_) => {
+            planning_poker_ui::error_page(
+                "400 - Bad Request",
+                "The form couldn't be read, please try again",
+                "/",
+            )
+        }
+        RouteError::NameTaken(_) | RouteError::Rejected(_) => {
+            planning_poker_ui::error_page("400 - Bad Request", &error.to_string(), "/")
+        }
+        RouteError::UnsupportedMethod => planning_poker_ui::error_page(
+            "405 - Method Not Allowed",
+            "This page can't be used that way",
+            "/",
+        ),
+        RouteError::MethodNotAllowed { method, allowed } => planning_poker_ui::error_page(
+            "405 - Method Not Allowed",
+            &format!("{method} isn't supported here. Allow: {allowed}"),
+            "/",
+        ),
+        RouteError::InvalidStories(errors) => planning_poker_ui::error_page(
+            "422 - Unprocessable Entity",
+            &errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+            "/",
+        ),
+        RouteError::InvalidToken | RouteError::ExpiredToken => planning_poker_ui::error_page(
+            "401 - Unauthorized",
+            "Your session is no longer valid, join the game again to continue",
+            "/",
+        ),
+        RouteError::ShuttingDown => planning_poker_ui::error_page(
+            "503 - Service Unavailable",
+            "The server is shutting down, please try again in a moment",
+            "/",
+        ),
+        RouteError::Render(message) | RouteError::RouteFailed(message) => {
+            tracing::error!("Route failed: {}", message);
+            planning_poker_ui::error_page(
+                "500 - Something Went Wrong",
+                "Something went wrong on our end, please try again",
+                "/",
+            )
         }
-        Ok(None) => Err(RouteError::RouteFailed("Game not found".to_string())),
-        Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
     }
 }
 
-/// Handles the create game router
+/// Converts `content` for the renderer, surfacing a template failure as a
+/// route error instead of a panic
+fn view(content: Containers) -> Result<Content, RouteError> {
+    Content::try_view(content).map_err(|e| RouteError::Render(e.to_string()))
+}
+
+/// Maximum number of open games listed in the home page lobby
+const LOBBY_GAME_LIMIT: usize = 20;
+
+/// Handles the home page route, listing the games that are open to join
+///
+/// The lobby is best-effort: if the games cannot be loaded the home page is
+/// still rendered with an empty lobby.
 ///
 /// # Errors
 ///
-/// * If method is not POST
-/// * If form data is missing
-/// * If form data is invalid
-/// * If creating game fails
-/// * If getting game fails
+/// * Infallible
 ///
 /// # Panics
 ///
 /// * Infallible
-pub async fn create_game_route(req: RouteRequest) -> Result<Content, RouteError> {
-    if !matches!(req.method, Method::Post) {
-        return Err(RouteError::UnsupportedMethod);
-    }
+pub async fn home_route(req: RouteRequest) -> Result<Content, RouteError> {
+    let (games, player_counts) = match load_lobby_games().await {
+        Ok(lobby) => lobby,
+        Err(e) => {
+            tracing::warn!("Failed to load games for the lobby: {e}");
+            (vec![], HashMap::new())
+        }
+    };
 
-    let form_data = req.parse_form::<CreateGameForm>()?;
+    let archived_games = match load_archived_games(&req).await {
+        Ok(archived_games) => archived_games,
+        Err(e) => {
+            tracing::warn!("Failed to load archived games: {e}");
+            vec![]
+        }
+    };
 
-    // Validate form data
-    if form_data.name.trim().is_empty() {
-        return Err(RouteError::RouteFailed("Game name is required".to_string()));
-    }
+    let default_voting_system = STATE.config().map_or_else(
+        |_| VotingSystem::Fibonacci.key(),
+        |config| config.voting.default_system.clone(),
+    );
+    let default_deck = resolve_voting_system(&default_voting_system);
+
+    // An invite link's `game` pre-fills the join form, as long as it names a game
+    let join_game_id = req
+        .query
+        .get("game")
+        .map(String::as_str)
+        .map(str::trim)
+        .filter(|game_id| Uuid::parse_str(game_id).is_ok());
+
+    let content = planning_poker_ui::home_content_with_games(
+        &games,
+        &player_counts,
+        &archived_games,
+        &default_voting_system,
+        &default_deck,
+        join_game_id,
+    );
+    let content = if req.path == "/home" {
+        content
+    } else {
+        planning_poker_ui::page_layout(&content)
+    };
 
-    if form_data.voting_system.trim().is_empty() {
-        return Err(RouteError::RouteFailed(
-            "Voting system is required".to_string(),
-        ));
-    }
-    let owner_id = Uuid::new_v4(); // TODO: Get from authentication
+    view(content)
+}
 
-    // Get session manager from global state
+/// Load the games waiting for players along with their player counts
+async fn load_lobby_games() -> Result<(Vec<Game>, HashMap<Uuid, usize>), RouteError> {
     let session_manager = STATE
         .get_session_manager()
         .await
         .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
 
-    match session_manager
-        .create_game(
-            form_data.name.clone(),
-            form_data.voting_system.clone(),
-            owner_id,
-        )
+    let games = session_manager
+        .list_games(Some(GameState::Waiting), LOBBY_GAME_LIMIT)
         .await
-    {
-        Ok(game) => {
-            tracing::info!("Create game success: game_id = {}", game.id);
-            let content = container! {
-                h2 { "Game Created!" }
-                div {
-                    (format!("Created game: {}", game.name))
-                }
-                div {
-                    (format!("Game ID: {}", game.id))
-                }
-                div margin-top=20 {
-                    anchor href=(format!("/game/{}", game.id)) margin=10 padding=10 background="#007bff" color="#fff" text-decoration="none" border-radius=5 {
-                        "Go to Game"
-                    }
-                    anchor href="/" margin=10 padding=10 background="#6c757d" color="#fff" text-decoration="none" border-radius=5 {
-                        "Back to Home"
-                    }
-                }
-            };
-            let success_content = planning_poker_ui::page_layout(&content);
-            Ok(Content::try_view(success_content).unwrap())
-        }
-        Err(e) => Err(RouteError::RouteFailed(format!(
-            "Failed to create game: {e}"
-        ))),
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    let game_ids: Vec<Uuid> = games.iter().map(|game| game.id).collect();
+    let player_counts = session_manager
+        .count_game_players(&game_ids)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    Ok((games, player_counts))
+}
+
+/// Load the archived games owned by the player named in the auth cookie
+///
+/// Visitors without a valid token have no archived games.
+async fn load_archived_games(req: &RouteRequest) -> Result<Vec<Game>, RouteError> {
+    let Some(token) = req
+        .cookies
+        .get(AUTH_COOKIE)
+        .and_then(|jwt| AuthToken::verify(jwt, &AUTH_SECRET).ok())
+    else {
+        return Ok(vec![]);
+    };
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    session_manager
+        .get_archived_games(token.player_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))
+}
+
+/// Handles the health check route
+pub async fn health_route(_req: RouteRequest) -> Content {
+    Content::Json(serde_json::json!({
+        "success": true
+    }))
+}
+
+/// Handles the metrics route, exposing game event counters for Prometheus to scrape
+pub async fn metrics_route(_req: RouteRequest) -> Content {
+    Content::Raw {
+        data: Bytes::from(STATE.metrics().render_prometheus()),
+        content_type: "text/plain; version=0.0.4".to_string(),
     }
 }
 
-/// Handles the game page route
+/// Set up log output from the `[logging]` config, falling back to the
+/// default format filtered by `RUST_LOG` when the config can't be loaded so
+/// [`init_config`] can still report why
+pub fn init_logging() {
+    logging::init(STATE.config().ok().map(|config| &config.logging));
+}
+
+/// Load and validate the application configuration
+///
+/// Binaries call this at startup so an invalid configuration fails fast
+/// instead of on the first request that needs it.
 ///
 /// # Errors
 ///
-/// * If method is not GET
-/// * If game ID is not a valid UUID
-/// * If game ID is not found
-/// * If getting game fails
-/// * If getting game players fails
-/// * If getting game votes fails
+/// * If the configuration cannot be loaded, is invalid or asks for
+///   something the app can't do
+pub fn init_config() -> Result<(), planning_poker_state::StateError> {
+    let config = STATE.config()?;
+    reject_unsupported(config)?;
+    require_auth_secret(&config.auth, cfg!(debug_assertions))?;
+    check_default_voting_system(&config.voting)?;
+    Ok(())
+}
+
+/// Fails when no auth secret is configured, unless `dev_build`
 ///
-/// # Panics
+/// A random per-process key would sign every player out on each restart and
+/// differ between instances behind a load balancer, so only development
+/// builds may run without `auth.secret`.
+fn require_auth_secret(auth: &AuthConfig, dev_build: bool) -> Result<(), ConfigError> {
+    if auth.secret.is_none() && !dev_build {
+        return Err(ConfigError::Invalid(
+            "auth.secret must be set (or PLANNING_POKER_AUTH_SECRET) outside development builds"
+                .to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Fails on settings the app's server can't honour instead of ignoring them
 ///
-/// * Infallible
-#[allow(clippy::cognitive_complexity)]
-pub async fn game_page_route(req: RouteRequest) -> Result<Content, RouteError> {
-    tracing::info!("game_page_route called with path: {}", req.path);
+/// The built-in HyperChad server doesn't terminate TLS, so a `server.tls`
+/// section is refused rather than serving plain HTTP where HTTPS was asked for.
+fn reject_unsupported(config: &Config) -> Result<(), ConfigError> {
+    if config.server.tls.is_some() {
+        return Err(ConfigError::Unsupported(
+            "server.tls is not supported by the built-in HyperChad server; remove it \
+             and serve the app behind a TLS-terminating proxy"
+                .to_string(),
+        ));
+    }
 
-    if !matches!(req.method, Method::Get) {
-        return Err(RouteError::UnsupportedMethod);
+    Ok(())
+}
+
+/// Fails unless `voting.default_system` names a built-in voting system or
+/// one of the configured decks
+fn check_default_voting_system(voting: &VotingConfig) -> Result<(), ConfigError> {
+    if configured_voting_system(voting, &voting.default_system).is_some() {
+        return Ok(());
     }
 
-    // Extract game_id from path like "/game/uuid-here"
-    let game_id_str = req.path.strip_prefix("/game/").unwrap_or("");
-    tracing::info!(
-        "Game page route: received path = {}, extracted game_id_str = {}",
-        req.path,
-        game_id_str
-    );
-    let game_id = Uuid::parse_str(game_id_str)?;
+    let builtin: Vec<String> = VotingSystem::builtin()
+        .iter()
+        .map(VotingSystem::key)
+        .collect();
+    let decks: Vec<&str> = voting.decks.keys().map(String::as_str).collect();
+    Err(ConfigError::Invalid(format!(
+        "voting.default_system '{}' is not a built-in system ({}) or a configured deck ({})",
+        voting.default_system,
+        builtin.join(", "),
+        decks.join(", ")
+    )))
+}
 
-    // Get session manager from global state
-    let session_manager = STATE
-        .get_session_manager()
-        .await
-        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+/// The voting system `name` refers to given the configured decks, or `None`
+/// for an unknown name
+///
+/// Named decks take precedence over built-in systems, and `custom:` deck
+/// strings are accepted as-is.
+fn configured_voting_system(voting: &VotingConfig, name: &str) -> Option<VotingSystem> {
+    if let Some(values) = voting.deck(name) {
+        return Some(VotingSystem::Custom(values.to_vec()));
+    }
 
-    match session_manager.get_game(game_id).await {
-        Ok(Some(game)) => {
-            let players = session_manager
-                .get_game_players(game_id)
-                .await
-                .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
-            tracing::debug!("Players: {players:?}");
-            let votes = session_manager
-                .get_game_votes(game_id)
-                .await
-                .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
-            tracing::debug!("Votes: {votes:?}");
-            let game_content =
-                planning_poker_ui::game_page_with_data(game_id_str, &game, &players, &votes);
-            Ok(Content::try_view(game_content).unwrap())
-        }
-        Ok(None) => Err(RouteError::RouteFailed("Game not found".to_string())),
-        Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
+    let name = name.trim();
+    if name.starts_with(planning_poker_poker::CUSTOM_DECK_PREFIX) {
+        return Some(VotingSystem::from_string(name));
     }
+
+    VotingSystem::from_builtin_name(name)
 }
 
-/// Handles the get game route
+/// Resolve a voting system reference against the configured named decks
+///
+/// Unknown names fall back to `VotingSystem::from_string`.
+fn resolve_voting_system(name: &str) -> VotingSystem {
+    STATE
+        .config()
+        .ok()
+        .and_then(|config| configured_voting_system(&config.voting, name))
+        .unwrap_or_else(|| VotingSystem::from_string(name))
+}
+
+/// Handles the voting systems route
+///
+/// Without a `voting_system` query parameter this lists every built-in
+/// voting system with its cards as JSON. With one, it renders the deck
+/// preview partial for that system so the create form can refresh it.
 ///
 /// # Errors
 ///
 /// * If method is not GET
-/// * If game ID is not a valid UUID
-/// * If game ID is not found
-/// * If getting game fails
-/// * If getting game players fails
-/// * If getting game votes fails
 ///
 /// # Panics
 ///
 /// * Infallible
-pub async fn get_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+pub async fn voting_systems_route(req: RouteRequest) -> Result<Content, RouteError> {
     if !matches!(req.method, Method::Get) {
         return Err(RouteError::UnsupportedMethod);
     }
 
-    // Extract game_id from path like "/api/games/uuid-here"
-    let game_id_str = req.path.strip_prefix("/api/games/").unwrap_or("");
-    let game_id = Uuid::parse_str(game_id_str)?;
+    if let Some(voting_system) = req.query.get("voting_system") {
+        let preview =
+            planning_poker_ui::deck_preview_for_system(&resolve_voting_system(voting_system));
+        return view(preview);
+    }
 
-    // Get session manager from global state
-    let session_manager = STATE
-        .get_session_manager()
-        .await
-        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    let mut systems: Vec<serde_json::Value> = VotingSystem::builtin()
+        .iter()
+        .map(|system| {
+            serde_json::json!({
+                "key": system.key(),
+                "name": system.display_name(),
+                "options": system.get_voting_options(),
+            })
+        })
+        .collect();
+
+    if let Ok(config) = STATE.config() {
+        systems.extend(config.voting.decks.iter().map(|(name, deck)| {
+            serde_json::json!({
+                "key": name,
+                "name": name,
+                "options": deck.values,
+            })
+        }));
+    }
 
-    match session_manager.get_game(game_id).await {
-        Ok(Some(game)) => {
-            let players = session_manager
-                .get_game_players(game_id)
-                .await
-                .unwrap_or_default();
-            let votes = if game.state == planning_poker_models::GameState::Revealed {
-                Some(
-                    session_manager
-                        .get_game_votes(game_id)
-                        .await
-                        .unwrap_or_default(),
-                )
-            } else {
-                None
-            };
+    Ok(Content::Json(
+        serde_json::json!({ "voting_systems": systems }),
+    ))
+}
 
-            let content = container! {
-                h2 { (format!("Game: {}", game.name)) }
-                div { (format!("State: {:?}", game.state)) }
+/// Parse and validate the join game form, returning the game ID alongside it
+///
+/// # Errors
+///
+/// * If the form cannot be parsed
+/// * If the game ID or player name is empty
+/// * If the game ID is not a valid UUID
+pub fn parse_join_game_form(req: &RouteRequest) -> Result<(Uuid, JoinGameForm), RouteError> {
+    let form_data = req.parse_form::<JoinGameForm>()?;
 
-                div margin-top=20 {
-                    h3 { "Players" }
-                    @for player in players {
-                        div { (format!("{} (joined: {})", player.name, player.joined_at.format("%H:%M"))) }
-                    }
-                }
+    // Validate form data
+    if form_data.game_id.trim().is_empty() {
+        return Err(RouteError::validation("game-id", "Game ID is required"));
+    }
 
-                @if let Some(votes) = votes {
-                    div margin-top=20 {
-                        h3 { "Votes" }
-                        @for vote in votes {
-                            div { (format!("Player {}: {}", vote.player_id, vote.value)) }
-                        }
-                    }
-                }
-            };
-            let game_content = planning_poker_ui::page_layout(&content);
-            Ok(Content::try_view(game_content).unwrap())
-        }
-        Ok(None) => Err(RouteError::RouteFailed("Game not found".to_string())),
-        Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
+    if form_data.player_name.trim().is_empty() {
+        return Err(RouteError::validation(
+            "player-name",
+            "Player name is required",
+        ));
+    }
+
+    form_data.role()?;
+
+    // Parse game ID as UUID
+    let game_id = Uuid::parse_str(&form_data.game_id)?;
+
+    Ok((game_id, form_data))
+}
+
+/// Parse and validate the observe game form, returning the game ID alongside it
+///
+/// # Errors
+///
+/// * If the form cannot be parsed
+/// * If the game ID or observer name is empty
+/// * If the game ID is not a valid UUID
+pub fn parse_observe_game_form(req: &RouteRequest) -> Result<(Uuid, ObserveGameForm), RouteError> {
+    let form_data = req.parse_form::<ObserveGameForm>()?;
+
+    if form_data.game_id.trim().is_empty() {
+        return Err(RouteError::validation("game-id", "Game ID is required"));
     }
+
+    if form_data.observer_name.trim().is_empty() {
+        return Err(RouteError::validation(
+            "observer-name",
+            "Observer name is required",
+        ));
+    }
+
+    let game_id = Uuid::parse_str(&form_data.game_id)?;
+
+    Ok((game_id, form_data))
 }
 
-/// Handles the join game API route
+/// Parse and validate the create game form
+///
+/// # Errors
+///
+/// * If the form cannot be parsed
+/// * If the game name or voting system is empty
+/// * If the custom deck is filled in but lists no cards
+pub fn parse_create_game_form(req: &RouteRequest) -> Result<CreateGameForm, RouteError> {
+    let form_data = req.parse_form::<CreateGameForm>()?;
+
+    // Validate form data
+    if form_data.name.trim().is_empty() {
+        return Err(RouteError::validation("name", "Game name is required"));
+    }
+
+    if form_data.custom_deck()?.is_none() && form_data.voting_system.trim().is_empty() {
+        return Err(RouteError::validation(
+            "voting_system",
+            "Voting system is required",
+        ));
+    }
+
+    Ok(form_data)
+}
+
+/// Parse the story to start voting on, along with the round's optional timer
+///
+/// Browsers post the story as a form field, while API clients may send a
+/// JSON body like `{"story": "Login page", "timer_seconds": "60"}` instead.
+/// A blank or missing story starts an untitled round, and a blank or missing
+/// timer an untimed one.
+///
+/// # Errors
+///
+/// * If the form or JSON body cannot be parsed
+/// * If the story is longer than `MAX_STORY_LENGTH` characters
+/// * If the timer is not a whole number of seconds up to `MAX_VOTING_TIMER_SECS`
+pub fn parse_start_voting_form(
+    req: &RouteRequest,
+) -> Result<(String, Option<Duration>), RouteError> {
+    let is_json = req
+        .headers
+        .get("content-type")
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+
+    let form_data: StartVotingForm = if is_json {
+        let body = req.body.as_ref().ok_or(RouteError::MissingFormData)?;
+        serde_json::from_slice(body).map_err(|e| RouteError::ParseBody(ParseError::SerdeJson(e)))?
+    } else {
+        req.parse_form::<StartVotingForm>()?
+    };
+
+    let story = form_data.story.trim();
+    if story.chars().count() > MAX_STORY_LENGTH {
+        return Err(RouteError::RouteFailed(format!(
+            "Story must be at most {MAX_STORY_LENGTH} characters"
+        )));
+    }
+
+    let timer = form_data
+        .timer_seconds
+        .as_deref()
+        .map(str::trim)
+        .filter(|secs| !secs.is_empty())
+        .map(|secs| match secs.parse::<u64>() {
+            Ok(secs) if (1..=MAX_VOTING_TIMER_SECS).contains(&secs) => {
+                Ok(Duration::from_secs(secs))
+            }
+            _ => Err(RouteError::RouteFailed(format!(
+                "Timer must be between 1 and {MAX_VOTING_TIMER_SECS} seconds"
+            ))),
+        })
+        .transpose()?;
+
+    let story = if story.is_empty() {
+        UNTITLED_STORY.to_string()
+    } else {
+        story.to_string()
+    };
+    Ok((story, timer))
+}
+
+/// Parse the vote form along with its optional confidence
+///
+/// # Errors
+///
+/// * If the form cannot be parsed
+/// * If the confidence is not a known level
+pub fn parse_vote_form(req: &RouteRequest) -> Result<(VoteForm, Option<Confidence>), RouteError> {
+    let form_data = req.parse_form::<VoteForm>()?;
+
+    let confidence = form_data
+        .confidence
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .map(str::parse::<Confidence>)
+        .transpose()
+        .map_err(RouteError::RouteFailed)?;
+
+    Ok((form_data, confidence))
+}
+
+/// Handles the join game route
 ///
 /// # Errors
 ///
@@ -678,184 +1544,355 @@ pub async fn get_game_route(req: RouteRequest) -> Result<Content, RouteError> {
 /// * If game ID is not a valid UUID
 /// * If game ID is not found
 /// * If adding player to game fails
+/// * If getting game players fails
 ///
 /// # Panics
 ///
 /// * Infallible
-pub async fn join_game_api_route(req: RouteRequest) -> Result<Content, RouteError> {
+pub async fn join_game_route(req: RouteRequest) -> Result<Content, RouteError> {
     if !matches!(req.method, Method::Post) {
         return Err(RouteError::UnsupportedMethod);
     }
 
-    // Extract game_id from path like "/api/games/uuid-here/join"
-    let path_parts: Vec<&str> = req.path.split('/').collect();
-    let game_id_str = path_parts.get(3).unwrap_or(&"");
-    let game_id = Uuid::parse_str(game_id_str)?;
-    let body = req.body.as_ref().ok_or(RouteError::MissingFormData)?;
-    let join_request: JoinGameRequest = serde_json::from_slice(body)
-        .map_err(|e| RouteError::ParseBody(ParseError::SerdeJson(e)))?;
+    let (game_id, form_data) = parse_join_game_form(&req)?;
+
+    let player = Player {
+        id: Uuid::new_v4(),
+        name: form_data.player_name.clone(),
+        role: form_data.role()?,
+        team_role: form_data.team_role(),
+        joined_at: Utc::now(),
+    };
+    add_player_and_redirect(&req, game_id, player).await
+}
+
+/// Handles the observe game route: GET shows the form to watch a game,
+/// POST adds the submitted name as an observer and sends them to the game
+///
+/// # Errors
+///
+/// * If method is not GET or POST
+/// * If game ID is not a valid UUID
+/// * If the form's game ID doesn't match the one in the path
+/// * If game ID is not found
+/// * If adding the observer to the game fails
+pub async fn observe_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+    // Extract game_id from path like "/game/uuid-here/observe"
+    let game_id_str = req
+        .path
+        .strip_prefix("/game/")
+        .and_then(|rest| rest.strip_suffix("/observe"))
+        .unwrap_or("");
+    let game_id = Uuid::parse_str(game_id_str)?;
+
+    match req.method {
+        Method::Get => view(planning_poker_ui::page_layout(
+            &planning_poker_ui::observe_game_content(game_id_str),
+        )),
+        Method::Post => {
+            let (form_game_id, form_data) = parse_observe_game_form(&req)?;
+            if form_game_id != game_id {
+                return Err(RouteError::validation(
+                    "game-id",
+                    "Game ID doesn't match the game being observed",
+                ));
+            }
+
+            let observer = Player {
+                id: Uuid::new_v4(),
+                name: form_data.observer_name,
+                role: PlayerRole::Observer,
+                team_role: TeamRole::default(),
+                joined_at: Utc::now(),
+            };
+            add_player_and_redirect(&req, game_id, observer).await
+        }
+        _ => Err(RouteError::UnsupportedMethod),
+    }
+}
 
+/// Add `player` to an existing game and send them straight to its page
+/// with a token for it
+async fn add_player_and_redirect(
+    req: &RouteRequest,
+    game_id: Uuid,
+    player: Player,
+) -> Result<Content, RouteError> {
     // Get session manager from global state
     let session_manager = STATE
         .get_session_manager()
         .await
         .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
 
+    // Check if game exists
     match session_manager.get_game(game_id).await {
         Ok(Some(_)) => {
-            let player = Player {
-                id: Uuid::new_v4(),
-                name: join_request.player_name,
-                is_observer: false,
-                joined_at: Utc::now(),
-            };
-            match session_manager
-                .add_player_to_game(game_id, player.clone())
-                .await
-            {
-                Ok(()) => {
-                    // Send real-time updates to all connected clients
-                    if let Ok(players) = session_manager.get_game_players(game_id).await {
-                        update_players_list(game_id_str, players).await;
-                    }
-
-                    let success_content = container! {
-                        div padding=20 {
-                            h2 { "Joined Game!" }
-                            div { "Successfully joined the game" }
-                            div { (format!("Your player ID: {}", player.id)) }
-                        }
-                    };
-                    Ok(Content::try_view(success_content).unwrap())
-                }
-                Err(e) => Err(RouteError::RouteFailed(format!("Failed to join game: {e}"))),
+            // Join the game directly via database
+            let player_id = player.id;
+            if let Err(e) = session_manager.add_player_to_game(game_id, player).await {
+                return Err(join_error(&e));
             }
+
+            // Send the player straight to the game page
+            tracing::info!("Join game success: game_id = {game_id}");
+            redirect_with_token(
+                req,
+                &Flash::Joined.add_to(&player_game_url(game_id, player_id)),
+                &AuthToken::new(player_id, game_id, token_ttl()),
+            )
         }
-        Ok(None) => Err(RouteError::RouteFailed("Game not found".to_string())),
+        Ok(None) => Err(RouteError::NotFound("Game not found".to_string())),
         Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
     }
 }
 
-/// Extract game ID from API path
-fn extract_game_id_from_path(path: &str) -> Result<(Uuid, &str), RouteError> {
-    let path_parts: Vec<&str> = path.split('/').collect();
-    let game_id_str = path_parts.get(3).unwrap_or(&"");
-    let game_id = Uuid::parse_str(game_id_str)?;
-    Ok((game_id, game_id_str))
+/// URL of the game page for a specific player
+fn player_game_url(game_id: Uuid, player_id: Uuid) -> String {
+    format!("/game/{game_id}?player_id={player_id}")
 }
 
-/// Get the first player from a game (temporary workaround for session management)
-async fn get_first_player(
-    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
-    game_id: Uuid,
-) -> Result<(Uuid, String), RouteError> {
-    let players = session_manager
-        .get_game_players(game_id)
-        .await
-        .unwrap_or_default();
-
-    players.first().map_or_else(
-        || Err(RouteError::RouteFailed("No players in game".to_string())),
-        |first_player| Ok((first_player.id, first_player.name.clone())),
-    )
+/// A one-off notice for the page a client is sent on to, carried in its
+/// `flash` query parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flash {
+    Joined,
+    Created,
 }
 
-/// Send vote result updates via SSE
-async fn send_vote_updates(
-    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
-    game_id: Uuid,
-    game_id_str: &str,
-) {
-    if let Ok(votes) = session_manager.get_game_votes(game_id).await {
-        if let Ok(Some(game)) = session_manager.get_game(game_id).await {
-            let revealed = matches!(game.state, GameState::Revealed);
-            tracing::info!(
-                "Updating vote results: {} votes, revealed: {}",
-                votes.len(),
-                revealed
-            );
-            update_vote_results(game_id_str, votes, revealed).await;
+impl Flash {
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Joined => "joined",
+            Self::Created => "created",
+        }
+    }
+
+    /// The notice a request's `flash` query parameter names, if any
+    fn from_request(req: &RouteRequest) -> Option<Self> {
+        match req.query.get("flash").map(String::as_str) {
+            Some("joined") => Some(Self::Joined),
+            Some("created") => Some(Self::Created),
+            _ => None,
+        }
+    }
+
+    /// `url` with this notice added to its query
+    fn add_to(self, url: &str) -> String {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        format!("{url}{separator}flash={}", self.key())
+    }
+
+    /// The text shown to `my_player_id`; a join notice needs to know who
+    /// joined, so it is dropped for anyone not in `players`
+    fn message(self, players: &[Player], my_player_id: Option<Uuid>) -> Option<String> {
+        match self {
+            Self::Joined => players
+                .iter()
+                .find(|player| Some(player.id) == my_player_id)
+                .map(|player| format!("Joined as {}", player.name)),
+            Self::Created => Some("Game created, share its ID so your team can join".to_string()),
         }
     }
 }
 
-/// Handles the vote route
-///
-/// # Errors
-///
-/// * If method is not POST
-/// * If game ID is not a valid UUID
-/// * If game ID is not found
-/// * If getting game fails
-/// * If getting game players fails
-/// * If casting vote fails
+/// Whether `req` came from a form posted by the page's script rather than a
+/// plain browser form post
+fn is_hx_request(req: &RouteRequest) -> bool {
+    req.headers
+        .get("hx-request")
+        .is_some_and(|value| value.trim() == "true")
+}
+
+/// Build a response that sends the client on to `url`
 ///
-/// # Panics
+/// hyperchad's `Content` has no redirect response, so this falls back to a
+/// meta refresh document with a plain link for clients that don't follow it.
+fn redirect_to(url: &str) -> Content {
+    redirect_document(url, "")
+}
+
+/// Send the client that made `req` on to `url`, storing `token` as the
+/// player's auth cookie
 ///
-/// * Infallible
-pub async fn vote_route(req: RouteRequest) -> Result<Content, RouteError> {
-    if !matches!(req.method, Method::Post) {
-        return Err(RouteError::UnsupportedMethod);
+/// A script-posted form swaps the response into the current page, where a
+/// meta refresh does nothing, so it gets a script that navigates instead.
+/// Plain form posts get the meta refresh document, whose link doubles as a
+/// success page if the refresh isn't followed.
+fn redirect_with_token(
+    req: &RouteRequest,
+    url: &str,
+    token: &AuthToken,
+) -> Result<Content, RouteError> {
+    let cookie = auth_cookie_script(token)?;
+    if !is_hx_request(req) {
+        return Ok(redirect_document(url, &cookie));
     }
 
-    let (game_id, game_id_str) = extract_game_id_from_path(&req.path)?;
-    let form_data = req.parse_form::<VoteForm>()?;
+    let link: String = planning_poker_ui::redirect_page(url)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    Ok(Content::Raw {
+        data: Bytes::from(format!(
+            "{cookie}<script>window.location.assign(\"{url}\");</script>{link}"
+        )),
+        content_type: "text/html".to_string(),
+    })
+}
 
-    let session_manager = STATE
-        .get_session_manager()
-        .await
-        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+fn redirect_document(url: &str, head: &str) -> Content {
+    let body: String = planning_poker_ui::redirect_page(url)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
 
-    let (player_id, player_name) = get_first_player(session_manager, game_id).await?;
+    let html = format!(
+        "<!DOCTYPE html><html><head>{head}<meta http-equiv=\"refresh\" content=\"0; url={url}\"></head><body>{body}</body></html>"
+    );
 
-    let vote = Vote {
-        player_id,
-        player_name,
-        value: form_data.vote,
-        cast_at: Utc::now(),
-    };
+    Content::Raw {
+        data: Bytes::from(html),
+        content_type: "text/html".to_string(),
+    }
+}
 
-    match session_manager.cast_vote(game_id, vote).await {
-        Ok(()) => {
-            tracing::info!(
-                "Vote cast successfully for game {}, triggering partial updates",
-                game_id
-            );
+/// How long newly issued auth tokens stay valid
+fn token_ttl() -> chrono::Duration {
+    let seconds = STATE.config().map_or_else(
+        |_| AuthConfig::default().token_ttl_seconds,
+        |config| config.auth.token_ttl_seconds,
+    );
+    i64::try_from(seconds)
+        .ok()
+        .and_then(chrono::Duration::try_seconds)
+        .unwrap_or(chrono::Duration::MAX)
+}
 
-            send_vote_updates(session_manager, game_id, game_id_str).await;
+/// Sign `token` into a script that stores it as the auth cookie
+///
+/// hyperchad's `Content` can't set response headers, so the cookie is
+/// written by the page instead of a `Set-Cookie` header.
+fn auth_cookie_script(token: &AuthToken) -> Result<String, RouteError> {
+    let jwt = token
+        .sign(&AUTH_SECRET)
+        .map_err(|e| RouteError::RouteFailed(format!("Failed to sign auth token: {e}")))?;
+    let max_age = (token.expires_at - token.issued_at).num_seconds();
+
+    Ok(format!(
+        "<script>document.cookie=\"{AUTH_COOKIE}={jwt}; Path=/; Max-Age={max_age}; SameSite=Strict\";</script>"
+    ))
+}
 
-            let success_content = container! {
-                div { "Vote cast successfully" }
-            };
-            Ok(Content::try_view(success_content).unwrap())
+/// Identify the player making a request to `game_id` from their auth cookie
+///
+/// Alongside the verified token this returns a fresh one when the current
+/// token is within five minutes of expiring.
+fn authenticate(
+    req: &RouteRequest,
+    game_id: Uuid,
+) -> Result<(AuthToken, Option<AuthToken>), RouteError> {
+    let jwt = req
+        .cookies
+        .get(AUTH_COOKIE)
+        .ok_or(RouteError::InvalidToken)?;
+    let token = AuthToken::verify(jwt, &AUTH_SECRET).map_err(|e| match e {
+        AuthError::ExpiredToken => RouteError::ExpiredToken,
+        AuthError::InvalidToken | AuthError::Encode(_) => RouteError::InvalidToken,
+    })?;
+
+    if token.game_id != game_id {
+        return Err(RouteError::Forbidden(
+            "Your token is for a different game".to_string(),
+        ));
+    }
+
+    let refreshed = token
+        .needs_refresh(Utc::now())
+        .then(|| token.refreshed(token_ttl()));
+    Ok((token, refreshed))
+}
+
+/// Respond with a toast for the acting player's `toast-area`
+///
+/// Partial updates reach every open page, so a message meant for one player
+/// rides back on the response to their own request instead.
+fn send_toast(
+    kind: ToastKind,
+    message: &str,
+    delivery: &Delivery,
+    refreshed: Option<&AuthToken>,
+) -> Result<Content, RouteError> {
+    let mut content = planning_poker_ui::toast_content(kind, message);
+    content.extend(delivery.warning());
+    respond_with_token(content, refreshed)
+}
+
+/// Answer a mutating route's recoverable errors with an error toast for the
+/// acting player instead of an error page
+///
+/// A change the game turned down, one the player isn't allowed to make or a
+/// form field that needs fixing can all be retried from the same page.
+/// Anything else, like a bad token or a lost database, keeps its error page.
+fn toast_errors(result: Result<Content, RouteError>) -> Result<Content, RouteError> {
+    match result {
+        Err(RouteError::Rejected(reason) | RouteError::Forbidden(reason)) => {
+            tracing::info!("Change turned down: {reason}");
+            send_toast(ToastKind::Error, &reason, &Delivery::default(), None)
+        }
+        Err(error @ RouteError::Validation { .. }) => {
+            tracing::info!("Change turned down: {error}");
+            send_toast(
+                ToastKind::Error,
+                &error.to_string(),
+                &Delivery::default(),
+                None,
+            )
         }
-        Err(e) => Err(RouteError::RouteFailed(format!("Failed to cast vote: {e}"))),
+        result => result,
     }
 }
 
-/// Handles the reveal votes route
+/// Respond with `content`, handing back `refreshed` as the new auth cookie if set
+fn respond_with_token(
+    content: Containers,
+    refreshed: Option<&AuthToken>,
+) -> Result<Content, RouteError> {
+    let Some(token) = refreshed else {
+        return view(content);
+    };
+
+    let body: String = content.iter().map(ToString::to_string).collect();
+    Ok(Content::Raw {
+        data: Bytes::from(format!("{body}{}", auth_cookie_script(token)?)),
+        content_type: "text/html".to_string(),
+    })
+}
+
+/// Handles the create game router
 ///
 /// # Errors
 ///
 /// * If method is not POST
-/// * If game ID is not a valid UUID
-/// * If game ID is not found
+/// * If form data is missing
+/// * If form data is invalid
+/// * If creating game fails
 /// * If getting game fails
-/// * If revealing votes fails
 ///
 /// # Panics
 ///
 /// * Infallible
-#[allow(clippy::cognitive_complexity)]
-pub async fn reveal_votes_route(req: RouteRequest) -> Result<Content, RouteError> {
+pub async fn create_game_route(req: RouteRequest) -> Result<Content, RouteError> {
     if !matches!(req.method, Method::Post) {
         return Err(RouteError::UnsupportedMethod);
     }
 
-    // Extract game_id from path like "/api/games/uuid-here/reveal"
-    let path_parts: Vec<&str> = req.path.split('/').collect();
-    let game_id_str = path_parts.get(3).unwrap_or(&"");
-    let game_id = Uuid::parse_str(game_id_str)?;
+    let form_data = parse_create_game_form(&req)?;
+    let settings = form_data.settings()?;
+    let voting_system = match form_data.custom_deck()? {
+        Some(deck) => deck,
+        None => resolve_voting_system(&form_data.voting_system),
+    };
+    let owner_id = Uuid::new_v4(); // TODO: Get from authentication
 
     // Get session manager from global state
     let session_manager = STATE
@@ -863,78 +1900,62 @@ pub async fn reveal_votes_route(req: RouteRequest) -> Result<Content, RouteError
         .await
         .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
 
-    // Reveal the votes first
-    match session_manager.reveal_votes(game_id).await {
-        Ok(()) => {
-            tracing::info!(
-                "Votes revealed successfully for game {}, triggering partial updates",
-                game_id
-            );
-
-            // Send partial updates via SSE instead of returning full page
-            if let Ok(Some(game)) = session_manager.get_game(game_id).await {
-                let status = match game.state {
-                    GameState::Waiting => "Waiting for players",
-                    GameState::Voting => "Voting in progress",
-                    GameState::Revealed => "Votes revealed",
-                };
-                tracing::info!(
-                    "Game state after reveal: {:?}, status: {}",
-                    game.state,
-                    status
-                );
-                update_game_status(game_id_str, status).await;
-
-                // Update voting section to reflect revealed state
-                let voting_active = matches!(game.state, GameState::Voting);
-                update_entire_voting_section(game_id_str, &game, voting_active).await;
-            }
-
-            if let Ok(votes) = session_manager.get_game_votes(game_id).await {
-                tracing::info!("Revealing {} votes", votes.len());
-                update_entire_results_section(game_id_str, votes, true).await;
-            }
-
-            // Return minimal success response
-            let success_content = container! {
-                div { "Votes revealed successfully" }
-            };
-            Ok(Content::try_view(success_content).unwrap())
+    match session_manager
+        .create_game_with_settings(
+            form_data.name.clone(),
+            voting_system.key(),
+            owner_id,
+            settings,
+        )
+        .await
+    {
+        Ok(game) => {
+            tracing::info!("Create game success: game_id = {}", game.id);
+            redirect_with_token(
+                &req,
+                &Flash::Created.add_to(&player_game_url(game.id, game.owner_id)),
+                &AuthToken::new(game.owner_id, game.id, token_ttl()),
+            )
         }
         Err(e) => Err(RouteError::RouteFailed(format!(
-            "Failed to reveal votes: {e}"
+            "Failed to create game: {e}"
         ))),
     }
 }
 
-/// Handles the start voting route
+/// Handles the game page route
 ///
 /// # Errors
 ///
-/// * If method is not POST
+/// * If method is not GET
 /// * If game ID is not a valid UUID
 /// * If game ID is not found
-/// * If getting game fails
-/// * If starting voting fails
-/// * If getting game votes fails
-/// * If getting game fails
-/// * If game state is not waiting
+/// * If getting the game snapshot fails
 ///
 /// # Panics
 ///
 /// * Infallible
 #[allow(clippy::cognitive_complexity)]
-pub async fn start_voting_route(req: RouteRequest) -> Result<Content, RouteError> {
-    if !matches!(req.method, Method::Post) {
+pub async fn game_page_route(req: RouteRequest) -> Result<Content, RouteError> {
+    tracing::info!("game_page_route called with path: {}", req.path);
+
+    if !matches!(req.method, Method::Get) {
         return Err(RouteError::UnsupportedMethod);
     }
 
-    // Extract game_id from path like "/api/games/uuid-here/start-voting"
-    let path_parts: Vec<&str> = req.path.split('/').collect();
-    let game_id_str = path_parts.get(3).unwrap_or(&"");
+    // Extract game_id from path like "/game/uuid-here"
+    let game_id_str = req.path.strip_prefix("/game/").unwrap_or("");
+    tracing::info!(
+        "Game page route: received path = {}, extracted game_id_str = {}",
+        req.path,
+        game_id_str
+    );
     let game_id = Uuid::parse_str(game_id_str)?;
-
-    tracing::info!("START VOTING: Received request for game {}", game_id);
+    // Viewers without a valid token for this game still see the page, just
+    // without the owner controls
+    let my_player_id = authenticate(&req, game_id)
+        .ok()
+        .map(|(token, _)| token.player_id);
 
     // Get session manager from global state
     let session_manager = STATE
@@ -942,230 +1963,3852 @@ pub async fn start_voting_route(req: RouteRequest) -> Result<Content, RouteError
         .await
         .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
 
-    // Check current game state before starting voting
-    if let Ok(Some(game)) = session_manager.get_game(game_id).await {
-        tracing::info!(
-            "START VOTING: Current game state before start: {:?}",
-            game.state
-        );
-    }
-
-    // Parse story from form data
-    let form_data = req.parse_form::<StartVotingForm>()?;
-    let story = form_data.story.trim().to_string();
-
-    // Use default if story is empty
-    let story = if story.is_empty() {
-        "Untitled Story".to_string()
-    } else {
-        story
-    };
-
-    match session_manager.start_voting(game_id, story).await {
-        Ok(()) => {
-            tracing::info!(
-                "START VOTING: session_manager.start_voting() completed successfully for game {}",
-                game_id
+    match session_manager.get_game_snapshot(game_id).await {
+        // A completed game is only looked back on, so it gets its summary
+        // instead of the voting controls
+        Ok(Some(snapshot)) if snapshot.game.state == GameState::Completed => {
+            let rounds = session_manager
+                .get_round_history(game_id)
+                .await
+                .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+            let content = planning_poker_ui::completed_game_content(
+                game_id_str,
+                &snapshot.game,
+                &snapshot.players,
+                &rounds,
             );
-
-            // Send partial updates via SSE instead of returning full page
-            if let Ok(Some(game)) = session_manager.get_game(game_id).await {
-                let status = match game.state {
-                    GameState::Waiting => "Waiting for players",
-                    GameState::Voting => "Voting in progress",
-                    GameState::Revealed => "Votes revealed",
-                };
-                tracing::info!(
-                    "START VOTING: Game state after start_voting call: {:?}, status: {}",
-                    game.state,
-                    status
-                );
-                update_game_status(game_id_str, status).await;
-
-                let voting_active = matches!(game.state, GameState::Voting);
-                tracing::info!("START VOTING: Calculated voting_active: {}", voting_active);
-
-                // Update the entire voting section to avoid partial update conflicts
-                update_entire_voting_section(game_id_str, &game, voting_active).await;
-
-                // Update story display and input
-                update_current_story(game.current_story.as_ref(), voting_active).await;
-                update_story_input(game_id_str, voting_active, game.current_story.as_ref()).await;
-            } else {
-                tracing::error!("START VOTING: Failed to get game after start_voting call");
-            }
-
-            if let Ok(votes) = session_manager.get_game_votes(game_id).await {
-                if let Ok(Some(game)) = session_manager.get_game(game_id).await {
-                    let votes_revealed = matches!(game.state, GameState::Revealed);
-                    update_entire_results_section(game_id_str, votes, votes_revealed).await;
-                }
-            }
-
-            // Return minimal success response
-            let success_content = container! {
-                div { "Voting started successfully" }
-            };
-            Ok(Content::try_view(success_content).unwrap())
+            view(planning_poker_ui::page_layout(&content))
         }
-        Err(e) => Err(RouteError::RouteFailed(format!(
-            "Failed to start voting: {e}"
-        ))),
+        // Observers watch the round without ever being offered a card
+        Ok(Some(snapshot))
+            if my_player_id.is_some_and(|id| {
+                snapshot
+                    .players
+                    .iter()
+                    .any(|player| player.id == id && player.is_observer())
+            }) =>
+        {
+            view(planning_poker_ui::observer_game_page(
+                game_id_str,
+                &snapshot.game,
+                &snapshot.players,
+                &snapshot.votes,
+            ))
+        }
+        Ok(Some(snapshot)) => {
+            tracing::debug!("Players: {:?}", snapshot.players);
+            tracing::debug!("Votes: {:?}", snapshot.votes);
+            let flash = Flash::from_request(&req)
+                .and_then(|flash| flash.message(&snapshot.players, my_player_id));
+            let public_url = STATE
+                .config()
+                .ok()
+                .and_then(|config| config.server.public_url.clone());
+            let game_content = planning_poker_ui::game_page_with_data(
+                game_id_str,
+                &snapshot.game,
+                &snapshot.players,
+                &snapshot.votes,
+                &snapshot.stories,
+                my_player_id,
+                &invite_url(public_url.as_deref(), &req.headers, game_id_str),
+                flash.as_deref(),
+            );
+            view(game_content)
+        }
+        Ok(None) => Err(RouteError::NotFound("Game not found".to_string())),
+        Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
     }
 }
 
-/// Handles the reset voting route
+/// The link that lands someone on the home page with `game_id` filled in
+///
+/// It starts with the configured `public_url`, or else the `Host` the request
+/// was sent to, so a link copied from the game page works from elsewhere.
+/// Without either it is a path on the current site.
+fn invite_url(
+    public_url: Option<&str>,
+    headers: &BTreeMap<String, String>,
+    game_id: &str,
+) -> String {
+    let base = public_url.map_or_else(
+        || {
+            headers.get("host").map_or_else(String::new, |host| {
+                let scheme = headers
+                    .get("x-forwarded-proto")
+                    .map_or("http", String::as_str);
+                format!("{scheme}://{host}")
+            })
+        },
+        |public_url| public_url.trim_end_matches('/').to_string(),
+    );
+    format!("{base}/?game={game_id}")
+}
+
+/// Handles the join prompt an invite link can point at, asking only for the
+/// name to join the game under
 ///
 /// # Errors
 ///
-/// * If method is not POST
+/// * If method is not GET
 /// * If game ID is not a valid UUID
 /// * If game ID is not found
-/// * If getting game fails
-/// * If resetting voting fails
-/// * If getting game votes fails
-///
-/// # Panics
-///
-/// * Infallible
-#[allow(clippy::cognitive_complexity)]
-pub async fn reset_voting_route(req: RouteRequest) -> Result<Content, RouteError> {
-    if !matches!(req.method, Method::Post) {
+/// * If getting the game fails
+pub async fn join_prompt_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
         return Err(RouteError::UnsupportedMethod);
     }
 
-    // Extract game_id from path like "/api/games/uuid-here/reset"
-    let path_parts: Vec<&str> = req.path.split('/').collect();
-    let game_id_str = path_parts.get(3).unwrap_or(&"");
+    // Extract game_id from path like "/join/uuid-here"
+    let game_id_str = req.path.strip_prefix("/join/").unwrap_or("");
     let game_id = Uuid::parse_str(game_id_str)?;
 
-    // Get session manager from global state
     let session_manager = STATE
         .get_session_manager()
         .await
         .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    let game = session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .ok_or_else(|| RouteError::NotFound("Game not found".to_string()))?;
 
-    match session_manager.reset_voting(game_id).await {
-        Ok(()) => {
-            tracing::info!(
-                "Voting reset successfully for game {}, triggering partial updates",
-                game_id
+    view(planning_poker_ui::page_layout(
+        &planning_poker_ui::join_prompt_content(game_id_str, &game.name),
+    ))
+}
+
+/// Handles the presentation route, rendering a read-only board of the game
+/// for a shared screen
+///
+/// The board keeps the section ids the game page's partial updates target,
+/// so it follows the game through the same updates.
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If getting the game snapshot fails
+pub async fn present_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/game/uuid-here/present"
+    let game_id_str = req
+        .path
+        .strip_prefix("/game/")
+        .and_then(|rest| rest.strip_suffix("/present"))
+        .unwrap_or("");
+    let game_id = Uuid::parse_str(game_id_str)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    match session_manager.get_game_snapshot(game_id).await {
+        Ok(Some(snapshot)) => view(planning_poker_ui::presentation_layout(
+            &planning_poker_ui::presentation_content(
+                &snapshot.game,
+                &snapshot.players,
+                &snapshot.votes,
+            ),
+        )),
+        Ok(None) => Err(RouteError::NotFound("Game not found".to_string())),
+        Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
+    }
+}
+
+/// Handles the game snapshot route, rendering every section a round
+/// transition can change for the game as it is now
+///
+/// Each section comes wrapped in an element with the id of the partial
+/// target it fills, so a client whose SSE connection dropped can fetch
+/// `/game/{id}/snapshot` when it reconnects and swap each one in to catch up.
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If loading the game, its votes or its players fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn game_snapshot_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/game/uuid-here/snapshot"
+    let game_id_str = req
+        .path
+        .strip_prefix("/game/")
+        .and_then(|rest| rest.strip_suffix("/snapshot"))
+        .unwrap_or("");
+    let game_id = Uuid::parse_str(game_id_str)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    let (game, votes, players) = load_game_snapshot(session_manager, game_id).await?;
+    let partials = game_snapshot_partials(game_id_str, &game, &votes, &players);
+
+    let content = container! {
+        @for (target, partial) in partials {
+            div id=(target) {
+                (partial)
+            }
+        }
+    };
+    view(content)
+}
+
+/// Handles the session summary page, showing how each player took part
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If getting the player stats fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn session_summary_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/game/uuid-here/summary"
+    let game_id_str = req
+        .path
+        .strip_prefix("/game/")
+        .and_then(|rest| rest.strip_suffix("/summary"))
+        .unwrap_or("");
+    let game_id = Uuid::parse_str(game_id_str)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    let game = session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .ok_or_else(|| RouteError::NotFound("Game not found".to_string()))?;
+    let stats = session_manager
+        .get_player_stats(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    let content = planning_poker_ui::session_summary_content(game_id_str, &game, &stats);
+    view(planning_poker_ui::page_layout(&content))
+}
+
+/// Handles the player stats API route, returning each player's stats as JSON
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If getting the player stats fails
+pub async fn player_stats_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/stats"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    if session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .is_none()
+    {
+        return Err(RouteError::NotFound("Game not found".to_string()));
+    }
+
+    let stats = session_manager
+        .get_player_stats(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    Ok(Content::Json(serde_json::json!({ "players": stats })))
+}
+
+/// Handles the audit log API route, returning the game's events as JSON,
+/// oldest first; only the game owner may read it
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If game ID is not a valid UUID
+/// * If the requester has no valid token for the game or isn't its owner
+/// * If game ID is not found
+/// * If getting the audit log fails
+pub async fn audit_log_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/audit-log"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let (token, _) = authenticate(&req, game_id)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    get_owned_game(&session_manager, game_id, token.player_id).await?;
+
+    let entries = session_manager
+        .get_game_audit_log(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    Ok(Content::Json(serde_json::json!({ "entries": entries })))
+}
+
+/// Handles the round history route
+///
+/// Lists a page of the game's completed rounds, latest first, starting at the
+/// `offset` query parameter. With `format=json` the page is returned as JSON,
+/// otherwise as the partial for the history section of the game page, or as
+/// the collapsed section when `collapsed=true`.
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If game ID is not a valid UUID
+/// * If the offset is not a number
+/// * If game ID is not found
+/// * If getting the round history fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn round_history_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/rounds"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+
+    if req
+        .query
+        .get("collapsed")
+        .is_some_and(|value| value == "true")
+    {
+        return view(planning_poker_ui::round_history_collapsed(&game_id_str));
+    }
+
+    let offset = req
+        .query
+        .get("offset")
+        .map_or(Ok(0), |offset| offset.parse::<usize>())
+        .map_err(|e| RouteError::RouteFailed(format!("Invalid offset: {e}")))?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    if session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .is_none()
+    {
+        return Err(RouteError::NotFound("Game not found".to_string()));
+    }
+
+    let history = session_manager
+        .get_round_history(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+    let rounds: Vec<_> = history
+        .iter()
+        .skip(offset)
+        .take(ROUND_HISTORY_PAGE_SIZE)
+        .cloned()
+        .collect();
+    let next_offset = Some(offset + rounds.len()).filter(|next| *next < history.len());
+
+    if req
+        .query
+        .get("format")
+        .is_some_and(|format| format == "json")
+    {
+        return Ok(Content::Json(serde_json::json!({
+            "rounds": rounds,
+            "next_offset": next_offset,
+        })));
+    }
+
+    view(planning_poker_ui::round_history_content(
+        &game_id_str,
+        &rounds,
+        offset,
+        next_offset,
+    ))
+}
+
+/// Handles the game search route, listing one page of the games whose name
+/// contains `q` as JSON along with how many games match in total
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If the query parameters are invalid, see [`SearchGamesRequest::from_query`]
+/// * If searching the games fails
+pub async fn search_games_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    let search = SearchGamesRequest::from_query(&req.query)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    let (games, total) = session_manager
+        .search_games(
+            &search.q,
+            search.state,
+            search.voting_system.as_deref(),
+            search.owner_id,
+            search.page,
+        )
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    Ok(Content::Json(serde_json::json!({
+        "games": games,
+        "total": total,
+        "page": search.page.page,
+        "page_size": search.page.page_size,
+    })))
+}
+
+/// Handles `GET /api/games`, listing one page of the games as JSON along
+/// with how many games there are in total
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If the query parameters are invalid, see [`GamesListRequest::from_query`]
+/// * If listing the games fails
+pub async fn list_games_api_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    let list = GamesListRequest::from_query(&req.query)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    let (games, total) = session_manager
+        .list_games_paginated(list.state, list.include_completed, list.page)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    Ok(Content::Json(serde_json::json!({
+        "games": games,
+        "total": total,
+        "page": list.page.page,
+        "page_size": list.page.page_size,
+    })))
+}
+
+/// Handles the games list page, showing one page of the games with their
+/// player counts
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If the query parameters are invalid, see [`GamesListRequest::from_query`]
+/// * If listing the games or their players fails
+pub async fn games_list_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    let list = GamesListRequest::from_query(&req.query)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    let (games, total) = session_manager
+        .list_games_paginated(list.state, list.include_completed, list.page)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    let mut player_counts = HashMap::new();
+    for game in &games {
+        let players = session_manager
+            .get_game_players(game.id)
+            .await
+            .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+        player_counts.insert(game.id, players.len());
+    }
+
+    view(planning_poker_ui::page_layout(
+        &planning_poker_ui::games_list_content(
+            &games,
+            &player_counts,
+            list.state,
+            list.page,
+            total,
+            list.include_completed,
+        ),
+    ))
+}
+
+/// Handles the get game route
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If getting game fails
+/// * If getting game players fails
+/// * If getting game votes fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn get_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let players_page = players_page_params(&req)?;
+
+    // Get session manager from global state
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    match session_manager.get_game(game_id).await {
+        Ok(Some(game)) => {
+            let (players, players_total) = match players_page {
+                Some(params) => session_manager
+                    .get_game_players_paginated(game_id, params)
+                    .await
+                    .unwrap_or_default(),
+                None => {
+                    let players = session_manager
+                        .get_game_players(game_id)
+                        .await
+                        .unwrap_or_default();
+                    let total = u64::try_from(players.len()).unwrap_or(u64::MAX);
+                    (players, total)
+                }
+            };
+            let votes = if game.state == planning_poker_models::GameState::Revealed {
+                Some(
+                    displayed_votes(session_manager, &game)
+                        .await
+                        .unwrap_or_default(),
+                )
+            } else {
+                None
+            };
+
+            let content = container! {
+                h2 { (format!("Game: {}", game.name)) }
+                div { (format!("State: {:?}", game.state)) }
+                @if let Some(deadline) = game.voting_deadline {
+                    div { (format!("Voting deadline: {}", deadline.to_rfc3339())) }
+                }
+
+                div margin-top=20 {
+                    h3 { "Players" }
+                    @if let Some(params) = players_page {
+                        div { (format!("Page {} ({} players in total)", params.page, players_total)) }
+                    }
+                    @for player in players {
+                        div { (format!("{} (joined: {})", player.name, player.joined_at.format("%H:%M"))) }
+                    }
+                }
+
+                @if let Some(votes) = votes {
+                    div margin-top=20 {
+                        h3 { "Votes" }
+                        @for vote in votes {
+                            div { (format!("Player {}: {}", vote.player_id, vote.value)) }
+                        }
+                    }
+                }
+            };
+            let game_content = planning_poker_ui::page_layout(&content);
+            view(game_content)
+        }
+        Ok(None) => Err(RouteError::NotFound("Game not found".to_string())),
+        Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
+    }
+}
+
+/// Reads `players_page` and `players_page_size` from the query, if either is given
+///
+/// # Errors
+///
+/// * If either is not a whole number of at least 1
+/// * If the page size is over `MAX_PLAYERS_PAGE_SIZE`
+fn players_page_params(req: &RouteRequest) -> Result<Option<PaginationParams>, RouteError> {
+    let page = req.query.get("players_page");
+    let page_size = req.query.get("players_page_size");
+    if page.is_none() && page_size.is_none() {
+        return Ok(None);
+    }
+
+    let parse = |name: &str, value: Option<&String>, default: u32| {
+        value
+            .map_or(Ok(default), |value| value.parse::<u32>())
+            .ok()
+            .filter(|value| *value >= 1)
+            .ok_or_else(|| RouteError::RouteFailed(format!("Invalid {name}")))
+    };
+    let page = parse("players_page", page, 1)?;
+    let page_size = parse("players_page_size", page_size, DEFAULT_PLAYERS_PAGE_SIZE)?;
+    if page_size > MAX_PLAYERS_PAGE_SIZE {
+        return Err(RouteError::RouteFailed(format!(
+            "players_page_size must be at most {MAX_PLAYERS_PAGE_SIZE}"
+        )));
+    }
+
+    Ok(Some(PaginationParams { page, page_size }))
+}
+
+/// Handles the join game API route
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If adding player to game fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn join_game_api_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/join"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let body = req.body.as_ref().ok_or(RouteError::MissingFormData)?;
+    let join_request: JoinGameRequest = serde_json::from_slice(body)
+        .map_err(|e| RouteError::ParseBody(ParseError::SerdeJson(e)))?;
+    if join_request.player_name.trim().is_empty() {
+        return Err(RouteError::validation(
+            "player_name",
+            "Player name is required",
+        ));
+    }
+
+    // Get session manager from global state
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    match session_manager.get_game(game_id).await {
+        Ok(Some(_)) => {
+            let player = Player {
+                id: Uuid::new_v4(),
+                name: join_request.player_name,
+                role: join_request.role,
+                team_role: join_request.team_role,
+                joined_at: Utc::now(),
+            };
+            match session_manager
+                .add_player_to_game(game_id, player.clone())
+                .await
+            {
+                Ok(()) => {
+                    // Send real-time updates to all connected clients
+                    UPDATES.mark_dirty(game_id, &[UpdateTarget::PlayersList]);
+
+                    let success_content = container! {
+                        div padding=20 {
+                            h2 { "Joined Game!" }
+                            div { "Successfully joined the game" }
+                            div { (format!("Your player ID: {}", player.id)) }
+                        }
+                    };
+                    view(success_content)
+                }
+                Err(e) => Err(join_error(&e)),
+            }
+        }
+        Ok(None) => Err(RouteError::NotFound("Game not found".to_string())),
+        Err(e) => Err(RouteError::RouteFailed(format!("Database error: {e}"))),
+    }
+}
+
+/// Turn a failure to add a player into the error shown to whoever was joining
+fn join_error(e: &anyhow::Error) -> RouteError {
+    e.downcast_ref::<NameTakenError>().map_or_else(
+        || RouteError::RouteFailed(format!("Failed to join game: {e}")),
+        |taken| RouteError::NameTaken(taken.name.clone()),
+    )
+}
+
+/// Load a game and ensure the requesting player is its owner
+async fn get_owned_game(
+    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
+    game_id: Uuid,
+    requester_id: Uuid,
+) -> Result<Game, RouteError> {
+    let game = session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .ok_or_else(|| RouteError::NotFound("Game not found".to_string()))?;
+
+    ensure_owner(&game, requester_id)?;
+    Ok(game)
+}
+
+/// Ensure `player_id` is the current owner of `game`
+fn ensure_owner(game: &Game, player_id: Uuid) -> Result<(), RouteError> {
+    if game.owner_id != player_id {
+        return Err(RouteError::Forbidden(
+            "Only the game owner can do that".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// The votes to show for a game
+///
+/// A revealed round shows the snapshot taken at reveal, so votes written after
+/// it can't change what everyone saw. Otherwise the live votes are shown.
+async fn displayed_votes(
+    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
+    game: &Game,
+) -> Result<Vec<Vote>, RouteError> {
+    let votes = if game.state == GameState::Revealed {
+        session_manager
+            .get_round_votes(game.id, game.round_id)
+            .await
+    } else {
+        session_manager.get_game_votes(game.id).await
+    };
+
+    votes.map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))
+}
+
+/// Re-render the whole results section from the game's current votes via SSE
+async fn refresh_results_section(
+    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
+    game_id: Uuid,
+    game_id_str: &str,
+    votes_revealed: bool,
+) -> Result<(), PartialUpdateError> {
+    let (game, votes, players) = load_game_snapshot(session_manager, game_id)
+        .await
+        .map_err(|e| PartialUpdateError::Load(e.to_string()))?;
+    let voting_system = VotingSystem::from_string(&game.voting_system);
+    let timing = game.round_timing(&votes);
+    update_entire_results_section(
+        game_id_str,
+        votes,
+        &players,
+        votes_revealed,
+        &voting_system,
+        timing.as_ref(),
+    )
+    .await
+}
+
+/// Render the coalesced `targets` of a game from its current state via SSE
+async fn flush_game_updates(game_id: Uuid, targets: Vec<UpdateTarget>) {
+    let Ok(session_manager) = STATE.get_session_manager().await else {
+        tracing::error!("Updates for game {} have no database", game_id);
+        return;
+    };
+    let mut delivery = Delivery::default();
+    let (game, votes, players) = match load_game_snapshot(session_manager, game_id).await {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            delivery.record(Err(PartialUpdateError::Load(e.to_string())));
+            return;
+        }
+    };
+    let game_id_str = game_id.to_string();
+
+    for target in targets {
+        let result = match target {
+            UpdateTarget::PlayersList => update_players_list(&game_id_str, players.clone()).await,
+            UpdateTarget::VoteResults => {
+                let revealed = game.state == GameState::Revealed;
+                let voting_system = VotingSystem::from_string(&game.voting_system);
+                update_vote_results(
+                    &game_id_str,
+                    votes.clone(),
+                    &players,
+                    revealed,
+                    &voting_system,
+                )
+                .await
+            }
+            // Only an open round's actions depend on the votes
+            UpdateTarget::GameActions if game.state == GameState::Voting => {
+                update_game_actions(session_manager, &game_id_str, &game).await
+            }
+            UpdateTarget::GameActions => Ok(()),
+        };
+        delivery.record(result);
+    }
+}
+
+/// Handles the vote route
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the token's player is not in the game
+/// * If getting game fails
+/// * If casting vote fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn vote_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let (token, refreshed) = authenticate(&req, game_id)?;
+    let (form_data, confidence) = parse_vote_form(&req)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    let player = session_manager
+        .get_player(game_id, token.player_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Failed to get player: {e}")))?
+        .ok_or_else(|| RouteError::Forbidden("You are not a player in this game".to_string()))?;
+
+    let game = session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Failed to get game: {e}")))?
+        .ok_or_else(|| RouteError::NotFound("Game not found".to_string()))?;
+    let cast_value = form_data.vote.clone();
+
+    let vote = Vote {
+        player_id: player.id,
+        player_name: player.name,
+        value: form_data.vote,
+        cast_at: Utc::now(),
+        confidence,
+    };
+
+    session_manager
+        .cast_vote(game_id, vote)
+        .await
+        .map_err(|e| RouteError::Rejected(e.to_string()))?;
+
+    tracing::info!(
+        "Vote cast successfully for game {}, triggering partial updates",
+        game_id
+    );
+
+    UPDATES.mark_dirty(
+        game_id,
+        &[UpdateTarget::VoteResults, UpdateTarget::GameActions],
+    );
+
+    let message = if game.settings.allow_vote_change {
+        format!("You voted {cast_value}")
+    } else {
+        format!("You voted {cast_value}, and it stands for the rest of this round")
+    };
+    send_toast(
+        ToastKind::Success,
+        &message,
+        &Delivery::default(),
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles the reveal votes route
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If getting game fails
+/// * If revealing votes fails
+///
+/// A requester who isn't the game owner gets an error toast rather than an
+/// error page.
+///
+/// # Panics
+///
+/// * Infallible
+#[allow(clippy::cognitive_complexity)]
+pub async fn reveal_votes_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/reveal"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    // Get session manager from global state
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    // Reveal the votes first
+    match session_manager.reveal_votes(game_id).await {
+        Ok(()) => {
+            tracing::info!(
+                "Votes revealed successfully for game {}, triggering partial updates",
+                game_id
+            );
+
+            let mut delivery = Delivery::default();
+            broadcast_game_snapshot(session_manager, game_id, &game_id_str, &mut delivery).await;
+
+            send_toast(
+                ToastKind::Success,
+                "Votes revealed",
+                &delivery,
+                refreshed.as_ref(),
+            )
+        }
+        Err(e) => Err(RouteError::Rejected(format!("Failed to reveal votes: {e}"))),
+    }
+}
+
+/// Every target a round transition can change, rendered for `game` as it is
+/// after the transition
+///
+/// Starting, revealing, resetting, revoting and finalizing all send this same
+/// set, so none of them can leave a stale target behind, and
+/// [`game_snapshot_route`] hands it to clients catching up after a dropped
+/// connection.
+fn game_snapshot_partials(
+    game_id: &str,
+    game: &Game,
+    votes: &[Vote],
+    players: &[Player],
+) -> Vec<(&'static str, Containers)> {
+    let voting_active = game.state == GameState::Voting;
+    let votes_revealed = game.state == GameState::Revealed;
+    let voting_system = VotingSystem::from_string(&game.voting_system);
+    let vote_count = if voting_active { votes.len() } else { 0 };
+    let timing = game.round_timing(votes);
+
+    vec![
+        (
+            "game-status",
+            planning_poker_ui::game_status_content(game.state.description()),
+        ),
+        (
+            "players-list",
+            planning_poker_ui::players_list_content(players),
+        ),
+        (
+            "current-story",
+            planning_poker_ui::current_story_section(&game.current_story, voting_active),
+        ),
+        (
+            "story-input",
+            planning_poker_ui::story_input_content(game_id, voting_active, &game.current_story),
+        ),
+        (
+            "voting-section",
+            planning_poker_ui::voting_section(game_id, game, voting_active, None),
+        ),
+        (
+            "results-section",
+            planning_poker_ui::results_section(
+                votes,
+                players,
+                votes_revealed,
+                &voting_system,
+                timing.as_ref(),
+            ),
+        ),
+        (
+            "game-actions",
+            planning_poker_ui::game_actions_content(
+                game_id,
+                &game.state,
+                &voting_system,
+                vote_count,
+            ),
+        ),
+    ]
+}
+
+/// A game with the votes on display and its players, read with one query each
+async fn load_game_snapshot(
+    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
+    game_id: Uuid,
+) -> Result<(Game, Vec<Vote>, Vec<Player>), RouteError> {
+    let game = session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .ok_or_else(|| RouteError::NotFound("Game not found".to_string()))?;
+    let votes = displayed_votes(session_manager, &game).await?;
+    let players = session_manager
+        .get_game_players(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?;
+
+    Ok((game, votes, players))
+}
+
+/// Send everyone the partials of [`game_snapshot_partials`] for the game as
+/// it is now, returning the game so the caller can act on its new state
+///
+/// Whether the partials went out is recorded in `delivery`.
+async fn broadcast_game_snapshot(
+    session_manager: &Arc<dyn planning_poker_session::SessionManager>,
+    game_id: Uuid,
+    game_id_str: &str,
+    delivery: &mut Delivery,
+) -> Option<Game> {
+    let (game, votes, players) = match load_game_snapshot(session_manager, game_id).await {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            tracing::error!("Failed to load snapshot of game {}: {}", game_id, e);
+            delivery.record(Err(PartialUpdateError::Load(e.to_string())));
+            return None;
+        }
+    };
+
+    tracing::info!(
+        "Broadcasting snapshot of game {}: state {:?}, {} votes",
+        game_id,
+        game.state,
+        votes.len()
+    );
+    // The snapshot re-renders every coalesced target from the same data, so
+    // it flushes them without waiting for the window to close
+    UPDATES.discard(game_id);
+    for (target, content) in game_snapshot_partials(game_id_str, &game, &votes, &players) {
+        delivery.record(send_partial_update(target, content).await);
+    }
+
+    Some(game)
+}
+
+/// Count down a timed round, revealing its votes once `deadline` passes
+///
+/// The countdown is broadcast about once a second. It stops without
+/// revealing if the round is revealed, reset or restarted with another
+/// deadline first.
+async fn run_voting_timer(game_id: Uuid, deadline: DateTime<Utc>) {
+    let game_id_str = game_id.to_string();
+    let Ok(session_manager) = STATE.get_session_manager().await else {
+        tracing::error!("Voting timer for game {} has no database", game_id);
+        return;
+    };
+
+    loop {
+        match session_manager.get_game(game_id).await {
+            Ok(Some(game))
+                if game.state == GameState::Voting && game.voting_deadline == Some(deadline) => {}
+            _ => return,
+        }
+
+        let remaining_secs = planning_poker_ui::remaining_secs(deadline, Utc::now());
+        if let Err(e) = send_partial_update(
+            "voting-timer",
+            planning_poker_ui::voting_timer_content(Some(remaining_secs)),
+        )
+        .await
+        {
+            tracing::warn!("Voting timer of game {} was not delivered: {}", game_id, e);
+        }
+        if remaining_secs == 0 {
+            break;
+        }
+
+        let until_deadline = (deadline - Utc::now()).to_std().unwrap_or_default();
+        switchy::unsync::time::sleep(until_deadline.min(TIMER_TICK_INTERVAL)).await;
+    }
+
+    match session_manager.reveal_if_expired(game_id).await {
+        Ok(true) => {
+            tracing::info!("Voting timer revealed votes for game {}", game_id);
+            broadcast_game_snapshot(
+                session_manager,
+                game_id,
+                &game_id_str,
+                &mut Delivery::default(),
+            )
+            .await;
+        }
+        Ok(false) => {}
+        Err(e) => tracing::error!("Voting timer failed to reveal game {}: {}", game_id, e),
+    }
+}
+
+/// Handles the start voting route
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If getting game fails
+/// * If starting voting fails
+/// * If getting game votes fails
+/// * If getting game fails
+/// * If game state is not waiting
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the story cannot be parsed or is too long
+///
+/// # Panics
+///
+/// * Infallible
+#[allow(clippy::cognitive_complexity)]
+pub async fn start_voting_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/start-voting"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    tracing::info!("START VOTING: Received request for game {}", game_id);
+
+    // Get session manager from global state
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    let game = get_owned_game(session_manager, game_id, token.player_id).await?;
+    tracing::info!(
+        "START VOTING: Current game state before start: {:?}",
+        game.state
+    );
+
+    let (story, timer) = parse_start_voting_form(&req)?;
+    let started = match timer {
+        Some(duration) => {
+            session_manager
+                .start_voting_with_timer(game_id, story, duration)
+                .await
+        }
+        None => session_manager.start_voting(game_id, story).await,
+    };
+
+    match started {
+        Ok(()) => {
+            tracing::info!(
+                "START VOTING: session_manager.start_voting() completed successfully for game {}",
+                game_id
+            );
+
+            // Send partial updates via SSE instead of returning full page
+            let mut delivery = Delivery::default();
+            if let Some(game) =
+                broadcast_game_snapshot(session_manager, game_id, &game_id_str, &mut delivery).await
+            {
+                if let Some(deadline) = game.voting_deadline {
+                    switchy::unsync::task::spawn(run_voting_timer(game_id, deadline));
+                }
+            } else {
+                tracing::error!("START VOTING: Failed to get game after start_voting call");
+            }
+
+            send_toast(
+                ToastKind::Success,
+                "Voting started",
+                &delivery,
+                refreshed.as_ref(),
+            )
+        }
+        Err(e) => Err(RouteError::Rejected(format!("Failed to start voting: {e}"))),
+    }
+}
+
+/// Handles the reset voting route
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+/// * If getting game fails
+/// * If resetting voting fails
+/// * If getting game votes fails
+/// * If the auth token is missing, invalid, expired or for another game
+///
+/// A requester who isn't the game owner gets an error toast rather than an
+/// error page.
+///
+/// # Panics
+///
+/// * Infallible
+#[allow(clippy::cognitive_complexity)]
+pub async fn reset_voting_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/reset"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    // Get session manager from global state
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    match session_manager.reset_voting(game_id).await {
+        Ok(()) => {
+            tracing::info!(
+                "Voting reset successfully for game {}, triggering partial updates",
+                game_id
+            );
+
+            // Send partial updates via SSE instead of returning full page
+            let mut delivery = Delivery::default();
+            broadcast_game_snapshot(session_manager, game_id, &game_id_str, &mut delivery).await;
+
+            send_toast(
+                ToastKind::Success,
+                "Voting reset",
+                &delivery,
+                refreshed.as_ref(),
+            )
+        }
+        Err(e) => Err(RouteError::Rejected(format!("Failed to reset voting: {e}"))),
+    }
+}
+
+/// Handles the revote route, restarting voting on the current story
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the requester is not the game owner
+/// * If votes have not been revealed yet
+/// * If restarting the vote fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn revote_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/revote"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    // Get session manager from global state
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    session_manager
+        .revote(game_id)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to restart voting: {e}")))?;
+
+    tracing::info!("Revote started for game {}", game_id);
+
+    let mut delivery = Delivery::default();
+    broadcast_game_snapshot(session_manager, game_id, &game_id_str, &mut delivery).await;
+
+    send_toast(
+        ToastKind::Success,
+        "Revote started",
+        &delivery,
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles the archive route, retiring a revealed game for good
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the game's votes are not revealed
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn archive_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/archive"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    session_manager
+        .archive_game(game_id)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to archive game: {e}")))?;
+
+    tracing::info!("Game {} archived", game_id);
+
+    let mut delivery = Delivery::default();
+    if let Ok(Some(game)) = session_manager.get_game(game_id).await {
+        delivery.record(update_game_status(&game_id_str, game.state.description()).await);
+        delivery.record(update_game_actions(session_manager, &game_id_str, &game).await);
+    }
+
+    send_toast(
+        ToastKind::Success,
+        "Game archived",
+        &delivery,
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles the complete route, finishing the game for good
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the game is already archived or completed
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn complete_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/complete"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    session_manager
+        .complete_game(game_id)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to complete game: {e}")))?;
+
+    tracing::info!("Game {} completed", game_id);
+
+    let mut delivery = Delivery::default();
+    if let Ok(Some(game)) = session_manager.get_game(game_id).await {
+        delivery.record(update_game_status(&game_id_str, game.state.description()).await);
+        delivery.record(update_game_actions(session_manager, &game_id_str, &game).await);
+    }
+
+    send_toast(
+        ToastKind::Success,
+        "Game completed",
+        &delivery,
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles the finalize route, recording the `value` form field as the
+/// current story's estimate and closing the round
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the form is missing the value
+/// * If votes have not been revealed or the value is not a card in the deck
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn finalize_estimate_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/finalize"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    let form_data = req.parse_form::<FinalizeEstimateForm>()?;
+    session_manager
+        .finalize_estimate(game_id, form_data.value.trim().to_string())
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to record estimate: {e}")))?;
+
+    tracing::info!(
+        "Recorded final estimate {} for game {}",
+        form_data.value,
+        game_id
+    );
+
+    let mut delivery = Delivery::default();
+    broadcast_game_snapshot(session_manager, game_id, &game_id_str, &mut delivery).await;
+    if let Ok(completed) = session_manager.get_completed_stories(game_id).await {
+        delivery.record(
+            send_partial_update(
+                "completed-stories",
+                planning_poker_ui::completed_stories_content(&completed),
+            )
+            .await,
+        );
+    }
+
+    send_toast(
+        ToastKind::Success,
+        "Final estimate recorded",
+        &delivery,
+        refreshed.as_ref(),
+    )
+}
+
+/// Read pasted text into the stories it lists, one per non-blank line
+///
+/// # Errors
+///
+/// * If the text lists more than [`MAX_IMPORTED_STORIES`] stories
+/// * If a story's title is longer than [`MAX_STORY_LENGTH`]
+pub fn parse_story_import(text: &str) -> Result<Vec<NewStory>, RouteError> {
+    let stories: Vec<(usize, NewStory)> = text
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| NewStory::parse_line(line).map(|story| (index + 1, story)))
+        .collect();
+
+    if stories.len() > MAX_IMPORTED_STORIES {
+        return Err(RouteError::RouteFailed(format!(
+            "Import at most {MAX_IMPORTED_STORIES} stories at a time; this paste has {}",
+            stories.len()
+        )));
+    }
+
+    if let Some((line, _)) = stories
+        .iter()
+        .find(|(_, story)| story.title.chars().count() > MAX_STORY_LENGTH)
+    {
+        return Err(RouteError::RouteFailed(format!(
+            "Story on line {line} must be at most {MAX_STORY_LENGTH} characters"
+        )));
+    }
+
+    Ok(stories.into_iter().map(|(_, story)| story).collect())
+}
+
+/// Handles the story route, replacing the text of the current story with
+/// the `story` form field without touching the round's votes or state
+///
+/// A blank story clears it. While waiting, the story pre-fills the next round.
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the form cannot be parsed or the story is longer than `MAX_STORY_LENGTH` characters
+/// * If the game is past voting
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn update_story_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/story"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let game_id_str = game_id.to_string();
+    let (token, refreshed) = authenticate(&req, game_id)?;
+    let form_data = req.parse_form::<UpdateStoryForm>()?;
+    let story = form_data.story.trim();
+    if story.chars().count() > MAX_STORY_LENGTH {
+        return Err(RouteError::validation(
+            "story",
+            &format!("Story must be at most {MAX_STORY_LENGTH} characters"),
+        ));
+    }
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    session_manager
+        .update_current_story(game_id, story.to_string())
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to update story: {e}")))?;
+
+    let mut delivery = Delivery::default();
+    if let Ok(Some(game)) = session_manager.get_game(game_id).await {
+        let voting_active = matches!(game.state, GameState::Voting);
+        delivery.record(update_current_story(game.current_story.as_ref(), voting_active).await);
+        delivery.record(
+            update_story_input(&game_id_str, voting_active, game.current_story.as_ref()).await,
+        );
+    }
+
+    let message = if story.is_empty() {
+        "Story cleared"
+    } else {
+        "Story updated"
+    };
+    send_toast(ToastKind::Success, message, &delivery, refreshed.as_ref())
+}
+
+/// Handles the story import route, adding each line of the `stories` form
+/// field to the backlog
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the pasted text lists too many stories or too long a title
+/// * If adding the stories fails
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn import_stories_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/stories/import"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    let form_data = req.parse_form::<ImportStoriesForm>()?;
+    let stories = parse_story_import(&form_data.stories)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    let result = session_manager
+        .import_stories(game_id, stories)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to import stories: {e}")))?;
+
+    tracing::info!(
+        "Imported stories into game {}: {} added, {} skipped",
+        game_id,
+        result.added,
+        result.skipped
+    );
+
+    let mut delivery = Delivery::default();
+    if let Ok(Some(snapshot)) = session_manager.get_game_snapshot(game_id).await {
+        delivery.record(
+            send_partial_update(
+                "story-backlog",
+                planning_poker_ui::story_backlog_content(&snapshot.stories),
+            )
+            .await,
+        );
+    }
+
+    let mut content = planning_poker_ui::story_import_result_content(result);
+    content.extend(delivery.warning());
+    respond_with_token(content, refreshed.as_ref())
+}
+
+/// Handles the story batch route, appending the stories of a JSON body to
+/// the end of the game's backlog and returning them as stored
+///
+/// Every story is checked before any is added, so a batch with one invalid
+/// story adds nothing and reports the problem with each invalid one.
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the body is missing or isn't a story batch
+/// * If the batch has more than [`MAX_IMPORTED_STORIES`] stories
+/// * If any story has a blank or too long title
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If adding the stories fails
+pub async fn batch_stories_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/stories/batch"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let (token, _) = authenticate(&req, game_id)?;
+
+    let body = req.body.as_ref().ok_or(RouteError::MissingFormData)?;
+    let batch: StoryBatchRequest = serde_json::from_slice(body)
+        .map_err(|e| RouteError::ParseBody(ParseError::SerdeJson(e)))?;
+    if batch.stories.len() > MAX_IMPORTED_STORIES {
+        return Err(RouteError::validation(
+            "stories",
+            &format!("Add at most {MAX_IMPORTED_STORIES} stories at a time"),
+        ));
+    }
+    StoryInput::validate_batch(&batch.stories).map_err(RouteError::InvalidStories)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    let stories = session_manager
+        .add_stories_batch(game_id, batch.stories)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Failed to add stories: {e}")))?;
+
+    tracing::info!("Added {} stories to game {}", stories.len(), game_id);
+
+    if !stories.is_empty() {
+        if let Ok(Some(snapshot)) = session_manager.get_game_snapshot(game_id).await {
+            Delivery::default().record(
+                send_partial_update(
+                    "story-backlog",
+                    planning_poker_ui::story_backlog_content(&snapshot.stories),
+                )
+                .await,
+            );
+        }
+    }
+
+    Ok(Content::Json(serde_json::json!({ "stories": stories })))
+}
+
+/// Handles the discard vote route, striking a single revealed vote from the round
+///
+/// Only the game owner may discard votes, and only once they are revealed.
+/// Discarding a vote that doesn't exist succeeds without changes.
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID or player ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the votes have not been revealed
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn discard_vote_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract ids from path like "/api/games/uuid-here/votes/player-uuid/discard"
+    let path = GamePath::parse(&req.path)?;
+    let game_id = path.game_id;
+    let game_id_str = game_id.to_string();
+    let player_id = path
+        .player_id()
+        .ok_or_else(|| RouteError::NotFound("Player not found".to_string()))?;
+    let (token, refreshed) = authenticate(&req, game_id)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    get_owned_game(session_manager, game_id, token.player_id).await?;
+
+    session_manager
+        .discard_vote(game_id, player_id)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to discard vote: {e}")))?;
+
+    tracing::info!("Discarded vote of player {} in game {}", player_id, game_id);
+
+    let mut delivery = Delivery::default();
+    delivery.record(refresh_results_section(session_manager, game_id, &game_id_str, true).await);
+
+    send_toast(
+        ToastKind::Success,
+        "Vote discarded",
+        &delivery,
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles the game owner kicking a player out of the game
+///
+/// The player is named either in the path, as in
+/// `/api/games/{id}/players/{player_id}/kick`, or by the `player_id` form
+/// field of `/api/games/{id}/kick`. The player's vote goes with them, and
+/// everyone's players list and results are refreshed.
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID or player ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the form is missing the player ID
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the player is not in the game or is the owner
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn kick_player_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract ids from path like "/api/games/uuid-here/players/player-uuid/kick"
+    let path = GamePath::parse(&req.path)?;
+    let game_id = path.game_id;
+    let (token, refreshed) = authenticate(&req, game_id)?;
+    let requester_id = token.player_id;
+    let player_id = match path.player_id() {
+        Some(player_id) => player_id,
+        None => Uuid::parse_str(req.parse_form::<KickPlayerForm>()?.player_id.trim())?,
+    };
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    get_owned_game(session_manager, game_id, requester_id).await?;
+
+    session_manager
+        .kick_player(game_id, requester_id, player_id)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to kick player: {e}")))?;
+
+    tracing::info!("Kicked player {} from game {}", player_id, game_id);
+
+    UPDATES.mark_dirty(
+        game_id,
+        &[
+            UpdateTarget::PlayersList,
+            UpdateTarget::VoteResults,
+            UpdateTarget::GameActions,
+        ],
+    );
+    UPDATES.flush_now(game_id).await;
+
+    send_toast(
+        ToastKind::Success,
+        "Player kicked",
+        &Delivery::default(),
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles a player leaving a game
+///
+/// The leaving player is the one the auth token was issued to. If they owned
+/// the game, ownership passes to the longest-standing player left. Everyone
+/// still in the game has their players list and vote progress refreshed.
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If game ID is not found
+/// * If the player is not in the game
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn leave_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/leave"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let (token, _) = authenticate(&req, game_id)?;
+    let player_id = token.player_id;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .ok_or_else(|| RouteError::NotFound("Game not found".to_string()))?;
+    session_manager
+        .get_player(game_id, player_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .ok_or_else(|| RouteError::Forbidden("You are not a player in this game".to_string()))?;
+
+    session_manager
+        .remove_player_from_game(game_id, player_id)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to leave game: {e}")))?;
+
+    tracing::info!("Player {} left game {}", player_id, game_id);
+
+    UPDATES.mark_dirty(
+        game_id,
+        &[
+            UpdateTarget::PlayersList,
+            UpdateTarget::VoteResults,
+            UpdateTarget::GameActions,
+        ],
+    );
+    UPDATES.flush_now(game_id).await;
+
+    send_toast(
+        ToastKind::Success,
+        "You left the game",
+        &Delivery::default(),
+        None,
+    )
+}
+
+/// Handles handing ownership of a game to another player
+///
+/// The requester is the player the auth token was issued to and the new
+/// owner is named by the `new_owner_id` form field. The new owner gets the
+/// round controls the next time they load the game page.
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID or the new owner ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the form is missing the new owner ID
+/// * If game ID is not found
+/// * If the requester is not the game owner
+/// * If the new owner is not in the game
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn transfer_ownership_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/transfer-ownership"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let (token, refreshed) = authenticate(&req, game_id)?;
+    let requester_id = token.player_id;
+    let new_owner_id = Uuid::parse_str(
+        req.parse_form::<TransferOwnershipForm>()?
+            .new_owner_id
+            .trim(),
+    )?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    get_owned_game(session_manager, game_id, requester_id).await?;
+    session_manager
+        .transfer_ownership(game_id, requester_id, new_owner_id)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to transfer ownership: {e}")))?;
+
+    tracing::info!(
+        "Ownership of game {} passed from {} to {}",
+        game_id,
+        requester_id,
+        new_owner_id
+    );
+
+    send_toast(
+        ToastKind::Success,
+        "Ownership transferred",
+        &Delivery::default(),
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles changing a player's role
+///
+/// The requester is the player the auth token was issued to. The game owner
+/// can change anyone's role; other players can only change their own.
+///
+/// # Errors
+///
+/// * If method is not POST
+/// * If game ID or player ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the role is not a known role
+/// * If game ID is not found
+/// * If the requester is neither the game owner nor the player
+/// * If the player is not in the game
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn set_player_role_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Post) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract ids from path like "/api/games/uuid-here/players/player-uuid/role"
+    let path = GamePath::parse(&req.path)?;
+    let game_id = path.game_id;
+    let game_id_str = game_id.to_string();
+    let player_id = path
+        .player_id()
+        .ok_or_else(|| RouteError::NotFound("Player not found".to_string()))?;
+    let (token, refreshed) = authenticate(&req, game_id)?;
+    let requester_id = token.player_id;
+    let form_data = req.parse_form::<PlayerRoleForm>()?;
+    let role = parse_role(&form_data.role)?;
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    if requester_id != player_id {
+        get_owned_game(session_manager, game_id, requester_id).await?;
+    }
+
+    session_manager
+        .set_player_role(game_id, player_id, role)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to change role: {e}")))?;
+
+    tracing::info!("Player {} in game {} is now {}", player_id, game_id, role);
+
+    let mut delivery = Delivery::default();
+    if let Ok(players) = session_manager.get_game_players(game_id).await {
+        delivery.record(update_players_list(&game_id_str, players).await);
+    }
+
+    // Revealed results group advisory votes apart, so regroup them for the new role
+    if let Ok(Some(game)) = session_manager.get_game(game_id).await {
+        if game.state == GameState::Revealed {
+            delivery.record(
+                refresh_results_section(session_manager, game_id, &game_id_str, true).await,
+            );
+        }
+    }
+
+    send_toast(
+        ToastKind::Success,
+        &format!("Role changed to {role}"),
+        &delivery,
+        refreshed.as_ref(),
+    )
+}
+
+/// Handles updating a player's name or observer flag
+///
+/// Accepts a JSON `PlayerUpdate` body. The requester is the player the auth
+/// token was issued to. The game owner can update anyone; other players can
+/// only update themselves.
+///
+/// # Errors
+///
+/// * If method is not PATCH
+/// * If game ID or player ID is not a valid UUID
+/// * If the auth token is missing, invalid, expired or for another game
+/// * If the body is not a valid `PlayerUpdate`
+/// * If game ID or player ID is not found
+/// * If the requester is neither the game owner nor the player
+/// * If the new name is already taken
+///
+/// # Panics
+///
+/// * Infallible
+pub async fn update_player_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Patch) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract ids from path like "/api/games/uuid-here/players/player-uuid"
+    let path = GamePath::parse(&req.path)?;
+    let game_id = path.game_id;
+    let game_id_str = game_id.to_string();
+    let player_id = path
+        .player_id()
+        .ok_or_else(|| RouteError::NotFound("Player not found".to_string()))?;
+    let (token, refreshed) = authenticate(&req, game_id)?;
+    let requester_id = token.player_id;
+    let body = req.body.as_ref().ok_or(RouteError::MissingFormData)?;
+    let update: PlayerUpdate = serde_json::from_slice(body)
+        .map_err(|e| RouteError::ParseBody(ParseError::SerdeJson(e)))?;
+
+    if update
+        .name
+        .as_ref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Err(RouteError::validation("name", "Player name is required"));
+    }
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    if requester_id != player_id {
+        get_owned_game(session_manager, game_id, requester_id).await?;
+    }
+
+    let mut player = session_manager
+        .get_player(game_id, player_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .ok_or_else(|| RouteError::NotFound("Player not found".to_string()))?;
+    update.apply(&mut player);
+
+    session_manager
+        .update_player(game_id, &player)
+        .await
+        .map_err(|e| RouteError::Rejected(format!("Failed to update player: {e}")))?;
+
+    tracing::info!("Updated player {} in game {}", player_id, game_id);
+
+    let mut delivery = Delivery::default();
+    if let Ok(players) = session_manager.get_game_players(game_id).await {
+        delivery.record(update_players_list(&game_id_str, players).await);
+    }
+
+    send_toast(
+        ToastKind::Success,
+        &format!("Updated {}", player.name),
+        &delivery,
+        refreshed.as_ref(),
+    )
+}
+
+/// Columns of the CSV and Markdown exports, one row per completed round
+const EXPORT_COLUMNS: [&str; 5] = [
+    "Story",
+    "Final estimate",
+    "Votes",
+    "First vote",
+    "Last vote",
+];
+
+/// Handles the export game route, returning the game's history for download
+///
+/// `format` is `json` (the default) for the full history, or `csv` or `md`
+/// for one row per completed round, oldest first. A game with an open round
+/// is exported as it stands; the JSON flags it `in_progress`.
+///
+/// # Errors
+///
+/// * If method is not GET
+/// * If the requested format is not supported
+/// * If game ID is not a valid UUID
+/// * If game ID is not found
+pub async fn export_game_route(req: RouteRequest) -> Result<Content, RouteError> {
+    if !matches!(req.method, Method::Get) {
+        return Err(RouteError::UnsupportedMethod);
+    }
+
+    // Extract game_id from path like "/api/games/uuid-here/export"
+    let game_id = GamePath::parse(&req.path)?.game_id;
+    let format = req.query.get("format").map_or("json", String::as_str);
+    if !matches!(format, "json" | "csv" | "md") {
+        return Err(RouteError::RouteFailed(format!(
+            "Unsupported export format: {format}"
+        )));
+    }
+
+    let session_manager = STATE
+        .get_session_manager()
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database connection failed: {e}")))?;
+
+    if session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Database error: {e}")))?
+        .is_none()
+    {
+        return Err(RouteError::NotFound("Game not found".to_string()));
+    }
+
+    let history = session_manager
+        .export_game_history(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Failed to export game: {e}")))?;
+    if format == "json" {
+        let json = history
+            .to_json_pretty()
+            .map_err(|e| RouteError::RouteFailed(format!("Failed to export game: {e}")))?;
+
+        return Ok(Content::Raw {
+            data: Bytes::from(json),
+            content_type: "application/json".to_string(),
+        });
+    }
+
+    let mut rounds = session_manager
+        .get_round_history(game_id)
+        .await
+        .map_err(|e| RouteError::RouteFailed(format!("Failed to export game: {e}")))?;
+    rounds.reverse();
+    let rows = export_rows(&history, &rounds);
+
+    let (data, content_type) = if format == "csv" {
+        (export_csv(&rows), "text/csv; charset=utf-8")
+    } else {
+        (export_markdown(&rows), "text/markdown; charset=utf-8")
+    };
+
+    Ok(Content::Raw {
+        data: Bytes::from(data),
+        content_type: content_type.to_string(),
+    })
+}
+
+/// One row of `EXPORT_COLUMNS` per round, listing each vote by player name
+fn export_rows(history: &GameHistory, rounds: &[RoundSummary]) -> Vec<[String; 5]> {
+    rounds
+        .iter()
+        .map(|round| {
+            let votes = history
+                .rounds
+                .iter()
+                .find(|recorded| recorded.round_id == round.round_id)
+                .map(|recorded| {
+                    recorded
+                        .votes
+                        .iter()
+                        .map(|vote| format!("{}: {}", vote.player_name, vote.value))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+                .unwrap_or_default();
+
+            [
+                round.story.clone().unwrap_or_default(),
+                round.final_estimate.clone().unwrap_or_default(),
+                votes,
+                round.first_vote_at.to_rfc3339(),
+                round.last_vote_at.to_rfc3339(),
+            ]
+        })
+        .collect()
+}
+
+/// The rows as CSV with a header line, quoting fields that need it
+fn export_csv(rows: &[[String; 5]]) -> String {
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    std::iter::once(EXPORT_COLUMNS.map(String::from))
+        .chain(rows.iter().cloned())
+        .map(|row| {
+            row.iter()
+                .map(|value| field(value))
+                .collect::<Vec<_>>()
+                .join(",")
+                + "\r\n"
+        })
+        .collect()
+}
+
+/// The rows as a Markdown pipe table
+fn export_markdown(rows: &[[String; 5]]) -> String {
+    fn cell(value: &str) -> String {
+        value
+            .replace('|', "\\|")
+            .replace("\r\n", " ")
+            .replace(['\n', '\r'], " ")
+    }
+    fn line(cells: impl Iterator<Item = String>) -> String {
+        format!("| {} |\n", cells.collect::<Vec<_>>().join(" | "))
+    }
+
+    let mut table = line(EXPORT_COLUMNS.iter().map(|column| cell(column)));
+    table.push_str(&line(EXPORT_COLUMNS.iter().map(|_| "---".to_string())));
+    for row in rows {
+        table.push_str(&line(row.iter().map(|value| cell(value))));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyperchad::router::{RequestInfo, RouteRequest};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_join_game_form_parsing() {
+        // Create a mock form data for multipart/form-data
+        let boundary = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+        let form_data = "------WebKitFormBoundary7MA4YWxkTrZu0gW\r\n\
+             Content-Disposition: form-data; name=\"game-id\"\r\n\r\n\
+             test-game-123\r\n\
+             ------WebKitFormBoundary7MA4YWxkTrZu0gW\r\n\
+             Content-Disposition: form-data; name=\"player-name\"\r\n\r\n\
+             John Doe\r\n\
+             ------WebKitFormBoundary7MA4YWxkTrZu0gW--\r\n"
+            .to_string();
+        let body = Bytes::from(form_data);
+
+        let mut headers = BTreeMap::new();
+        headers.insert(
+            "content-type".to_string(),
+            format!("multipart/form-data; boundary={boundary}"),
+        );
+
+        let req = RouteRequest {
+            path: "/join-game".to_string(),
+            method: Method::Post,
+            query: BTreeMap::new(),
+            headers,
+            cookies: BTreeMap::new(),
+            info: RequestInfo::default(),
+            body: Some(Arc::new(body)),
+        };
+
+        // Test that the form parsing works
+        let result = join_game_route(req).await;
+
+        // The result should be an error because UUID parsing will fail for "test-game-123"
+        // but it should get past the form parsing stage
+        match result {
+            Err(RouteError::InvalidUuid(_)) => {
+                // This is expected - the form was parsed successfully but UUID parsing failed
+            }
+            Err(other) => {
+                // Let's see what error we actually get
+                println!("Got error: {other:?}");
+                panic!("Expected InvalidUuid error, got a different error type");
+            }
+            Ok(_) => panic!("Expected an error but got success"),
+        }
+    }
+
+    #[test]
+    fn test_join_game_form_deserialization() {
+        let form_data = JoinGameForm {
+            game_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+            player_name: "Test Player".to_string(),
+            role: None,
+            team_role: None,
+        };
+
+        assert_eq!(form_data.game_id, "550e8400-e29b-41d4-a716-446655440000");
+        assert_eq!(form_data.player_name, "Test Player");
+    }
+
+    #[test]
+    fn test_join_game_form_role_defaults_to_voter() {
+        let form = |role: Option<&str>| JoinGameForm {
+            game_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+            player_name: "Test Player".to_string(),
+            role: role.map(ToString::to_string),
+            team_role: None,
+        };
+
+        assert_eq!(form(None).role().unwrap(), PlayerRole::Voter);
+        assert_eq!(form(Some(" ")).role().unwrap(), PlayerRole::Voter);
+        assert_eq!(form(Some("advisor")).role().unwrap(), PlayerRole::Advisor);
+        assert!(matches!(
+            form(Some("lead")).role(),
+            Err(RouteError::RouteFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_join_game_form_team_role_keeps_unlisted_names() {
+        let form = |team_role: Option<&str>| JoinGameForm {
+            game_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+            player_name: "Test Player".to_string(),
+            role: None,
+            team_role: team_role.map(ToString::to_string),
+        };
+
+        assert_eq!(form(None).team_role(), TeamRole::default());
+        assert_eq!(form(Some("Tech Lead")).team_role(), TeamRole::TechLead);
+        assert_eq!(
+            form(Some("Scrum Master")).team_role(),
+            TeamRole::Other("Scrum Master".to_string())
+        );
+    }
+
+    #[test]
+    fn test_create_game_form_deserialization() {
+        let form_data = CreateGameForm {
+            name: "Test Game".to_string(),
+            voting_system: "fibonacci".to_string(),
+            lock_votes: None,
+            custom_deck: None,
+            auto_archive_hours: None,
+            webhook_url: None,
+        };
+
+        assert_eq!(form_data.name, "Test Game");
+        assert_eq!(form_data.voting_system, "fibonacci");
+    }
+
+    #[test]
+    fn test_create_game_form_custom_deck() {
+        let form = |custom_deck: Option<&str>| CreateGameForm {
+            name: "Test Game".to_string(),
+            voting_system: String::new(),
+            lock_votes: None,
+            custom_deck: custom_deck.map(ToString::to_string),
+            auto_archive_hours: None,
+            webhook_url: None,
+        };
+
+        assert!(form(None).custom_deck().unwrap().is_none());
+        assert!(form(Some("  ")).custom_deck().unwrap().is_none());
+        assert_eq!(
+            form(Some("XS, S, M")).custom_deck().unwrap().unwrap().key(),
+            "custom:XS,S,M"
+        );
+        assert!(matches!(
+            form(Some(",,")).custom_deck(),
+            Err(RouteError::RouteFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_game_form_auto_archive_delay() {
+        let form = |hours: Option<&str>| CreateGameForm {
+            name: "Test Game".to_string(),
+            voting_system: "fibonacci".to_string(),
+            lock_votes: None,
+            custom_deck: None,
+            auto_archive_hours: hours.map(ToString::to_string),
+            webhook_url: None,
+        };
+
+        assert_eq!(form(None).settings().unwrap().auto_archive_after, None);
+        assert_eq!(form(Some(" ")).settings().unwrap().auto_archive_after, None);
+        assert_eq!(
+            form(Some("24")).settings().unwrap().auto_archive_after,
+            Some(Duration::from_secs(24 * 60 * 60))
+        );
+        assert!(matches!(
+            form(Some("0")).settings(),
+            Err(RouteError::RouteFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_game_form_webhook_url() {
+        let form = |url: Option<&str>| CreateGameForm {
+            name: "Test Game".to_string(),
+            voting_system: "fibonacci".to_string(),
+            lock_votes: None,
+            custom_deck: None,
+            auto_archive_hours: None,
+            webhook_url: url.map(ToString::to_string),
+        };
+
+        assert_eq!(form(None).settings().unwrap().webhook_url, None);
+        assert_eq!(form(Some(" ")).settings().unwrap().webhook_url, None);
+        assert_eq!(
+            form(Some(" https://chat.example.com/hook "))
+                .settings()
+                .unwrap()
+                .webhook_url,
+            Some("https://chat.example.com/hook".to_string())
+        );
+        assert!(matches!(
+            form(Some("ftp://chat.example.com/hook")).settings(),
+            Err(RouteError::RouteFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_player_game_url_targets_game_page() {
+        let game_id = Uuid::new_v4();
+        let player_id = Uuid::new_v4();
+
+        assert_eq!(
+            player_game_url(game_id, player_id),
+            format!("/game/{game_id}?player_id={player_id}")
+        );
+    }
+
+    #[test]
+    fn test_redirect_to_refreshes_to_game_url() {
+        let url = player_game_url(Uuid::new_v4(), Uuid::new_v4());
+
+        let Content::Raw { data, content_type } = redirect_to(&url) else {
+            panic!("Expected a raw HTML redirect response");
+        };
+
+        assert_eq!(content_type, "text/html");
+        let html = String::from_utf8(data.to_vec()).unwrap();
+        assert!(html.contains(&format!("content=\"0; url={url}\"")));
+    }
+
+    #[test]
+    fn test_redirect_with_token_stores_auth_cookie() {
+        let game_id = Uuid::new_v4();
+        let player_id = Uuid::new_v4();
+        let token = AuthToken::new(player_id, game_id, chrono::Duration::hours(1));
+
+        let req = get_request("/join-game", BTreeMap::new());
+        let Content::Raw { data, .. } =
+            redirect_with_token(&req, &player_game_url(game_id, player_id), &token).unwrap()
+        else {
+            panic!("Expected a raw HTML redirect response");
+        };
+
+        let html = String::from_utf8(data.to_vec()).unwrap();
+        let jwt = html
+            .split(&format!("{AUTH_COOKIE}="))
+            .nth(1)
+            .and_then(|rest| rest.split(';').next())
+            .unwrap();
+        assert_eq!(AuthToken::verify(jwt, &AUTH_SECRET).unwrap(), token);
+        assert!(html.contains("Max-Age=3600; SameSite=Strict"));
+    }
+
+    #[test]
+    fn test_script_posted_forms_are_redirected_by_script() {
+        let token = AuthToken::new(Uuid::new_v4(), Uuid::new_v4(), chrono::Duration::hours(1));
+        let url = Flash::Joined.add_to(&player_game_url(token.game_id, token.player_id));
+        let mut req = get_request("/join-game", BTreeMap::new());
+        req.headers
+            .insert("hx-request".to_string(), "true".to_string());
+
+        let Content::Raw { data, .. } = redirect_with_token(&req, &url, &token).unwrap() else {
+            panic!("Expected a raw HTML redirect response");
+        };
+        let html = String::from_utf8(data.to_vec()).unwrap();
+
+        assert!(html.contains(&format!("window.location.assign(\"{url}\")")));
+        assert!(html.contains(&format!("{AUTH_COOKIE}=")));
+        assert!(!html.contains("http-equiv"));
+        assert!(url.ends_with("&flash=joined"));
+    }
+
+    #[test]
+    fn test_flash_names_the_player_who_joined() {
+        let alice = Player {
+            id: Uuid::new_v4(),
+            name: "Alice".to_string(),
+            role: PlayerRole::default(),
+            team_role: TeamRole::default(),
+            joined_at: Utc::now(),
+        };
+        let mut query = BTreeMap::new();
+        query.insert("flash".to_string(), "joined".to_string());
+        let flash = Flash::from_request(&get_request("/game/x", query)).unwrap();
+
+        assert_eq!(
+            flash.message(std::slice::from_ref(&alice), Some(alice.id)),
+            Some("Joined as Alice".to_string())
+        );
+        assert_eq!(flash.message(&[alice], Some(Uuid::new_v4())), None);
+        assert_eq!(
+            Flash::from_request(&get_request("/game/x", BTreeMap::new())),
+            None
+        );
+        assert_eq!(Flash::Created.add_to("/game/x"), "/game/x?flash=created");
+    }
+
+    fn request_with_token(token: &AuthToken) -> RouteRequest {
+        let mut req = get_request("/api/games/x/vote", BTreeMap::new());
+        req.cookies
+            .insert(AUTH_COOKIE.to_string(), token.sign(&AUTH_SECRET).unwrap());
+        req
+    }
+
+    #[test]
+    fn test_authenticate_identifies_player_from_cookie() {
+        let game_id = Uuid::new_v4();
+        let token = AuthToken::new(Uuid::new_v4(), game_id, chrono::Duration::hours(1));
+
+        let (authenticated, refreshed) =
+            authenticate(&request_with_token(&token), game_id).unwrap();
+        assert_eq!(authenticated, token);
+        assert!(refreshed.is_none());
+
+        assert!(matches!(
+            authenticate(&request_with_token(&token), Uuid::new_v4()),
+            Err(RouteError::Forbidden(_))
+        ));
+        assert!(matches!(
+            authenticate(&get_request("/api/games/x/vote", BTreeMap::new()), game_id),
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_authenticate_rejects_expired_and_refreshes_expiring_tokens() {
+        let game_id = Uuid::new_v4();
+        let now = Utc::now();
+        let token = |expires_at| AuthToken {
+            player_id: Uuid::new_v4(),
+            game_id,
+            issued_at: now - chrono::Duration::hours(1),
+            expires_at,
+        };
+
+        let expired = token(now - chrono::Duration::minutes(1));
+        assert!(matches!(
+            authenticate(&request_with_token(&expired), game_id),
+            Err(RouteError::ExpiredToken)
+        ));
+
+        let expiring = token(now + chrono::Duration::minutes(2));
+        let (_, refreshed) = authenticate(&request_with_token(&expiring), game_id).unwrap();
+        let refreshed = refreshed.unwrap();
+        assert_eq!(refreshed.player_id, expiring.player_id);
+        assert!(refreshed.expires_at > expiring.expires_at);
+    }
+
+    #[tokio::test]
+    async fn test_export_game_route_rejects_unknown_format() {
+        let mut query = BTreeMap::new();
+        query.insert("format".to_string(), "xml".to_string());
+
+        let result = export_game_route(get_request(
+            &format!("/api/games/{}/export", Uuid::new_v4()),
+            query,
+        ))
+        .await;
+
+        assert!(
+            matches!(result, Err(RouteError::RouteFailed(message)) if message == "Unsupported export format: xml")
+        );
+    }
+
+    #[test]
+    fn test_export_rows_quote_csv_and_escape_markdown() {
+        let cast_at = Utc::now();
+        let round_id = Uuid::new_v4();
+        let vote = |name: &str, value: &str| Vote {
+            player_id: Uuid::new_v4(),
+            player_name: name.to_string(),
+            value: value.to_string(),
+            cast_at,
+            confidence: None,
+        };
+        let history = GameHistory {
+            game: Game {
+                id: Uuid::new_v4(),
+                name: "Game".to_string(),
+                owner_id: Uuid::new_v4(),
+                voting_system: "fibonacci".to_string(),
+                state: GameState::Revealed,
+                current_story: None,
+                created_at: cast_at,
+                updated_at: cast_at,
+                settings: GameSettings::default(),
+                round_id,
+                voting_started_at: None,
+                voting_deadline: None,
+                completed_at: None,
+            },
+            in_progress: false,
+            participants: vec![],
+            rounds: vec![planning_poker_models::RoundHistory {
+                round_id,
+                story: Some("Login, \"SSO\" | 2FA".to_string()),
+                votes: vec![vote("Alice", "3"), vote("Bob", "5")],
+            }],
+            final_estimates: vec![],
+        };
+        let rounds = [RoundSummary {
+            round_id,
+            story: Some("Login, \"SSO\" | 2FA".to_string()),
+            final_estimate: Some("5".to_string()),
+            distribution: vec![("3".to_string(), 1), ("5".to_string(), 1)],
+            first_vote_at: cast_at,
+            last_vote_at: cast_at,
+        }];
+
+        let rows = export_rows(&history, &rounds);
+        assert_eq!(rows[0][2], "Alice: 3; Bob: 5");
+
+        let csv = export_csv(&rows);
+        let lines: Vec<_> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "Story,Final estimate,Votes,First vote,Last vote");
+        assert!(lines[1].starts_with("\"Login, \"\"SSO\"\" | 2FA\",5,Alice: 3; Bob: 5,"));
+
+        let markdown = export_markdown(&rows);
+        let lines: Vec<_> = markdown.lines().collect();
+        assert_eq!(
+            lines[0],
+            "| Story | Final estimate | Votes | First vote | Last vote |"
+        );
+        assert_eq!(lines[1], "| --- | --- | --- | --- | --- |");
+        assert!(lines[2].starts_with("| Login, \"SSO\" \\| 2FA | 5 | Alice: 3; Bob: 5 | "));
+    }
+
+    #[tokio::test]
+    async fn test_round_history_route_rejects_invalid_offset() {
+        let mut query = BTreeMap::new();
+        query.insert("offset".to_string(), "latest".to_string());
+
+        let result = round_history_route(get_request(
+            &format!("/api/games/{}/rounds", Uuid::new_v4()),
+            query,
+        ))
+        .await;
+
+        assert!(
+            matches!(result, Err(RouteError::RouteFailed(message)) if message.starts_with("Invalid offset"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_game_route_rejects_invalid_players_page() {
+        for (name, value) in [
+            ("players_page", "0"),
+            ("players_page", "first"),
+            ("players_page_size", "101"),
+        ] {
+            let mut query = BTreeMap::new();
+            query.insert(name.to_string(), value.to_string());
+
+            let result = get_game_route(get_request(
+                &format!("/api/games/{}", Uuid::new_v4()),
+                query,
+            ))
+            .await;
+
+            assert!(
+                matches!(result, Err(RouteError::RouteFailed(ref message)) if message.contains("players_page")),
+                "{name}={value} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_error_pages_renders_not_found_page() {
+        let content = with_error_pages(Err(RouteError::NotFound("Game not found".to_string())));
+
+        let rendered = format!("{content:?}");
+        assert!(rendered.contains("404 - Not Found"));
+        assert!(rendered.contains("Game not found"));
+    }
+
+    #[test]
+    fn test_with_error_pages_renders_forbidden_page() {
+        let content = with_error_pages(Err(RouteError::Forbidden(
+            "Only the game owner can do that".to_string(),
+        )));
+
+        let rendered = format!("{content:?}");
+        assert!(rendered.contains("403 - Forbidden"));
+        assert!(rendered.contains("Only the game owner can do that"));
+    }
+
+    #[test]
+    fn test_mutating_routes_answer_recoverable_errors_with_a_toast() {
+        for error in [
+            RouteError::Rejected("You already voted this round".to_string()),
+            RouteError::Forbidden("Only the game owner can do that".to_string()),
+        ] {
+            let message = error.to_string();
+            let rendered = format!("{:?}", toast_errors(Err(error)).unwrap());
+            assert!(rendered.contains("toast-error"), "{message}");
+            assert!(rendered.contains(message.trim_start_matches("Forbidden: ")));
+        }
+
+        assert!(matches!(
+            toast_errors(Err(RouteError::InvalidToken)),
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_with_error_pages_renders_validation_errors() {
+        let content =
+            with_error_pages(Err(RouteError::validation("name", "Game name is required")));
+
+        let rendered = format!("{content:?}");
+        assert!(rendered.contains("400 - Bad Request"));
+        assert!(rendered.contains("name"));
+        assert!(rendered.contains("Game name is required"));
+    }
+
+    #[test]
+    fn test_with_error_pages_hides_internal_failures() {
+        let content = with_error_pages(Err(RouteError::RouteFailed(
+            "Database error: disk I/O error".to_string(),
+        )));
+
+        let rendered = format!("{content:?}");
+        assert!(rendered.contains("500 - Something Went Wrong"));
+        assert!(!rendered.contains("disk I/O error"));
+    }
+
+    #[test]
+    fn test_with_error_pages_renders_wrong_method_page() {
+        let content = with_error_pages(Err(RouteError::UnsupportedMethod));
+
+        assert!(format!("{content:?}").contains("405 - Method Not Allowed"));
+    }
+
+    #[test]
+    fn test_with_error_pages_lists_the_allowed_methods() {
+        let content = with_error_pages(Err(RouteError::MethodNotAllowed {
+            method: "PATCH".to_string(),
+            allowed: "GET, POST",
+        }));
+
+        let rendered = format!("{content:?}");
+        assert!(rendered.contains("405 - Method Not Allowed"));
+        assert!(rendered.contains("Allow: GET, POST"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_game_link_renders_bad_request_page() {
+        let content = with_error_pages(
+            game_page_route(get_request("/game/not-a-uuid", BTreeMap::new())).await,
+        );
+
+        let rendered = format!("{content:?}");
+        assert!(rendered.contains("400 - Bad Request"));
+        assert!(rendered.contains("That game link doesn't look right"));
+        assert!(rendered.contains("Back to Home"));
+    }
+
+    fn get_request(path: &str, query: BTreeMap<String, String>) -> RouteRequest {
+        RouteRequest {
+            path: path.to_string(),
+            method: Method::Get,
+            query,
+            headers: BTreeMap::new(),
+            cookies: BTreeMap::new(),
+            info: RequestInfo::default(),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_search_games_request_reads_filters_and_defaults() {
+        let query = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                .collect()
+        };
+        let owner_id = Uuid::new_v4();
+
+        let search = SearchGamesRequest::from_query(&query(&[
+            ("q", " sprint 14 "),
+            ("state", "Waiting"),
+            ("voting_system", "fibonacci"),
+            ("owner_id", &owner_id.to_string()),
+        ]))
+        .unwrap();
+        assert_eq!(
+            search,
+            SearchGamesRequest {
+                q: "sprint 14".to_string(),
+                state: Some(GameState::Waiting),
+                voting_system: Some("fibonacci".to_string()),
+                owner_id: Some(owner_id),
+                page: PaginationParams {
+                    page: 1,
+                    page_size: DEFAULT_SEARCH_PAGE_SIZE,
+                },
+            }
+        );
+
+        for (name, invalid) in [
+            ("state", query(&[("q", "sprint"), ("state", "Sleeping")])),
+            ("page", query(&[("q", "sprint"), ("page", "0")])),
+            ("page_size", query(&[("q", "sprint"), ("page_size", "101")])),
+        ] {
+            assert!(
+                matches!(
+                    SearchGamesRequest::from_query(&invalid),
+                    Err(RouteError::Validation { ref field, .. }) if field == name
+                ),
+                "invalid {name} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_games_list_request_hides_completed_games_unless_asked() {
+        let query = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                .collect()
+        };
+
+        assert_eq!(
+            GamesListRequest::from_query(&query(&[])).unwrap(),
+            GamesListRequest {
+                state: None,
+                include_completed: false,
+                page: PaginationParams {
+                    page: 1,
+                    page_size: DEFAULT_GAMES_LIST_PAGE_SIZE,
+                },
+            }
+        );
+        for (completed, included) in [("1", true), ("true", true), ("0", false), ("", false)] {
+            let list = GamesListRequest::from_query(&query(&[("completed", completed)])).unwrap();
+            assert_eq!(list.include_completed, included, "completed={completed}");
+        }
+
+        let list =
+            GamesListRequest::from_query(&query(&[("state", "Voting"), ("page", "3")])).unwrap();
+        assert_eq!(list.state, Some(GameState::Voting));
+        assert_eq!(list.page.page, 3);
+        assert!(GamesListRequest::from_query(&query(&[("page_size", "101")])).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_games_route_rejects_short_queries() {
+        for q in ["", "a", " s "] {
+            let query = BTreeMap::from([("q".to_string(), q.to_string())]);
+            let result = search_games_route(get_request("/api/games/search", query)).await;
+
+            assert!(
+                matches!(
+                    result,
+                    Err(RouteError::Validation { ref field, ref message })
+                        if field == "q" && message.contains("at least 2 characters")
+                ),
+                "{q:?} was searched for"
             );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_voting_systems_route_lists_builtin_decks() {
+        let content = voting_systems_route(get_request("/api/voting-systems", BTreeMap::new()))
+            .await
+            .unwrap();
+
+        let Content::Json(json) = content else {
+            panic!("Expected a JSON response");
+        };
+        let systems = json["voting_systems"].as_array().unwrap();
+        assert_eq!(systems.len(), VotingSystem::builtin().len());
+        assert_eq!(systems[1]["key"], "tshirt");
+        assert_eq!(
+            systems[1]["options"],
+            serde_json::json!(VotingSystem::TShirtSizes.get_voting_options())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_voting_systems_route_renders_preview_for_selection() {
+        let query = BTreeMap::from([("voting_system".to_string(), "powers_of_2".to_string())]);
+        let content = voting_systems_route(get_request("/api/voting-systems", query))
+            .await
+            .unwrap();
+
+        assert!(format!("{content:?}").contains("Powers of Two deck:"));
+    }
+
+    #[test]
+    fn test_configured_voting_system_prefers_named_decks_then_builtin_systems() {
+        let mut voting = VotingConfig::default();
+        voting.decks.insert(
+            "tshirt".to_string(),
+            planning_poker_config::DeckConfig {
+                values: vec!["S".to_string(), "M".to_string(), "L".to_string()],
+            },
+        );
+
+        let deck = configured_voting_system(&voting, "tshirt").unwrap();
+        let game = planning_poker_poker::PlanningPokerGame::new(
+            "Sprint".to_string(),
+            Uuid::new_v4(),
+            deck,
+        );
+        assert_eq!(game.voting_system.get_voting_options(), vec!["S", "M", "L"]);
+        assert!(matches!(
+            configured_voting_system(&voting, "powers_of_2"),
+            Some(VotingSystem::PowersOfTwo)
+        ));
+        assert!(configured_voting_system(&voting, "unknown").is_none());
+    }
+
+    #[test]
+    fn test_default_voting_system_must_be_known() {
+        let mut voting = VotingConfig::default();
+        assert!(check_default_voting_system(&voting).is_ok());
+
+        voting.default_system = "mysteryteam".to_string();
+        let error = check_default_voting_system(&voting).unwrap_err();
+
+        assert!(matches!(error, ConfigError::Invalid(_)));
+        assert!(error
+            .to_string()
+            .contains("voting.default_system 'mysteryteam'"));
+    }
+
+    #[test]
+    fn test_tls_config_is_refused_as_unsupported() {
+        let mut config = Config::default();
+        assert!(reject_unsupported(&config).is_ok());
+
+        config.server.tls = Some(planning_poker_config::TlsConfig::default());
+        let error = reject_unsupported(&config).unwrap_err();
+
+        assert!(matches!(error, ConfigError::Unsupported(_)));
+        assert!(error.to_string().contains("server.tls is not supported"));
+    }
+
+    #[test]
+    fn test_release_builds_require_an_auth_secret() {
+        let mut auth = AuthConfig::default();
+        assert!(require_auth_secret(&auth, true).is_ok());
+
+        let error = require_auth_secret(&auth, false).unwrap_err();
+        assert!(error.to_string().contains("auth.secret must be set"));
+
+        auth.secret = Some("signing-key".to_string());
+        assert!(require_auth_secret(&auth, false).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_route_serves_prometheus_text() {
+        let Content::Raw { data, content_type } =
+            metrics_route(get_request("/metrics", BTreeMap::new())).await
+        else {
+            panic!("Expected a raw response");
+        };
+
+        assert!(content_type.starts_with("text/plain"));
+        let body = String::from_utf8(data.to_vec()).unwrap();
+        assert!(body.contains("# TYPE games_created_total counter\n"));
+        assert!(body.contains("games_reset_total "));
+    }
+
+    #[tokio::test]
+    async fn test_finalize_route_requires_a_token() {
+        let path = format!("/api/games/{}/finalize", Uuid::new_v4());
+        let req = multipart_request(
+            &path,
+            b"--fuzz\r\nContent-Disposition: form-data; name=\"value\"\r\n\r\n5\r\n--fuzz--\r\n",
+        );
+
+        assert!(matches!(
+            finalize_estimate_route(req).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_complete_route_requires_post_and_a_token() {
+        let path = format!("/api/games/{}/complete", Uuid::new_v4());
+
+        assert!(matches!(
+            complete_game_route(get_request(&path, BTreeMap::new())).await,
+            Err(RouteError::UnsupportedMethod)
+        ));
+        assert!(matches!(
+            complete_game_route(multipart_request(&path, b"--fuzz--\r\n")).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
 
-            // Send partial updates via SSE instead of returning full page
-            if let Ok(Some(game)) = session_manager.get_game(game_id).await {
-                let status = match game.state {
-                    GameState::Waiting => "Waiting for players",
-                    GameState::Voting => "Voting in progress",
-                    GameState::Revealed => "Votes revealed",
-                };
-                tracing::info!(
-                    "Game state after reset: {:?}, status: {}",
-                    game.state,
-                    status
-                );
-                update_game_status(game_id_str, status).await;
+    #[tokio::test]
+    async fn test_archive_route_requires_post_and_a_token() {
+        let path = format!("/api/games/{}/archive", Uuid::new_v4());
 
-                let voting_active = matches!(game.state, GameState::Voting);
-                update_vote_buttons(game_id_str, voting_active).await;
-                update_story_input(game_id_str, voting_active, game.current_story.as_ref()).await;
-                update_current_story(game.current_story.as_ref(), voting_active).await;
-                update_game_actions(game_id_str, game.state).await;
-            }
+        assert!(matches!(
+            archive_game_route(get_request(&path, BTreeMap::new())).await,
+            Err(RouteError::UnsupportedMethod)
+        ));
+        assert!(matches!(
+            archive_game_route(multipart_request(&path, b"--fuzz--\r\n")).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
 
-            // After reset, votes should be empty
-            if let Ok(votes) = session_manager.get_game_votes(game_id).await {
-                tracing::info!("Votes after reset: {} votes found", votes.len());
-                update_vote_results(game_id_str, votes, false).await;
-            }
+    #[test]
+    fn test_parse_story_import_skips_blank_lines() {
+        let stories = parse_story_import("KEY-1 | Login\n\n   \nSearch\n").unwrap();
+
+        assert_eq!(stories.len(), 2);
+        assert_eq!(stories[0].key.as_deref(), Some("KEY-1"));
+        assert_eq!(stories[1].title, "Search");
+    }
+
+    #[test]
+    fn test_parse_story_import_rejects_oversized_pastes() {
+        let paste: String = (1..=500).map(|n| format!("Story {n}\n")).collect();
+
+        assert!(matches!(
+            parse_story_import(&paste),
+            Err(RouteError::RouteFailed(message)) if message.contains("at most 200 stories")
+        ));
+        assert!(matches!(
+            parse_story_import(&"x".repeat(MAX_STORY_LENGTH + 1)),
+            Err(RouteError::RouteFailed(message)) if message.starts_with("Story on line 1")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_route_requires_get_and_a_token_for_the_game() {
+        let game_id = Uuid::new_v4();
+        let path = format!("/api/games/{game_id}/audit-log");
+
+        let mut post = get_request(&path, BTreeMap::new());
+        post.method = Method::Post;
+        assert!(matches!(
+            audit_log_route(post).await,
+            Err(RouteError::UnsupportedMethod)
+        ));
+
+        assert!(matches!(
+            audit_log_route(get_request(&path, BTreeMap::new())).await,
+            Err(RouteError::InvalidToken)
+        ));
+
+        let token = AuthToken::new(Uuid::new_v4(), Uuid::new_v4(), chrono::Duration::hours(1));
+        let mut req = request_with_token(&token);
+        req.path = path;
+        assert!(matches!(
+            audit_log_route(req).await,
+            Err(RouteError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_kick_player_route_requires_post() {
+        let path = format!(
+            "/api/games/{}/players/{}/kick",
+            Uuid::new_v4(),
+            Uuid::new_v4()
+        );
+
+        let result = kick_player_route(get_request(&path, BTreeMap::new())).await;
+
+        assert!(matches!(result, Err(RouteError::UnsupportedMethod)));
+    }
+
+    #[tokio::test]
+    async fn test_kick_route_reads_target_from_form() {
+        let game_id = Uuid::new_v4();
+        let path = format!("/api/games/{game_id}/kick");
+        let form = |player_id: &str| {
+            multipart_request(
+                &path,
+                format!(
+                    "--fuzz\r\nContent-Disposition: form-data; name=\"player_id\"\r\n\r\n{player_id}\r\n--fuzz--\r\n"
+                )
+                .as_bytes(),
+            )
+        };
+        let token = AuthToken::new(Uuid::new_v4(), game_id, chrono::Duration::hours(1));
+
+        let mut req = form("not-a-uuid");
+        req.cookies
+            .insert(AUTH_COOKIE.to_string(), token.sign(&AUTH_SECRET).unwrap());
+        assert!(matches!(
+            kick_player_route(req).await,
+            Err(RouteError::InvalidUuid(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_discard_vote_route_rejects_a_forged_requester() {
+        let path = format!(
+            "/api/games/{}/votes/{}/discard",
+            Uuid::new_v4(),
+            Uuid::new_v4()
+        );
+        let mut req = multipart_request(&path, b"--fuzz--\r\n");
+        req.query
+            .insert("player_id".to_string(), Uuid::new_v4().to_string());
+
+        assert!(matches!(
+            discard_vote_route(req).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_kick_route_ignores_a_forged_requester() {
+        let game_id = Uuid::new_v4();
+        let path = format!("/api/games/{game_id}/players/{}/kick", Uuid::new_v4());
+        let mut req = multipart_request(&path, b"--fuzz--\r\n");
+        req.query
+            .insert("player_id".to_string(), Uuid::new_v4().to_string());
+
+        assert!(matches!(
+            kick_player_route(req).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    /// Log output captured from a test subscriber
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_traced_route_tags_handler_logs_with_the_request_id() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let request_id = Uuid::new_v4();
+        let mut req = get_request("/api/games/x/vote", BTreeMap::new());
+        req.headers
+            .insert(REQUEST_ID_HEADER.to_string(), request_id.to_string());
+
+        let result = traced_route(req, |_| async {
+            tracing::info!("Querying the database");
+            Err(RouteError::UnsupportedMethod)
+        })
+        .await;
+        assert!(matches!(result, Err(RouteError::UnsupportedMethod)));
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let tagged = format!("route{{request_id={request_id} path=/api/games/x/vote}}");
+        for message in [
+            "Request started",
+            "Querying the database",
+            "Request finished: error",
+        ] {
+            let line = output
+                .lines()
+                .find(|line| line.contains(message))
+                .unwrap_or_else(|| panic!("missing {message:?} in {output}"));
+            assert!(line.contains(&tagged), "untagged line: {line}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_game_action_is_not_found_instead_of_reading_the_game() {
+        let mut req = get_request(
+            &format!("/api/games/{}/unknown-thing", Uuid::new_v4()),
+            BTreeMap::new(),
+        );
+        req.method = Method::Post;
+
+        let result = games_api_route(req).await;
+        assert!(
+            matches!(result, Err(RouteError::NotFound(ref message)) if message.contains("unknown-thing")),
+            "{result:?}"
+        );
+    }
 
-            // Return minimal success response
-            let success_content = container! {
-                div { "Voting reset successfully" }
+    #[tokio::test]
+    async fn test_each_path_answers_to_its_own_methods() {
+        let game = format!("/api/games/{}", Uuid::new_v4());
+        let player = Uuid::new_v4();
+        // Every request here fails before touching the database: the wrong
+        // methods with a 405, the right ones inside the handler they reach
+        let cases = [
+            (Method::Patch, "/api/games".to_string(), Some("GET, POST")),
+            (Method::Get, "/api/games".to_string(), None),
+            (Method::Post, "/api/games".to_string(), None),
+            (Method::Post, "/api/games/search".to_string(), Some("GET")),
+            (Method::Get, "/api/games/search".to_string(), None),
+            (Method::Post, game.clone(), Some("GET")),
+            (Method::Patch, game.clone(), Some("GET")),
+            (Method::Get, format!("{game}/vote"), Some("POST")),
+            (Method::Patch, format!("{game}/vote"), Some("POST")),
+            (Method::Post, format!("{game}/vote"), None),
+            (Method::Get, format!("{game}/reveal"), Some("POST")),
+            (Method::Get, format!("{game}/stories/import"), Some("POST")),
+            (
+                Method::Get,
+                format!("{game}/players/{player}/kick"),
+                Some("POST"),
+            ),
+            (Method::Post, format!("{game}/players/{player}/kick"), None),
+            (
+                Method::Post,
+                format!("{game}/players/{player}"),
+                Some("PATCH"),
+            ),
+            (
+                Method::Get,
+                format!("{game}/players/{player}"),
+                Some("PATCH"),
+            ),
+            (Method::Patch, format!("{game}/players/{player}"), None),
+            (Method::Post, format!("{game}/export"), Some("GET")),
+            (Method::Get, format!("{game}/export"), None),
+            (Method::Post, format!("{game}/audit-log"), Some("GET")),
+        ];
+
+        for (method, path, expected) in cases {
+            let query = BTreeMap::from([
+                ("q".to_string(), String::new()),
+                ("state".to_string(), "bogus".to_string()),
+                ("format".to_string(), "xml".to_string()),
+            ]);
+            let mut req = get_request(&path, query);
+            req.method = method;
+            let method = method_name(&req.method);
+
+            let result = if path == "/api/games" {
+                games_collection_route(req).await
+            } else {
+                games_api_route(req).await
             };
-            Ok(Content::try_view(success_content).unwrap())
+            match (result, expected) {
+                (Err(RouteError::MethodNotAllowed { allowed, .. }), Some(expected)) => {
+                    assert_eq!(allowed, expected, "{method} {path}");
+                }
+                (
+                    Err(RouteError::MethodNotAllowed { .. } | RouteError::UnsupportedMethod),
+                    None,
+                )
+                | (Ok(_), _) => panic!("{method} {path} wasn't dispatched to its handler"),
+                (Err(_), None) => {}
+                (result, Some(_)) => panic!("{method} {path} should be a 405, got {result:?}"),
+            }
         }
-        Err(e) => Err(RouteError::RouteFailed(format!(
-            "Failed to reset voting: {e}"
-        ))),
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use bytes::Bytes;
-    use hyperchad::router::{RequestInfo, RouteRequest};
-    use std::collections::BTreeMap;
-    use std::sync::Arc;
+    #[test]
+    fn test_invite_url_prefers_the_public_url_over_the_host() {
+        let headers = BTreeMap::from([
+            ("host".to_string(), "10.0.0.5:8080".to_string()),
+            ("x-forwarded-proto".to_string(), "https".to_string()),
+        ]);
+
+        assert_eq!(
+            invite_url(Some("https://poker.example.com/"), &headers, "abc"),
+            "https://poker.example.com/?game=abc"
+        );
+        assert_eq!(
+            invite_url(None, &headers, "abc"),
+            "https://10.0.0.5:8080/?game=abc"
+        );
+        let host = BTreeMap::from([("host".to_string(), "localhost:8080".to_string())]);
+        assert_eq!(
+            invite_url(None, &host, "abc"),
+            "http://localhost:8080/?game=abc"
+        );
+        assert_eq!(invite_url(None, &BTreeMap::new(), "abc"), "/?game=abc");
+    }
 
     #[tokio::test]
-    async fn test_join_game_form_parsing() {
-        // Create a mock form data for multipart/form-data
-        let boundary = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
-        let form_data = "------WebKitFormBoundary7MA4YWxkTrZu0gW\r\n\
-             Content-Disposition: form-data; name=\"game-id\"\r\n\r\n\
-             test-game-123\r\n\
-             ------WebKitFormBoundary7MA4YWxkTrZu0gW\r\n\
-             Content-Disposition: form-data; name=\"player-name\"\r\n\r\n\
-             John Doe\r\n\
-             ------WebKitFormBoundary7MA4YWxkTrZu0gW--\r\n"
-            .to_string();
-        let body = Bytes::from(form_data);
+    async fn test_join_prompt_rejects_malformed_game_links() {
+        let result = join_prompt_route(get_request("/join/not-a-uuid", BTreeMap::new())).await;
+        assert!(
+            matches!(result, Err(RouteError::InvalidUuid(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_request_id_falls_back_to_a_fresh_one() {
+        let mut req = get_request("/api/games/x", BTreeMap::new());
+        let generated = request_id(&req);
+        assert_ne!(generated, request_id(&req));
+
+        req.headers
+            .insert(REQUEST_ID_HEADER.to_string(), "not-a-uuid".to_string());
+        assert_ne!(request_id(&req), generated);
+
+        let given = Uuid::new_v4();
+        req.headers
+            .insert(REQUEST_ID_HEADER.to_string(), given.to_string());
+        assert_eq!(request_id(&req), given);
+    }
+
+    #[tokio::test]
+    async fn test_game_snapshot_route_requires_get_and_a_game_id() {
+        let path = format!("/game/{}/snapshot", Uuid::new_v4());
+
+        assert!(matches!(
+            game_snapshot_route(multipart_request(&path, b"--fuzz--\r\n")).await,
+            Err(RouteError::UnsupportedMethod)
+        ));
+        assert!(matches!(
+            game_snapshot_route(get_request("/game/not-a-uuid/snapshot", BTreeMap::new())).await,
+            Err(RouteError::InvalidUuid(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_leave_game_route_requires_post_and_a_token() {
+        let path = format!("/api/games/{}/leave", Uuid::new_v4());
+
+        assert!(matches!(
+            leave_game_route(get_request(&path, BTreeMap::new())).await,
+            Err(RouteError::UnsupportedMethod)
+        ));
+        assert!(matches!(
+            leave_game_route(multipart_request(&path, b"--fuzz--\r\n")).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_join_error_explains_a_taken_name() {
+        let taken = anyhow::Error::new(NameTakenError {
+            name: "Alice".to_string(),
+        });
+        assert_eq!(
+            join_error(&taken).to_string(),
+            "The name 'Alice' is already taken in this game, please pick another one"
+        );
+
+        assert!(matches!(
+            join_error(&anyhow::anyhow!("Game is full")),
+            RouteError::RouteFailed(message) if message == "Failed to join game: Game is full"
+        ));
+    }
+
+    #[test]
+    fn test_owner_controls_follow_transferred_ownership() {
+        let old_owner = Uuid::new_v4();
+        let new_owner = Uuid::new_v4();
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id: new_owner,
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Waiting,
+            current_story: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            settings: GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+
+        // Starting a round is checked the same way as every other owner action
+        assert!(ensure_owner(&game, new_owner).is_ok());
+        assert!(matches!(
+            ensure_owner(&game, old_owner),
+            Err(RouteError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_render_is_reported_and_counted() {
+        let failed_before = STATE.metrics().get(Metric::PartialUpdatesFailed);
+
+        // Stands in for a renderer whose client connection is gone
+        let result = deliver_partial(
+            "vote-results",
+            container! { div { "2 votes cast" } },
+            |_partial| async { Err::<(), _>("connection closed") },
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(PartialUpdateError::Render { ref target, ref message })
+                if target == "vote-results" && message.contains("connection closed")
+        ));
+        assert_eq!(
+            STATE.metrics().get(Metric::PartialUpdatesFailed),
+            failed_before + 1
+        );
+
+        let delivered = deliver_partial(
+            "vote-results",
+            container! { div { "2 votes cast" } },
+            |_partial| async { Ok::<(), &str>(()) },
+        )
+        .await;
+        assert!(delivered.is_ok());
+    }
+
+    #[test]
+    fn test_delivery_warns_only_after_a_failure() {
+        let mut delivery = Delivery::default();
+        delivery.record(Ok(()));
+        assert!(delivery.warning().is_empty());
+
+        delivery.record(Err(PartialUpdateError::NoRenderer));
+        delivery.record(Err(PartialUpdateError::Load("Game not found".to_string())));
+
+        let warning = format!("{:?}", delivery.warning());
+        assert!(warning.contains("broadcast-warning"));
+        assert!(warning.contains("Renderer is not initialized"));
+        assert!(!warning.contains("Game not found"));
+    }
+
+    #[test]
+    fn test_every_round_transition_refreshes_the_same_targets() {
+        let player = Player {
+            id: Uuid::new_v4(),
+            name: "Alice".to_string(),
+            role: PlayerRole::Voter,
+            team_role: TeamRole::default(),
+            joined_at: Utc::now(),
+        };
+        let vote = Vote {
+            player_id: player.id,
+            player_name: player.name.clone(),
+            value: "5".to_string(),
+            cast_at: Utc::now(),
+            confidence: None,
+        };
+        let game = |state| Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id: player.id,
+            voting_system: "fibonacci".to_string(),
+            state,
+            current_story: Some("Login page".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            settings: GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+
+        // Start and revote open a round, reveal shows it, reset and finalize close it
+        for (state, votes) in [
+            (GameState::Voting, vec![]),
+            (GameState::Voting, vec![vote.clone()]),
+            (GameState::Revealed, vec![vote.clone()]),
+            (GameState::Waiting, vec![]),
+        ] {
+            let partials = game_snapshot_partials("game", &game(state), &votes, &[player.clone()]);
+            let targets: Vec<&str> = partials.iter().map(|(target, _)| *target).collect();
+            assert_eq!(
+                targets,
+                [
+                    "game-status",
+                    "players-list",
+                    "current-story",
+                    "story-input",
+                    "voting-section",
+                    "results-section",
+                    "game-actions",
+                ]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_ownership_route_requires_new_owner() {
+        let game_id = Uuid::new_v4();
+        let path = format!("/api/games/{game_id}/transfer-ownership");
+        let mut req = multipart_request(
+            &path,
+            b"--fuzz\r\nContent-Disposition: form-data; name=\"new_owner_id\"\r\n\r\nnot-a-uuid\r\n--fuzz--\r\n",
+        );
+        let token = AuthToken::new(Uuid::new_v4(), game_id, chrono::Duration::hours(1));
+        req.cookies
+            .insert(AUTH_COOKIE.to_string(), token.sign(&AUTH_SECRET).unwrap());
+
+        assert!(matches!(
+            transfer_ownership_route(req).await,
+            Err(RouteError::InvalidUuid(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_ownership_route_rejects_a_forged_requester() {
+        let path = format!("/api/games/{}/transfer-ownership", Uuid::new_v4());
+        let body = format!(
+            "--fuzz\r\nContent-Disposition: form-data; name=\"new_owner_id\"\r\n\r\n{}\r\n--fuzz--\r\n",
+            Uuid::new_v4()
+        );
+        let mut req = multipart_request(&path, body.as_bytes());
+        req.query
+            .insert("player_id".to_string(), Uuid::new_v4().to_string());
+
+        assert!(matches!(
+            transfer_ownership_route(req).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
 
+    #[tokio::test]
+    async fn test_set_player_role_route_rejects_a_forged_requester() {
+        let player_id = Uuid::new_v4();
+        let path = format!("/api/games/{}/players/{player_id}/role", Uuid::new_v4());
+        let mut req = form_request(&path, &[("role", "Observer")]);
+        req.query
+            .insert("player_id".to_string(), player_id.to_string());
+
+        assert!(matches!(
+            set_player_role_route(req).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_player_route_requires_patch() {
+        let path = format!("/api/games/{}/players/{}", Uuid::new_v4(), Uuid::new_v4());
+
+        let result = update_player_route(get_request(&path, BTreeMap::new())).await;
+
+        assert!(matches!(result, Err(RouteError::UnsupportedMethod)));
+    }
+
+    #[tokio::test]
+    async fn test_update_player_route_rejects_a_forged_requester() {
+        let player_id = Uuid::new_v4();
+        let path = format!("/api/games/{}/players/{player_id}", Uuid::new_v4());
+        let mut query = BTreeMap::new();
+        query.insert("player_id".to_string(), player_id.to_string());
+        let mut req = get_request(&path, query);
+        req.method = Method::Patch;
+        req.body = Some(Arc::new(Bytes::from_static(br#"{"name": "Mallory"}"#)));
+
+        assert!(matches!(
+            update_player_route(req).await,
+            Err(RouteError::InvalidToken)
+        ));
+    }
+
+    fn multipart_request(path: &str, body: &[u8]) -> RouteRequest {
         let mut headers = BTreeMap::new();
         headers.insert(
             "content-type".to_string(),
-            format!("multipart/form-data; boundary={boundary}"),
+            "multipart/form-data; boundary=fuzz".to_string(),
         );
 
-        let req = RouteRequest {
-            path: "/join-game".to_string(),
+        RouteRequest {
+            path: path.to_string(),
             method: Method::Post,
             query: BTreeMap::new(),
             headers,
             cookies: BTreeMap::new(),
             info: RequestInfo::default(),
-            body: Some(Arc::new(body)),
+            body: Some(Arc::new(Bytes::copy_from_slice(body))),
+        }
+    }
+
+    #[test]
+    fn test_parse_start_voting_form_reads_form_or_json_story() {
+        let path = "/api/games/x/start-voting";
+        let form = |story: &str| {
+            multipart_request(
+                path,
+                format!(
+                    "--fuzz\r\nContent-Disposition: form-data; name=\"story\"\r\n\r\n{story}\r\n--fuzz--\r\n"
+                )
+                .as_bytes(),
+            )
+        };
+        let json = |body: &str| {
+            let mut req = multipart_request(path, body.as_bytes());
+            req.headers
+                .insert("content-type".to_string(), "application/json".to_string());
+            req
         };
 
-        // Test that the form parsing works
-        let result = join_game_route(req).await;
+        assert_eq!(
+            parse_start_voting_form(&form("  Login page ")).unwrap(),
+            ("Login page".to_string(), None)
+        );
+        assert_eq!(
+            parse_start_voting_form(&json(r#"{"story": "Checkout"}"#))
+                .unwrap()
+                .0,
+            "Checkout"
+        );
+        assert_eq!(
+            parse_start_voting_form(&form(" ")).unwrap().0,
+            UNTITLED_STORY
+        );
+        assert_eq!(
+            parse_start_voting_form(&json("{}")).unwrap().0,
+            UNTITLED_STORY
+        );
+        assert!(matches!(
+            parse_start_voting_form(&form(&"x".repeat(MAX_STORY_LENGTH + 1))),
+            Err(RouteError::RouteFailed(_))
+        ));
+        assert!(matches!(
+            parse_start_voting_form(&json("not json")),
+            Err(RouteError::ParseBody(_))
+        ));
+    }
 
-        // The result should be an error because UUID parsing will fail for "test-game-123"
-        // but it should get past the form parsing stage
+    #[test]
+    fn test_parse_start_voting_form_reads_optional_timer() {
+        let json = |body: &str| {
+            let mut req = multipart_request("/api/games/x/start-voting", body.as_bytes());
+            req.headers
+                .insert("content-type".to_string(), "application/json".to_string());
+            req
+        };
+
+        assert_eq!(
+            parse_start_voting_form(&json(r#"{"story": "Search", "timer_seconds": "90"}"#))
+                .unwrap(),
+            ("Search".to_string(), Some(Duration::from_secs(90)))
+        );
+        assert_eq!(
+            parse_start_voting_form(&json(r#"{"timer_seconds": " "}"#))
+                .unwrap()
+                .1,
+            None
+        );
+        for secs in ["0", "-5", "soon", "3601"] {
+            assert!(
+                matches!(
+                    parse_start_voting_form(&json(&format!(r#"{{"timer_seconds": "{secs}"}}"#))),
+                    Err(RouteError::RouteFailed(_))
+                ),
+                "accepted {secs}"
+            );
+        }
+    }
+
+    fn form_request(path: &str, fields: &[(&str, &str)]) -> RouteRequest {
+        let mut body = String::new();
+        for (name, value) in fields {
+            body.push_str(&format!(
+                "--fuzz\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            ));
+        }
+        body.push_str("--fuzz--\r\n");
+        multipart_request(path, body.as_bytes())
+    }
+
+    fn assert_invalid_field(result: Result<Content, RouteError>, expected: &str) {
         match result {
-            Err(RouteError::InvalidUuid(_)) => {
-                // This is expected - the form was parsed successfully but UUID parsing failed
-            }
-            Err(other) => {
-                // Let's see what error we actually get
-                println!("Got error: {other:?}");
-                panic!("Expected InvalidUuid error, got a different error type");
+            Err(RouteError::Validation { field, .. }) => assert_eq!(field, expected),
+            other => panic!("expected a validation error for {expected}, got {other:?}"),
+        }
+    }
+
+    /// A story batch POST for `game_id` by a player holding a valid token
+    fn story_batch_request(game_id: Uuid, body: &str) -> RouteRequest {
+        let token = AuthToken::new(Uuid::new_v4(), game_id, chrono::Duration::hours(1));
+        let mut req = multipart_request(&format!("/api/games/{game_id}/stories/batch"), b"");
+        req.headers.clear();
+        req.cookies
+            .insert(AUTH_COOKIE.to_string(), token.sign(&AUTH_SECRET).unwrap());
+        req.body = Some(Arc::new(Bytes::copy_from_slice(body.as_bytes())));
+        req
+    }
+
+    #[tokio::test]
+    async fn test_story_batch_reports_each_invalid_story_before_adding_any() {
+        let game_id = Uuid::new_v4();
+        let body = serde_json::json!({
+            "stories": [
+                { "title": "Login page", "description": "Email and password" },
+                { "title": "  " },
+                { "title": "x".repeat(501) },
+            ]
+        });
+
+        let result = games_api_route(story_batch_request(game_id, &body.to_string())).await;
+        match result {
+            Err(RouteError::InvalidStories(errors)) => {
+                assert_eq!(
+                    errors.iter().map(|error| error.index).collect::<Vec<_>>(),
+                    [1, 2]
+                );
             }
-            Ok(_) => panic!("Expected an error but got success"),
+            other => panic!("expected invalid stories, got {other:?}"),
         }
+
+        let result =
+            batch_stories_route(story_batch_request(game_id, r#"{"stories": "Login"}"#)).await;
+        assert!(
+            matches!(result, Err(RouteError::ParseBody(_))),
+            "{result:?}"
+        );
+
+        let stories = vec![serde_json::json!({ "title": "Story" }); MAX_IMPORTED_STORIES + 1];
+        let body = serde_json::json!({ "stories": stories });
+        let result = batch_stories_route(story_batch_request(game_id, &body.to_string())).await;
+        assert_invalid_field(result, "stories");
     }
 
     #[test]
-    fn test_join_game_form_deserialization() {
-        let form_data = JoinGameForm {
-            game_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
-            player_name: "Test Player".to_string(),
-        };
+    fn test_with_error_pages_lists_invalid_stories() {
+        let content = with_error_pages(Err(RouteError::InvalidStories(vec![StoryInputError {
+            index: 1,
+            message: "Title is required".to_string(),
+        }])));
+
+        let rendered = format!("{content:?}");
+        assert!(rendered.contains("422 - Unprocessable Entity"));
+        assert!(rendered.contains("Story 2: Title is required"));
+    }
 
-        assert_eq!(form_data.game_id, "550e8400-e29b-41d4-a716-446655440000");
-        assert_eq!(form_data.player_name, "Test Player");
+    #[tokio::test]
+    async fn test_create_game_route_reports_empty_name() {
+        let req = form_request(
+            "/api/games",
+            &[("name", "  "), ("voting_system", "fibonacci")],
+        );
+
+        assert_invalid_field(create_game_route(req).await, "name");
     }
 
-    #[test]
-    fn test_create_game_form_deserialization() {
-        let form_data = CreateGameForm {
-            name: "Test Game".to_string(),
-            voting_system: "fibonacci".to_string(),
-        };
+    #[tokio::test]
+    async fn test_join_routes_report_empty_player_name() {
+        let game_id = Uuid::new_v4().to_string();
+        let req = form_request("/join-game", &[("game-id", &game_id), ("player-name", "")]);
+        assert_invalid_field(join_game_route(req).await, "player-name");
+
+        let mut req = multipart_request(
+            &format!("/api/games/{game_id}/join"),
+            br#"{"player_name": " "}"#,
+        );
+        req.headers
+            .insert("content-type".to_string(), "application/json".to_string());
+        assert_invalid_field(join_game_api_route(req).await, "player_name");
+    }
 
-        assert_eq!(form_data.name, "Test Game");
-        assert_eq!(form_data.voting_system, "fibonacci");
+    #[tokio::test]
+    async fn test_observe_route_reports_empty_name_and_mismatched_game() {
+        let game_id = Uuid::new_v4().to_string();
+        let path = format!("/game/{game_id}/observe");
+
+        let req = form_request(&path, &[("game-id", &game_id), ("observer-name", " ")]);
+        assert_invalid_field(observe_game_route(req).await, "observer-name");
+
+        let other_game_id = Uuid::new_v4().to_string();
+        let req = form_request(
+            &path,
+            &[("game-id", &other_game_id), ("observer-name", "Olive")],
+        );
+        assert_invalid_field(observe_game_route(req).await, "game-id");
+    }
+
+    #[test]
+    fn test_form_parsers_reject_malformed_bodies_without_panicking() {
+        let bodies: [&[u8]; 6] = [
+            b"",
+            b"--fuzz--\r\n",
+            b"--fuzz\r\nContent-Disposition: form-data\r\n\r\n",
+            b"--fuzz\r\nContent-Disposition: form-data; name=\"game-id\"\r\n\r\n\xff\xfe\r\n--fuzz--\r\n",
+            b"--fuzz\r\nContent-Disposition: form-data; name=\"vote\"\r\n\r\n5\r\n--fuzz\r\nContent-Disposition: form-data; name=\"confidence\"\r\n\r\nextreme\r\n--fuzz--\r\n",
+            &[0, 159, 146, 150, 13, 10, 45, 45],
+        ];
+
+        for body in bodies {
+            assert!(parse_join_game_form(&multipart_request("/join-game", body)).is_err());
+            assert!(parse_observe_game_form(&multipart_request("/game/x/observe", body)).is_err());
+            assert!(parse_create_game_form(&multipart_request("/api/games", body)).is_err());
+            assert!(parse_vote_form(&multipart_request("/api/games/x/vote", body)).is_err());
+        }
     }
 }
@@ -0,0 +1,110 @@
+//! Log output set up from the `[logging]` config
+
+use planning_poker_config::{LogFormat, LoggingConfig};
+use tracing::Subscriber;
+use tracing_subscriber::{fmt::MakeWriter, util::SubscriberInitExt as _, EnvFilter};
+
+/// The filter for `logging`: `RUST_LOG` when it is set, otherwise the
+/// configured level
+#[must_use]
+pub fn filter(logging: &LoggingConfig) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&logging.level))
+}
+
+/// A subscriber writing the events `filter` lets through to `writer` in
+/// `format`
+#[must_use]
+pub fn subscriber<W>(
+    format: LogFormat,
+    filter: EnvFilter,
+    writer: W,
+) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer);
+
+    match format {
+        LogFormat::Pretty => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(builder.json().finish()),
+        LogFormat::Compact => Box::new(builder.compact().finish()),
+    }
+}
+
+/// Install the global subscriber for `logging`, writing to stdout
+///
+/// Without a config the default format is used, filtered by `RUST_LOG`, so
+/// the reason the config couldn't be loaded can still be logged.
+pub fn init(logging: Option<&LoggingConfig>) {
+    let (format, filter) = logging.map_or_else(
+        || (LogFormat::default(), EnvFilter::from_default_env()),
+        |logging| (logging.log_format(), filter(logging)),
+    );
+
+    subscriber(format, filter, std::io::stdout).init();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// What `format` writes for one info and one debug event at the `info` level
+    fn log_output(format: LogFormat) -> String {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = subscriber(format, EnvFilter::new("info"), move || writer.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(game_id = "abc", "Game created");
+            tracing::debug!("Filtered out");
+        });
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(!output.contains("Filtered out"), "{format:?}: {output}");
+        output
+    }
+
+    #[test]
+    fn test_json_format_writes_one_object_per_event() {
+        let output = log_output(LogFormat::Json);
+
+        let event: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["fields"]["message"], "Game created");
+        assert_eq!(event["fields"]["game_id"], "abc");
+    }
+
+    #[test]
+    fn test_text_formats_write_readable_lines() {
+        for format in [LogFormat::Pretty, LogFormat::Compact] {
+            let output = log_output(format);
+
+            assert!(output.contains("Game created"), "{format:?}: {output}");
+            assert!(
+                serde_json::from_str::<serde_json::Value>(output.trim()).is_err(),
+                "{format:?} wrote JSON: {output}"
+            );
+        }
+    }
+}
@@ -2,19 +2,25 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
-use planning_poker_app::{build_app, create_app_router, init, set_renderer};
+use planning_poker_app::{
+    build_app, create_app_router, graceful_shutdown, init, init_config, init_logging, set_renderer,
+    shutdown::wait_for_signal, shutdown_timeout,
+};
 use std::sync::Arc;
-use tracing::info;
+use tracing::{error, info};
 
 #[allow(clippy::cognitive_complexity)]
 fn main() -> Result<(), hyperchad::app::Error> {
-    // Initialize tracing - respect RUST_LOG environment variable
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
+    // Log in the configured format; RUST_LOG still overrides the level
+    init_logging();
 
     info!("Starting Planning Poker App");
 
+    if let Err(e) = init_config() {
+        error!("Invalid configuration: {e}");
+        std::process::exit(1);
+    }
+
     // Create runtime like MoosicBox does
     let runtime = switchy::unsync::runtime::Builder::new()
         .max_blocking_threads(64)
@@ -37,8 +43,24 @@ fn main() -> Result<(), hyperchad::app::Error> {
     set_renderer(renderer);
     info!("Renderer set successfully");
 
+    // Drain requests and flush updates on SIGTERM/SIGINT; installing the
+    // handlers replaces the default of exiting at once, so exit here after
+    let timeout = shutdown_timeout();
+    runtime.spawn(async move {
+        match wait_for_signal().await {
+            Ok(()) => {
+                graceful_shutdown(timeout).await;
+                std::process::exit(0);
+            }
+            Err(e) => error!("Failed to listen for shutdown signals: {e}"),
+        }
+    });
+
     info!("Running hyperchad app with built-in CLI");
     app.run()?;
 
+    // The server may also stop on its own; finish the shutdown either way
+    runtime.block_on(graceful_shutdown(timeout));
+
     Ok(())
 }
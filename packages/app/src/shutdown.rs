@@ -0,0 +1,158 @@
+//! Graceful shutdown on `SIGTERM` and `SIGINT`
+//!
+//! Once a signal arrives, new API requests are turned away, the requests
+//! already running get up to `server.shutdown_timeout_seconds` to finish and
+//! the coalesced partial updates are flushed before the process exits.
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// How often draining checks whether the requests it waits for are done
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Counts the requests being handled, turning new ones away once draining
+/// has begun
+#[derive(Debug, Default)]
+pub struct InFlight {
+    active: AtomicUsize,
+    draining: AtomicBool,
+}
+
+impl InFlight {
+    /// A tracker with no requests running that still accepts new ones
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            active: AtomicUsize::new(0),
+            draining: AtomicBool::new(false),
+        }
+    }
+
+    /// Counts a request as running for as long as the guard is held, or
+    /// `None` if draining has begun and the request should be turned away
+    pub fn start(&self) -> Option<RequestGuard<'_>> {
+        // Counted before the check, so a drain that starts in between still
+        // waits for this request or sees it give up
+        self.active.fetch_add(1, Ordering::SeqCst);
+        let guard = RequestGuard(self);
+        (!self.draining.load(Ordering::SeqCst)).then_some(guard)
+    }
+
+    /// Number of requests currently running
+    #[must_use]
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Turns new requests away and waits up to `timeout` for the running
+    /// ones to finish, returning whether they all did
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        self.draining.store(true, Ordering::SeqCst);
+        let deadline = Instant::now() + timeout;
+
+        while self.active() > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            switchy::unsync::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+
+        true
+    }
+}
+
+/// Keeps a request counted as running until it is dropped
+#[must_use]
+#[derive(Debug)]
+pub struct RequestGuard<'a>(&'a InFlight);
+
+impl Drop for RequestGuard<'_> {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Resolves once the process is asked to stop with `SIGTERM` or `SIGINT`
+///
+/// # Errors
+///
+/// * If the signal handlers cannot be installed
+#[cfg(unix)]
+pub async fn wait_for_signal() -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+
+    tokio::select! {
+        _ = terminate.recv() => tracing::info!("Received SIGTERM"),
+        _ = interrupt.recv() => tracing::info!("Received SIGINT"),
+    }
+
+    Ok(())
+}
+
+/// Resolves once the process is asked to stop with Ctrl-C
+///
+/// # Errors
+///
+/// * If the signal handler cannot be installed
+#[cfg(not(unix))]
+pub async fn wait_for_signal() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await?;
+    tracing::info!("Received Ctrl-C");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_are_counted_until_their_guard_drops() {
+        let in_flight = InFlight::new();
+
+        let first = in_flight.start().unwrap();
+        let second = in_flight.start().unwrap();
+        assert_eq!(in_flight.active(), 2);
+
+        drop(first);
+        drop(second);
+        assert_eq!(in_flight.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_draining_turns_new_requests_away() {
+        let in_flight = InFlight::new();
+
+        assert!(in_flight.drain(Duration::ZERO).await);
+
+        assert!(in_flight.start().is_none());
+        assert_eq!(in_flight.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_running_requests() {
+        let in_flight = InFlight::new();
+        let running = in_flight.start().unwrap();
+
+        let (drained, ()) = tokio::join!(in_flight.drain(Duration::from_secs(5)), async move {
+            switchy::unsync::time::sleep(Duration::from_millis(30)).await;
+            drop(running);
+        });
+
+        assert!(drained);
+        assert_eq!(in_flight.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_gives_up_after_its_timeout() {
+        let in_flight = InFlight::new();
+        let _running = in_flight.start().unwrap();
+
+        assert!(!in_flight.drain(Duration::from_millis(20)).await);
+        assert_eq!(in_flight.active(), 1);
+    }
+}
@@ -0,0 +1,229 @@
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// How close to expiry a token gets before requests using it are issued a fresh one
+pub const REFRESH_WINDOW: Duration = Duration::minutes(5);
+
+/// JOSE header of every token, which are always HS256 JWTs
+const HEADER: &str = r#"{"alg":"HS256","typ":"JWT"}"#;
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Invalid token")]
+    InvalidToken,
+    #[error("Token expired")]
+    ExpiredToken,
+    #[error("Failed to encode token: {0}")]
+    Encode(#[from] serde_json::Error),
+}
+
+/// Proof that a player joined a game, carried by the client as a signed JWT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthToken {
+    pub player_id: Uuid,
+    pub game_id: Uuid,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// JWT claims, with times as whole seconds since the epoch
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: Uuid,
+    game_id: Uuid,
+    iat: i64,
+    exp: i64,
+}
+
+impl AuthToken {
+    /// A token for `player_id` in `game_id` issued now and valid for `ttl`
+    #[must_use]
+    pub fn new(player_id: Uuid, game_id: Uuid, ttl: Duration) -> Self {
+        // Tokens only carry whole seconds, so drop the rest up front to
+        // make a verified token equal the one that was signed
+        let issued_at = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap_or_default();
+
+        Self {
+            player_id,
+            game_id,
+            issued_at,
+            expires_at: issued_at
+                .checked_add_signed(ttl)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        }
+    }
+
+    /// Encode this token as an HS256 JWT signed with `secret`
+    ///
+    /// # Errors
+    ///
+    /// * If the claims cannot be serialized
+    pub fn sign(&self, secret: &[u8]) -> Result<String, AuthError> {
+        let claims = serde_json::to_vec(&Claims {
+            sub: self.player_id,
+            game_id: self.game_id,
+            iat: self.issued_at.timestamp(),
+            exp: self.expires_at.timestamp(),
+        })?;
+
+        let message = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(HEADER),
+            URL_SAFE_NO_PAD.encode(claims)
+        );
+        let signature = URL_SAFE_NO_PAD.encode(mac(secret, &message).finalize().into_bytes());
+
+        Ok(format!("{message}.{signature}"))
+    }
+
+    /// Decode a JWT produced by [`AuthToken::sign`], checking its signature and expiry
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::InvalidToken` if the token is malformed or its signature doesn't match
+    /// * `AuthError::ExpiredToken` if the token has expired
+    pub fn verify(token: &str, secret: &[u8]) -> Result<Self, AuthError> {
+        Self::verify_at(token, secret, Utc::now())
+    }
+
+    /// [`AuthToken::verify`] as of `now`
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::InvalidToken` if the token is malformed or its signature doesn't match
+    /// * `AuthError::ExpiredToken` if the token has expired at `now`
+    pub fn verify_at(token: &str, secret: &[u8], now: DateTime<Utc>) -> Result<Self, AuthError> {
+        let (message, signature) = token.rsplit_once('.').ok_or(AuthError::InvalidToken)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| AuthError::InvalidToken)?;
+        mac(secret, message)
+            .verify_slice(&signature)
+            .map_err(|_| AuthError::InvalidToken)?;
+
+        // The signature covers the header, so it can only be ours
+        let (_, claims) = message.split_once('.').ok_or(AuthError::InvalidToken)?;
+        let claims = URL_SAFE_NO_PAD
+            .decode(claims)
+            .map_err(|_| AuthError::InvalidToken)?;
+        let claims: Claims =
+            serde_json::from_slice(&claims).map_err(|_| AuthError::InvalidToken)?;
+
+        let token = Self {
+            player_id: claims.sub,
+            game_id: claims.game_id,
+            issued_at: DateTime::from_timestamp(claims.iat, 0).ok_or(AuthError::InvalidToken)?,
+            expires_at: DateTime::from_timestamp(claims.exp, 0).ok_or(AuthError::InvalidToken)?,
+        };
+
+        if token.expires_at <= now {
+            return Err(AuthError::ExpiredToken);
+        }
+
+        Ok(token)
+    }
+
+    /// Whether a request made at `now` should be handed a fresh token
+    #[must_use]
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - now <= REFRESH_WINDOW
+    }
+
+    /// A new token for the same player and game, valid for `ttl` from now
+    #[must_use]
+    pub fn refreshed(&self, ttl: Duration) -> Self {
+        Self::new(self.player_id, self.game_id, ttl)
+    }
+}
+
+/// # Panics
+///
+/// * Infallible, HMAC accepts keys of any length
+fn mac(secret: &[u8], message: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"test-secret";
+
+    fn token() -> AuthToken {
+        AuthToken::new(Uuid::new_v4(), Uuid::new_v4(), Duration::hours(1))
+    }
+
+    #[test]
+    fn test_signed_token_verifies_to_the_same_token() {
+        let token = token();
+        let jwt = token.sign(SECRET).unwrap();
+
+        assert_eq!(jwt.split('.').count(), 3);
+        assert_eq!(AuthToken::verify(&jwt, SECRET).unwrap(), token);
+    }
+
+    #[test]
+    fn test_tampered_or_foreign_tokens_are_invalid() {
+        let jwt = token().sign(SECRET).unwrap();
+
+        assert!(matches!(
+            AuthToken::verify(&jwt, b"other-secret"),
+            Err(AuthError::InvalidToken)
+        ));
+
+        // Swap in the claims of a token for someone else
+        let other = token().sign(SECRET).unwrap();
+        let parts: Vec<&str> = jwt.split('.').collect();
+        let other_parts: Vec<&str> = other.split('.').collect();
+        let forged = format!("{}.{}.{}", parts[0], other_parts[1], parts[2]);
+        assert!(matches!(
+            AuthToken::verify(&forged, SECRET),
+            Err(AuthError::InvalidToken)
+        ));
+
+        for garbage in ["", "not-a-token", "a.b.c"] {
+            assert!(matches!(
+                AuthToken::verify(garbage, SECRET),
+                Err(AuthError::InvalidToken)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_expired_token_is_rejected() {
+        let token = token();
+        let jwt = token.sign(SECRET).unwrap();
+
+        assert!(
+            AuthToken::verify_at(&jwt, SECRET, token.expires_at - Duration::seconds(1)).is_ok()
+        );
+        assert!(matches!(
+            AuthToken::verify_at(&jwt, SECRET, token.expires_at),
+            Err(AuthError::ExpiredToken)
+        ));
+    }
+
+    #[test]
+    fn test_token_needs_refresh_within_five_minutes_of_expiry() {
+        let token = token();
+
+        assert!(!token.needs_refresh(token.expires_at - Duration::minutes(6)));
+        assert!(token.needs_refresh(token.expires_at - Duration::minutes(5)));
+
+        let refreshed = token.refreshed(Duration::hours(1));
+        assert_eq!(refreshed.player_id, token.player_id);
+        assert_eq!(refreshed.game_id, token.game_id);
+        assert!(!refreshed.needs_refresh(Utc::now()));
+    }
+}
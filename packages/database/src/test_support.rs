@@ -0,0 +1,43 @@
+//! Databases for tests to run against
+
+use crate::{create_connection, Database, DatabaseConfig};
+
+/// A fresh, empty in-memory `SQLite` database
+///
+/// Every call opens a database of its own, so tests never see each other's
+/// tables or rows.
+///
+/// # Panics
+///
+/// * If the database can't be opened
+pub async fn in_memory() -> Box<dyn Database> {
+    create_connection(DatabaseConfig {
+        database_url: "sqlite://:memory:".to_string(),
+        connection_attempts: 1,
+        ..DatabaseConfig::default()
+    })
+    .await
+    .expect("Failed to open an in-memory SQLite database")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_databases_are_isolated() {
+        let first = in_memory().await;
+        let second = in_memory().await;
+
+        first
+            .exec_raw("CREATE TABLE only_here (id INTEGER)")
+            .await
+            .unwrap();
+
+        // Fails if the two share a database, as the table would already exist
+        second
+            .exec_raw("CREATE TABLE only_here (id INTEGER)")
+            .await
+            .unwrap();
+    }
+}
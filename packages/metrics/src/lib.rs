@@ -0,0 +1,141 @@
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::{
+    fmt::Write as _,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Game events tracked by the metrics registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    GamesCreated,
+    PlayersJoined,
+    VotesCast,
+    GamesRevealed,
+    GamesReset,
+    PartialUpdatesFailed,
+}
+
+impl Metric {
+    pub const ALL: [Self; 6] = [
+        Self::GamesCreated,
+        Self::PlayersJoined,
+        Self::VotesCast,
+        Self::GamesRevealed,
+        Self::GamesReset,
+        Self::PartialUpdatesFailed,
+    ];
+
+    /// Name the metric is exported under
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::GamesCreated => "games_created_total",
+            Self::PlayersJoined => "players_joined_total",
+            Self::VotesCast => "votes_cast_total",
+            Self::GamesRevealed => "games_revealed_total",
+            Self::GamesReset => "games_reset_total",
+            Self::PartialUpdatesFailed => "partial_updates_failed_total",
+        }
+    }
+
+    #[must_use]
+    pub const fn help(self) -> &'static str {
+        match self {
+            Self::GamesCreated => "Total number of games created",
+            Self::PlayersJoined => "Total number of players that joined a game",
+            Self::VotesCast => "Total number of votes cast",
+            Self::GamesRevealed => "Total number of rounds whose votes were revealed",
+            Self::GamesReset => "Total number of rounds reset",
+            Self::PartialUpdatesFailed => {
+                "Total number of partial updates the renderer failed to send to clients"
+            }
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Process-wide counters for game events, rendered in the Prometheus text format
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    values: [AtomicU64; Metric::ALL.len()],
+}
+
+impl MetricsRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn increment(&self, metric: Metric) {
+        self.values[metric.index()].fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn get(&self, metric: Metric) -> u64 {
+        self.values[metric.index()].load(Ordering::Relaxed)
+    }
+
+    /// Serialize every metric in the Prometheus text exposition format
+    #[must_use]
+    pub fn render_prometheus(&self) -> String {
+        let mut output = String::new();
+
+        for metric in Metric::ALL {
+            let name = metric.name();
+            let _ = write!(
+                output,
+                "# HELP {name} {}\n# TYPE {name} counter\n{name} {}\n",
+                metric.help(),
+                self.get(metric),
+            );
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_start_at_zero() {
+        let registry = MetricsRegistry::new();
+
+        for metric in Metric::ALL {
+            assert_eq!(registry.get(metric), 0);
+        }
+    }
+
+    #[test]
+    fn test_increment_only_affects_that_metric() {
+        let registry = MetricsRegistry::new();
+
+        registry.increment(Metric::VotesCast);
+        registry.increment(Metric::VotesCast);
+
+        assert_eq!(registry.get(Metric::VotesCast), 2);
+        assert_eq!(registry.get(Metric::GamesCreated), 0);
+    }
+
+    #[test]
+    fn test_render_prometheus_format() {
+        let registry = MetricsRegistry::new();
+        registry.increment(Metric::GamesCreated);
+
+        let output = registry.render_prometheus();
+
+        assert!(output.contains(
+            "# HELP games_created_total Total number of games created\n\
+             # TYPE games_created_total counter\n\
+             games_created_total 1\n"
+        ));
+        assert_eq!(output.lines().count(), Metric::ALL.len() * 3);
+    }
+}
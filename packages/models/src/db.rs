@@ -1,10 +1,13 @@
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 use moosicbox_json_utils::{database::ToValue as _, ParseError, ToValueType};
 use switchy::database::{DatabaseValue, Row};
 use uuid::Uuid;
 
-use crate::{Game, GameState, Player, Vote};
+use crate::{
+    AuditEventType, AuditLogEntry, Confidence, Game, GameSettings, GameState, Player, PlayerRole,
+    Story, StoryEstimate, TeamRole, Vote,
+};
 
 // ToValueType implementations following MoosicBox pattern
 
@@ -13,31 +16,29 @@ impl moosicbox_json_utils::MissingValue<GameState> for &Row {}
 impl moosicbox_json_utils::MissingValue<Game> for &Row {}
 impl moosicbox_json_utils::MissingValue<Player> for &Row {}
 impl moosicbox_json_utils::MissingValue<Vote> for &Row {}
+impl moosicbox_json_utils::MissingValue<Story> for &Row {}
+impl moosicbox_json_utils::MissingValue<StoryEstimate> for &Row {}
+impl moosicbox_json_utils::MissingValue<AuditLogEntry> for &Row {}
 
 // ToValueType for GameState (local type, so orphan rule allows this)
 impl ToValueType<GameState> for DatabaseValue {
     fn to_value_type(self) -> Result<GameState, ParseError> {
         let state_str: String = (&self).to_value_type()?;
-        match state_str.as_str() {
-            "Waiting" => Ok(GameState::Waiting),
-            "Voting" => Ok(GameState::Voting),
-            "Revealed" => Ok(GameState::Revealed),
-            _ => Err(ParseError::ConvertType(format!(
-                "Invalid GameState: {state_str}"
-            ))),
-        }
+        GameState::from_str(&state_str).map_err(ParseError::ConvertType)
     }
 }
 
 // ToValueType for Game (local type, so orphan rule allows this)
 impl ToValueType<Game> for &Row {
     fn to_value_type(self) -> Result<Game, ParseError> {
+        let id = {
+            let uuid_str: String = self.to_value("id")?;
+            Uuid::from_str(&uuid_str)
+                .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in id: {e}")))?
+        };
+
         Ok(Game {
-            id: {
-                let uuid_str: String = self.to_value("id")?;
-                Uuid::from_str(&uuid_str)
-                    .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in id: {e}")))?
-            },
+            id,
             name: self.to_value("name")?,
             owner_id: {
                 let uuid_str: String = self.to_value("owner_id")?;
@@ -50,6 +51,36 @@ impl ToValueType<Game> for &Row {
             current_story: self.to_value("current_story")?,
             created_at: self.to_value("created_at")?,
             updated_at: self.to_value("updated_at")?,
+            settings: {
+                // Rows written before the column existed allowed vote changes
+                let allow_vote_change: Option<bool> =
+                    self.to_value("allow_vote_change").unwrap_or(None);
+                // Rows written before the column existed are never archived on their own
+                let auto_archive_after_secs: Option<i64> =
+                    self.to_value("auto_archive_after_secs").unwrap_or(None);
+                GameSettings {
+                    allow_vote_change: allow_vote_change.unwrap_or(true),
+                    auto_archive_after: auto_archive_after_secs
+                        .and_then(|secs| u64::try_from(secs).ok())
+                        .map(Duration::from_secs),
+                    webhook_url: self.to_value("webhook_url").unwrap_or(None),
+                }
+            },
+            round_id: {
+                // Games created before rounds were tracked are still on their first
+                // round, which is identified by the game id
+                let round_id: Option<String> = self.to_value("round_id").unwrap_or(None);
+                round_id
+                    .map(|uuid_str| Uuid::from_str(&uuid_str))
+                    .transpose()
+                    .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in round_id: {e}")))?
+                    .unwrap_or(id)
+            },
+            // Rows written before the column existed have no start time
+            voting_started_at: self.to_value("voting_started_at").unwrap_or(None),
+            // Rows written before the column existed are untimed
+            voting_deadline: self.to_value("voting_deadline").unwrap_or(None),
+            completed_at: self.to_value("completed_at").unwrap_or(None),
         })
     }
 }
@@ -64,7 +95,21 @@ impl ToValueType<Player> for &Row {
                     .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in id: {e}")))?
             },
             name: self.to_value("name")?,
-            is_observer: self.to_value("is_observer")?,
+            role: {
+                // Rows written before the role column existed only have is_observer
+                let role: Option<String> = self.to_value("role").unwrap_or(None);
+                match role {
+                    Some(role) => PlayerRole::from_str(&role).map_err(ParseError::ConvertType)?,
+                    None => PlayerRole::from_is_observer(self.to_value("is_observer")?),
+                }
+            },
+            team_role: {
+                // Rows written before the team_role column existed have none
+                let team_role: Option<String> = self.to_value("team_role").unwrap_or(None);
+                team_role
+                    .and_then(|team_role| TeamRole::from_str(&team_role).ok())
+                    .unwrap_or_default()
+            },
             joined_at: self.to_value("joined_at")?,
         })
     }
@@ -83,6 +128,97 @@ impl ToValueType<Vote> for &Row {
             player_name: self.to_value("player_name")?,
             value: self.to_value("value")?,
             cast_at: self.to_value("cast_at")?,
+            confidence: {
+                // Rows written before the confidence column existed have no value,
+                // but one that is there has to decode
+                let confidence: Option<String> = if self.get("confidence").is_some() {
+                    self.to_value("confidence")?
+                } else {
+                    None
+                };
+                confidence
+                    .map(|value| Confidence::from_str(&value))
+                    .transpose()
+                    .map_err(ParseError::ConvertType)?
+            },
+        })
+    }
+}
+
+// ToValueType for Story (local type, so orphan rule allows this)
+impl ToValueType<Story> for &Row {
+    fn to_value_type(self) -> Result<Story, ParseError> {
+        Ok(Story {
+            id: {
+                let uuid_str: String = self.to_value("id")?;
+                Uuid::from_str(&uuid_str)
+                    .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in id: {e}")))?
+            },
+            game_id: {
+                let uuid_str: String = self.to_value("game_id")?;
+                Uuid::from_str(&uuid_str)
+                    .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in game_id: {e}")))?
+            },
+            title: self.to_value("title")?,
+            // Rows written before the column existed have no key
+            key: self.to_value("story_key").unwrap_or(None),
+            description: self.to_value("description")?,
+            // Rows written before the columns existed are open stories
+            acceptance_criteria: self.to_value("acceptance_criteria").unwrap_or(None),
+            final_estimate: self.to_value("final_estimate").unwrap_or(None),
+            completed_at: self.to_value("completed_at").unwrap_or(None),
+            order: self.to_value("story_order")?,
+            created_at: self.to_value("created_at")?,
+        })
+    }
+}
+
+// ToValueType for StoryEstimate (local type, so orphan rule allows this)
+impl ToValueType<StoryEstimate> for &Row {
+    fn to_value_type(self) -> Result<StoryEstimate, ParseError> {
+        Ok(StoryEstimate {
+            game_id: {
+                let uuid_str: String = self.to_value("game_id")?;
+                Uuid::from_str(&uuid_str)
+                    .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in game_id: {e}")))?
+            },
+            story: self.to_value("story")?,
+            final_value: self.to_value("final_value")?,
+            recorded_at: self.to_value("recorded_at")?,
+        })
+    }
+}
+
+// ToValueType for AuditLogEntry (local type, so orphan rule allows this)
+impl ToValueType<AuditLogEntry> for &Row {
+    fn to_value_type(self) -> Result<AuditLogEntry, ParseError> {
+        Ok(AuditLogEntry {
+            id: {
+                let uuid_str: String = self.to_value("id")?;
+                Uuid::from_str(&uuid_str)
+                    .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in id: {e}")))?
+            },
+            game_id: {
+                let uuid_str: String = self.to_value("game_id")?;
+                Uuid::from_str(&uuid_str)
+                    .map_err(|e| ParseError::ConvertType(format!("Invalid Uuid in game_id: {e}")))?
+            },
+            actor_id: {
+                let uuid_str: String = self.to_value("actor_id")?;
+                Uuid::from_str(&uuid_str).map_err(|e| {
+                    ParseError::ConvertType(format!("Invalid Uuid in actor_id: {e}"))
+                })?
+            },
+            event_type: {
+                let event_type: String = self.to_value("event_type")?;
+                AuditEventType::from_str(&event_type).map_err(ParseError::ConvertType)?
+            },
+            details: {
+                let details: String = self.to_value("details")?;
+                serde_json::from_str(&details)
+                    .map_err(|e| ParseError::ConvertType(format!("Invalid JSON in details: {e}")))?
+            },
+            occurred_at: self.to_value("occurred_at")?,
         })
     }
 }
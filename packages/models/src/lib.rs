@@ -2,6 +2,8 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
+use std::{fmt, str::FromStr, time::Duration};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -19,6 +21,76 @@ pub struct Game {
     pub current_story: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub settings: GameSettings,
+    /// Identifies the current round; reset and revote start a new one
+    #[serde(default)]
+    pub round_id: Uuid,
+    /// When voting on the current round opened; `None` while waiting
+    #[serde(default)]
+    pub voting_started_at: Option<DateTime<Utc>>,
+    /// When a timed round reveals its votes on its own; `None` for untimed rounds
+    #[serde(default)]
+    pub voting_deadline: Option<DateTime<Utc>>,
+    /// When the owner marked the game completed; `None` until then
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl Game {
+    /// How long the current round took, once it has been revealed
+    ///
+    /// A revealed game isn't written to again until the next round, so its
+    /// `updated_at` is the moment the votes were revealed.
+    #[must_use]
+    pub fn round_timing(&self, votes: &[Vote]) -> Option<RoundTiming> {
+        if self.state != GameState::Revealed {
+            return None;
+        }
+        RoundTiming::measure(self.voting_started_at, self.updated_at, votes)
+    }
+
+    /// Whether the game has sat revealed for longer than its
+    /// `auto_archive_after` setting allows
+    #[must_use]
+    pub fn is_due_for_archival(&self, now: DateTime<Utc>) -> bool {
+        self.state.can_transition_to(&GameState::Archived)
+            && self
+                .settings
+                .auto_archive_after
+                .and_then(|after| chrono::Duration::from_std(after).ok())
+                .is_some_and(|after| self.updated_at + after <= now)
+    }
+}
+
+/// Per-game rules chosen when the game is created
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSettings {
+    /// Whether players may replace their vote during a round
+    #[serde(default = "default_allow_vote_change")]
+    pub allow_vote_change: bool,
+    /// How long a game may stay revealed before it is archived on its own;
+    /// `None` keeps it until it is archived by hand
+    #[serde(default)]
+    pub auto_archive_after: Option<Duration>,
+    /// Receives a [`RoundResultPayload`] whenever votes are revealed or a
+    /// story's estimate is finalized
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+const fn default_allow_vote_change() -> bool {
+    true
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            allow_vote_change: default_allow_vote_change(),
+            auto_archive_after: None,
+            webhook_url: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,22 +98,387 @@ pub enum GameState {
     Waiting,
     Voting,
     Revealed,
+    /// Kept for its history only; no more rounds are played
+    Archived,
+    /// The owner finished the game; its results stay viewable by link
+    Completed,
+}
+
+impl GameState {
+    /// How the game page describes this state to players
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::Waiting => "Waiting for players",
+            Self::Voting => "Voting in progress",
+            Self::Revealed => "Votes revealed",
+            Self::Archived => "Game archived",
+            Self::Completed => "Game completed",
+        }
+    }
+
+    /// Whether the game is over, so nobody may join or vote any more
+    #[must_use]
+    pub const fn has_ended(&self) -> bool {
+        matches!(self, Self::Archived | Self::Completed)
+    }
+
+    /// Whether a game in this state may move to `next`
+    ///
+    /// A game is archived once its votes are revealed and may be completed at
+    /// any point; once archived or completed it never leaves that state.
+    #[must_use]
+    pub const fn can_transition_to(&self, next: &Self) -> bool {
+        match (self, next) {
+            (Self::Archived | Self::Completed, _) => false,
+            (_, Self::Archived) => matches!(self, Self::Revealed),
+            _ => true,
+        }
+    }
+}
+
+/// The name the state is stored under in the `games` table
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Waiting => "Waiting",
+            Self::Voting => "Voting",
+            Self::Revealed => "Revealed",
+            Self::Archived => "Archived",
+            Self::Completed => "Completed",
+        })
+    }
+}
+
+impl FromStr for GameState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Waiting" => Ok(Self::Waiting),
+            "Voting" => Ok(Self::Voting),
+            "Revealed" => Ok(Self::Revealed),
+            "Archived" => Ok(Self::Archived),
+            "Completed" => Ok(Self::Completed),
+            _ => Err(format!("Invalid GameState: {s}")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "PlayerRecord", into = "PlayerRecord")]
 pub struct Player {
     pub id: Uuid,
     pub name: String,
-    pub is_observer: bool,
+    pub role: PlayerRole,
+    pub team_role: TeamRole,
     pub joined_at: DateTime<Utc>,
 }
 
+impl Player {
+    #[must_use]
+    pub const fn is_observer(&self) -> bool {
+        matches!(self.role, PlayerRole::Observer)
+    }
+}
+
+/// Serialized form of `Player`
+///
+/// Keeps the `is_observer` flag for API consumers that predate roles, and
+/// derives the role from it when a payload has no `role`.
+#[derive(Serialize, Deserialize)]
+struct PlayerRecord {
+    id: Uuid,
+    name: String,
+    #[serde(default)]
+    role: Option<PlayerRole>,
+    #[serde(default)]
+    is_observer: bool,
+    #[serde(default)]
+    team_role: TeamRole,
+    joined_at: DateTime<Utc>,
+}
+
+impl From<PlayerRecord> for Player {
+    fn from(record: PlayerRecord) -> Self {
+        Self {
+            id: record.id,
+            name: record.name,
+            role: record
+                .role
+                .unwrap_or_else(|| PlayerRole::from_is_observer(record.is_observer)),
+            team_role: record.team_role,
+            joined_at: record.joined_at,
+        }
+    }
+}
+
+impl From<Player> for PlayerRecord {
+    fn from(player: Player) -> Self {
+        Self {
+            is_observer: player.is_observer(),
+            id: player.id,
+            name: player.name,
+            role: Some(player.role),
+            team_role: player.team_role,
+            joined_at: player.joined_at,
+        }
+    }
+}
+
+/// How a player takes part in estimating
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayerRole {
+    /// Votes, and the vote counts toward consensus and statistics
+    #[default]
+    Voter,
+    /// Votes are shown on reveal but don't count toward consensus
+    Advisor,
+    /// Watches without voting
+    Observer,
+}
+
+impl PlayerRole {
+    #[must_use]
+    pub const fn from_is_observer(is_observer: bool) -> Self {
+        if is_observer {
+            Self::Observer
+        } else {
+            Self::Voter
+        }
+    }
+
+    #[must_use]
+    pub const fn can_vote(self) -> bool {
+        !matches!(self, Self::Observer)
+    }
+
+    /// Whether this role's votes are included in consensus and statistics
+    #[must_use]
+    pub const fn is_counted(self) -> bool {
+        matches!(self, Self::Voter)
+    }
+}
+
+impl fmt::Display for PlayerRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Voter => "Voter",
+            Self::Advisor => "Advisor",
+            Self::Observer => "Observer",
+        })
+    }
+}
+
+impl FromStr for PlayerRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "voter" => Ok(Self::Voter),
+            "advisor" => Ok(Self::Advisor),
+            "observer" => Ok(Self::Observer),
+            _ => Err(format!("Invalid player role: {s}")),
+        }
+    }
+}
+
+/// What a player does on the team, shown next to their name for context
+///
+/// Unlike [`PlayerRole`] this has no effect on voting; watching without
+/// voting is the `Observer` player role.
+///
+/// Serialized as its display name, e.g. `"Tech Lead"`, the same text that is
+/// stored in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum TeamRole {
+    Developer,
+    Designer,
+    ProductManager,
+    TechLead,
+    Qa,
+    /// Any other role by name, or no role at all when the name is empty
+    Other(String),
+}
+
+impl Default for TeamRole {
+    fn default() -> Self {
+        Self::Other(String::new())
+    }
+}
+
+impl TeamRole {
+    /// The roles offered when joining, in the order they are listed
+    #[must_use]
+    pub const fn known() -> [Self; 5] {
+        [
+            Self::Developer,
+            Self::Designer,
+            Self::ProductManager,
+            Self::TechLead,
+            Self::Qa,
+        ]
+    }
+
+    /// Whether a role was given at all
+    #[must_use]
+    pub const fn is_set(&self) -> bool {
+        !matches!(self, Self::Other(name) if name.is_empty())
+    }
+}
+
+impl fmt::Display for TeamRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Developer => "Developer",
+            Self::Designer => "Designer",
+            Self::ProductManager => "Product Manager",
+            Self::TechLead => "Tech Lead",
+            Self::Qa => "QA",
+            Self::Other(name) if name.is_empty() => "Other",
+            Self::Other(name) => name,
+        })
+    }
+}
+
+impl FromStr for TeamRole {
+    type Err = std::convert::Infallible;
+
+    /// Known roles match ignoring case, spaces and underscores; anything else
+    /// is kept as an `Other` role, with `Other` itself meaning none
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        let key: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '_' && *c != '-')
+            .collect::<String>()
+            .to_lowercase();
+
+        Ok(match key.as_str() {
+            "developer" => Self::Developer,
+            "designer" => Self::Designer,
+            "productmanager" => Self::ProductManager,
+            "techlead" => Self::TechLead,
+            "qa" => Self::Qa,
+            "" | "other" => Self::default(),
+            _ => Self::Other(name.to_string()),
+        })
+    }
+}
+
+impl From<String> for TeamRole {
+    fn from(name: String) -> Self {
+        match name.parse() {
+            Ok(role) => role,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl From<TeamRole> for String {
+    fn from(role: TeamRole) -> Self {
+        role.to_string()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vote {
     pub player_id: Uuid,
     pub player_name: String,
     pub value: String,
     pub cast_at: DateTime<Utc>,
+    #[serde(default)]
+    pub confidence: Option<Confidence>,
+}
+
+impl Vote {
+    #[must_use]
+    pub const fn is_low_confidence(&self) -> bool {
+        matches!(self.confidence, Some(Confidence::Low))
+    }
+}
+
+/// How sure a player is about their estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+        })
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = String;
+
+    /// Accepts the level names case-insensitively, or `1`-`3` from low to high
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "low" | "1" => Ok(Self::Low),
+            "medium" | "2" => Ok(Self::Medium),
+            "high" | "3" => Ok(Self::High),
+            _ => Err(format!("Invalid confidence: {s}")),
+        }
+    }
+}
+
+/// How long a round of voting took, in whole seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundTiming {
+    /// From the start of voting until the reveal
+    pub round_seconds: i64,
+    /// From the start of voting until the last vote came in; `None` without votes
+    pub slowest_voter_seconds: Option<i64>,
+}
+
+impl RoundTiming {
+    /// Time a round that ran from `started_at` until `ended_at`
+    ///
+    /// Returns `None` when the start wasn't recorded, as for rounds started
+    /// before timing was tracked.
+    #[must_use]
+    pub fn measure(
+        started_at: Option<DateTime<Utc>>,
+        ended_at: DateTime<Utc>,
+        votes: &[Vote],
+    ) -> Option<Self> {
+        let started_at = started_at?;
+        let since_start = |at: DateTime<Utc>| (at - started_at).num_seconds().max(0);
+
+        Some(Self {
+            round_seconds: since_start(ended_at),
+            slowest_voter_seconds: votes.iter().map(|vote| since_start(vote.cast_at)).max(),
+        })
+    }
+}
+
+impl fmt::Display for RoundTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "round took {}", format_elapsed(self.round_seconds))?;
+        if let Some(slowest) = self.slowest_voter_seconds {
+            write!(f, ", slowest voter {}", format_elapsed(slowest))?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a number of seconds as `45s` or `2m 14s`
+#[must_use]
+pub fn format_elapsed(seconds: i64) -> String {
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,29 +491,508 @@ pub struct Session {
     pub last_seen: DateTime<Utc>,
 }
 
+/// A story estimated in a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Story {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub title: String,
+    /// Ticket key from the team's tracker, such as `KEY-123`
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// What the story has to do to count as done
+    #[serde(default)]
+    pub acceptance_criteria: Option<String>,
+    /// The estimate the team settled on, once the story is completed
+    #[serde(default)]
+    pub final_estimate: Option<String>,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Position of the story in the game's backlog, lowest first
+    pub order: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl fmt::Display for Story {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "{key} {}", self.title),
+            None => f.write_str(&self.title),
+        }
+    }
+}
+
+/// A story to be added to a game's backlog, as read from one line of
+/// imported text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewStory {
+    pub key: Option<String>,
+    pub title: String,
+}
+
+impl NewStory {
+    /// Read a `title` or `KEY-123 | title` line, or `None` for a blank one
+    #[must_use]
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        let (key, title) = match line.split_once('|') {
+            Some((key, title)) if !title.trim().is_empty() => {
+                (Some(key.trim()).filter(|key| !key.is_empty()), title.trim())
+            }
+            _ => (None, line),
+        };
+
+        Some(Self {
+            key: key.map(ToString::to_string),
+            title: title.to_string(),
+        })
+    }
+
+    /// Whether this is the story with `key` and `title`, ignoring case and
+    /// surrounding whitespace
+    #[must_use]
+    pub fn is_same_story(&self, key: Option<&str>, title: &str) -> bool {
+        let normalize = |value: &str| value.trim().to_lowercase();
+        self.key.as_deref().map(normalize) == key.map(normalize)
+            && normalize(&self.title) == normalize(title)
+    }
+}
+
+/// Longest title, in characters, a story added through a batch may have
+pub const MAX_STORY_TITLE_LENGTH: usize = 500;
+
+/// A story to be added to a game's backlog as one entry of a JSON batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoryInput {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub acceptance_criteria: Option<String>,
+}
+
+impl StoryInput {
+    /// Check the title is not blank and at most [`MAX_STORY_TITLE_LENGTH`]
+    /// characters long
+    ///
+    /// # Errors
+    ///
+    /// * If the title is blank or too long, with a message saying which
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("Title is required".to_string());
+        }
+        if self.title.chars().count() > MAX_STORY_TITLE_LENGTH {
+            return Err(format!(
+                "Title must be at most {MAX_STORY_TITLE_LENGTH} characters"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check every story of a batch, collecting the problems of all the
+    /// invalid ones instead of stopping at the first
+    ///
+    /// # Errors
+    ///
+    /// * If any story is invalid, with one error per invalid story
+    pub fn validate_batch(stories: &[Self]) -> Result<(), Vec<StoryInputError>> {
+        let errors: Vec<StoryInputError> = stories
+            .iter()
+            .enumerate()
+            .filter_map(|(index, story)| {
+                story
+                    .validate()
+                    .err()
+                    .map(|message| StoryInputError { index, message })
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Why one story of a batch couldn't be added
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoryInputError {
+    /// Position of the story in the batch, counting from 0
+    pub index: usize,
+    pub message: String,
+}
+
+impl fmt::Display for StoryInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Story {}: {}", self.index + 1, self.message)
+    }
+}
+
+/// How a bulk story import went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoryImport {
+    pub added: usize,
+    /// Stories left out because the backlog or the import already had them
+    pub skipped: usize,
+}
+
+/// The value a story was estimated at, recorded when its round settled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryEstimate {
+    pub game_id: Uuid,
+    pub story: String,
+    pub final_value: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Which page of a long list to read, counting pages from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaginationParams {
+    pub page: u32,
+    pub page_size: u32,
+}
+
+impl PaginationParams {
+    /// How many items come before the page; page 0 is read as page 1
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        (self.page.saturating_sub(1) as usize).saturating_mul(self.page_size as usize)
+    }
+
+    /// How many items the page holds at most
+    #[must_use]
+    pub const fn limit(&self) -> usize {
+        self.page_size as usize
+    }
+
+    /// How many items are read to reach the end of the page
+    #[must_use]
+    pub const fn end(&self) -> usize {
+        self.offset().saturating_add(self.limit())
+    }
+}
+
+/// Everything a client needs to render a game, read in one go
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub game: Game,
+    pub players: Vec<Player>,
+    /// The votes players see: the revealed snapshot once the round is revealed,
+    /// the live votes before that
+    pub votes: Vec<Vote>,
+    pub stories: Vec<Story>,
+}
+
+/// A game's full record, as exported for archiving or reporting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameHistory {
+    pub game: Game,
+    /// Whether a round is still open; its votes stay hidden until it is revealed
+    pub in_progress: bool,
+    pub participants: Vec<Player>,
+    /// Every revealed round, oldest first
+    pub rounds: Vec<RoundHistory>,
+    /// The estimate each story settled on in its last revealed round
+    pub final_estimates: Vec<FinalEstimate>,
+}
+
+impl GameHistory {
+    /// Serialize the history as indented JSON
+    ///
+    /// # Errors
+    ///
+    /// * If the history cannot be serialized
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// The votes of one revealed round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundHistory {
+    pub round_id: Uuid,
+    /// The story being estimated; `None` for rounds revealed before stories were recorded
+    pub story: Option<String>,
+    pub votes: Vec<Vote>,
+}
+
+/// How one revealed round turned out, as listed in a game's round history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundSummary {
+    pub round_id: Uuid,
+    pub story: Option<String>,
+    /// The estimate the round settled on; `None` when no vote carried one
+    pub final_estimate: Option<String>,
+    /// How many votes each card got, in deck order; cards nobody played are left out
+    pub distribution: Vec<(String, usize)>,
+    pub first_vote_at: DateTime<Utc>,
+    pub last_vote_at: DateTime<Utc>,
+}
+
+/// The estimate a story ended up with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalEstimate {
+    pub story: String,
+    /// `None` when no vote in the round carried an estimate
+    pub estimate: Option<String>,
+}
+
+/// How one player took part across every revealed round of a game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub player_id: Uuid,
+    /// The name on the player's most recent vote
+    pub player_name: String,
+    pub rounds_participated: usize,
+    /// Rounds where the player's vote was the estimate the story ended with
+    pub matched_final_estimate: usize,
+    /// Mean of the player's numeric votes; `None` when they cast none
+    pub average_vote: Option<f64>,
+}
+
+/// Body of a webhook notification, tagged with the event that triggered it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookPayload {
+    GameCreated {
+        game_id: Uuid,
+        name: String,
+        voting_system: String,
+    },
+    VotingStarted {
+        game_id: Uuid,
+        story: String,
+    },
+    VotesRevealed {
+        game_id: Uuid,
+        story: Option<String>,
+        votes: Vec<Vote>,
+    },
+    VotingReset {
+        game_id: Uuid,
+    },
+}
+
+impl WebhookPayload {
+    /// The game the event happened in
+    #[must_use]
+    pub const fn game_id(&self) -> Uuid {
+        match self {
+            Self::GameCreated { game_id, .. }
+            | Self::VotingStarted { game_id, .. }
+            | Self::VotesRevealed { game_id, .. }
+            | Self::VotingReset { game_id } => *game_id,
+        }
+    }
+}
+
+/// What a game's own webhook is being told about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundResultEvent {
+    VotesRevealed,
+    EstimateFinalized,
+}
+
+/// Body posted to a game's `webhook_url` once a round has a result
+///
+/// Sent when votes are revealed and again when the owner finalizes the
+/// story's estimate, so a chat integration can post both.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundResultPayload {
+    pub event: RoundResultEvent,
+    pub game_id: Uuid,
+    pub game_name: String,
+    pub story: Option<String>,
+    /// Every vote cast in the round
+    pub votes: Vec<Vote>,
+    /// How many votes each card got, in deck order; cards nobody played are left out
+    pub distribution: Vec<(String, usize)>,
+    /// The estimate recorded for the story; `None` when votes were revealed
+    /// without reaching consensus
+    pub final_estimate: Option<String>,
+}
+
+/// Kinds of game events kept in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditEventType {
+    GameCreated,
+    PlayerJoined,
+    PlayerKicked,
+    VotingStarted,
+    VoteCast,
+    VotesRevealed,
+    VotingReset,
+    OwnershipTransferred,
+}
+
+impl fmt::Display for AuditEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::GameCreated => "GameCreated",
+            Self::PlayerJoined => "PlayerJoined",
+            Self::PlayerKicked => "PlayerKicked",
+            Self::VotingStarted => "VotingStarted",
+            Self::VoteCast => "VoteCast",
+            Self::VotesRevealed => "VotesRevealed",
+            Self::VotingReset => "VotingReset",
+            Self::OwnershipTransferred => "OwnershipTransferred",
+        })
+    }
+}
+
+impl FromStr for AuditEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GameCreated" => Ok(Self::GameCreated),
+            "PlayerJoined" => Ok(Self::PlayerJoined),
+            "PlayerKicked" => Ok(Self::PlayerKicked),
+            "VotingStarted" => Ok(Self::VotingStarted),
+            "VoteCast" => Ok(Self::VoteCast),
+            "VotesRevealed" => Ok(Self::VotesRevealed),
+            "VotingReset" => Ok(Self::VotingReset),
+            "OwnershipTransferred" => Ok(Self::OwnershipTransferred),
+            _ => Err(format!("Invalid audit event type: {s}")),
+        }
+    }
+}
+
+/// One event in a game's audit log
+///
+/// Actions only the owner can take, such as revealing votes, are recorded
+/// against whoever owned the game at the time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub actor_id: Uuid,
+    pub event_type: AuditEventType,
+    /// Event specific context, such as the story voting started on
+    pub details: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl AuditLogEntry {
+    /// An entry for an event happening now
+    #[must_use]
+    pub fn new(
+        game_id: Uuid,
+        actor_id: Uuid,
+        event_type: AuditEventType,
+        details: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            game_id,
+            actor_id,
+            event_type,
+            details,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
 // WebSocket message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ClientMessage {
-    JoinGame { game_id: Uuid, player_name: String },
+    JoinGame {
+        game_id: Uuid,
+        player_name: String,
+    },
     LeaveGame,
-    CastVote { value: String },
-    StartVoting { story: String },
+    CastVote {
+        value: String,
+    },
+    StartVoting {
+        story: String,
+    },
     RevealVotes,
     ResetVoting,
+    Revote,
+    /// Owner-only: remove a player from the game
+    KickPlayer {
+        target_player_id: Uuid,
+    },
+    TransferOwnership {
+        new_owner_id: Uuid,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ServerMessage {
-    GameJoined { game: Game, players: Vec<Player> },
-    PlayerJoined { player: Player },
-    PlayerLeft { player_id: Uuid },
-    VotingStarted { story: String },
-    VoteCast { player_id: Uuid, has_voted: bool },
-    VotesRevealed { votes: Vec<Vote> },
+    GameJoined {
+        game: Game,
+        players: Vec<Player>,
+    },
+    PlayerJoined {
+        player: Player,
+    },
+    PlayerLeft {
+        player_id: Uuid,
+    },
+    OwnerChanged {
+        owner_id: Uuid,
+    },
+    VotingStarted {
+        story: String,
+    },
+    StoryUpdated {
+        story: Option<String>,
+    },
+    VoteCast {
+        player_id: Uuid,
+        has_voted: bool,
+    },
+    VotesRevealed {
+        votes: Vec<Vote>,
+    },
     VotingReset,
-    Error { message: String },
+    TimerTick {
+        remaining_secs: u64,
+    },
+    /// Sent to a player just before their connection is closed for being kicked
+    Kicked,
+    OwnershipTransferred {
+        new_owner_id: Uuid,
+    },
+    /// The server is stopping and expects to be gone in about this long
+    ServerShuttingDown {
+        estimated_seconds: u64,
+    },
+    Error {
+        code: ErrorCode,
+        message: String,
+    },
+}
+
+/// What went wrong, carried by `ServerMessage::Error` so clients can react
+/// without matching on the message text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorCode {
+    GameNotFound,
+    /// The player may not do this, e.g. a round control used by someone other
+    /// than the game owner
+    NotAuthorized,
+    /// The game is not in a state that allows this, e.g. revealing votes
+    /// before voting started
+    InvalidState,
+    PlayerNotInGame,
+    /// The message itself was malformed or had invalid values
+    InvalidRequest,
+    /// Anything else, such as a database failure
+    Internal,
 }
 
 // API request/response types
@@ -95,5 +1011,538 @@ pub struct CreateGameResponse {
 pub struct GetGameResponse {
     pub game: Game,
     pub players: Vec<Player>,
+    /// The votes snapshotted when the current round was revealed; `None` before that
     pub votes: Option<Vec<Vote>>,
+    /// How long the revealed round took; `None` before the reveal or without a recorded start
+    #[serde(default)]
+    pub timing: Option<RoundTiming>,
+}
+
+/// Changes to a player; fields left out are kept as they are
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerUpdate {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub is_observer: Option<bool>,
+    #[serde(default)]
+    pub role: Option<PlayerRole>,
+}
+
+impl PlayerUpdate {
+    /// Apply the changes to `player`
+    ///
+    /// `role` wins over `is_observer` when both are given. Clearing
+    /// `is_observer` turns an observer back into a voter and leaves other
+    /// roles alone.
+    pub fn apply(&self, player: &mut Player) {
+        if let Some(name) = &self.name {
+            player.name.clone_from(name);
+        }
+
+        if let Some(role) = self.role {
+            player.role = role;
+        } else if let Some(is_observer) = self.is_observer {
+            if is_observer {
+                player.role = PlayerRole::Observer;
+            } else if player.is_observer() {
+                player.role = PlayerRole::Voter;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(role: PlayerRole) -> Player {
+        Player {
+            id: Uuid::new_v4(),
+            name: "Alice".to_string(),
+            role,
+            team_role: TeamRole::default(),
+            joined_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_player_serializes_role_and_is_observer() {
+        let json = serde_json::to_value(player(PlayerRole::Observer)).unwrap();
+
+        assert_eq!(json["role"], "Observer");
+        assert_eq!(json["is_observer"], true);
+
+        let json = serde_json::to_value(player(PlayerRole::Advisor)).unwrap();
+
+        assert_eq!(json["role"], "Advisor");
+        assert_eq!(json["is_observer"], false);
+    }
+
+    #[test]
+    fn test_player_without_role_derives_it_from_is_observer() {
+        let mut json = serde_json::to_value(player(PlayerRole::Voter)).unwrap();
+        let object = json.as_object_mut().unwrap();
+        object.remove("role");
+        object.insert("is_observer".to_string(), serde_json::Value::Bool(true));
+
+        let parsed: Player = serde_json::from_value(json).unwrap();
+
+        assert_eq!(parsed.role, PlayerRole::Observer);
+    }
+
+    #[test]
+    fn test_player_role_round_trips_through_strings() {
+        for role in [PlayerRole::Voter, PlayerRole::Advisor, PlayerRole::Observer] {
+            assert_eq!(role.to_string().parse::<PlayerRole>().unwrap(), role);
+        }
+        assert_eq!(
+            "ADVISOR".parse::<PlayerRole>().unwrap(),
+            PlayerRole::Advisor
+        );
+        assert!("lead".parse::<PlayerRole>().is_err());
+    }
+
+    #[test]
+    fn test_team_role_round_trips_through_strings() {
+        let roles = TeamRole::known().into_iter().chain([
+            TeamRole::default(),
+            TeamRole::Other("Scrum Master".to_string()),
+        ]);
+        for role in roles {
+            assert_eq!(role.to_string().parse::<TeamRole>().unwrap(), role);
+        }
+        assert_eq!(
+            "product_manager".parse::<TeamRole>().unwrap(),
+            TeamRole::ProductManager
+        );
+        assert!(!" ".parse::<TeamRole>().unwrap().is_set());
+    }
+
+    #[test]
+    fn test_team_role_serializes_as_its_name() {
+        let mut alice = player(PlayerRole::Voter);
+        alice.team_role = TeamRole::TechLead;
+
+        let json = serde_json::to_value(&alice).unwrap();
+        assert_eq!(json["team_role"], "Tech Lead");
+
+        let parsed: TeamRole = serde_json::from_value(serde_json::json!("Scrum Master")).unwrap();
+        assert_eq!(parsed, TeamRole::Other("Scrum Master".to_string()));
+    }
+
+    #[test]
+    fn test_player_without_team_role_has_none() {
+        let mut json = serde_json::to_value(player(PlayerRole::Voter)).unwrap();
+        json.as_object_mut().unwrap().remove("team_role");
+
+        let parsed: Player = serde_json::from_value(json).unwrap();
+
+        assert_eq!(parsed.team_role, TeamRole::default());
+    }
+
+    #[test]
+    fn test_game_state_round_trips_through_strings() {
+        for state in [
+            GameState::Waiting,
+            GameState::Voting,
+            GameState::Revealed,
+            GameState::Archived,
+            GameState::Completed,
+        ] {
+            assert_eq!(state.to_string().parse::<GameState>().unwrap(), state);
+        }
+        assert_eq!(GameState::Revealed.to_string(), "Revealed");
+        assert!("Finished".parse::<GameState>().is_err());
+    }
+
+    #[test]
+    fn test_player_update_applies_only_given_fields() {
+        let mut alice = player(PlayerRole::Advisor);
+
+        PlayerUpdate {
+            name: Some("Alicia".to_string()),
+            ..Default::default()
+        }
+        .apply(&mut alice);
+        assert_eq!(alice.name, "Alicia");
+        assert_eq!(alice.role, PlayerRole::Advisor);
+
+        PlayerUpdate {
+            is_observer: Some(false),
+            ..Default::default()
+        }
+        .apply(&mut alice);
+        assert_eq!(alice.role, PlayerRole::Advisor);
+
+        PlayerUpdate {
+            is_observer: Some(true),
+            ..Default::default()
+        }
+        .apply(&mut alice);
+        assert_eq!(alice.role, PlayerRole::Observer);
+
+        PlayerUpdate {
+            is_observer: Some(false),
+            ..Default::default()
+        }
+        .apply(&mut alice);
+        assert_eq!(alice.role, PlayerRole::Voter);
+    }
+
+    #[test]
+    fn test_pagination_params_pick_the_requested_page() {
+        let page = |page, page_size| PaginationParams { page, page_size };
+
+        assert_eq!((page(2, 1).offset(), page(2, 1).end()), (1, 2));
+        assert_eq!((page(0, 20).offset(), page(0, 20).end()), (0, 20));
+        assert_eq!((page(3, 20).offset(), page(3, 20).end()), (40, 60));
+        assert_eq!(page(3, 20).limit(), 20);
+    }
+
+    #[test]
+    fn test_server_error_carries_its_code() {
+        let json = serde_json::to_value(ServerMessage::Error {
+            code: ErrorCode::GameNotFound,
+            message: "Game not found".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(json["type"], "Error");
+        assert_eq!(json["code"], "GameNotFound");
+        assert_eq!(json["message"], "Game not found");
+    }
+
+    #[test]
+    fn test_revealed_votes_carry_their_confidence() {
+        let vote = |confidence| Vote {
+            player_id: Uuid::new_v4(),
+            player_name: "Alice".to_string(),
+            value: "5".to_string(),
+            cast_at: Utc::now(),
+            confidence,
+        };
+
+        let json = serde_json::to_value(ServerMessage::VotesRevealed {
+            votes: vec![vote(Some(Confidence::Low)), vote(None)],
+        })
+        .unwrap();
+
+        assert_eq!(json["type"], "VotesRevealed");
+        assert_eq!(json["votes"][0]["confidence"], "Low");
+        assert!(json["votes"][1]["confidence"].is_null());
+    }
+
+    #[test]
+    fn test_votes_sent_without_confidence_have_none() {
+        let message = serde_json::json!({
+            "type": "VotesRevealed",
+            "votes": [{
+                "player_id": Uuid::new_v4(),
+                "player_name": "Alice",
+                "value": "5",
+                "cast_at": Utc::now(),
+            }],
+        });
+
+        let ServerMessage::VotesRevealed { votes } = serde_json::from_value(message).unwrap()
+        else {
+            panic!("not a VotesRevealed message");
+        };
+        assert_eq!(votes[0].confidence, None);
+    }
+
+    #[test]
+    fn test_shutdown_notice_carries_its_estimate() {
+        let json = serde_json::to_value(ServerMessage::ServerShuttingDown {
+            estimated_seconds: 30,
+        })
+        .unwrap();
+
+        assert_eq!(json["type"], "ServerShuttingDown");
+        assert_eq!(json["estimated_seconds"], 30);
+    }
+
+    #[test]
+    fn test_webhook_payload_is_tagged_with_its_event() {
+        let game_id = Uuid::new_v4();
+
+        let json = serde_json::to_value(WebhookPayload::VotingStarted {
+            game_id,
+            story: "Login page".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "event": "voting_started",
+                "game_id": game_id,
+                "story": "Login page",
+            })
+        );
+
+        let json = serde_json::to_value(WebhookPayload::VotingReset { game_id }).unwrap();
+        assert_eq!(json["event"], "voting_reset");
+        assert_eq!(WebhookPayload::VotingReset { game_id }.game_id(), game_id);
+    }
+
+    #[test]
+    fn test_round_result_payload_round_trips() {
+        let game_id = Uuid::new_v4();
+        let payload = RoundResultPayload {
+            event: RoundResultEvent::EstimateFinalized,
+            game_id,
+            game_name: "Sprint 12".to_string(),
+            story: Some("Login page".to_string()),
+            votes: vec![vote_at(Utc::now())],
+            distribution: vec![("5".to_string(), 1)],
+            final_estimate: Some("5".to_string()),
+        };
+
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["event"], "estimate_finalized");
+        assert_eq!(json["distribution"], serde_json::json!([["5", 1]]));
+
+        let parsed: RoundResultPayload = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.event, RoundResultEvent::EstimateFinalized);
+        assert_eq!(parsed.game_id, game_id);
+        assert_eq!(parsed.votes.len(), 1);
+        assert_eq!(parsed.final_estimate.as_deref(), Some("5"));
+    }
+
+    fn vote_at(cast_at: DateTime<Utc>) -> Vote {
+        Vote {
+            player_id: Uuid::new_v4(),
+            player_name: "Player".to_string(),
+            value: "5".to_string(),
+            cast_at,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_revealed_game_is_due_for_archival_after_its_delay() {
+        let revealed_at = Utc::now();
+        let mut game = Game {
+            id: Uuid::new_v4(),
+            name: "Sprint 12".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Revealed,
+            current_story: None,
+            created_at: revealed_at,
+            updated_at: revealed_at,
+            settings: GameSettings {
+                auto_archive_after: Some(Duration::from_hours(1)),
+                ..GameSettings::default()
+            },
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let later = revealed_at + chrono::Duration::hours(2);
+
+        assert!(!game.is_due_for_archival(revealed_at));
+        assert!(game.is_due_for_archival(later));
+
+        game.state = GameState::Voting;
+        assert!(!game.is_due_for_archival(later));
+
+        game.state = GameState::Revealed;
+        game.settings.auto_archive_after = None;
+        assert!(!game.is_due_for_archival(later));
+    }
+
+    #[test]
+    fn test_only_revealed_games_can_be_archived() {
+        assert!(GameState::Revealed.can_transition_to(&GameState::Archived));
+        assert!(!GameState::Waiting.can_transition_to(&GameState::Archived));
+        assert!(!GameState::Voting.can_transition_to(&GameState::Archived));
+        assert!(!GameState::Archived.can_transition_to(&GameState::Waiting));
+    }
+
+    #[test]
+    fn test_completed_games_have_ended_for_good() {
+        assert!(GameState::Waiting.can_transition_to(&GameState::Completed));
+        assert!(GameState::Revealed.can_transition_to(&GameState::Completed));
+        assert!(!GameState::Archived.can_transition_to(&GameState::Completed));
+        assert!(!GameState::Completed.can_transition_to(&GameState::Waiting));
+        assert!(!GameState::Completed.can_transition_to(&GameState::Archived));
+
+        assert!(GameState::Completed.has_ended());
+        assert!(GameState::Archived.has_ended());
+        assert!(!GameState::Revealed.has_ended());
+    }
+
+    #[test]
+    fn test_audit_event_types_round_trip_through_strings() {
+        for event_type in [
+            AuditEventType::GameCreated,
+            AuditEventType::PlayerJoined,
+            AuditEventType::PlayerKicked,
+            AuditEventType::VotingStarted,
+            AuditEventType::VoteCast,
+            AuditEventType::VotesRevealed,
+            AuditEventType::VotingReset,
+            AuditEventType::OwnershipTransferred,
+        ] {
+            assert_eq!(
+                event_type.to_string().parse::<AuditEventType>(),
+                Ok(event_type)
+            );
+        }
+        assert!("Exploded".parse::<AuditEventType>().is_err());
+    }
+
+    #[test]
+    fn test_new_story_reads_optional_key() {
+        assert_eq!(NewStory::parse_line("   "), None);
+        assert_eq!(
+            NewStory::parse_line("  Login page "),
+            Some(NewStory {
+                key: None,
+                title: "Login page".to_string()
+            })
+        );
+        let keyed = NewStory::parse_line("KEY-123 | Login page").unwrap();
+        assert_eq!(keyed.key.as_deref(), Some("KEY-123"));
+        assert_eq!(keyed.title, "Login page");
+        assert_eq!(NewStory::parse_line("| Login page").unwrap().key, None);
+        assert_eq!(NewStory::parse_line("KEY-1 |").unwrap().title, "KEY-1 |");
+
+        assert!(keyed.is_same_story(Some("key-123"), " login PAGE"));
+        assert!(!keyed.is_same_story(None, "Login page"));
+    }
+
+    #[test]
+    fn test_story_batch_reports_every_invalid_story() {
+        let story = |title: &str| StoryInput {
+            title: title.to_string(),
+            description: None,
+            acceptance_criteria: None,
+        };
+
+        assert_eq!(StoryInput::validate_batch(&[]), Ok(()));
+        assert_eq!(
+            StoryInput::validate_batch(&[story("Login page"), story(&"x".repeat(500))]),
+            Ok(())
+        );
+
+        let errors = StoryInput::validate_batch(&[
+            story("Login page"),
+            story("  "),
+            story(&"x".repeat(501)),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            errors.iter().map(|error| error.index).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert_eq!(errors[0].to_string(), "Story 2: Title is required");
+        assert!(errors[1].message.contains("500"));
+    }
+
+    #[test]
+    fn test_round_timing_under_a_minute() {
+        let started = Utc::now();
+        let timing = RoundTiming::measure(
+            Some(started),
+            started + chrono::Duration::seconds(45),
+            &[vote_at(started + chrono::Duration::seconds(30))],
+        )
+        .unwrap();
+
+        assert_eq!(timing.to_string(), "round took 45s, slowest voter 30s");
+    }
+
+    #[test]
+    fn test_round_timing_over_several_minutes() {
+        let started = Utc::now();
+        let timing = RoundTiming::measure(
+            Some(started),
+            started + chrono::Duration::seconds(134),
+            &[
+                vote_at(started + chrono::Duration::seconds(20)),
+                vote_at(started + chrono::Duration::seconds(110)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            timing,
+            RoundTiming {
+                round_seconds: 134,
+                slowest_voter_seconds: Some(110),
+            }
+        );
+        assert_eq!(
+            timing.to_string(),
+            "round took 2m 14s, slowest voter 1m 50s"
+        );
+    }
+
+    #[test]
+    fn test_round_timing_needs_a_start_time() {
+        let now = Utc::now();
+
+        assert_eq!(RoundTiming::measure(None, now, &[vote_at(now)]), None);
+
+        let timing = RoundTiming::measure(Some(now), now, &[]).unwrap();
+        assert_eq!(timing.to_string(), "round took 0s");
+    }
+
+    #[test]
+    fn test_game_history_json_lists_rounds_and_estimates() {
+        let alice = player(PlayerRole::Voter);
+        let history = GameHistory {
+            game: Game {
+                id: Uuid::new_v4(),
+                name: "Sprint 12".to_string(),
+                owner_id: alice.id,
+                voting_system: "fibonacci".to_string(),
+                state: GameState::Voting,
+                current_story: Some("Search".to_string()),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                settings: GameSettings::default(),
+                round_id: Uuid::new_v4(),
+                voting_started_at: Some(Utc::now()),
+                voting_deadline: None,
+                completed_at: None,
+            },
+            in_progress: true,
+            participants: vec![alice.clone()],
+            rounds: vec![RoundHistory {
+                round_id: Uuid::new_v4(),
+                story: Some("Login page".to_string()),
+                votes: vec![Vote {
+                    player_id: alice.id,
+                    player_name: alice.name.clone(),
+                    value: "8".to_string(),
+                    cast_at: Utc::now(),
+                    confidence: Some(Confidence::High),
+                }],
+            }],
+            final_estimates: vec![FinalEstimate {
+                story: "Login page".to_string(),
+                estimate: Some("8".to_string()),
+            }],
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&history.to_json_pretty().unwrap()).unwrap();
+
+        assert_eq!(json["game"]["name"], "Sprint 12");
+        assert_eq!(json["in_progress"], true);
+        assert_eq!(json["participants"][0]["name"], alice.name);
+        assert_eq!(json["rounds"][0]["story"], "Login page");
+        assert_eq!(json["rounds"][0]["votes"][0]["player_name"], alice.name);
+        assert_eq!(json["rounds"][0]["votes"][0]["value"], "8");
+        assert!(json["rounds"][0]["votes"][0]["cast_at"].is_string());
+        assert_eq!(
+            json["final_estimates"],
+            serde_json::json!([{ "story": "Login page", "estimate": "8" }])
+        );
+    }
 }
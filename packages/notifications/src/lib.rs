@@ -0,0 +1,310 @@
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::{fmt::Write as _, time::Duration};
+
+use hmac::{Hmac, Mac};
+use planning_poker_config::{WebhookConfig, WebhookEvent};
+use planning_poker_models::{RoundResultPayload, WebhookPayload};
+use sha2::Sha256;
+
+/// Header carrying the HMAC-SHA256 signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// How long a single webhook delivery may take before it is abandoned
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many times a game's own webhook is tried before the result is dropped
+const GAME_WEBHOOK_ATTEMPTS: usize = 2;
+
+/// Delivers game events to the configured webhooks
+///
+/// Deliveries are best effort: a webhook that fails or times out is logged
+/// and skipped so it can never fail the game action that triggered it.
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    webhooks: Vec<WebhookConfig>,
+}
+
+impl WebhookNotifier {
+    #[must_use]
+    pub fn new(webhooks: Vec<WebhookConfig>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(DELIVERY_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            webhooks,
+        }
+    }
+
+    /// POST `payload` as JSON to every webhook subscribed to its event
+    pub async fn notify(&self, payload: &WebhookPayload) {
+        let event = event_of(payload);
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to serialize webhook payload: {e}");
+                return;
+            }
+        };
+
+        for webhook in self
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.subscribes_to(event))
+        {
+            let mut request = self
+                .client
+                .post(&webhook.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(secret) = &webhook.secret {
+                request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+            }
+
+            match request
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+            {
+                Ok(_) => tracing::debug!("Delivered {event:?} webhook to {}", webhook.url),
+                Err(e) => tracing::warn!(
+                    "Failed to deliver {event:?} webhook to {}: {e}",
+                    webhook.url
+                ),
+            }
+        }
+    }
+
+    /// POST `payload` as JSON to a game's own webhook at `url`
+    ///
+    /// A failed delivery is retried once before it is logged and dropped.
+    pub async fn notify_game(&self, url: &str, payload: &RoundResultPayload) {
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to serialize round result payload: {e}");
+                return;
+            }
+        };
+
+        for attempt in 1..=GAME_WEBHOOK_ATTEMPTS {
+            let result = self
+                .client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+
+            match result {
+                Ok(_) => {
+                    tracing::debug!("Delivered {:?} webhook to {url}", payload.event);
+                    return;
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to deliver {:?} webhook to {url} (attempt {attempt} of {GAME_WEBHOOK_ATTEMPTS}): {e}",
+                    payload.event
+                ),
+            }
+        }
+    }
+}
+
+/// Signature of `body` under `secret`, formatted as `sha256=<hex digest>`
+///
+/// # Panics
+///
+/// * Infallible, HMAC accepts keys of any length
+#[must_use]
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+
+    let mut signature = String::from("sha256=");
+    for byte in mac.finalize().into_bytes() {
+        let _ = write!(signature, "{byte:02x}");
+    }
+    signature
+}
+
+const fn event_of(payload: &WebhookPayload) -> WebhookEvent {
+    match payload {
+        WebhookPayload::GameCreated { .. } => WebhookEvent::GameCreated,
+        WebhookPayload::VotingStarted { .. } => WebhookEvent::VotingStarted,
+        WebhookPayload::VotesRevealed { .. } => WebhookEvent::VotesRevealed,
+        WebhookPayload::VotingReset { .. } => WebhookEvent::VotingReset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+    use wiremock::{
+        matchers::{header, header_exists, method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn webhook(
+        server: &MockServer,
+        secret: Option<&str>,
+        events: Vec<WebhookEvent>,
+    ) -> WebhookConfig {
+        WebhookConfig {
+            url: format!("{}/hook", server.uri()),
+            secret: secret.map(ToString::to_string),
+            events,
+        }
+    }
+
+    fn started(game_id: Uuid) -> WebhookPayload {
+        WebhookPayload::VotingStarted {
+            game_id,
+            story: "Login page".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sign_matches_known_digest() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_posts_signed_payload() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header("content-type", "application/json"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let game_id = Uuid::new_v4();
+        WebhookNotifier::new(vec![webhook(&server, Some("secret"), WebhookEvent::all())])
+            .notify(&started(game_id))
+            .await;
+
+        let requests: Vec<Request> = server.received_requests().await.unwrap();
+        let request = &requests[0];
+        let body: serde_json::Value = request.body_json().unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "event": "voting_started",
+                "game_id": game_id,
+                "story": "Login page",
+            })
+        );
+        assert_eq!(
+            request.headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign("secret", &request.body)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_without_secret_is_unsigned() {
+        let server = MockServer::start().await;
+        Mock::given(header_exists(SIGNATURE_HEADER))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        WebhookNotifier::new(vec![webhook(&server, None, WebhookEvent::all())])
+            .notify(&started(Uuid::new_v4()))
+            .await;
+    }
+
+    fn round_result(game_id: Uuid) -> RoundResultPayload {
+        RoundResultPayload {
+            event: planning_poker_models::RoundResultEvent::VotesRevealed,
+            game_id,
+            game_name: "Sprint 12".to_string(),
+            story: Some("Login page".to_string()),
+            votes: Vec::new(),
+            distribution: vec![("5".to_string(), 2)],
+            final_estimate: Some("5".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_game_retries_once_after_a_failure() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let game_id = Uuid::new_v4();
+        WebhookNotifier::new(Vec::new())
+            .notify_game(&format!("{}/hook", server.uri()), &round_result(game_id))
+            .await;
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = requests[1].body_json().unwrap();
+        assert_eq!(body["event"], "votes_revealed");
+        assert_eq!(body["game_id"], serde_json::json!(game_id));
+        assert_eq!(body["game_name"], "Sprint 12");
+        assert_eq!(body["final_estimate"], "5");
+    }
+
+    #[tokio::test]
+    async fn test_notify_game_gives_up_after_the_retry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        WebhookNotifier::new(Vec::new())
+            .notify_game(&server.uri(), &round_result(Uuid::new_v4()))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_notify_skips_unsubscribed_events_and_survives_failures() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let notifier = WebhookNotifier::new(vec![webhook(
+            &server,
+            None,
+            vec![WebhookEvent::VotingReset],
+        )]);
+        let game_id = Uuid::new_v4();
+        notifier.notify(&started(game_id)).await;
+        notifier
+            .notify(&WebhookPayload::VotingReset { game_id })
+            .await;
+    }
+}
@@ -0,0 +1,244 @@
+use planning_poker_models::Vote;
+
+use crate::{VoteValue, VotingSystem};
+
+/// How closely a round of revealed votes agrees
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConsensusResult {
+    /// The most common estimate, ties going to the lower card
+    pub mode: Option<String>,
+    /// Whether every estimate lies within one deck step of every other
+    pub consensus: bool,
+    /// The median estimate rounded to the nearest card in the deck
+    pub suggested: Option<String>,
+    /// The lowest and highest estimates
+    pub spread: Option<(String, String)>,
+    /// Votes left out because they carry no estimate, such as `?` or `☕`
+    pub excluded: usize,
+}
+
+/// A card on the deck's ladder of estimates
+struct Step<'a> {
+    /// Position of the card in the full deck
+    ordinal: usize,
+    value: VoteValue,
+    card: &'a str,
+}
+
+/// A vote placed on the deck's ladder of estimate cards
+struct Estimate {
+    step: usize,
+    number: Option<f64>,
+}
+
+/// Work out whether `votes` agree, using `deck` to measure how far apart they are
+///
+/// Special cards and values that can't be placed on the deck are excluded
+/// and only counted.
+#[must_use]
+pub fn compute_consensus(votes: &[Vote], deck: &VotingSystem) -> ConsensusResult {
+    let options = deck.get_voting_options();
+    // Cards that carry an estimate, in the order the deck declares them
+    let steps: Vec<Step> = options
+        .iter()
+        .enumerate()
+        .map(|(ordinal, option)| Step {
+            ordinal,
+            value: VoteValue::parse(deck, option),
+            card: option.as_str(),
+        })
+        .filter(|step| step.value != VoteValue::Special)
+        .collect();
+
+    let mut estimates: Vec<Estimate> = votes
+        .iter()
+        .filter_map(|vote| place(&steps, deck, &vote.value))
+        .collect();
+    let excluded = votes.len() - estimates.len();
+
+    if estimates.is_empty() {
+        return ConsensusResult {
+            excluded,
+            ..ConsensusResult::default()
+        };
+    }
+
+    estimates.sort_by_key(|estimate| estimate.step);
+    let card = |step: usize| steps[step].card.to_string();
+    let lowest = estimates[0].step;
+    let highest = estimates[estimates.len() - 1].step;
+
+    ConsensusResult {
+        mode: Some(card(mode_step(&estimates))),
+        consensus: highest - lowest <= 1,
+        suggested: Some(card(median_step(&steps, &estimates))),
+        spread: Some((card(lowest), card(highest))),
+        excluded,
+    }
+}
+
+/// Place a vote on the deck, snapping numbers that aren't cards to the nearest one
+fn place(steps: &[Step], deck: &VotingSystem, raw: &str) -> Option<Estimate> {
+    let number = VoteValue::parse(deck, raw).as_number();
+
+    let step = match deck.ordinal_of(raw) {
+        // Special cards are in the deck but not on the ladder
+        Some(ordinal) => steps.iter().position(|step| step.ordinal == ordinal)?,
+        None => nearest_numeric_step(steps, number?)?,
+    };
+
+    Some(Estimate { step, number })
+}
+
+/// The numeric card closest to `number`, ties going to the later card
+fn nearest_numeric_step(steps: &[Step], number: f64) -> Option<usize> {
+    steps
+        .iter()
+        .enumerate()
+        .filter_map(|(i, step)| {
+            step.value
+                .as_number()
+                .map(|card| (i, (card - number).abs()))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(i, _)| i)
+}
+
+/// Expects `estimates` sorted by step
+fn mode_step(estimates: &[Estimate]) -> usize {
+    let mut best = (estimates[0].step, 0);
+    for run in estimates.chunk_by(|a, b| a.step == b.step) {
+        if run.len() > best.1 {
+            best = (run[0].step, run.len());
+        }
+    }
+    best.0
+}
+
+/// Expects `estimates` sorted by step
+///
+/// All-numeric rounds take the median of the numbers; otherwise the median
+/// is taken over deck positions. Either way a median between two cards
+/// rounds up.
+fn median_step(steps: &[Step], estimates: &[Estimate]) -> usize {
+    let mid = estimates.len() / 2;
+    let numbers: Option<Vec<f64>> = estimates.iter().map(|estimate| estimate.number).collect();
+
+    if let Some(mut numbers) = numbers {
+        numbers.sort_by(f64::total_cmp);
+        let median = if numbers.len().is_multiple_of(2) {
+            f64::midpoint(numbers[mid - 1], numbers[mid])
+        } else {
+            numbers[mid]
+        };
+        if let Some(step) = nearest_numeric_step(steps, median) {
+            return step;
+        }
+    }
+
+    if estimates.len().is_multiple_of(2) {
+        (estimates[mid - 1].step + estimates[mid].step).div_ceil(2)
+    } else {
+        estimates[mid].step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn votes(values: &[&str]) -> Vec<Vote> {
+        values
+            .iter()
+            .map(|value| Vote {
+                player_id: Uuid::new_v4(),
+                player_name: "Player".to_string(),
+                value: (*value).to_string(),
+                cast_at: Utc::now(),
+                confidence: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unanimous_votes_reach_consensus() {
+        let result = compute_consensus(&votes(&["5", "5", "5"]), &VotingSystem::Fibonacci);
+
+        assert_eq!(
+            result,
+            ConsensusResult {
+                mode: Some("5".to_string()),
+                consensus: true,
+                suggested: Some("5".to_string()),
+                spread: Some(("5".to_string(), "5".to_string())),
+                excluded: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_adjacent_cards_are_near_consensus() {
+        let result =
+            compute_consensus(&votes(&["3", "5", "5", "3", "5"]), &VotingSystem::Fibonacci);
+
+        assert!(result.consensus);
+        assert_eq!(result.mode, Some("5".to_string()));
+        assert_eq!(result.suggested, Some("5".to_string()));
+
+        // An even split has a median between the cards, which rounds up
+        let result = compute_consensus(&votes(&["3", "5"]), &VotingSystem::Fibonacci);
+        assert!(result.consensus);
+        assert_eq!(result.mode, Some("3".to_string()));
+        assert_eq!(result.suggested, Some("5".to_string()));
+    }
+
+    #[test]
+    fn test_widely_split_votes_have_no_consensus() {
+        let result = compute_consensus(&votes(&["2", "3", "8", "13"]), &VotingSystem::Fibonacci);
+
+        assert!(!result.consensus);
+        assert_eq!(result.spread, Some(("2".to_string(), "13".to_string())));
+        // The median of 5.5 rounds to the nearest card
+        assert_eq!(result.suggested, Some("5".to_string()));
+    }
+
+    #[test]
+    fn test_special_cards_are_excluded_but_counted() {
+        let result = compute_consensus(&votes(&["8", "?", "☕", "8"]), &VotingSystem::Fibonacci);
+
+        assert!(result.consensus);
+        assert_eq!(result.suggested, Some("8".to_string()));
+        assert_eq!(result.excluded, 2);
+
+        let result = compute_consensus(&votes(&["?"]), &VotingSystem::Fibonacci);
+        assert_eq!(
+            result,
+            ConsensusResult {
+                excluded: 1,
+                ..ConsensusResult::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_labels_use_deck_positions() {
+        let result = compute_consensus(&votes(&["S", "M", "m"]), &VotingSystem::TShirtSizes);
+        assert!(result.consensus);
+        assert_eq!(result.mode, Some("M".to_string()));
+
+        let result = compute_consensus(&votes(&["XS", "L"]), &VotingSystem::TShirtSizes);
+        assert!(!result.consensus);
+        assert_eq!(result.suggested, Some("M".to_string()));
+    }
+
+    #[test]
+    fn test_numbers_off_the_deck_snap_to_the_nearest_card() {
+        let result = compute_consensus(&votes(&["4", "5"]), &VotingSystem::Fibonacci);
+
+        assert!(result.consensus);
+        assert_eq!(result.spread, Some(("5".to_string(), "5".to_string())));
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use planning_poker_models::{Player, TeamRole, Vote};
+use uuid::Uuid;
+
+use crate::{numeric_value, vote_value::is_special_card, VotingSystem};
+
+/// How many times each vote value was cast
+#[must_use]
+pub fn vote_distribution(votes: &[Vote]) -> HashMap<String, usize> {
+    let mut distribution = HashMap::new();
+    for vote in votes {
+        *distribution
+            .entry(vote.value.trim().to_string())
+            .or_insert(0) += 1;
+    }
+    distribution
+}
+
+/// How a round's votes were spread, overall and within each team role
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoteStatistics {
+    /// How many times each vote value was cast
+    pub distribution: HashMap<String, usize>,
+    /// The same count for each team role that cast a vote
+    ///
+    /// Votes of players who have since left have no role and are only in
+    /// `distribution`.
+    pub by_team_role: HashMap<TeamRole, HashMap<String, usize>>,
+}
+
+/// [`vote_distribution`] of the votes, also broken down by the team role of
+/// the player who cast each one
+#[must_use]
+pub fn vote_statistics(votes: &[Vote], players: &[Player]) -> VoteStatistics {
+    let mut by_team_role: HashMap<TeamRole, Vec<Vote>> = HashMap::new();
+    for vote in votes {
+        if let Some(player) = players.iter().find(|player| player.id == vote.player_id) {
+            by_team_role
+                .entry(player.team_role.clone())
+                .or_default()
+                .push(vote.clone());
+        }
+    }
+
+    VoteStatistics {
+        distribution: vote_distribution(votes),
+        by_team_role: by_team_role
+            .into_iter()
+            .map(|(team_role, votes)| (team_role, vote_distribution(&votes)))
+            .collect(),
+    }
+}
+
+/// The value every vote agrees on, ignoring special cards such as `?`
+///
+/// Returns `None` if the votes disagree or none of them carry an estimate.
+#[must_use]
+pub fn consensus_recommendation(votes: &[Vote]) -> Option<String> {
+    let mut estimates = votes
+        .iter()
+        .map(|vote| vote.value.trim())
+        .filter(|value| !is_special_card(value));
+
+    let first = estimates.next()?;
+    estimates
+        .all(|value| value == first)
+        .then(|| first.to_string())
+}
+
+/// The mean of the votes that carry a numeric estimate
+///
+/// Returns `None` if none of them do, such as a round of only `?` cards.
+#[must_use]
+pub fn numeric_average(votes: &[Vote], voting_system: &VotingSystem) -> Option<f64> {
+    let numbers: Vec<f64> = votes
+        .iter()
+        .filter_map(|vote| numeric_value(vote, voting_system))
+        .collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = numbers.len() as f64;
+    (!numbers.is_empty()).then(|| numbers.iter().sum::<f64>() / count)
+}
+
+/// Whether every vote with a numeric estimate is the same number
+///
+/// A round without any numeric votes has no consensus.
+#[must_use]
+pub fn has_consensus(votes: &[Vote], voting_system: &VotingSystem) -> bool {
+    let mut numbers = votes
+        .iter()
+        .filter_map(|vote| numeric_value(vote, voting_system));
+
+    numbers.next().is_some_and(|first| {
+        #[allow(clippy::float_cmp)]
+        numbers.all(|number| number == first)
+    })
+}
+
+/// Players whose numeric vote lies more than `threshold` standard deviations
+/// from the mean, in the order the votes were given
+///
+/// Votes without a numeric estimate take no part, and a round whose numbers
+/// all agree has no outliers.
+#[must_use]
+pub fn vote_outliers(
+    votes: &[Vote],
+    voting_system: &VotingSystem,
+    threshold: f64,
+) -> Vec<(Uuid, String)> {
+    let numeric: Vec<(&Vote, f64)> = votes
+        .iter()
+        .filter_map(|vote| numeric_value(vote, voting_system).map(|number| (vote, number)))
+        .collect();
+    if numeric.len() < 2 {
+        return Vec::new();
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = numeric.len() as f64;
+    let mean = numeric.iter().map(|(_, number)| number).sum::<f64>() / count;
+    let variance = numeric
+        .iter()
+        .map(|(_, number)| (number - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    let deviation = variance.sqrt();
+    if deviation == 0.0 {
+        return Vec::new();
+    }
+
+    numeric
+        .into_iter()
+        .filter(|(_, number)| (number - mean).abs() > threshold * deviation)
+        .map(|(vote, _)| (vote.player_id, vote.value.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use planning_poker_models::PlayerRole;
+
+    use super::*;
+
+    fn votes(values: &[&str]) -> Vec<Vote> {
+        values
+            .iter()
+            .map(|value| Vote {
+                player_id: Uuid::new_v4(),
+                player_name: "Player".to_string(),
+                value: (*value).to_string(),
+                cast_at: Utc::now(),
+                confidence: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_average_and_consensus_only_count_numeric_votes() {
+        let deck = VotingSystem::Fibonacci;
+
+        let agreed = votes(&["5", "5", "?"]);
+        assert_eq!(numeric_average(&agreed, &deck), Some(5.0));
+        assert!(has_consensus(&agreed, &deck));
+
+        let split = votes(&["3", "8"]);
+        assert_eq!(numeric_average(&split, &deck), Some(5.5));
+        assert!(!has_consensus(&split, &deck));
+
+        let unsure = votes(&["?", "?"]);
+        assert_eq!(numeric_average(&unsure, &deck), None);
+        assert!(!has_consensus(&unsure, &deck));
+    }
+
+    #[test]
+    fn test_uniform_votes_agree_without_outliers() {
+        let votes = votes(&["5", "5", "5"]);
+
+        assert_eq!(
+            vote_distribution(&votes),
+            HashMap::from([("5".to_string(), 3)])
+        );
+        assert_eq!(consensus_recommendation(&votes).as_deref(), Some("5"));
+        assert!(vote_outliers(&votes, &VotingSystem::Fibonacci, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_bimodal_votes_have_no_consensus() {
+        let votes = votes(&["2", "2", "13", "13"]);
+
+        assert_eq!(
+            vote_distribution(&votes),
+            HashMap::from([("2".to_string(), 2), ("13".to_string(), 2)])
+        );
+        assert_eq!(consensus_recommendation(&votes), None);
+        // Every vote sits exactly one standard deviation from the mean
+        assert!(vote_outliers(&votes, &VotingSystem::Fibonacci, 1.0).is_empty());
+        assert_eq!(
+            vote_outliers(&votes, &VotingSystem::Fibonacci, 0.5).len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_statistics_break_the_distribution_down_by_team_role() {
+        let votes = votes(&["3", "5", "5", "8"]);
+        let players: Vec<Player> = [TeamRole::Developer, TeamRole::Developer, TeamRole::Qa]
+            .into_iter()
+            .zip(&votes)
+            .map(|(team_role, vote)| Player {
+                id: vote.player_id,
+                name: "Player".to_string(),
+                role: PlayerRole::Voter,
+                team_role,
+                joined_at: Utc::now(),
+            })
+            .collect();
+
+        let statistics = vote_statistics(&votes, &players);
+
+        assert_eq!(statistics.distribution, vote_distribution(&votes));
+        assert_eq!(
+            statistics.by_team_role,
+            HashMap::from([
+                (
+                    TeamRole::Developer,
+                    HashMap::from([("3".to_string(), 1), ("5".to_string(), 1)])
+                ),
+                (TeamRole::Qa, HashMap::from([("5".to_string(), 1)])),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_question_marks_are_counted_but_not_estimates() {
+        let votes = votes(&["3", "?", "3", "3", "3", "21"]);
+
+        assert_eq!(vote_distribution(&votes)["?"], 1);
+        assert_eq!(consensus_recommendation(&votes), None);
+        assert_eq!(consensus_recommendation(&votes[..4]).as_deref(), Some("3"));
+        assert_eq!(consensus_recommendation(&votes[1..2]), None);
+
+        let outliers = vote_outliers(&votes, &VotingSystem::Fibonacci, 1.5);
+        assert_eq!(outliers, vec![(votes[5].player_id, "21".to_string())]);
+    }
+}
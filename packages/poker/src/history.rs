@@ -0,0 +1,247 @@
+use planning_poker_models::{FinalEstimate, PlayerStats, RoundHistory, RoundSummary};
+
+use crate::{compute_consensus, numeric_value, VotingSystem};
+
+/// The estimate each story ended with, in the order stories were first estimated
+///
+/// A story's estimate is the suggestion from its last revealed round, so a
+/// revote replaces the earlier result. Rounds without a story are skipped.
+#[must_use]
+pub fn final_estimates(rounds: &[RoundHistory], deck: &VotingSystem) -> Vec<FinalEstimate> {
+    let mut estimates: Vec<FinalEstimate> = Vec::new();
+
+    for round in rounds {
+        let Some(story) = &round.story else {
+            continue;
+        };
+        let estimate = compute_consensus(&round.votes, deck).suggested;
+
+        match estimates
+            .iter_mut()
+            .find(|final_estimate| &final_estimate.story == story)
+        {
+            Some(final_estimate) => final_estimate.estimate = estimate,
+            None => estimates.push(FinalEstimate {
+                story: story.clone(),
+                estimate,
+            }),
+        }
+    }
+
+    estimates
+}
+
+/// Summarize a revealed round, or `None` if nobody voted in it
+///
+/// The round's estimate is the suggestion from its own votes. Votes that
+/// aren't cards in `deck` are counted after the deck's cards.
+#[must_use]
+pub fn round_summary(round: &RoundHistory, deck: &VotingSystem) -> Option<RoundSummary> {
+    let first_vote_at = round.votes.iter().map(|vote| vote.cast_at).min()?;
+    let last_vote_at = round.votes.iter().map(|vote| vote.cast_at).max()?;
+    let options = deck.get_voting_options();
+
+    let mut counts: Vec<(Option<usize>, String, usize)> = Vec::new();
+    for vote in &round.votes {
+        let ordinal = deck.ordinal_of(&vote.value);
+        let card = ordinal.map_or_else(|| vote.value.trim().to_string(), |i| options[i].clone());
+        match counts.iter_mut().find(|(_, counted, _)| *counted == card) {
+            Some((_, _, count)) => *count += 1,
+            None => counts.push((ordinal, card, 1)),
+        }
+    }
+    // Off-deck values have no ordinal, and `None` would sort them first
+    counts.sort_by_key(|(ordinal, _, _)| ordinal.unwrap_or(usize::MAX));
+
+    Some(RoundSummary {
+        round_id: round.round_id,
+        story: round.story.clone(),
+        final_estimate: compute_consensus(&round.votes, deck).suggested,
+        distribution: counts
+            .into_iter()
+            .map(|(_, card, count)| (card, count))
+            .collect(),
+        first_vote_at,
+        last_vote_at,
+    })
+}
+
+/// Per-player participation across `rounds`, in the order players first voted
+///
+/// A vote matches when it is the same card as the estimate its story ended
+/// with; rounds without a story are compared against their own suggestion.
+#[must_use]
+pub fn player_stats(rounds: &[RoundHistory], deck: &VotingSystem) -> Vec<PlayerStats> {
+    let estimates = final_estimates(rounds, deck);
+    let mut stats: Vec<(PlayerStats, Vec<f64>)> = Vec::new();
+
+    for round in rounds {
+        let estimate = round.story.as_ref().map_or_else(
+            || compute_consensus(&round.votes, deck).suggested,
+            |story| {
+                estimates
+                    .iter()
+                    .find(|final_estimate| &final_estimate.story == story)
+                    .and_then(|final_estimate| final_estimate.estimate.clone())
+            },
+        );
+        let estimate_card = estimate.as_deref().and_then(|card| deck.ordinal_of(card));
+
+        for vote in &round.votes {
+            let index = stats
+                .iter()
+                .position(|(player, _)| player.player_id == vote.player_id)
+                .unwrap_or_else(|| {
+                    stats.push((
+                        PlayerStats {
+                            player_id: vote.player_id,
+                            player_name: vote.player_name.clone(),
+                            rounds_participated: 0,
+                            matched_final_estimate: 0,
+                            average_vote: None,
+                        },
+                        Vec::new(),
+                    ));
+                    stats.len() - 1
+                });
+            let (player, numbers) = &mut stats[index];
+
+            player.player_name.clone_from(&vote.player_name);
+            player.rounds_participated += 1;
+            if estimate_card.is_some() && deck.ordinal_of(&vote.value) == estimate_card {
+                player.matched_final_estimate += 1;
+            }
+            numbers.extend(numeric_value(vote, deck));
+        }
+    }
+
+    stats
+        .into_iter()
+        .map(|(mut player, numbers)| {
+            #[allow(clippy::cast_precision_loss)]
+            let count = numbers.len() as f64;
+            player.average_vote =
+                (!numbers.is_empty()).then(|| numbers.iter().sum::<f64>() / count);
+            player
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use planning_poker_models::Vote;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn round(story: Option<&str>, votes: &[(Uuid, &str)]) -> RoundHistory {
+        RoundHistory {
+            round_id: Uuid::new_v4(),
+            story: story.map(ToString::to_string),
+            votes: votes
+                .iter()
+                .map(|(player_id, value)| Vote {
+                    player_id: *player_id,
+                    player_name: format!("Player {player_id}"),
+                    value: (*value).to_string(),
+                    cast_at: Utc::now(),
+                    confidence: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_revote_replaces_the_final_estimate() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let rounds = [
+            round(Some("Login"), &[(alice, "3"), (bob, "13")]),
+            round(Some("Search"), &[(alice, "8"), (bob, "8")]),
+            round(Some("Login"), &[(alice, "5"), (bob, "5")]),
+        ];
+
+        let estimates = final_estimates(&rounds, &VotingSystem::Fibonacci);
+
+        assert_eq!(
+            estimates,
+            [
+                FinalEstimate {
+                    story: "Login".to_string(),
+                    estimate: Some("5".to_string()),
+                },
+                FinalEstimate {
+                    story: "Search".to_string(),
+                    estimate: Some("8".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_player_stats_count_rounds_matches_and_average() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let rounds = [
+            round(Some("Login"), &[(alice, "3"), (bob, "5")]),
+            round(Some("Login"), &[(alice, "5"), (bob, "5")]),
+            round(Some("Search"), &[(alice, "8")]),
+        ];
+
+        let stats = player_stats(&rounds, &VotingSystem::Fibonacci);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].player_id, alice);
+        assert_eq!(stats[0].rounds_participated, 3);
+        // The first Login round doesn't match the 5 the story ended with
+        assert_eq!(stats[0].matched_final_estimate, 2);
+        assert_eq!(stats[0].average_vote, Some(16.0 / 3.0));
+        assert_eq!(stats[1].rounds_participated, 2);
+        assert_eq!(stats[1].matched_final_estimate, 2);
+        assert_eq!(stats[1].average_vote, Some(5.0));
+    }
+
+    #[test]
+    fn test_player_without_numeric_votes_has_no_average() {
+        let alice = Uuid::new_v4();
+        let rounds = [round(None, &[(alice, "?")]), round(None, &[(alice, "☕")])];
+
+        let stats = player_stats(&rounds, &VotingSystem::Fibonacci);
+
+        assert_eq!(stats[0].rounds_participated, 2);
+        assert_eq!(stats[0].matched_final_estimate, 0);
+        assert_eq!(stats[0].average_vote, None);
+    }
+
+    #[test]
+    fn test_round_summary_tallies_votes_in_deck_order() {
+        let summary = round_summary(
+            &round(
+                Some("Login"),
+                &[
+                    (Uuid::new_v4(), "8"),
+                    (Uuid::new_v4(), "maybe"),
+                    (Uuid::new_v4(), "3"),
+                    (Uuid::new_v4(), "8.0"),
+                ],
+            ),
+            &VotingSystem::Fibonacci,
+        )
+        .unwrap();
+
+        assert_eq!(summary.story.as_deref(), Some("Login"));
+        assert_eq!(
+            summary.distribution,
+            [
+                ("3".to_string(), 1),
+                ("8".to_string(), 2),
+                ("maybe".to_string(), 1)
+            ]
+        );
+        assert_eq!(summary.final_estimate, Some("8".to_string()));
+        assert!(summary.first_vote_at <= summary.last_vote_at);
+
+        assert!(round_summary(&round(None, &[]), &VotingSystem::Fibonacci).is_none());
+    }
+}
@@ -4,9 +4,22 @@
 
 use anyhow::Result;
 use planning_poker_models::{GameState, Player, Vote};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use uuid::Uuid;
 
+mod consensus;
+mod distribution;
+mod history;
+mod vote_value;
+
+pub use consensus::{compute_consensus, ConsensusResult};
+pub use distribution::{
+    consensus_recommendation, has_consensus, numeric_average, vote_distribution, vote_outliers,
+    vote_statistics, VoteStatistics,
+};
+pub use history::{final_estimates, player_stats, round_summary};
+pub use vote_value::{numeric_value, sort_votes, VoteValue};
+
 pub struct PlanningPokerGame {
     pub id: Uuid,
     pub name: String,
@@ -15,6 +28,8 @@ pub struct PlanningPokerGame {
     pub players: HashMap<Uuid, Player>,
     pub votes: HashMap<Uuid, Vote>,
     pub current_story: Option<String>,
+    /// Stories waiting to be estimated after the current one, front first
+    pub backlog: VecDeque<String>,
     pub voting_system: VotingSystem,
 }
 
@@ -26,16 +41,109 @@ pub enum VotingSystem {
     Custom(Vec<String>),
 }
 
+/// Prefix marking a voting system string as a custom, comma-separated deck
+pub const CUSTOM_DECK_PREFIX: &str = "custom:";
+
 impl VotingSystem {
+    /// The built-in voting systems offered when creating a game
+    #[must_use]
+    pub fn builtin() -> Vec<Self> {
+        vec![Self::Fibonacci, Self::TShirtSizes, Self::PowersOfTwo]
+    }
+
+    /// Parse a voting system string
+    ///
+    /// Strings starting with `custom:` are read as a comma-separated custom
+    /// deck; anything unrecognized falls back to Fibonacci.
     #[must_use]
     pub fn from_string(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "tshirt" | "t-shirt" | "tshirtsizes" => Self::TShirtSizes,
-            "powers_of_2" | "powersoftwo" | "powers_of_two" => Self::PowersOfTwo,
-            _ => Self::Fibonacci, // Default fallback (includes "fibonacci")
+        if let Some(deck) = s
+            .trim()
+            .strip_prefix(CUSTOM_DECK_PREFIX)
+            .and_then(Self::custom_from_list)
+        {
+            return deck;
+        }
+
+        Self::from_builtin_name(s).unwrap_or(Self::Fibonacci) // Default fallback
+    }
+
+    /// Build a custom deck from a comma-separated list of cards
+    ///
+    /// Blank entries are skipped; returns `None` if no cards are left.
+    #[must_use]
+    pub fn custom_from_list(list: &str) -> Option<Self> {
+        let values: Vec<String> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string)
+            .collect();
+
+        (!values.is_empty()).then_some(Self::Custom(values))
+    }
+
+    /// Look up a built-in voting system by name, without any fallback
+    #[must_use]
+    pub fn from_builtin_name(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "fibonacci" => Some(Self::Fibonacci),
+            "tshirt" | "t-shirt" | "tshirtsizes" => Some(Self::TShirtSizes),
+            "powers_of_2" | "powersoftwo" | "powers_of_two" => Some(Self::PowersOfTwo),
+            _ => None,
+        }
+    }
+
+    /// The canonical string for this voting system, as accepted by `from_string`
+    #[must_use]
+    pub fn key(&self) -> String {
+        match self {
+            Self::Fibonacci => "fibonacci".to_string(),
+            Self::TShirtSizes => "tshirt".to_string(),
+            Self::PowersOfTwo => "powers_of_2".to_string(),
+            Self::Custom(options) => format!("{CUSTOM_DECK_PREFIX}{}", options.join(",")),
+        }
+    }
+
+    /// Human readable name of this voting system
+    #[must_use]
+    pub const fn display_name(&self) -> &'static str {
+        match self {
+            Self::Fibonacci => "Fibonacci",
+            Self::TShirtSizes => "T-Shirt Sizes",
+            Self::PowersOfTwo => "Powers of Two",
+            Self::Custom(_) => "Custom",
         }
     }
 
+    /// Position of `value` in this deck's declared order
+    ///
+    /// Cards match case-insensitively and numbers match by value, so `0.50`
+    /// finds `0.5`. Returns `None` for values the deck doesn't have.
+    #[must_use]
+    pub fn ordinal_of(&self, value: &str) -> Option<usize> {
+        let value = value.trim();
+        let number = vote_value::parse_number(value);
+
+        self.get_voting_options().iter().position(|option| {
+            let option = option.trim();
+            option.eq_ignore_ascii_case(value)
+                || number.is_some_and(|number| {
+                    vote_value::parse_number(option)
+                        .is_some_and(|card| card.total_cmp(&number).is_eq())
+                })
+        })
+    }
+
+    /// The deck's own spelling of `value`, so `xl` resolves to `XL`
+    ///
+    /// Returns `None` for values the deck doesn't have.
+    #[must_use]
+    pub fn card_for(&self, value: &str) -> Option<String> {
+        self.ordinal_of(value)
+            .map(|ordinal| self.get_voting_options().swap_remove(ordinal))
+    }
+
     #[must_use]
     pub fn get_voting_options(&self) -> Vec<String> {
         match self {
@@ -89,6 +197,7 @@ impl PlanningPokerGame {
             players: HashMap::new(),
             votes: HashMap::new(),
             current_story: None,
+            backlog: VecDeque::new(),
             voting_system,
         }
     }
@@ -134,14 +243,19 @@ impl PlanningPokerGame {
     ///
     /// # Errors
     ///
-    /// Returns an error if the game is not in voting state or if the player is not in the game
+    /// Returns an error if the game is not in voting state, if the player is not in the game or
+    /// if the player is an observer
     pub fn cast_vote(&mut self, player_id: Uuid, vote: Vote) -> Result<()> {
         if self.state != GameState::Voting {
             return Err(anyhow::anyhow!("Not in voting state"));
         }
 
-        if !self.players.contains_key(&player_id) {
+        let Some(player) = self.players.get(&player_id) else {
             return Err(anyhow::anyhow!("Player not in game"));
+        };
+
+        if !player.role.can_vote() {
+            return Err(anyhow::anyhow!("Observers can't vote"));
         }
 
         self.votes.insert(player_id, vote);
@@ -164,16 +278,76 @@ impl PlanningPokerGame {
 
     /// Reset the voting session
     ///
+    /// Resetting a revealed round moves on to the next story in the backlog,
+    /// if there is one.
+    ///
     /// # Errors
     ///
     /// Currently never returns an error, but returns Result for future extensibility
     pub fn reset_voting(&mut self) -> Result<()> {
+        let was_revealed = self.state == GameState::Revealed;
+
         self.state = GameState::Waiting;
         self.votes.clear();
         self.current_story = None;
+
+        if was_revealed {
+            self.next_story()?;
+        }
+        Ok(())
+    }
+
+    /// Vote on the current story again, discarding the revealed votes
+    ///
+    /// Unlike `reset_voting` this keeps `current_story` and goes straight
+    /// back to voting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the votes have not been revealed
+    pub fn revote(&mut self) -> Result<()> {
+        if self.state != GameState::Revealed {
+            return Err(anyhow::anyhow!(
+                "Revoting is only possible after votes are revealed"
+            ));
+        }
+
+        self.state = GameState::Voting;
+        self.votes.clear();
         Ok(())
     }
 
+    /// Add a story to the end of the backlog
+    pub fn enqueue_story(&mut self, story: String) {
+        self.backlog.push_back(story);
+    }
+
+    /// Start voting on the story at the front of the backlog
+    ///
+    /// Returns `false` and leaves the game as it is when the backlog is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the game is not in the Waiting state
+    pub fn next_story(&mut self) -> Result<bool> {
+        if self.state != GameState::Waiting {
+            return Err(anyhow::anyhow!("Cannot start voting in current state"));
+        }
+
+        let Some(story) = self.backlog.pop_front() else {
+            return Ok(false);
+        };
+
+        self.start_voting(story)?;
+        Ok(true)
+    }
+
+    /// Number of stories still waiting in the backlog
+    #[must_use]
+    pub fn remaining_stories(&self) -> usize {
+        self.backlog.len()
+    }
+
     #[must_use]
     pub fn get_voting_options(&self) -> Vec<String> {
         self.voting_system.get_voting_options()
@@ -186,6 +360,282 @@ impl PlanningPokerGame {
 
     #[must_use]
     pub fn all_players_voted(&self) -> bool {
-        self.players.len() == self.votes.len()
+        self.players
+            .values()
+            .filter(|player| player.role.can_vote())
+            .all(|player| self.votes.contains_key(&player.id))
+    }
+
+    /// How many times each value was cast in the current round
+    #[must_use]
+    pub fn get_vote_distribution(&self) -> HashMap<String, usize> {
+        vote_distribution(&self.current_votes())
+    }
+
+    /// The round's distribution, also broken down by team role
+    #[must_use]
+    pub fn get_vote_statistics(&self) -> VoteStatistics {
+        let players: Vec<Player> = self.players.values().cloned().collect();
+        vote_statistics(&self.current_votes(), &players)
+    }
+
+    /// The value the whole round agrees on, ignoring `?` votes
+    #[must_use]
+    pub fn get_consensus_recommendation(&self) -> Option<String> {
+        consensus_recommendation(&self.current_votes())
+    }
+
+    /// Players whose numeric vote is more than `threshold` standard
+    /// deviations from the round's mean
+    #[must_use]
+    pub fn get_outliers(&self, threshold: f64) -> Vec<(Uuid, String)> {
+        vote_outliers(&self.current_votes(), &self.voting_system, threshold)
+    }
+
+    fn current_votes(&self) -> Vec<Vote> {
+        self.votes.values().cloned().collect()
+    }
+}
+
+/// The votes that count toward the estimate
+///
+/// Advisory votes are left out. A vote whose player is no longer in `players`
+/// is kept, since there is no role to exclude it by.
+#[must_use]
+pub fn counted_votes(votes: &[Vote], players: &[Player]) -> Vec<Vote> {
+    votes
+        .iter()
+        .filter(|vote| {
+            players
+                .iter()
+                .find(|player| player.id == vote.player_id)
+                .is_none_or(|player| player.role.is_counted())
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use planning_poker_models::{PlayerRole, TeamRole};
+
+    fn player(role: PlayerRole) -> Player {
+        Player {
+            id: Uuid::new_v4(),
+            name: role.to_string(),
+            role,
+            team_role: TeamRole::default(),
+            joined_at: Utc::now(),
+        }
+    }
+
+    fn vote(player_id: Uuid, value: &str) -> Vote {
+        Vote {
+            player_id,
+            player_name: "Player".to_string(),
+            value: value.to_string(),
+            cast_at: Utc::now(),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_counted_votes_skip_advisors() {
+        let developer = player(PlayerRole::Voter);
+        let advisor = player(PlayerRole::Advisor);
+        let departed = Uuid::new_v4();
+        let votes = vec![
+            vote(developer.id, "3"),
+            vote(advisor.id, "13"),
+            vote(departed, "5"),
+        ];
+
+        let counted: Vec<String> = counted_votes(&votes, &[developer, advisor])
+            .into_iter()
+            .map(|vote| vote.value)
+            .collect();
+
+        assert_eq!(counted, vec!["3", "5"]);
+    }
+
+    #[test]
+    fn test_observers_cannot_vote_and_are_not_waited_for() {
+        let mut game =
+            PlanningPokerGame::new("Game".to_string(), Uuid::new_v4(), VotingSystem::Fibonacci);
+        let voter = player(PlayerRole::Voter);
+        let observer = player(PlayerRole::Observer);
+        game.add_player(voter.clone()).unwrap();
+        game.add_player(observer.clone()).unwrap();
+        game.start_voting("Story".to_string()).unwrap();
+
+        assert!(game.cast_vote(observer.id, vote(observer.id, "5")).is_err());
+        assert!(!game.all_players_voted());
+
+        game.cast_vote(voter.id, vote(voter.id, "5")).unwrap();
+        assert!(game.all_players_voted());
+    }
+
+    fn game() -> PlanningPokerGame {
+        PlanningPokerGame::new("Game".to_string(), Uuid::new_v4(), VotingSystem::Fibonacci)
+    }
+
+    #[test]
+    fn test_enqueue_story_adds_to_backlog_in_order() {
+        let mut game = game();
+
+        game.enqueue_story("First".to_string());
+        game.enqueue_story("Second".to_string());
+
+        assert_eq!(game.remaining_stories(), 2);
+        assert_eq!(game.backlog, ["First", "Second"]);
+        assert_eq!(game.state, GameState::Waiting);
+        assert!(game.current_story.is_none());
+    }
+
+    #[test]
+    fn test_reset_after_reveal_advances_through_backlog() {
+        let mut game = game();
+        game.enqueue_story("First".to_string());
+        game.enqueue_story("Second".to_string());
+
+        assert!(game.next_story().unwrap());
+        assert_eq!(game.current_story.as_deref(), Some("First"));
+        assert_eq!(game.state, GameState::Voting);
+        assert_eq!(game.remaining_stories(), 1);
+
+        game.reveal_votes().unwrap();
+        game.reset_voting().unwrap();
+        assert_eq!(game.current_story.as_deref(), Some("Second"));
+        assert_eq!(game.state, GameState::Voting);
+        assert_eq!(game.remaining_stories(), 0);
+
+        // With the backlog empty the game waits for the next story
+        game.reveal_votes().unwrap();
+        game.reset_voting().unwrap();
+        assert!(game.current_story.is_none());
+        assert_eq!(game.state, GameState::Waiting);
+    }
+
+    #[test]
+    fn test_story_survives_revote_but_not_reset() {
+        let mut game = game();
+        let voter = player(PlayerRole::Voter);
+        game.add_player(voter.clone()).unwrap();
+        game.start_voting("Login page".to_string()).unwrap();
+
+        // Only a revealed round can be revoted
+        assert!(game.revote().is_err());
+
+        game.cast_vote(voter.id, vote(voter.id, "8")).unwrap();
+        game.reveal_votes().unwrap();
+        game.revote().unwrap();
+        assert_eq!(game.state, GameState::Voting);
+        assert_eq!(game.current_story.as_deref(), Some("Login page"));
+        assert!(game.votes.is_empty());
+
+        game.reveal_votes().unwrap();
+        game.reset_voting().unwrap();
+        assert_eq!(game.state, GameState::Waiting);
+        assert!(game.current_story.is_none());
+    }
+
+    #[test]
+    fn test_next_story_with_empty_backlog_or_active_round() {
+        let mut game = game();
+
+        assert!(!game.next_story().unwrap());
+        assert_eq!(game.state, GameState::Waiting);
+
+        game.enqueue_story("Queued".to_string());
+        game.start_voting("Ad hoc".to_string()).unwrap();
+        assert!(game.next_story().is_err());
+        assert_eq!(game.remaining_stories(), 1);
+
+        // Resetting a round that was never revealed doesn't pull the next story
+        game.reset_voting().unwrap();
+        assert_eq!(game.state, GameState::Waiting);
+        assert_eq!(game.remaining_stories(), 1);
+    }
+
+    #[test]
+    fn test_game_reports_distribution_consensus_and_outliers() {
+        let mut game = game();
+        let voters: Vec<Player> = (0..4).map(|_| player(PlayerRole::Voter)).collect();
+        for voter in &voters {
+            game.add_player(voter.clone()).unwrap();
+        }
+        game.start_voting("Story".to_string()).unwrap();
+        for (voter, value) in voters.iter().zip(["3", "3", "3", "?"]) {
+            game.cast_vote(voter.id, vote(voter.id, value)).unwrap();
+        }
+
+        assert_eq!(game.get_vote_distribution()["3"], 3);
+        assert_eq!(
+            game.get_vote_statistics().by_team_role[&TeamRole::default()]["3"],
+            3
+        );
+        assert_eq!(game.get_consensus_recommendation().as_deref(), Some("3"));
+        assert!(game.get_outliers(1.0).is_empty());
+    }
+
+    #[test]
+    fn test_from_string_parses_custom_deck() {
+        let system = VotingSystem::from_string("custom: 1, 2 ,3,,?");
+
+        assert_eq!(system.get_voting_options(), vec!["1", "2", "3", "?"]);
+        assert_eq!(system.key(), "custom:1,2,3,?");
+    }
+
+    #[test]
+    fn test_custom_from_list_keeps_card_order() {
+        let deck = VotingSystem::custom_from_list(" XS, S ,, M ").unwrap();
+
+        assert_eq!(deck.get_voting_options(), vec!["XS", "S", "M"]);
+        assert_eq!(deck.key(), "custom:XS,S,M");
+        assert!(VotingSystem::custom_from_list(" , ").is_none());
+    }
+
+    #[test]
+    fn test_ordinal_of_matches_cards_by_text_and_value() {
+        let deck = VotingSystem::from_string("custom:0.5,?,1,XL");
+
+        assert_eq!(deck.ordinal_of("0.5"), Some(0));
+        assert_eq!(deck.ordinal_of("0.50"), Some(0));
+        assert_eq!(deck.ordinal_of("?"), Some(1));
+        assert_eq!(deck.ordinal_of(" xl "), Some(3));
+        assert_eq!(deck.ordinal_of("2"), None);
+        assert_eq!(VotingSystem::Fibonacci.ordinal_of("13"), Some(6));
+    }
+
+    #[test]
+    fn test_card_for_returns_the_decks_spelling() {
+        assert_eq!(
+            VotingSystem::TShirtSizes.card_for(" xl "),
+            Some("XL".to_string())
+        );
+        assert_eq!(
+            VotingSystem::Fibonacci.card_for("8.0"),
+            Some("8".to_string())
+        );
+        assert_eq!(VotingSystem::TShirtSizes.card_for("999"), None);
+    }
+
+    #[test]
+    fn test_empty_custom_deck_falls_back_to_fibonacci() {
+        assert!(matches!(
+            VotingSystem::from_string("custom: , "),
+            VotingSystem::Fibonacci
+        ));
+    }
+
+    #[test]
+    fn test_builtin_keys_round_trip() {
+        for system in VotingSystem::builtin() {
+            let parsed = VotingSystem::from_string(&system.key());
+            assert_eq!(parsed.key(), system.key());
+            assert_eq!(parsed.get_voting_options(), system.get_voting_options());
+        }
     }
 }
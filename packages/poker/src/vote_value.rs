@@ -0,0 +1,263 @@
+use std::cmp::Ordering;
+
+use planning_poker_models::Vote;
+
+use crate::VotingSystem;
+
+/// A vote value interpreted against the deck it was cast from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VoteValue {
+    /// A number such as `13` or `0.5`
+    Numeric(f64),
+    /// A non-numeric card (e.g. a T-shirt size), by its position in the deck
+    Label(usize),
+    /// A card that carries no estimate, such as `?` or `☕`
+    Special,
+}
+
+impl VoteValue {
+    /// Classify a raw vote value
+    ///
+    /// Numbers parse as numbers regardless of the deck, other cards map to
+    /// their position in `deck`, and anything else is special.
+    #[must_use]
+    pub fn parse(deck: &VotingSystem, raw: &str) -> Self {
+        let raw = raw.trim();
+
+        if let Some(number) = parse_number(raw) {
+            return Self::Numeric(number);
+        }
+
+        deck.get_voting_options()
+            .iter()
+            .position(|option| option.trim().eq_ignore_ascii_case(raw))
+            .filter(|_| !is_special_card(raw))
+            .map_or(Self::Special, Self::Label)
+    }
+
+    /// The number this value stands for, if it is numeric
+    #[must_use]
+    pub const fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Numeric(number) => Some(*number),
+            Self::Label(_) | Self::Special => None,
+        }
+    }
+
+    /// Total ordering for display: numbers ascending, then labels in deck
+    /// order, then special cards
+    #[must_use]
+    pub fn cmp_for_display(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.total_cmp(b),
+            (Self::Label(a), Self::Label(b)) => a.cmp(b),
+            (Self::Special, Self::Special) => Ordering::Equal,
+            (Self::Numeric(_), _) | (Self::Label(_), Self::Special) => Ordering::Less,
+            (_, Self::Numeric(_)) | (Self::Special, Self::Label(_)) => Ordering::Greater,
+        }
+    }
+}
+
+/// The numeric estimate of a vote, or `None` for labels and special cards
+#[must_use]
+pub fn numeric_value(vote: &Vote, voting_system: &VotingSystem) -> Option<f64> {
+    VoteValue::parse(voting_system, &vote.value).as_number()
+}
+
+/// Sort votes into the order the deck declares its cards
+///
+/// Cards in the deck come first, in deck order, followed by numbers the deck
+/// doesn't have in ascending order, then anything else in the order it was
+/// cast. Votes for the same card are ordered by player name.
+pub fn sort_votes(votes: &mut [Vote], voting_system: &VotingSystem) {
+    votes.sort_by(|a, b| {
+        let a_key = SortKey::of(voting_system, &a.value);
+        let b_key = SortKey::of(voting_system, &b.value);
+        a_key.cmp(&b_key).then_with(|| {
+            if a_key == SortKey::Unknown {
+                Ordering::Equal
+            } else {
+                a.player_name.cmp(&b.player_name)
+            }
+        })
+    });
+}
+
+/// Where a vote value sorts relative to the deck
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortKey {
+    Card(usize),
+    OffDeck(f64),
+    Unknown,
+}
+
+impl SortKey {
+    fn of(deck: &VotingSystem, raw: &str) -> Self {
+        deck.ordinal_of(raw).map_or_else(
+            || parse_number(raw.trim()).map_or(Self::Unknown, Self::OffDeck),
+            Self::Card,
+        )
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Card(a), Self::Card(b)) => a.cmp(b),
+            (Self::OffDeck(a), Self::OffDeck(b)) => a.total_cmp(b),
+            (Self::Unknown, Self::Unknown) => Ordering::Equal,
+            (Self::Card(_), _) | (Self::OffDeck(_), Self::Unknown) => Ordering::Less,
+            (_, Self::Card(_)) | (Self::Unknown, Self::OffDeck(_)) => Ordering::Greater,
+        }
+    }
+}
+
+pub fn parse_number(raw: &str) -> Option<f64> {
+    if raw == "½" {
+        return Some(0.5);
+    }
+
+    raw.parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+pub fn is_special_card(raw: &str) -> bool {
+    matches!(raw, "?" | "☕")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use proptest::prelude::*;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn vote(name: &str, value: &str) -> Vote {
+        Vote {
+            player_id: Uuid::new_v4(),
+            player_name: name.to_string(),
+            value: value.to_string(),
+            cast_at: Utc::now(),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_classifies_common_values() {
+        let fibonacci = VotingSystem::Fibonacci;
+        let tshirt = VotingSystem::TShirtSizes;
+
+        assert_eq!(VoteValue::parse(&fibonacci, "13"), VoteValue::Numeric(13.0));
+        assert_eq!(VoteValue::parse(&fibonacci, "0.5"), VoteValue::Numeric(0.5));
+        assert_eq!(VoteValue::parse(&tshirt, "XS"), VoteValue::Label(0));
+        assert_eq!(VoteValue::parse(&tshirt, "xl"), VoteValue::Label(4));
+        assert_eq!(VoteValue::parse(&tshirt, "?"), VoteValue::Special);
+        assert_eq!(VoteValue::parse(&fibonacci, "☕"), VoteValue::Special);
+        assert_eq!(VoteValue::parse(&fibonacci, "XS"), VoteValue::Special);
+        assert_eq!(VoteValue::parse(&fibonacci, "NaN"), VoteValue::Special);
+    }
+
+    #[test]
+    fn test_numeric_value_ignores_labels() {
+        assert_eq!(
+            numeric_value(&vote("A", "8"), &VotingSystem::Fibonacci),
+            Some(8.0)
+        );
+        assert_eq!(
+            numeric_value(&vote("A", "M"), &VotingSystem::TShirtSizes),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sort_votes_uses_deck_order() {
+        let mut votes = vec![
+            vote("Dana", "?"),
+            vote("Carol", "XL"),
+            vote("Bob", "S"),
+            vote("Alice", "XL"),
+        ];
+
+        sort_votes(&mut votes, &VotingSystem::TShirtSizes);
+
+        let order: Vec<_> = votes.iter().map(|v| v.player_name.as_str()).collect();
+        assert_eq!(order, vec!["Bob", "Alice", "Carol", "Dana"]);
+    }
+
+    #[test]
+    fn test_sort_votes_orders_numbers_numerically() {
+        let mut votes = vec![vote("A", "13"), vote("B", "2"), vote("C", "?")];
+
+        sort_votes(&mut votes, &VotingSystem::Fibonacci);
+
+        let values: Vec<_> = votes.iter().map(|v| v.value.as_str()).collect();
+        assert_eq!(values, vec!["2", "13", "?"]);
+    }
+
+    #[test]
+    fn test_sort_votes_follows_declared_deck_order() {
+        let deck = VotingSystem::from_string("custom:0.5,?,1,2,13,5");
+        let mut votes = vec![
+            vote("A", "5"),
+            vote("B", "zz"),
+            vote("C", "13"),
+            vote("D", "3"),
+            vote("E", "?"),
+            vote("F", "abc"),
+            vote("G", "0.5"),
+            vote("H", "½"),
+        ];
+
+        sort_votes(&mut votes, &deck);
+
+        let order: Vec<_> = votes.iter().map(|v| v.player_name.as_str()).collect();
+        assert_eq!(order, vec!["G", "H", "E", "C", "A", "D", "B", "F"]);
+    }
+
+    #[test]
+    fn test_every_builtin_option_classifies() {
+        for deck in VotingSystem::builtin() {
+            for option in deck.get_voting_options() {
+                let value = VoteValue::parse(&deck, &option);
+                assert_eq!(
+                    value == VoteValue::Special,
+                    is_special_card(&option),
+                    "{option} in {}",
+                    deck.key()
+                );
+            }
+        }
+    }
+
+    fn any_deck() -> impl Strategy<Value = VotingSystem> {
+        prop_oneof![
+            Just(VotingSystem::Fibonacci),
+            Just(VotingSystem::TShirtSizes),
+            Just(VotingSystem::PowersOfTwo),
+            prop::collection::vec(".{0,4}", 1..8).prop_map(VotingSystem::Custom),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_deck_options_never_panic(deck in any_deck()) {
+            for option in deck.get_voting_options() {
+                let value = VoteValue::parse(&deck, &option);
+                prop_assert_eq!(value.cmp_for_display(&value), Ordering::Equal);
+            }
+        }
+
+        #[test]
+        fn prop_arbitrary_input_never_panics(deck in any_deck(), raw in ".*") {
+            let value = VoteValue::parse(&deck, &raw);
+            if let VoteValue::Label(ordinal) = value {
+                prop_assert!(ordinal < deck.get_voting_options().len());
+            }
+        }
+
+        #[test]
+        fn prop_display_order_is_antisymmetric(deck in any_deck(), a in ".{0,4}", b in ".{0,4}") {
+            let a = VoteValue::parse(&deck, &a);
+            let b = VoteValue::parse(&deck, &b);
+            prop_assert_eq!(a.cmp_for_display(&b), b.cmp_for_display(&a).reverse());
+        }
+    }
+}
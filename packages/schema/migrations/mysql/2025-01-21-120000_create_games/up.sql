@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS games (
+    id VARCHAR(36) PRIMARY KEY NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    owner_id VARCHAR(36) NOT NULL,
+    -- Custom decks are stored inline as "custom:<cards>", so this is TEXT from the start
+    voting_system TEXT NOT NULL,
+    state VARCHAR(20) NOT NULL DEFAULT 'Waiting',
+    current_story TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS players;
\ No newline at end of file
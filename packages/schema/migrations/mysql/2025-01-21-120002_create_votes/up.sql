@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS votes (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    game_id VARCHAR(36) NOT NULL,
+    player_id VARCHAR(36) NOT NULL,
+    value VARCHAR(10) NOT NULL,
+    cast_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    FOREIGN KEY (player_id) REFERENCES players(id) ON DELETE CASCADE,
+    UNIQUE(game_id, player_id)
+);
\ No newline at end of file
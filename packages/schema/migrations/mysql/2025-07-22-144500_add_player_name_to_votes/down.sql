@@ -0,0 +1 @@
+ALTER TABLE votes DROP COLUMN player_name;
\ No newline at end of file
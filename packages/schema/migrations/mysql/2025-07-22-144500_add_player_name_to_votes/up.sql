@@ -0,0 +1 @@
+ALTER TABLE votes ADD COLUMN player_name VARCHAR(255) NOT NULL DEFAULT '';
\ No newline at end of file
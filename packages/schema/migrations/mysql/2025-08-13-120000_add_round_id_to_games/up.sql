@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN round_id VARCHAR(36);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS round_votes (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    game_id VARCHAR(36) NOT NULL,
+    round_id VARCHAR(36) NOT NULL,
+    player_id VARCHAR(36) NOT NULL,
+    player_name VARCHAR(255) NOT NULL,
+    value VARCHAR(10) NOT NULL,
+    confidence VARCHAR(10),
+    cast_at DATETIME NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    UNIQUE(game_id, round_id, player_id)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS stories (
+    id VARCHAR(36) PRIMARY KEY NOT NULL,
+    game_id VARCHAR(36) NOT NULL,
+    title VARCHAR(500) NOT NULL,
+    description TEXT,
+    story_order INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
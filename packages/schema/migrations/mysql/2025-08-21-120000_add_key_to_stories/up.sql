@@ -0,0 +1 @@
+ALTER TABLE stories ADD COLUMN story_key TEXT;
@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN webhook_url TEXT;
@@ -0,0 +1,11 @@
+-- No foreign key to games: the log outlives the game it describes
+CREATE TABLE IF NOT EXISTS audit_log (
+    seq BIGINT AUTO_INCREMENT PRIMARY KEY,
+    id VARCHAR(36) NOT NULL UNIQUE,
+    game_id VARCHAR(36) NOT NULL,
+    actor_id VARCHAR(36) NOT NULL,
+    event_type VARCHAR(30) NOT NULL,
+    details TEXT NOT NULL,
+    occurred_at DATETIME NOT NULL,
+    INDEX audit_log_game_id (game_id)
+);
@@ -0,0 +1 @@
+ALTER TABLE players ADD COLUMN team_role VARCHAR(100) NOT NULL DEFAULT 'Other';
@@ -0,0 +1 @@
+DROP INDEX idx_games_name ON games;
@@ -0,0 +1 @@
+CREATE INDEX idx_games_name ON games (name);
@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN allow_vote_change BOOLEAN NOT NULL DEFAULT TRUE;
\ No newline at end of file
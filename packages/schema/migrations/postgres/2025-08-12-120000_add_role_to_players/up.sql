@@ -0,0 +1,2 @@
+ALTER TABLE players ADD COLUMN role VARCHAR(20) NOT NULL DEFAULT 'Voter';
+UPDATE players SET role = 'Observer' WHERE is_observer;
@@ -0,0 +1,3 @@
+-- Revert games.voting_system from TEXT back to VARCHAR(50)
+-- WARNING: This will fail if any stored custom deck is longer than 50 characters
+ALTER TABLE games ALTER COLUMN voting_system TYPE VARCHAR(50);
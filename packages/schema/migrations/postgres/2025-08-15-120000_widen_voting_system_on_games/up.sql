@@ -0,0 +1,2 @@
+-- Custom decks are stored inline as "custom:<cards>", which outgrows VARCHAR(50)
+ALTER TABLE games ALTER COLUMN voting_system TYPE TEXT;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS estimates (
+    id BIGSERIAL PRIMARY KEY,
+    game_id VARCHAR(36) NOT NULL,
+    story TEXT NOT NULL,
+    final_value TEXT NOT NULL,
+    recorded_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+ALTER TABLE games DROP COLUMN auto_archive_after_secs;
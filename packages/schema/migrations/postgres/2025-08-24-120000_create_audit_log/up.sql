@@ -0,0 +1,12 @@
+-- No foreign key to games: the log outlives the game it describes
+CREATE TABLE IF NOT EXISTS audit_log (
+    seq BIGSERIAL PRIMARY KEY,
+    id VARCHAR(36) NOT NULL UNIQUE,
+    game_id VARCHAR(36) NOT NULL,
+    actor_id VARCHAR(36) NOT NULL,
+    event_type VARCHAR(30) NOT NULL,
+    details TEXT NOT NULL,
+    occurred_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_game_id ON audit_log (game_id);
@@ -0,0 +1,3 @@
+ALTER TABLE stories ADD COLUMN acceptance_criteria TEXT;
+ALTER TABLE stories ADD COLUMN final_estimate VARCHAR(100);
+ALTER TABLE stories ADD COLUMN completed_at TIMESTAMP;
@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN allow_vote_change INTEGER NOT NULL DEFAULT 1;
\ No newline at end of file
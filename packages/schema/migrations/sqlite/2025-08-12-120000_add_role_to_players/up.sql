@@ -0,0 +1,2 @@
+ALTER TABLE players ADD COLUMN role TEXT NOT NULL DEFAULT 'Voter';
+UPDATE players SET role = 'Observer' WHERE is_observer = 1;
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS round_votes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id TEXT NOT NULL,
+    round_id TEXT NOT NULL,
+    player_id TEXT NOT NULL,
+    player_name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    confidence TEXT,
+    cast_at TEXT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    UNIQUE(game_id, round_id, player_id)
+);
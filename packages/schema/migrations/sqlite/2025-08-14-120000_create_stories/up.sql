@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS stories (
+    id TEXT PRIMARY KEY NOT NULL,
+    game_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    story_order INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
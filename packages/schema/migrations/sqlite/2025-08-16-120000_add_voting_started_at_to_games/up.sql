@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN voting_started_at TEXT;
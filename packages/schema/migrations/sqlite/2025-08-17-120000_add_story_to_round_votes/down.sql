@@ -0,0 +1 @@
+ALTER TABLE round_votes DROP COLUMN story;
@@ -0,0 +1 @@
+ALTER TABLE round_votes ADD COLUMN story TEXT;
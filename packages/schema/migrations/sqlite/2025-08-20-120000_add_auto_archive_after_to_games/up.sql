@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN auto_archive_after_secs INTEGER;
@@ -0,0 +1 @@
+ALTER TABLE players ADD COLUMN team_role TEXT NOT NULL DEFAULT 'Other';
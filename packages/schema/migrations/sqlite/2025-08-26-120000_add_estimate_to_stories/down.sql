@@ -0,0 +1,3 @@
+ALTER TABLE stories DROP COLUMN completed_at;
+ALTER TABLE stories DROP COLUMN final_estimate;
+ALTER TABLE stories DROP COLUMN acceptance_criteria;
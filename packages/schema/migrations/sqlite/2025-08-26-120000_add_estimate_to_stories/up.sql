@@ -0,0 +1,3 @@
+ALTER TABLE stories ADD COLUMN acceptance_criteria TEXT;
+ALTER TABLE stories ADD COLUMN final_estimate TEXT;
+ALTER TABLE stories ADD COLUMN completed_at TEXT;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS idx_games_name ON games (name);
@@ -174,6 +174,12 @@ pub const POSTGRES_MIGRATIONS: Migrations = Migrations {
     directory: &include_dir::include_dir!("$CARGO_MANIFEST_DIR/migrations/postgres"),
 };
 
+// Embedded migrations for MySQL/MariaDB
+#[cfg(feature = "mysql")]
+pub const MYSQL_MIGRATIONS: Migrations = Migrations {
+    directory: &include_dir::include_dir!("$CARGO_MANIFEST_DIR/migrations/mysql"),
+};
+
 /// Main migration function for the planning poker database
 ///
 /// # Errors
@@ -188,6 +194,13 @@ pub async fn migrate(#[allow(unused)] db: &dyn Database) -> Result<(), MigrateEr
         tracing::debug!("migrate: finished running postgres migrations");
     }
 
+    #[cfg(feature = "mysql")]
+    {
+        tracing::debug!("migrate: running mysql migrations");
+        MYSQL_MIGRATIONS.run(db).await?;
+        tracing::debug!("migrate: finished running mysql migrations");
+    }
+
     #[cfg(feature = "sqlite")]
     {
         tracing::debug!("migrate: running sqlite migrations");
@@ -215,6 +228,13 @@ pub async fn migrate_until(
         tracing::debug!("migrate_until: finished running postgres migrations");
     }
 
+    #[cfg(feature = "mysql")]
+    {
+        tracing::debug!("migrate_until: running mysql migrations");
+        MYSQL_MIGRATIONS.run_until(db, migration_name).await?;
+        tracing::debug!("migrate_until: finished running mysql migrations");
+    }
+
     #[cfg(feature = "sqlite")]
     {
         tracing::debug!("migrate_until: running sqlite migrations");
@@ -227,7 +247,7 @@ pub async fn migrate_until(
 
 #[cfg(test)]
 mod tests {
-    #[cfg(any(feature = "sqlite", feature = "postgres"))]
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
     use super::*;
 
     #[test]
@@ -240,6 +260,10 @@ mod tests {
         {
             assert!(POSTGRES_MIGRATIONS.directory.dirs().count() > 0);
         }
+        #[cfg(feature = "mysql")]
+        {
+            assert!(MYSQL_MIGRATIONS.directory.dirs().count() > 0);
+        }
     }
 
     #[test]
@@ -257,12 +281,18 @@ mod tests {
                 );
 
                 // down.sql is optional but if it exists, it should be valid UTF-8
+                // and actually undo something
                 let down_file_path = format!("{migration_name}/down.sql");
                 if let Some(down_file) = migration_dir.get_file(&down_file_path) {
+                    let down_sql = down_file.contents_utf8();
                     assert!(
-                        down_file.contents_utf8().is_some(),
+                        down_sql.is_some(),
                         "Invalid UTF-8 in down.sql for migration: {migration_name}"
                     );
+                    assert!(
+                        down_sql.is_some_and(|sql| !sql.trim().is_empty()),
+                        "Empty down.sql for migration: {migration_name}"
+                    );
                 }
             }
         }
@@ -279,12 +309,46 @@ mod tests {
                 );
 
                 // down.sql is optional but if it exists, it should be valid UTF-8
+                // and actually undo something
                 let down_file_path = format!("{migration_name}/down.sql");
                 if let Some(down_file) = migration_dir.get_file(&down_file_path) {
+                    let down_sql = down_file.contents_utf8();
                     assert!(
-                        down_file.contents_utf8().is_some(),
+                        down_sql.is_some(),
                         "Invalid UTF-8 in down.sql for migration: {migration_name}"
                     );
+                    assert!(
+                        down_sql.is_some_and(|sql| !sql.trim().is_empty()),
+                        "Empty down.sql for migration: {migration_name}"
+                    );
+                }
+            }
+        }
+        #[cfg(feature = "mysql")]
+        {
+            for migration_dir in MYSQL_MIGRATIONS.directory.dirs() {
+                let migration_name = migration_dir.path().file_name().unwrap().to_string_lossy();
+
+                // Check that up.sql exists
+                let up_file_path = format!("{migration_name}/up.sql");
+                assert!(
+                    migration_dir.get_file(&up_file_path).is_some(),
+                    "Missing up.sql for migration: {migration_name}"
+                );
+
+                // down.sql is optional but if it exists, it should be valid UTF-8
+                // and actually undo something
+                let down_file_path = format!("{migration_name}/down.sql");
+                if let Some(down_file) = migration_dir.get_file(&down_file_path) {
+                    let down_sql = down_file.contents_utf8();
+                    assert!(
+                        down_sql.is_some(),
+                        "Invalid UTF-8 in down.sql for migration: {migration_name}"
+                    );
+                    assert!(
+                        down_sql.is_some_and(|sql| !sql.trim().is_empty()),
+                        "Empty down.sql for migration: {migration_name}"
+                    );
                 }
             }
         }
@@ -0,0 +1,528 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use planning_poker_models::{
+    AuditLogEntry, Game, GameHistory, GameSettings, GameSnapshot, GameState, NewStory,
+    PaginationParams, Player, PlayerRole, PlayerStats, RoundSummary, Session, Story, StoryEstimate,
+    StoryImport, StoryInput, Vote,
+};
+use uuid::Uuid;
+
+use crate::SessionManager;
+
+/// Default time a cached game is served before it is read from the inner manager again
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// A `SessionManager` that caches `get_game` results in memory
+///
+/// Every write that touches a game drops that game from the cache once the
+/// write completes, so reads through this manager never see a stale game.
+/// Writes made directly to the inner manager are only picked up once the
+/// cached entry expires.
+pub struct CachedSessionManager {
+    inner: Arc<dyn SessionManager>,
+    ttl: Duration,
+    games: Arc<Mutex<HashMap<Uuid, (Game, Instant)>>>,
+}
+
+impl CachedSessionManager {
+    #[must_use]
+    pub fn new(inner: Arc<dyn SessionManager>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            games: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The cached games; a panic while they were locked leaves at worst a
+    /// stale entry, so a poisoned lock is used as is
+    fn games(&self) -> MutexGuard<'_, HashMap<Uuid, (Game, Instant)>> {
+        self.games.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn cached(&self, game_id: Uuid) -> Option<Game> {
+        let mut games = self.games();
+        match games.get(&game_id) {
+            Some((game, cached_at)) if cached_at.elapsed() < self.ttl => Some(game.clone()),
+            Some(_) => {
+                games.remove(&game_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn store(&self, game: &Game) {
+        self.games().insert(game.id, (game.clone(), Instant::now()));
+    }
+
+    fn invalidate(&self, game_id: Uuid) {
+        self.games().remove(&game_id);
+    }
+}
+
+#[async_trait]
+impl SessionManager for CachedSessionManager {
+    async fn create_game(
+        &self,
+        name: String,
+        voting_system: String,
+        owner_id: Uuid,
+    ) -> Result<Game> {
+        self.inner.create_game(name, voting_system, owner_id).await
+    }
+
+    async fn create_game_with_settings(
+        &self,
+        name: String,
+        voting_system: String,
+        owner_id: Uuid,
+        settings: GameSettings,
+    ) -> Result<Game> {
+        self.inner
+            .create_game_with_settings(name, voting_system, owner_id, settings)
+            .await
+    }
+
+    async fn get_game(&self, game_id: Uuid) -> Result<Option<Game>> {
+        if let Some(game) = self.cached(game_id) {
+            return Ok(Some(game));
+        }
+
+        let game = self.inner.get_game(game_id).await?;
+        if let Some(game) = &game {
+            self.store(game);
+        }
+        Ok(game)
+    }
+
+    async fn get_game_snapshot(&self, game_id: Uuid) -> Result<Option<GameSnapshot>> {
+        // Players and votes aren't cached, so the whole snapshot comes from the inner manager
+        self.inner.get_game_snapshot(game_id).await
+    }
+
+    async fn export_game_history(&self, game_id: Uuid) -> Result<GameHistory> {
+        self.inner.export_game_history(game_id).await
+    }
+
+    async fn get_player_stats(&self, game_id: Uuid) -> Result<Vec<PlayerStats>> {
+        self.inner.get_player_stats(game_id).await
+    }
+
+    async fn get_round_history(&self, game_id: Uuid) -> Result<Vec<RoundSummary>> {
+        self.inner.get_round_history(game_id).await
+    }
+
+    async fn update_game(&self, game: &Game) -> Result<()> {
+        let result = self.inner.update_game(game).await;
+        self.invalidate(game.id);
+        result
+    }
+
+    async fn transfer_ownership(
+        &self,
+        game_id: Uuid,
+        current_owner_id: Uuid,
+        new_owner_id: Uuid,
+    ) -> Result<()> {
+        let result = self
+            .inner
+            .transfer_ownership(game_id, current_owner_id, new_owner_id)
+            .await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn delete_game(&self, game_id: Uuid) -> Result<()> {
+        let result = self.inner.delete_game(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn list_games(&self, state: Option<GameState>, limit: usize) -> Result<Vec<Game>> {
+        self.inner.list_games(state, limit).await
+    }
+
+    async fn list_games_paginated(
+        &self,
+        state: Option<GameState>,
+        include_completed: bool,
+        params: PaginationParams,
+    ) -> Result<(Vec<Game>, u64)> {
+        self.inner
+            .list_games_paginated(state, include_completed, params)
+            .await
+    }
+
+    async fn search_games(
+        &self,
+        query: &str,
+        state: Option<GameState>,
+        voting_system: Option<&str>,
+        owner_id: Option<Uuid>,
+        params: PaginationParams,
+    ) -> Result<(Vec<Game>, u64)> {
+        self.inner
+            .search_games(query, state, voting_system, owner_id, params)
+            .await
+    }
+
+    async fn archive_game(&self, game_id: Uuid) -> Result<()> {
+        let result = self.inner.archive_game(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn get_archived_games(&self, owner_id: Uuid) -> Result<Vec<Game>> {
+        self.inner.get_archived_games(owner_id).await
+    }
+
+    async fn complete_game(&self, game_id: Uuid) -> Result<()> {
+        let result = self.inner.complete_game(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn add_player_to_game(&self, game_id: Uuid, player: Player) -> Result<()> {
+        let result = self.inner.add_player_to_game(game_id, player).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn remove_player_from_game(&self, game_id: Uuid, player_id: Uuid) -> Result<()> {
+        let result = self.inner.remove_player_from_game(game_id, player_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn kick_player(&self, game_id: Uuid, requester_id: Uuid, target_id: Uuid) -> Result<()> {
+        let result = self
+            .inner
+            .kick_player(game_id, requester_id, target_id)
+            .await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn get_game_players(&self, game_id: Uuid) -> Result<Vec<Player>> {
+        self.inner.get_game_players(game_id).await
+    }
+
+    async fn get_game_players_paginated(
+        &self,
+        game_id: Uuid,
+        params: PaginationParams,
+    ) -> Result<(Vec<Player>, u64)> {
+        self.inner.get_game_players_paginated(game_id, params).await
+    }
+
+    async fn count_game_players(&self, game_ids: &[Uuid]) -> Result<HashMap<Uuid, usize>> {
+        self.inner.count_game_players(game_ids).await
+    }
+
+    async fn get_player(&self, game_id: Uuid, player_id: Uuid) -> Result<Option<Player>> {
+        self.inner.get_player(game_id, player_id).await
+    }
+
+    async fn update_player(&self, game_id: Uuid, player: &Player) -> Result<()> {
+        let result = self.inner.update_player(game_id, player).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn set_player_role(
+        &self,
+        game_id: Uuid,
+        player_id: Uuid,
+        role: PlayerRole,
+    ) -> Result<()> {
+        let result = self.inner.set_player_role(game_id, player_id, role).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn cast_vote(&self, game_id: Uuid, vote: Vote) -> Result<()> {
+        let result = self.inner.cast_vote(game_id, vote).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn get_game_votes(&self, game_id: Uuid) -> Result<Vec<Vote>> {
+        self.inner.get_game_votes(game_id).await
+    }
+
+    async fn get_game_votes_paginated(
+        &self,
+        game_id: Uuid,
+        params: PaginationParams,
+    ) -> Result<(Vec<Vote>, u64)> {
+        self.inner.get_game_votes_paginated(game_id, params).await
+    }
+
+    async fn clear_game_votes(&self, game_id: Uuid) -> Result<()> {
+        let result = self.inner.clear_game_votes(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn discard_vote(&self, game_id: Uuid, player_id: Uuid) -> Result<()> {
+        let result = self.inner.discard_vote(game_id, player_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn get_round_votes(&self, game_id: Uuid, round_id: Uuid) -> Result<Vec<Vote>> {
+        self.inner.get_round_votes(game_id, round_id).await
+    }
+
+    async fn start_voting(&self, game_id: Uuid, story: String) -> Result<()> {
+        let result = self.inner.start_voting(game_id, story).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn start_voting_with_timer(
+        &self,
+        game_id: Uuid,
+        story: String,
+        duration: Duration,
+    ) -> Result<()> {
+        let result = self
+            .inner
+            .start_voting_with_timer(game_id, story, duration)
+            .await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn reveal_votes(&self, game_id: Uuid) -> Result<()> {
+        let result = self.inner.reveal_votes(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn reveal_if_expired(&self, game_id: Uuid) -> Result<bool> {
+        let result = self.inner.reveal_if_expired(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn reset_voting(&self, game_id: Uuid) -> Result<()> {
+        let result = self.inner.reset_voting(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn revote(&self, game_id: Uuid) -> Result<()> {
+        let result = self.inner.revote(game_id).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn record_estimate(
+        &self,
+        game_id: Uuid,
+        story: String,
+        final_value: String,
+    ) -> Result<()> {
+        self.inner
+            .record_estimate(game_id, story, final_value)
+            .await
+    }
+
+    async fn update_current_story(&self, game_id: Uuid, story: String) -> Result<()> {
+        let result = self.inner.update_current_story(game_id, story).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn finalize_estimate(&self, game_id: Uuid, final_value: String) -> Result<()> {
+        let result = self.inner.finalize_estimate(game_id, final_value).await;
+        self.invalidate(game_id);
+        result
+    }
+
+    async fn get_estimate_history(&self, game_id: Uuid) -> Result<Vec<StoryEstimate>> {
+        self.inner.get_estimate_history(game_id).await
+    }
+
+    async fn import_stories(&self, game_id: Uuid, stories: Vec<NewStory>) -> Result<StoryImport> {
+        self.inner.import_stories(game_id, stories).await
+    }
+
+    async fn add_stories_batch(
+        &self,
+        game_id: Uuid,
+        stories: Vec<StoryInput>,
+    ) -> Result<Vec<Story>> {
+        self.inner.add_stories_batch(game_id, stories).await
+    }
+
+    async fn update_story_estimate(
+        &self,
+        game_id: Uuid,
+        story_id: Uuid,
+        estimate: String,
+    ) -> Result<()> {
+        self.inner
+            .update_story_estimate(game_id, story_id, estimate)
+            .await
+    }
+
+    async fn get_completed_stories(&self, game_id: Uuid) -> Result<Vec<Story>> {
+        self.inner.get_completed_stories(game_id).await
+    }
+
+    async fn log_event(&self, entry: AuditLogEntry) -> Result<()> {
+        self.inner.log_event(entry).await
+    }
+
+    async fn get_game_audit_log(&self, game_id: Uuid) -> Result<Vec<AuditLogEntry>> {
+        self.inner.get_game_audit_log(game_id).await
+    }
+
+    async fn create_session(&self, session: Session) -> Result<()> {
+        self.inner.create_session(session).await
+    }
+
+    async fn get_session(&self, connection_id: &str) -> Result<Option<Session>> {
+        self.inner.get_session(connection_id).await
+    }
+
+    async fn update_session_last_seen(&self, connection_id: &str) -> Result<()> {
+        self.inner.update_session_last_seen(connection_id).await
+    }
+
+    async fn delete_session(&self, connection_id: &str) -> Result<()> {
+        self.inner.delete_session(connection_id).await
+    }
+
+    async fn cleanup_expired_sessions(&self) -> Result<()> {
+        self.inner.cleanup_expired_sessions().await
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::test_support::session;
+
+    async fn setup(ttl: Duration) -> (Arc<dyn SessionManager>, CachedSessionManager) {
+        let inner = session().await;
+        let cached = CachedSessionManager::new(inner.clone(), ttl);
+        (inner, cached)
+    }
+
+    async fn create_game(manager: &CachedSessionManager) -> Game {
+        manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_game_is_served_from_cache_until_it_expires() {
+        let (inner, cached) = setup(Duration::from_secs(60)).await;
+        let game = create_game(&cached).await;
+
+        assert_eq!(
+            cached.get_game(game.id).await.unwrap().unwrap().state,
+            GameState::Waiting
+        );
+
+        // Changed behind the cache's back, so the cached copy is still served
+        inner
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            cached.get_game(game.id).await.unwrap().unwrap().state,
+            GameState::Waiting
+        );
+
+        let (inner, uncached) = setup(Duration::ZERO).await;
+        let game = create_game(&uncached).await;
+        uncached.get_game(game.id).await.unwrap();
+        inner
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            uncached.get_game(game.id).await.unwrap().unwrap().state,
+            GameState::Voting
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_game_is_not_cached() {
+        let (_inner, cached) = setup(Duration::from_secs(60)).await;
+
+        assert!(cached.get_game(Uuid::new_v4()).await.unwrap().is_none());
+        assert!(cached.games.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cache_keeps_working_after_a_panic_poisons_its_lock() {
+        let (_inner, cached) = setup(Duration::from_secs(60)).await;
+        let game = create_game(&cached).await;
+
+        let games = cached.games.clone();
+        std::thread::spawn(move || {
+            let _games = games.lock().unwrap();
+            panic!("poison the cache lock");
+        })
+        .join()
+        .unwrap_err();
+        assert!(cached.games.is_poisoned());
+
+        assert_eq!(cached.get_game(game.id).await.unwrap().unwrap().id, game.id);
+        assert!(cached.cached(game.id).is_some());
+        cached
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        assert!(cached.cached(game.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_writes_invalidate_the_cached_game() {
+        let (_inner, cached) = setup(Duration::from_secs(60)).await;
+        let game = create_game(&cached).await;
+
+        cached.get_game(game.id).await.unwrap();
+        cached
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            cached.get_game(game.id).await.unwrap().unwrap().state,
+            GameState::Voting
+        );
+
+        cached.reveal_votes(game.id).await.unwrap();
+        assert_eq!(
+            cached.get_game(game.id).await.unwrap().unwrap().state,
+            GameState::Revealed
+        );
+
+        cached.reset_voting(game.id).await.unwrap();
+        assert_eq!(
+            cached.get_game(game.id).await.unwrap().unwrap().state,
+            GameState::Waiting
+        );
+
+        let mut renamed = cached.get_game(game.id).await.unwrap().unwrap();
+        renamed.name = "Renamed".to_string();
+        cached.update_game(&renamed).await.unwrap();
+        assert_eq!(
+            cached.get_game(game.id).await.unwrap().unwrap().name,
+            "Renamed"
+        );
+    }
+}
@@ -2,16 +2,42 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::Utc;
-use moosicbox_json_utils::ToValueType;
+use chrono::{DateTime, Utc};
+use moosicbox_json_utils::{database::ToValue as _, ToValueType};
 use planning_poker_database::{Database, DatabaseValue};
-use planning_poker_models::{Game, GameState, Player, Session, Vote};
-use switchy::database::query::FilterableQuery;
+use planning_poker_metrics::{Metric, MetricsRegistry};
+use planning_poker_models::{
+    AuditEventType, AuditLogEntry, FinalEstimate, Game, GameHistory, GameSettings, GameSnapshot,
+    GameState, NewStory, PaginationParams, Player, PlayerRole, PlayerStats, RoundHistory,
+    RoundResultEvent, RoundResultPayload, RoundSummary, Session, Story, StoryEstimate, StoryImport,
+    StoryInput, Vote, WebhookPayload,
+};
+use planning_poker_notifications::WebhookNotifier;
+use planning_poker_poker::{
+    compute_consensus, counted_votes, final_estimates, player_stats, round_summary, VotingSystem,
+};
+use switchy::database::{
+    query::{FilterableQuery, SortDirection},
+    DatabaseError,
+};
 use tracing::warn;
 use uuid::Uuid;
 
+pub mod cache;
+#[cfg(all(feature = "sqlite", any(test, feature = "test-support")))]
+pub mod test_support;
+
+pub use cache::{CachedSessionManager, DEFAULT_CACHE_TTL};
+
 #[async_trait]
 pub trait SessionManager: Send + Sync {
     async fn create_game(
@@ -20,21 +46,159 @@ pub trait SessionManager: Send + Sync {
         voting_system: String,
         owner_id: Uuid,
     ) -> Result<Game>;
+    async fn create_game_with_settings(
+        &self,
+        name: String,
+        voting_system: String,
+        owner_id: Uuid,
+        settings: GameSettings,
+    ) -> Result<Game>;
     async fn get_game(&self, game_id: Uuid) -> Result<Option<Game>>;
+    async fn get_game_snapshot(&self, game_id: Uuid) -> Result<Option<GameSnapshot>>;
+    /// Everything recorded about a game, including every revealed round
+    async fn export_game_history(&self, game_id: Uuid) -> Result<GameHistory>;
+    /// How each player took part across the game's revealed rounds
+    async fn get_player_stats(&self, game_id: Uuid) -> Result<Vec<PlayerStats>>;
+    /// A summary of every completed round of a game, latest first
+    async fn get_round_history(&self, game_id: Uuid) -> Result<Vec<RoundSummary>>;
     async fn update_game(&self, game: &Game) -> Result<()>;
+    /// Hand control of a game from its current owner to `new_owner_id`, who
+    /// must be one of its players
+    async fn transfer_ownership(
+        &self,
+        game_id: Uuid,
+        current_owner_id: Uuid,
+        new_owner_id: Uuid,
+    ) -> Result<()>;
     async fn delete_game(&self, game_id: Uuid) -> Result<()>;
+    /// Games in `state`, newest first; without a state every game except the
+    /// archived ones
+    async fn list_games(&self, state: Option<GameState>, limit: usize) -> Result<Vec<Game>>;
+    /// One page of the games in `state`, most recently active first, along
+    /// with how many games are listed in total
+    ///
+    /// Without a state every game except the archived ones is listed, and the
+    /// completed ones only when `include_completed` is set.
+    async fn list_games_paginated(
+        &self,
+        state: Option<GameState>,
+        include_completed: bool,
+        params: PaginationParams,
+    ) -> Result<(Vec<Game>, u64)>;
+    /// One page of the games whose name contains `query`, ignoring case,
+    /// newest first and narrowed by whichever filters are given, along with
+    /// how many games match in total
+    ///
+    /// Archived games are only found when `state` asks for them.
+    async fn search_games(
+        &self,
+        query: &str,
+        state: Option<GameState>,
+        voting_system: Option<&str>,
+        owner_id: Option<Uuid>,
+        params: PaginationParams,
+    ) -> Result<(Vec<Game>, u64)>;
+    /// Retire a revealed game, keeping it for its history only
+    async fn archive_game(&self, game_id: Uuid) -> Result<()>;
+    /// The archived games owned by `owner_id`, most recently archived first
+    async fn get_archived_games(&self, owner_id: Uuid) -> Result<Vec<Game>>;
+    /// Finish a game for good, stamping when it was completed; its history
+    /// stays readable but nobody may join or vote any more
+    async fn complete_game(&self, game_id: Uuid) -> Result<()>;
 
     async fn add_player_to_game(&self, game_id: Uuid, player: Player) -> Result<()>;
+    /// Remove a player, handing ownership to the earliest-joined remaining
+    /// player when the owner leaves
     async fn remove_player_from_game(&self, game_id: Uuid, player_id: Uuid) -> Result<()>;
+    /// Remove `target_id` and their vote from a game on behalf of its owner
+    async fn kick_player(&self, game_id: Uuid, requester_id: Uuid, target_id: Uuid) -> Result<()>;
     async fn get_game_players(&self, game_id: Uuid) -> Result<Vec<Player>>;
+    /// One page of a game's players in the order they joined, along with
+    /// how many players the game has in total
+    async fn get_game_players_paginated(
+        &self,
+        game_id: Uuid,
+        params: PaginationParams,
+    ) -> Result<(Vec<Player>, u64)>;
+    /// How many players each of `game_ids` has, read in one grouped query;
+    /// a game without players counts zero
+    async fn count_game_players(&self, game_ids: &[Uuid]) -> Result<HashMap<Uuid, usize>>;
+    async fn get_player(&self, game_id: Uuid, player_id: Uuid) -> Result<Option<Player>>;
+    async fn update_player(&self, game_id: Uuid, player: &Player) -> Result<()>;
+    async fn set_player_role(&self, game_id: Uuid, player_id: Uuid, role: PlayerRole)
+        -> Result<()>;
 
     async fn cast_vote(&self, game_id: Uuid, vote: Vote) -> Result<()>;
     async fn get_game_votes(&self, game_id: Uuid) -> Result<Vec<Vote>>;
+    /// One page of a game's votes in the order they were cast, along with
+    /// how many votes the game has in total
+    async fn get_game_votes_paginated(
+        &self,
+        game_id: Uuid,
+        params: PaginationParams,
+    ) -> Result<(Vec<Vote>, u64)>;
     async fn clear_game_votes(&self, game_id: Uuid) -> Result<()>;
+    async fn discard_vote(&self, game_id: Uuid, player_id: Uuid) -> Result<()>;
+    async fn get_round_votes(&self, game_id: Uuid, round_id: Uuid) -> Result<Vec<Vote>>;
 
     async fn start_voting(&self, game_id: Uuid, story: String) -> Result<()>;
+    /// Start voting on `story` with a deadline `duration` from now, after
+    /// which [`SessionManager::reveal_if_expired`] reveals the votes
+    async fn start_voting_with_timer(
+        &self,
+        game_id: Uuid,
+        story: String,
+        duration: Duration,
+    ) -> Result<()>;
     async fn reveal_votes(&self, game_id: Uuid) -> Result<()>;
+    /// Reveal the votes of a timed round whose deadline has passed, returning
+    /// whether they were revealed
+    async fn reveal_if_expired(&self, game_id: Uuid) -> Result<bool>;
     async fn reset_voting(&self, game_id: Uuid) -> Result<()>;
+    async fn revote(&self, game_id: Uuid) -> Result<()>;
+    /// Replace the current story's text, or clear it when `story` is blank,
+    /// leaving the round's state and votes as they are; allowed while
+    /// waiting, to set up the next round, or while voting
+    async fn update_current_story(&self, game_id: Uuid, story: String) -> Result<()>;
+
+    /// Record the value a story was estimated at, replacing any earlier
+    /// estimate of the same story
+    async fn record_estimate(
+        &self,
+        game_id: Uuid,
+        story: String,
+        final_value: String,
+    ) -> Result<()>;
+    /// Settle a revealed round on `final_value`, recording it as the current
+    /// story's estimate and returning the game to Waiting
+    async fn finalize_estimate(&self, game_id: Uuid, final_value: String) -> Result<()>;
+    /// Every recorded estimate of a game, oldest first
+    async fn get_estimate_history(&self, game_id: Uuid) -> Result<Vec<StoryEstimate>>;
+    /// Append `stories` to the end of a game's backlog in order, leaving out
+    /// any the backlog already has or that repeat an earlier one
+    async fn import_stories(&self, game_id: Uuid, stories: Vec<NewStory>) -> Result<StoryImport>;
+    /// Append `stories` to the end of a game's backlog in order, returning
+    /// them as stored; nothing is added unless every story is valid
+    async fn add_stories_batch(
+        &self,
+        game_id: Uuid,
+        stories: Vec<StoryInput>,
+    ) -> Result<Vec<Story>>;
+    /// Settle a backlog story on `estimate`, marking it completed now
+    async fn update_story_estimate(
+        &self,
+        game_id: Uuid,
+        story_id: Uuid,
+        estimate: String,
+    ) -> Result<()>;
+    /// The backlog stories of a game that have a final estimate, in the
+    /// order they were completed
+    async fn get_completed_stories(&self, game_id: Uuid) -> Result<Vec<Story>>;
+
+    /// Append `entry` to its game's audit log
+    async fn log_event(&self, entry: AuditLogEntry) -> Result<()>;
+    /// Every audit log entry of a game, oldest first
+    async fn get_game_audit_log(&self, game_id: Uuid) -> Result<Vec<AuditLogEntry>>;
 
     async fn create_session(&self, session: Session) -> Result<()>;
     async fn get_session(&self, connection_id: &str) -> Result<Option<Session>>;
@@ -43,9 +207,58 @@ pub trait SessionManager: Send + Sync {
     async fn cleanup_expired_sessions(&self) -> Result<()>;
 }
 
+/// Default number of players allowed to join a single game
+pub const DEFAULT_MAX_PLAYERS_PER_GAME: usize = 50;
+
+/// Default time a session may stay idle before it is cleaned up
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(3600);
+
+/// A player tried to use a name another player in the same game already has
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameTakenError {
+    pub name: String,
+}
+
+impl fmt::Display for NameTakenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Player name '{}' is already taken in this game",
+            self.name
+        )
+    }
+}
+
+impl std::error::Error for NameTakenError {}
+
+/// Game and normalized name of every player part way through joining
+type JoiningNames = Arc<Mutex<HashSet<(Uuid, String)>>>;
+
 pub struct DatabaseSessionManager {
     #[allow(dead_code)]
     db: std::sync::Arc<Box<dyn Database>>,
+    max_players_per_game: usize,
+    allow_duplicate_player_names: bool,
+    session_ttl: Duration,
+    metrics: Arc<MetricsRegistry>,
+    notifier: Option<Arc<WebhookNotifier>>,
+    joining_names: JoiningNames,
+}
+
+/// Hold on a name while its player is added, so a second join under the same
+/// name fails instead of racing past the uniqueness check
+struct NameReservation {
+    names: JoiningNames,
+    key: (Uuid, String),
+}
+
+impl Drop for NameReservation {
+    fn drop(&mut self) {
+        self.names
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.key);
+    }
 }
 
 impl DatabaseSessionManager {
@@ -53,9 +266,114 @@ impl DatabaseSessionManager {
     pub fn new(db: Box<dyn Database>) -> Self {
         Self {
             db: std::sync::Arc::new(db),
+            max_players_per_game: DEFAULT_MAX_PLAYERS_PER_GAME,
+            allow_duplicate_player_names: false,
+            session_ttl: DEFAULT_SESSION_TTL,
+            metrics: Arc::new(MetricsRegistry::new()),
+            notifier: None,
+            joining_names: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Limit how many players may join a single game
+    #[must_use]
+    pub const fn with_max_players_per_game(mut self, max_players_per_game: usize) -> Self {
+        self.max_players_per_game = max_players_per_game;
+        self
+    }
+
+    /// Allow several players in the same game to share a name
+    ///
+    /// Names are unique per game (case-insensitively) by default so votes
+    /// can't be attributed to the wrong person.
+    #[must_use]
+    pub const fn with_allow_duplicate_player_names(mut self, allow: bool) -> Self {
+        self.allow_duplicate_player_names = allow;
+        self
+    }
+
+    /// Set how long a session may stay idle before `cleanup_expired_sessions` removes it
+    #[must_use]
+    pub const fn with_session_ttl(mut self, session_ttl: Duration) -> Self {
+        self.session_ttl = session_ttl;
+        self
+    }
+
+    /// Record game events in a shared registry instead of a private one
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    #[must_use]
+    pub const fn metrics(&self) -> &Arc<MetricsRegistry> {
+        &self.metrics
+    }
+
+    /// Send game events to webhooks once the change they describe is stored
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: Arc<WebhookNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    async fn notify(&self, payload: WebhookPayload) {
+        if let Some(notifier) = &self.notifier {
+            notifier.notify(&payload).await;
+        }
+    }
+
+    /// Record an event in the game's audit log
+    ///
+    /// A failure is logged rather than returned, since the change being
+    /// audited has already been stored.
+    async fn audit(
+        &self,
+        game_id: Uuid,
+        actor_id: Uuid,
+        event_type: AuditEventType,
+        details: serde_json::Value,
+    ) {
+        let entry = AuditLogEntry::new(game_id, actor_id, event_type, details);
+        if let Err(e) = self.log_event(entry).await {
+            warn!("Failed to record {event_type} in the audit log of game {game_id}: {e}");
         }
     }
 
+    /// Post a round's result to the game's own webhook, if it has one
+    async fn notify_game(
+        &self,
+        game: &Game,
+        event: RoundResultEvent,
+        votes: Vec<Vote>,
+        final_estimate: Option<String>,
+    ) {
+        let (Some(notifier), Some(url)) = (&self.notifier, &game.settings.webhook_url) else {
+            return;
+        };
+
+        let round = RoundHistory {
+            round_id: game.round_id,
+            story: game.current_story.clone(),
+            votes,
+        };
+        let distribution = round_summary(&round, &VotingSystem::from_string(&game.voting_system))
+            .map(|summary| summary.distribution)
+            .unwrap_or_default();
+
+        let payload = RoundResultPayload {
+            event,
+            game_id: game.id,
+            game_name: game.name.clone(),
+            story: round.story,
+            votes: round.votes,
+            distribution,
+            final_estimate,
+        };
+        notifier.notify_game(url, &payload).await;
+    }
+
     /// Initialize the database schema by running migrations
     ///
     /// # Errors
@@ -71,6 +389,291 @@ impl DatabaseSessionManager {
         tracing::info!("Database migrations completed successfully");
         Ok(())
     }
+
+    /// Copy the live votes of a round into its immutable `round_votes` snapshot
+    ///
+    /// Replaces any earlier snapshot of the same round.
+    async fn snapshot_round(
+        &self,
+        game_id: Uuid,
+        round_id: Uuid,
+        story: Option<&str>,
+    ) -> Result<()> {
+        self.db
+            .delete("round_votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("round_id", DatabaseValue::String(round_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        for vote in self.get_game_votes(game_id).await? {
+            self.db
+                .insert("round_votes")
+                .value("game_id", DatabaseValue::String(game_id.to_string()))
+                .value("round_id", DatabaseValue::String(round_id.to_string()))
+                .value(
+                    "player_id",
+                    DatabaseValue::String(vote.player_id.to_string()),
+                )
+                .value("player_name", DatabaseValue::String(vote.player_name))
+                .value("value", DatabaseValue::String(vote.value))
+                .value(
+                    "confidence",
+                    vote.confidence.map_or(DatabaseValue::Null, |confidence| {
+                        DatabaseValue::String(confidence.to_string())
+                    }),
+                )
+                .value("cast_at", DatabaseValue::DateTime(vote.cast_at.naive_utc()))
+                .value(
+                    "story",
+                    story.map_or(DatabaseValue::Null, |story| {
+                        DatabaseValue::String(story.to_string())
+                    }),
+                )
+                .execute(&**self.db)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// How many rows of `table` belong to a game, reading only their ids
+    async fn count_rows(&self, table: &str, game_id: Uuid) -> Result<u64> {
+        let rows = self
+            .db
+            .select(table)
+            .columns(&["id"])
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        Ok(u64::try_from(rows.len())?)
+    }
+
+    /// Fail if the game has been archived or completed, which ends it for good
+    async fn ensure_not_ended(&self, game_id: Uuid) -> Result<()> {
+        match self.get_game(game_id).await?.map(|game| game.state) {
+            Some(GameState::Archived) => Err(anyhow::anyhow!("Game is archived")),
+            Some(GameState::Completed) => Err(anyhow::anyhow!("Game is completed")),
+            _ => Ok(()),
+        }
+    }
+
+    /// Open a round of voting on `story`, to be revealed at `deadline` if set
+    async fn open_voting(
+        &self,
+        game_id: Uuid,
+        story: String,
+        deadline: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.ensure_not_ended(game_id).await?;
+        let owner_id = self.get_game(game_id).await?.map(|game| game.owner_id);
+
+        // The state, story and timing are set by one statement, so a round is
+        // never left half open
+        self.db
+            .update("games")
+            .value(
+                "state",
+                DatabaseValue::String(GameState::Voting.to_string()),
+            )
+            .value("current_story", DatabaseValue::String(story.clone()))
+            .value("voting_started_at", DatabaseValue::Now)
+            .value(
+                "voting_deadline",
+                deadline.map_or(DatabaseValue::Null, |deadline| {
+                    DatabaseValue::DateTime(deadline.naive_utc())
+                }),
+            )
+            .value("updated_at", DatabaseValue::Now)
+            .where_eq("id", DatabaseValue::String(game_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        if let Some(owner_id) = owner_id {
+            self.audit(
+                game_id,
+                owner_id,
+                AuditEventType::VotingStarted,
+                serde_json::json!({ "story": story, "deadline": deadline }),
+            )
+            .await;
+        }
+        self.notify(WebhookPayload::VotingStarted { game_id, story })
+            .await;
+
+        Ok(())
+    }
+
+    /// Every revealed round of a game in the order they were revealed
+    async fn get_game_rounds(&self, game_id: Uuid) -> Result<Vec<RoundHistory>> {
+        let rows = self
+            .db
+            .select("round_votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("id", SortDirection::Asc)
+            .execute(&**self.db)
+            .await?;
+
+        let mut rounds: Vec<RoundHistory> = Vec::new();
+        for row in &rows {
+            let round_id: String = row
+                .to_value("round_id")
+                .map_err(|e| anyhow::anyhow!("Failed to read round_id: {}", e))?;
+            let round_id = Uuid::parse_str(&round_id)?;
+            let vote: Vote = row
+                .to_value_type()
+                .map_err(|e| anyhow::anyhow!("Failed to convert row to Vote: {}", e))?;
+
+            match rounds.iter_mut().find(|round| round.round_id == round_id) {
+                Some(round) => round.votes.push(vote),
+                None => rounds.push(RoundHistory {
+                    round_id,
+                    // Rounds revealed before the column existed have no story
+                    story: row.to_value("story").unwrap_or(None),
+                    votes: vec![vote],
+                }),
+            }
+        }
+
+        Ok(rounds)
+    }
+
+    /// The stories of a game in backlog order
+    async fn get_game_stories(&self, game_id: Uuid) -> Result<Vec<Story>> {
+        let rows = self
+            .db
+            .select("stories")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("story_order", SortDirection::Asc)
+            .execute(&**self.db)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Story: {}", e))
+            })
+            .collect()
+    }
+
+    /// Reject `name` if another player in `players` already goes by it
+    fn ensure_unique_name(&self, players: &[Player], player_id: Uuid, name: &str) -> Result<()> {
+        if self.allow_duplicate_player_names {
+            return Ok(());
+        }
+
+        let normalized = normalize_name(name);
+        if players.iter().any(|existing| {
+            existing.id != player_id && normalize_name(&existing.name) == normalized
+        }) {
+            return Err(NameTakenError {
+                name: name.to_string(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Reserve `name` in `game_id` until the returned reservation is dropped
+    ///
+    /// Returns `None` when duplicate names are allowed. A poisoned lock is
+    /// used as is, since each insert or remove leaves the set consistent.
+    fn reserve_name(&self, game_id: Uuid, name: &str) -> Result<Option<NameReservation>> {
+        if self.allow_duplicate_player_names {
+            return Ok(None);
+        }
+
+        let key = (game_id, normalize_name(name));
+        if !self
+            .joining_names
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key.clone())
+        {
+            return Err(NameTakenError {
+                name: name.to_string(),
+            }
+            .into());
+        }
+
+        Ok(Some(NameReservation {
+            names: self.joining_names.clone(),
+            key,
+        }))
+    }
+}
+
+/// A player name as compared for uniqueness within a game
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// `value` as a quoted SQL string literal, for the raw queries the builder
+/// can't express
+///
+/// Backslashes are refused: MySQL reads them as escapes and the other
+/// databases don't, so no one quoting is safe for all three.
+fn sql_string(value: &str) -> Result<String> {
+    if value.contains('\\') {
+        return Err(anyhow::anyhow!("Backslashes are not allowed here"));
+    }
+    Ok(format!("'{}'", value.replace('\'', "''")))
+}
+
+/// A LIKE pattern, to be used with `ESCAPE '!'`, matching text that contains
+/// `query` as typed; a backslash matches any one character, as
+/// [`sql_string`] can't quote it
+fn like_contains(query: &str) -> String {
+    let mut pattern = String::from("%");
+    for c in query.chars() {
+        match c {
+            '%' | '_' | '!' => {
+                pattern.push('!');
+                pattern.push(c);
+            }
+            '\\' => pattern.push('_'),
+            c => pattern.push(c),
+        }
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Whether `story`, the text a round was started on, names `backlog_story`,
+/// with or without its ticket key
+fn is_backlog_story(backlog_story: &Story, story: &str) -> bool {
+    let normalize = |value: &str| value.trim().to_lowercase();
+    let story = normalize(story);
+    normalize(&backlog_story.to_string()) == story || normalize(&backlog_story.title) == story
+}
+
+/// How the auto-archive delay of a game is stored, in whole seconds
+fn auto_archive_after_value(settings: &GameSettings) -> DatabaseValue {
+    settings
+        .auto_archive_after
+        .map_or(DatabaseValue::Null, |after| {
+            DatabaseValue::Number(i64::try_from(after.as_secs()).unwrap_or(i64::MAX))
+        })
+}
+
+/// Narrow a query on `games` to the ones in `state`, or to every game but the
+/// archived ones, and the completed ones unless `include_completed` is set
+fn where_listed<Q: FilterableQuery>(
+    query: Q,
+    state: Option<GameState>,
+    include_completed: bool,
+) -> Q {
+    let not_in = |query: Q, state: GameState| {
+        query.where_not_eq("state", DatabaseValue::String(state.to_string()))
+    };
+
+    match state {
+        Some(state) => query.where_eq("state", DatabaseValue::String(state.to_string())),
+        None if include_completed => not_in(query, GameState::Archived),
+        None => not_in(not_in(query, GameState::Archived), GameState::Completed),
+    }
 }
 
 #[async_trait]
@@ -80,8 +683,20 @@ impl SessionManager for DatabaseSessionManager {
         name: String,
         voting_system: String,
         owner_id: Uuid,
+    ) -> Result<Game> {
+        self.create_game_with_settings(name, voting_system, owner_id, GameSettings::default())
+            .await
+    }
+
+    async fn create_game_with_settings(
+        &self,
+        name: String,
+        voting_system: String,
+        owner_id: Uuid,
+        settings: GameSettings,
     ) -> Result<Game> {
         let game_id = Uuid::new_v4();
+        let round_id = Uuid::new_v4();
         let now = Utc::now();
 
         self.db
@@ -93,8 +708,29 @@ impl SessionManager for DatabaseSessionManager {
                 "voting_system",
                 DatabaseValue::String(voting_system.clone()),
             )
-            .value("state", DatabaseValue::String("Waiting".to_string()))
+            .value(
+                "state",
+                DatabaseValue::String(GameState::Waiting.to_string()),
+            )
             .value("current_story", DatabaseValue::Null)
+            .value(
+                "allow_vote_change",
+                DatabaseValue::Bool(settings.allow_vote_change),
+            )
+            .value(
+                "auto_archive_after_secs",
+                auto_archive_after_value(&settings),
+            )
+            .value(
+                "webhook_url",
+                settings
+                    .webhook_url
+                    .as_ref()
+                    .map_or(DatabaseValue::Null, |url| {
+                        DatabaseValue::String(url.clone())
+                    }),
+            )
+            .value("round_id", DatabaseValue::String(round_id.to_string()))
             .value("created_at", DatabaseValue::Now)
             .value("updated_at", DatabaseValue::Now)
             .execute(&**self.db)
@@ -109,8 +745,31 @@ impl SessionManager for DatabaseSessionManager {
             current_story: None,
             created_at: now,
             updated_at: now,
+            settings,
+            round_id,
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
         };
 
+        self.metrics.increment(Metric::GamesCreated);
+        self.audit(
+            game_id,
+            owner_id,
+            AuditEventType::GameCreated,
+            serde_json::json!({
+                "name": game.name,
+                "voting_system": game.voting_system,
+            }),
+        )
+        .await;
+        self.notify(WebhookPayload::GameCreated {
+            game_id,
+            name: game.name.clone(),
+            voting_system: game.voting_system.clone(),
+        })
+        .await;
+
         tracing::info!("Created game: {:?}", game);
         Ok(game)
     }
@@ -136,14 +795,118 @@ impl SessionManager for DatabaseSessionManager {
         }
     }
 
-    async fn update_game(&self, game: &Game) -> Result<()> {
-        tracing::info!("Updating game: {:?}", game);
+    async fn get_game_snapshot(&self, game_id: Uuid) -> Result<Option<GameSnapshot>> {
+        tracing::info!("Getting snapshot of game: {}", game_id);
+
+        let Some(game) = self.get_game(game_id).await? else {
+            return Ok(None);
+        };
 
-        let state_str = match game.state {
-            GameState::Waiting => "Waiting",
-            GameState::Voting => "Voting",
-            GameState::Revealed => "Revealed",
+        // Revealed votes are read from the round the game row names, so a
+        // reveal or reset landing between these reads can't pair the game
+        // with another round's votes
+        let players = self.get_game_players(game_id).await?;
+        let votes = if game.state == GameState::Revealed {
+            self.get_round_votes(game_id, game.round_id).await?
+        } else {
+            self.get_game_votes(game_id).await?
         };
+        let stories = self.get_game_stories(game_id).await?;
+
+        Ok(Some(GameSnapshot {
+            game,
+            players,
+            votes,
+            stories,
+        }))
+    }
+
+    async fn export_game_history(&self, game_id: Uuid) -> Result<GameHistory> {
+        tracing::info!("Exporting history of game: {}", game_id);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let participants = self.get_game_players(game_id).await?;
+        let rounds = self.get_game_rounds(game_id).await?;
+        let mut final_estimates =
+            final_estimates(&rounds, &VotingSystem::from_string(&game.voting_system));
+
+        // An estimate the team recorded overrides the one suggested by the votes
+        for recorded in self.get_estimate_history(game_id).await? {
+            match final_estimates
+                .iter_mut()
+                .find(|final_estimate| final_estimate.story == recorded.story)
+            {
+                Some(final_estimate) => final_estimate.estimate = Some(recorded.final_value),
+                None => final_estimates.push(FinalEstimate {
+                    story: recorded.story,
+                    estimate: Some(recorded.final_value),
+                }),
+            }
+        }
+
+        Ok(GameHistory {
+            in_progress: game.state == GameState::Voting,
+            game,
+            participants,
+            rounds,
+            final_estimates,
+        })
+    }
+
+    async fn get_player_stats(&self, game_id: Uuid) -> Result<Vec<PlayerStats>> {
+        tracing::info!("Getting player stats of game: {}", game_id);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let rounds = self.get_game_rounds(game_id).await?;
+
+        Ok(player_stats(
+            &rounds,
+            &VotingSystem::from_string(&game.voting_system),
+        ))
+    }
+
+    async fn get_round_history(&self, game_id: Uuid) -> Result<Vec<RoundSummary>> {
+        tracing::info!("Getting round history of game: {}", game_id);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let deck = VotingSystem::from_string(&game.voting_system);
+        // Rounds whose votes were cleared before they were kept have nothing
+        // to summarize and are left out
+        let mut rounds: Vec<RoundSummary> = self
+            .get_game_rounds(game_id)
+            .await?
+            .iter()
+            .filter_map(|round| round_summary(round, &deck))
+            .collect();
+
+        // An estimate the team recorded overrides the one suggested by the
+        // votes of the story's latest round
+        for recorded in self.get_estimate_history(game_id).await? {
+            if let Some(round) = rounds
+                .iter_mut()
+                .rev()
+                .find(|round| round.story.as_ref() == Some(&recorded.story))
+            {
+                round.final_estimate = Some(recorded.final_value);
+            }
+        }
+
+        rounds.reverse();
+
+        Ok(rounds)
+    }
+
+    async fn update_game(&self, game: &Game) -> Result<()> {
+        tracing::info!("Updating game: {:?}", game);
 
         self.db
             .update("games")
@@ -152,7 +915,7 @@ impl SessionManager for DatabaseSessionManager {
                 "voting_system",
                 DatabaseValue::String(game.voting_system.clone()),
             )
-            .value("state", DatabaseValue::String(state_str.to_string()))
+            .value("state", DatabaseValue::String(game.state.to_string()))
             .value(
                 "current_story",
                 game.current_story
@@ -161,6 +924,45 @@ impl SessionManager for DatabaseSessionManager {
                         DatabaseValue::String(story.clone())
                     }),
             )
+            .value(
+                "allow_vote_change",
+                DatabaseValue::Bool(game.settings.allow_vote_change),
+            )
+            .value(
+                "auto_archive_after_secs",
+                auto_archive_after_value(&game.settings),
+            )
+            .value(
+                "webhook_url",
+                game.settings
+                    .webhook_url
+                    .as_ref()
+                    .map_or(DatabaseValue::Null, |url| {
+                        DatabaseValue::String(url.clone())
+                    }),
+            )
+            .value("round_id", DatabaseValue::String(game.round_id.to_string()))
+            .value(
+                "voting_started_at",
+                game.voting_started_at
+                    .map_or(DatabaseValue::Null, |started_at| {
+                        DatabaseValue::DateTime(started_at.naive_utc())
+                    }),
+            )
+            .value(
+                "voting_deadline",
+                game.voting_deadline
+                    .map_or(DatabaseValue::Null, |deadline| {
+                        DatabaseValue::DateTime(deadline.naive_utc())
+                    }),
+            )
+            .value(
+                "completed_at",
+                game.completed_at
+                    .map_or(DatabaseValue::Null, |completed_at| {
+                        DatabaseValue::DateTime(completed_at.naive_utc())
+                    }),
+            )
             .value("updated_at", DatabaseValue::Now)
             .where_eq("id", DatabaseValue::String(game.id.to_string()))
             .execute(&**self.db)
@@ -169,196 +971,3741 @@ impl SessionManager for DatabaseSessionManager {
         Ok(())
     }
 
-    async fn delete_game(&self, game_id: Uuid) -> Result<()> {
-        // TODO: Implement database deletion
-        tracing::info!("Deleting game: {}", game_id);
-        Ok(())
-    }
+    async fn transfer_ownership(
+        &self,
+        game_id: Uuid,
+        current_owner_id: Uuid,
+        new_owner_id: Uuid,
+    ) -> Result<()> {
+        tracing::info!(
+            "Transferring ownership of game {} from {} to {}",
+            game_id,
+            current_owner_id,
+            new_owner_id
+        );
 
-    async fn add_player_to_game(&self, game_id: Uuid, player: Player) -> Result<()> {
-        tracing::info!("Adding player {} to game {}", player.id, game_id);
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        if game.owner_id != current_owner_id {
+            return Err(anyhow::anyhow!(
+                "Only the game owner can transfer ownership"
+            ));
+        }
+        if self.get_player(game_id, new_owner_id).await?.is_none() {
+            return Err(anyhow::anyhow!("Player not found in this game"));
+        }
 
         self.db
-            .insert("players")
-            .value("id", DatabaseValue::String(player.id.to_string()))
-            .value("game_id", DatabaseValue::String(game_id.to_string()))
-            .value("name", DatabaseValue::String(player.name))
-            .value("is_observer", DatabaseValue::Bool(player.is_observer))
-            .value("joined_at", DatabaseValue::Now)
+            .update("games")
+            .value("owner_id", DatabaseValue::String(new_owner_id.to_string()))
+            .value("updated_at", DatabaseValue::Now)
+            .where_eq("id", DatabaseValue::String(game_id.to_string()))
             .execute(&**self.db)
             .await?;
 
+        self.audit(
+            game_id,
+            current_owner_id,
+            AuditEventType::OwnershipTransferred,
+            serde_json::json!({ "new_owner_id": new_owner_id }),
+        )
+        .await;
+
         Ok(())
     }
 
-    async fn remove_player_from_game(&self, game_id: Uuid, player_id: Uuid) -> Result<()> {
+    async fn delete_game(&self, game_id: Uuid) -> Result<()> {
         // TODO: Implement database deletion
-        tracing::info!("Removing player {} from game {}", player_id, game_id);
+        tracing::info!("Deleting game: {}", game_id);
         Ok(())
     }
 
-    async fn get_game_players(&self, game_id: Uuid) -> Result<Vec<Player>> {
-        tracing::info!("Getting players for game: {}", game_id);
+    async fn list_games(&self, state: Option<GameState>, limit: usize) -> Result<Vec<Game>> {
+        tracing::info!("Listing games with state: {:?}", state);
 
-        let rows = self
-            .db
-            .select("players")
-            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+        let mut query = self.db.select("games");
+
+        query = match state {
+            Some(state) => query.where_eq("state", DatabaseValue::String(state.to_string())),
+            None => query.where_not_eq(
+                "state",
+                DatabaseValue::String(GameState::Archived.to_string()),
+            ),
+        };
+
+        let rows = query
+            .sort("created_at", SortDirection::Desc)
+            .limit(limit)
             .execute(&**self.db)
             .await?;
 
-        let players: Vec<Player> = rows
+        let games: Vec<Game> = rows
             .iter()
             .map(|row| {
                 row.to_value_type()
-                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Player: {}", e))
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Game: {}", e))
             })
             .collect::<Result<Vec<_>>>()?;
-        Ok(players)
+        Ok(games)
     }
 
-    async fn cast_vote(&self, game_id: Uuid, vote: Vote) -> Result<()> {
-        tracing::info!("Casting vote for game {}: {:?}", game_id, vote);
+    async fn list_games_paginated(
+        &self,
+        state: Option<GameState>,
+        include_completed: bool,
+        params: PaginationParams,
+    ) -> Result<(Vec<Game>, u64)> {
+        tracing::info!(
+            "Listing games with state {:?}, completed included: {} ({:?})",
+            state,
+            include_completed,
+            params
+        );
+
+        // Only the ids are read to count, and only the page itself to list,
+        // so a long history of games isn't loaded for each page
+        let ids = where_listed(
+            self.db.select("games").columns(&["id"]),
+            state,
+            include_completed,
+        )
+        .execute(&**self.db)
+        .await?;
+        let rows = where_listed(self.db.select("games"), state, include_completed)
+            .sort("updated_at", SortDirection::Desc)
+            .offset(params.offset())
+            .limit(params.limit())
+            .execute(&**self.db)
+            .await?;
+
+        let games = rows
+            .iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Game: {}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok((games, u64::try_from(ids.len())?))
+    }
+
+    async fn search_games(
+        &self,
+        query: &str,
+        state: Option<GameState>,
+        voting_system: Option<&str>,
+        owner_id: Option<Uuid>,
+        params: PaginationParams,
+    ) -> Result<(Vec<Game>, u64)> {
+        tracing::info!(
+            "Searching games for {:?} with state {:?}, voting system {:?} and owner {:?} ({:?})",
+            query,
+            state,
+            voting_system,
+            owner_id,
+            params
+        );
+
+        // The query builder has no LIKE or COUNT, so the search is raw SQL;
+        // states and UUIDs render safely, and everything the user typed goes
+        // through `sql_string`
+        let mut conditions = vec![match state {
+            Some(state) => format!("state = '{state}'"),
+            None => format!("state <> '{}'", GameState::Archived),
+        }];
+        if let Some(voting_system) = voting_system {
+            conditions.push(format!("voting_system = {}", sql_string(voting_system)?));
+        }
+        if let Some(owner_id) = owner_id {
+            conditions.push(format!("owner_id = '{owner_id}'"));
+        }
+        conditions.push(format!(
+            "LOWER(name) LIKE {} ESCAPE '!'",
+            sql_string(&like_contains(&query.trim().to_lowercase()))?
+        ));
+        let conditions = conditions.join(" AND ");
+
+        let total: i64 = self
+            .db
+            .query_raw(&format!(
+                "SELECT COUNT(*) AS game_count FROM games WHERE {conditions}"
+            ))
+            .await?
+            .first()
+            .map_or(Ok(0), |row| row.to_value("game_count"))?;
+
+        let games = self
+            .db
+            .query_raw(&format!(
+                "SELECT * FROM games WHERE {conditions} ORDER BY created_at DESC \
+                 LIMIT {} OFFSET {}",
+                params.limit(),
+                params.offset()
+            ))
+            .await?
+            .iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Game: {}", e))
+            })
+            .collect::<Result<Vec<Game>>>()?;
+
+        Ok((games, u64::try_from(total)?))
+    }
+
+    async fn archive_game(&self, game_id: Uuid) -> Result<()> {
+        tracing::info!("Archiving game: {}", game_id);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if !game.state.can_transition_to(&GameState::Archived) {
+            return Err(anyhow::anyhow!("Only revealed games can be archived"));
+        }
 
-        // First, delete any existing vote from this player for this game
         self.db
-            .delete("votes")
-            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
-            .where_eq(
-                "player_id",
-                DatabaseValue::String(vote.player_id.to_string()),
+            .update("games")
+            .value(
+                "state",
+                DatabaseValue::String(GameState::Archived.to_string()),
             )
+            .value("updated_at", DatabaseValue::Now)
+            .where_eq("id", DatabaseValue::String(game_id.to_string()))
             .execute(&**self.db)
             .await?;
 
-        // Insert the new vote
+        Ok(())
+    }
+
+    async fn complete_game(&self, game_id: Uuid) -> Result<()> {
+        tracing::info!("Completing game: {}", game_id);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if !game.state.can_transition_to(&GameState::Completed) {
+            return Err(anyhow::anyhow!(
+                "Game is {} already",
+                game.state.to_string().to_lowercase()
+            ));
+        }
+
         self.db
-            .insert("votes")
-            .value("game_id", DatabaseValue::String(game_id.to_string()))
+            .update("games")
             .value(
-                "player_id",
-                DatabaseValue::String(vote.player_id.to_string()),
+                "state",
+                DatabaseValue::String(GameState::Completed.to_string()),
             )
-            .value("player_name", DatabaseValue::String(vote.player_name))
-            .value("value", DatabaseValue::String(vote.value))
-            .value("cast_at", DatabaseValue::Now)
+            .value("voting_deadline", DatabaseValue::Null)
+            .value("completed_at", DatabaseValue::Now)
+            .value("updated_at", DatabaseValue::Now)
+            .where_eq("id", DatabaseValue::String(game_id.to_string()))
             .execute(&**self.db)
             .await?;
 
         Ok(())
     }
 
-    async fn get_game_votes(&self, game_id: Uuid) -> Result<Vec<Vote>> {
-        tracing::info!("Getting votes for game: {}", game_id);
+    async fn get_archived_games(&self, owner_id: Uuid) -> Result<Vec<Game>> {
+        tracing::info!("Getting archived games of owner: {}", owner_id);
 
         let rows = self
             .db
-            .select("votes")
-            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .select("games")
+            .where_eq(
+                "state",
+                DatabaseValue::String(GameState::Archived.to_string()),
+            )
+            .where_eq("owner_id", DatabaseValue::String(owner_id.to_string()))
+            .sort("updated_at", SortDirection::Desc)
             .execute(&**self.db)
             .await?;
 
-        let votes: Vec<Vote> = rows
-            .iter()
+        rows.iter()
             .map(|row| {
                 row.to_value_type()
-                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Vote: {}", e))
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Game: {}", e))
             })
-            .collect::<Result<Vec<_>>>()?;
-        Ok(votes)
+            .collect()
     }
 
-    async fn clear_game_votes(&self, game_id: Uuid) -> Result<()> {
-        tracing::info!("Clearing votes for game: {}", game_id);
+    async fn add_player_to_game(&self, game_id: Uuid, player: Player) -> Result<()> {
+        tracing::info!("Adding player {} to game {}", player.id, game_id);
+
+        self.ensure_not_ended(game_id).await?;
+
+        // Held until the player is inserted, covering the gap after the check below
+        let _reservation = self.reserve_name(game_id, &player.name)?;
+
+        // Checking the seats and the name and inserting the new player happen
+        // in one transaction, so two joins can't both take the last free seat
+        // or the same name
+        let tx = self.db.begin_transaction().await?;
+        let joined = async {
+            let players = tx
+                .select("players")
+                .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+                .execute(&*tx)
+                .await?
+                .iter()
+                .map(|row| {
+                    row.to_value_type()
+                        .map_err(|e| anyhow::anyhow!("Failed to convert row to Player: {}", e))
+                })
+                .collect::<Result<Vec<Player>>>()?;
+            if players.len() >= self.max_players_per_game {
+                return Err(anyhow::anyhow!(
+                    "Game is full ({} players maximum)",
+                    self.max_players_per_game
+                ));
+            }
+            self.ensure_unique_name(&players, player.id, &player.name)?;
+
+            tx.insert("players")
+                .value("id", DatabaseValue::String(player.id.to_string()))
+                .value("game_id", DatabaseValue::String(game_id.to_string()))
+                .value("name", DatabaseValue::String(player.name.clone()))
+                .value("role", DatabaseValue::String(player.role.to_string()))
+                .value("is_observer", DatabaseValue::Bool(player.is_observer()))
+                .value(
+                    "team_role",
+                    DatabaseValue::String(player.team_role.to_string()),
+                )
+                .value("joined_at", DatabaseValue::Now)
+                .execute(&*tx)
+                .await?;
+
+            Ok(())
+        }
+        .await;
+
+        match joined {
+            Ok(()) => tx.commit().await?,
+            Err(e) => {
+                if let Err(rollback) = tx.rollback().await {
+                    warn!(
+                        "Failed to roll back adding player {} to game {game_id}: {rollback}",
+                        player.id
+                    );
+                }
+                return Err(e);
+            }
+        }
+
+        self.metrics.increment(Metric::PlayersJoined);
+        self.audit(
+            game_id,
+            player.id,
+            AuditEventType::PlayerJoined,
+            serde_json::json!({ "name": player.name, "role": player.role }),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    async fn remove_player_from_game(&self, game_id: Uuid, player_id: Uuid) -> Result<()> {
+        tracing::info!("Removing player {} from game {}", player_id, game_id);
 
         self.db
-            .delete("votes")
+            .delete("players")
             .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("id", DatabaseValue::String(player_id.to_string()))
             .execute(&**self.db)
             .await?;
 
-        Ok(())
-    }
+        // Without an owner nobody could run the round, so someone has to take over
+        let Some(game) = self.get_game(game_id).await? else {
+            return Ok(());
+        };
+        if game.owner_id == player_id {
+            let players = self.get_game_players(game_id).await?;
+            if let Some(successor) = players.iter().min_by_key(|player| player.joined_at) {
+                self.transfer_ownership(game_id, player_id, successor.id)
+                    .await?;
+            }
+        }
 
-    async fn create_session(&self, session: Session) -> Result<()> {
-        // TODO: Implement database insertion
-        tracing::info!("Creating session: {:?}", session);
         Ok(())
     }
 
-    async fn get_session(&self, connection_id: &str) -> Result<Option<Session>> {
-        // TODO: Implement database query
-        tracing::info!("Getting session: {}", connection_id);
-        Ok(None)
-    }
+    async fn kick_player(&self, game_id: Uuid, requester_id: Uuid, target_id: Uuid) -> Result<()> {
+        tracing::info!(
+            "Player {} kicking player {} from game {}",
+            requester_id,
+            target_id,
+            game_id
+        );
 
-    async fn update_session_last_seen(&self, connection_id: &str) -> Result<()> {
-        // TODO: Implement database update
-        tracing::info!("Updating session last seen: {}", connection_id);
-        Ok(())
-    }
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
 
-    async fn delete_session(&self, connection_id: &str) -> Result<()> {
-        // TODO: Implement database deletion
-        tracing::info!("Deleting session: {}", connection_id);
-        Ok(())
-    }
+        if game.owner_id != requester_id {
+            return Err(anyhow::anyhow!("Only the game owner can kick players"));
+        }
 
-    async fn cleanup_expired_sessions(&self) -> Result<()> {
-        // TODO: Implement cleanup logic
-        tracing::info!("Cleaning up expired sessions");
-        Ok(())
-    }
+        if target_id == game.owner_id {
+            return Err(anyhow::anyhow!("The game owner can't kick themselves"));
+        }
 
-    async fn start_voting(&self, game_id: Uuid, story: String) -> Result<()> {
-        tracing::info!("Starting voting for game {} with story: {}", game_id, story);
+        if self.get_player(game_id, target_id).await?.is_none() {
+            return Err(anyhow::anyhow!("Player not found in this game"));
+        }
+
+        self.remove_player_from_game(game_id, target_id).await?;
 
         self.db
-            .update("games")
-            .value("state", DatabaseValue::String("Voting".to_string()))
-            .value("current_story", DatabaseValue::String(story))
-            .value("updated_at", DatabaseValue::Now)
-            .where_eq("id", DatabaseValue::String(game_id.to_string()))
+            .delete("votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("player_id", DatabaseValue::String(target_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        // A revealed round shows its snapshot, so the vote has to go from there too
+        self.db
+            .delete("round_votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("round_id", DatabaseValue::String(game.round_id.to_string()))
+            .where_eq("player_id", DatabaseValue::String(target_id.to_string()))
             .execute(&**self.db)
             .await?;
 
+        self.audit(
+            game_id,
+            requester_id,
+            AuditEventType::PlayerKicked,
+            serde_json::json!({ "player_id": target_id }),
+        )
+        .await;
+
         Ok(())
     }
 
-    async fn reveal_votes(&self, game_id: Uuid) -> Result<()> {
-        tracing::info!("Revealing votes for game {}", game_id);
+    async fn get_game_players(&self, game_id: Uuid) -> Result<Vec<Player>> {
+        tracing::info!("Getting players for game: {}", game_id);
 
-        self.db
-            .update("games")
-            .value("state", DatabaseValue::String("Revealed".to_string()))
-            .value("updated_at", DatabaseValue::Now)
-            .where_eq("id", DatabaseValue::String(game_id.to_string()))
+        let rows = self
+            .db
+            .select("players")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("joined_at", SortDirection::Asc)
             .execute(&**self.db)
             .await?;
 
-        Ok(())
+        let players: Vec<Player> = rows
+            .iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Player: {}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(players)
     }
 
-    async fn reset_voting(&self, game_id: Uuid) -> Result<()> {
-        tracing::info!("Resetting voting for game {}", game_id);
+    async fn get_game_players_paginated(
+        &self,
+        game_id: Uuid,
+        params: PaginationParams,
+    ) -> Result<(Vec<Player>, u64)> {
+        tracing::info!("Getting players for game: {} ({:?})", game_id, params);
 
-        // Clear all votes for this game
-        self.db
-            .delete("votes")
+        let total = self.count_rows("players", game_id).await?;
+        let rows = self
+            .db
+            .select("players")
             .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("joined_at", SortDirection::Asc)
+            .offset(params.offset())
+            .limit(params.limit())
             .execute(&**self.db)
             .await?;
 
-        // Reset game state to Waiting
-        self.db
-            .update("games")
-            .value("state", DatabaseValue::String("Waiting".to_string()))
-            .value("current_story", DatabaseValue::Null)
-            .value("updated_at", DatabaseValue::Now)
-            .where_eq("id", DatabaseValue::String(game_id.to_string()))
-            .execute(&**self.db)
+        let players = rows
+            .iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Player: {}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok((players, total))
+    }
+
+    async fn count_game_players(&self, game_ids: &[Uuid]) -> Result<HashMap<Uuid, usize>> {
+        tracing::info!("Counting players for {} games", game_ids.len());
+
+        let mut counts: HashMap<Uuid, usize> = game_ids.iter().map(|id| (*id, 0)).collect();
+        if game_ids.is_empty() {
+            return Ok(counts);
+        }
+
+        // The query builder can't group, and UUIDs render as hex and dashes
+        // only, so they are safe to list in the query itself
+        let ids = game_ids
+            .iter()
+            .map(|id| format!("'{id}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let rows = self
+            .db
+            .query_raw(&format!(
+                "SELECT game_id, COUNT(*) AS player_count FROM players \
+                 WHERE game_id IN ({ids}) GROUP BY game_id"
+            ))
             .await?;
 
+        for row in &rows {
+            let game_id: String = row.to_value("game_id")?;
+            let player_count: i64 = row.to_value("player_count")?;
+            counts.insert(Uuid::parse_str(&game_id)?, usize::try_from(player_count)?);
+        }
+        Ok(counts)
+    }
+
+    async fn get_player(&self, game_id: Uuid, player_id: Uuid) -> Result<Option<Player>> {
+        tracing::info!("Getting player {} in game {}", player_id, game_id);
+
+        let row = self
+            .db
+            .select("players")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("id", DatabaseValue::String(player_id.to_string()))
+            .execute_first(&**self.db)
+            .await?;
+
+        row.map(|row| {
+            (&row)
+                .to_value_type()
+                .map_err(|e| anyhow::anyhow!("Failed to convert row to Player: {}", e))
+        })
+        .transpose()
+    }
+
+    async fn update_player(&self, game_id: Uuid, player: &Player) -> Result<()> {
+        tracing::info!("Updating player {:?} in game {}", player, game_id);
+
+        // The name is checked in the same transaction as the update, so a
+        // rename can't race a join or another rename onto the same name
+        let tx = self.db.begin_transaction().await?;
+        let updated = async {
+            let players = tx
+                .select("players")
+                .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+                .execute(&*tx)
+                .await?
+                .iter()
+                .map(|row| {
+                    row.to_value_type()
+                        .map_err(|e| anyhow::anyhow!("Failed to convert row to Player: {}", e))
+                })
+                .collect::<Result<Vec<Player>>>()?;
+            if !players.iter().any(|existing| existing.id == player.id) {
+                return Err(anyhow::anyhow!("Player not found in this game"));
+            }
+            self.ensure_unique_name(&players, player.id, &player.name)?;
+
+            tx.update("players")
+                .value("name", DatabaseValue::String(player.name.clone()))
+                .value("role", DatabaseValue::String(player.role.to_string()))
+                .value("is_observer", DatabaseValue::Bool(player.is_observer()))
+                .value(
+                    "team_role",
+                    DatabaseValue::String(player.team_role.to_string()),
+                )
+                .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+                .where_eq("id", DatabaseValue::String(player.id.to_string()))
+                .execute(&*tx)
+                .await?;
+
+            Ok(())
+        }
+        .await;
+
+        match updated {
+            Ok(()) => tx.commit().await?,
+            Err(e) => {
+                if let Err(rollback) = tx.rollback().await {
+                    warn!(
+                        "Failed to roll back updating player {} in game {game_id}: {rollback}",
+                        player.id
+                    );
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn set_player_role(
+        &self,
+        game_id: Uuid,
+        player_id: Uuid,
+        role: PlayerRole,
+    ) -> Result<()> {
+        tracing::info!(
+            "Setting role of player {} in game {} to {}",
+            player_id,
+            game_id,
+            role
+        );
+
+        let mut player = self
+            .get_player(game_id, player_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Player not found in this game"))?;
+        player.role = role;
+
+        self.update_player(game_id, &player).await
+    }
+
+    async fn cast_vote(&self, game_id: Uuid, mut vote: Vote) -> Result<()> {
+        tracing::info!("Casting vote for game {}: {:?}", game_id, vote);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.state.has_ended() {
+            return Err(anyhow::anyhow!(
+                "Game is {}",
+                game.state.to_string().to_lowercase()
+            ));
+        }
+        if game.state != GameState::Voting {
+            return Err(anyhow::anyhow!("Not in voting state"));
+        }
+
+        let voter = self
+            .get_player(game_id, vote.player_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Player not found in this game"))?;
+        if !voter.role.can_vote() {
+            return Err(anyhow::anyhow!("Observers can't vote"));
+        }
+
+        // "?" abstains in every deck, custom ones included; anything else
+        // has to be a card, stored in the deck's own spelling
+        vote.value = if vote.value.trim() == "?" {
+            "?".to_string()
+        } else {
+            VotingSystem::from_string(&game.voting_system)
+                .card_for(&vote.value)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("{} is not a card in this game's deck", vote.value)
+                })?
+        };
+
+        // The check and the write share a transaction, and the unique
+        // (game_id, player_id) key turns a racing second vote into an error
+        // rather than a replaced one
+        let tx = self.db.begin_transaction().await?;
+        let cast = async {
+            if game.settings.allow_vote_change {
+                tx.delete("votes")
+                    .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+                    .where_eq(
+                        "player_id",
+                        DatabaseValue::String(vote.player_id.to_string()),
+                    )
+                    .execute(&*tx)
+                    .await?;
+            } else {
+                let existing = tx
+                    .select("votes")
+                    .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+                    .where_eq(
+                        "player_id",
+                        DatabaseValue::String(vote.player_id.to_string()),
+                    )
+                    .execute_first(&*tx)
+                    .await?;
+
+                if existing.is_some() {
+                    return Err(anyhow::anyhow!("You already voted this round"));
+                }
+            }
+
+            tx.insert("votes")
+                .value("game_id", DatabaseValue::String(game_id.to_string()))
+                .value(
+                    "player_id",
+                    DatabaseValue::String(vote.player_id.to_string()),
+                )
+                .value(
+                    "player_name",
+                    DatabaseValue::String(vote.player_name.clone()),
+                )
+                .value("value", DatabaseValue::String(vote.value.clone()))
+                .value(
+                    "confidence",
+                    vote.confidence.map_or(DatabaseValue::Null, |confidence| {
+                        DatabaseValue::String(confidence.to_string())
+                    }),
+                )
+                .value("cast_at", DatabaseValue::Now)
+                .execute(&*tx)
+                .await?;
+
+            Ok(())
+        }
+        .await;
+
+        match cast {
+            Ok(()) => tx.commit().await?,
+            Err(e) => {
+                if let Err(rollback) = tx.rollback().await {
+                    warn!(
+                        "Failed to roll back a vote by {} in game {game_id}: {rollback}",
+                        vote.player_id
+                    );
+                }
+                return Err(e);
+            }
+        }
+
+        self.metrics.increment(Metric::VotesCast);
+        // The value is left out, so the log can't give away votes before the reveal
+        self.audit(
+            game_id,
+            vote.player_id,
+            AuditEventType::VoteCast,
+            serde_json::json!({ "round_id": game.round_id }),
+        )
+        .await;
+
         Ok(())
     }
+
+    async fn get_game_votes(&self, game_id: Uuid) -> Result<Vec<Vote>> {
+        tracing::info!("Getting votes for game: {}", game_id);
+
+        let rows = self
+            .db
+            .select("votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("cast_at", SortDirection::Asc)
+            .execute(&**self.db)
+            .await?;
+
+        let votes: Vec<Vote> = rows
+            .iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Vote: {}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(votes)
+    }
+
+    async fn get_game_votes_paginated(
+        &self,
+        game_id: Uuid,
+        params: PaginationParams,
+    ) -> Result<(Vec<Vote>, u64)> {
+        tracing::info!("Getting votes for game: {} ({:?})", game_id, params);
+
+        let total = self.count_rows("votes", game_id).await?;
+        let rows = self
+            .db
+            .select("votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("cast_at", SortDirection::Asc)
+            .offset(params.offset())
+            .limit(params.limit())
+            .execute(&**self.db)
+            .await?;
+
+        let votes = rows
+            .iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Vote: {}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok((votes, total))
+    }
+
+    async fn clear_game_votes(&self, game_id: Uuid) -> Result<()> {
+        tracing::info!("Clearing votes for game: {}", game_id);
+
+        self.db
+            .delete("votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn discard_vote(&self, game_id: Uuid, player_id: Uuid) -> Result<()> {
+        tracing::info!(
+            "Discarding vote of player {} in game {}",
+            player_id,
+            game_id
+        );
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.state != GameState::Revealed {
+            return Err(anyhow::anyhow!(
+                "Votes can only be discarded after they are revealed"
+            ));
+        }
+
+        self.db
+            .delete("votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("player_id", DatabaseValue::String(player_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        // The revealed results read from the snapshot, so drop the vote there too
+        self.db
+            .delete("round_votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("round_id", DatabaseValue::String(game.round_id.to_string()))
+            .where_eq("player_id", DatabaseValue::String(player_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_round_votes(&self, game_id: Uuid, round_id: Uuid) -> Result<Vec<Vote>> {
+        tracing::info!("Getting votes of round {} in game {}", round_id, game_id);
+
+        let rows = self
+            .db
+            .select("round_votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("round_id", DatabaseValue::String(round_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to Vote: {}", e))
+            })
+            .collect()
+    }
+
+    async fn record_estimate(
+        &self,
+        game_id: Uuid,
+        story: String,
+        final_value: String,
+    ) -> Result<()> {
+        tracing::info!(
+            "Recording estimate {} for story {} in game {}",
+            final_value,
+            story,
+            game_id
+        );
+
+        let existing = self
+            .db
+            .select("estimates")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("story", DatabaseValue::String(story.clone()))
+            .execute_first(&**self.db)
+            .await?;
+
+        // Updating in place keeps the story where it was first estimated in the history
+        if existing.is_some() {
+            self.db
+                .update("estimates")
+                .value("final_value", DatabaseValue::String(final_value))
+                .value("recorded_at", DatabaseValue::Now)
+                .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+                .where_eq("story", DatabaseValue::String(story))
+                .execute(&**self.db)
+                .await?;
+        } else {
+            self.db
+                .insert("estimates")
+                .value("game_id", DatabaseValue::String(game_id.to_string()))
+                .value("story", DatabaseValue::String(story))
+                .value("final_value", DatabaseValue::String(final_value))
+                .value("recorded_at", DatabaseValue::Now)
+                .execute(&**self.db)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn update_current_story(&self, game_id: Uuid, story: String) -> Result<()> {
+        tracing::info!("Updating current story of game {} to: {}", game_id, story);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        if !matches!(game.state, GameState::Waiting | GameState::Voting) {
+            return Err(anyhow::anyhow!(
+                "The story can only be edited while waiting or voting"
+            ));
+        }
+
+        let story = story.trim();
+        self.db
+            .update("games")
+            .value(
+                "current_story",
+                if story.is_empty() {
+                    DatabaseValue::Null
+                } else {
+                    DatabaseValue::String(story.to_string())
+                },
+            )
+            .value("updated_at", DatabaseValue::Now)
+            .where_eq("id", DatabaseValue::String(game_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn finalize_estimate(&self, game_id: Uuid, final_value: String) -> Result<()> {
+        tracing::info!("Finalizing estimate {} for game {}", final_value, game_id);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.state != GameState::Revealed {
+            return Err(anyhow::anyhow!(
+                "A final estimate can only be recorded after votes are revealed"
+            ));
+        }
+
+        // Store the deck's own spelling of the card, so `xl` is recorded as `XL`
+        let deck = VotingSystem::from_string(&game.voting_system);
+        let card = deck
+            .card_for(&final_value)
+            .ok_or_else(|| anyhow::anyhow!("{final_value} is not a card in this game's deck"))?;
+        let story = game
+            .current_story
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("There is no story to record an estimate for"))?;
+
+        // A round on a story from the backlog completes that story too
+        let backlog_story = self
+            .get_game_stories(game_id)
+            .await?
+            .into_iter()
+            .find(|backlog_story| is_backlog_story(backlog_story, &story));
+        if let Some(backlog_story) = backlog_story {
+            self.update_story_estimate(game_id, backlog_story.id, card.clone())
+                .await?;
+        }
+
+        self.record_estimate(game_id, story, card.clone()).await?;
+        let votes = self.get_round_votes(game_id, game.round_id).await?;
+        self.reset_voting(game_id).await?;
+
+        self.notify_game(
+            &game,
+            RoundResultEvent::EstimateFinalized,
+            votes,
+            Some(card),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    async fn get_estimate_history(&self, game_id: Uuid) -> Result<Vec<StoryEstimate>> {
+        tracing::info!("Getting estimate history of game: {}", game_id);
+
+        let rows = self
+            .db
+            .select("estimates")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("id", SortDirection::Asc)
+            .execute(&**self.db)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to StoryEstimate: {}", e))
+            })
+            .collect()
+    }
+
+    async fn import_stories(&self, game_id: Uuid, stories: Vec<NewStory>) -> Result<StoryImport> {
+        tracing::info!("Importing {} stories into game {}", stories.len(), game_id);
+
+        if self.get_game(game_id).await?.is_none() {
+            return Err(anyhow::anyhow!("Game not found"));
+        }
+
+        let backlog = self.get_game_stories(game_id).await?;
+        let mut next_order = backlog
+            .iter()
+            .map(|story| story.order)
+            .max()
+            .map_or(0, |order| order + 1);
+
+        let mut imported: Vec<NewStory> = Vec::new();
+        let mut skipped = 0;
+        for story in stories {
+            let duplicate = backlog
+                .iter()
+                .any(|existing| story.is_same_story(existing.key.as_deref(), &existing.title))
+                || imported
+                    .iter()
+                    .any(|earlier| story.is_same_story(earlier.key.as_deref(), &earlier.title));
+            if duplicate {
+                skipped += 1;
+                continue;
+            }
+
+            self.db
+                .insert("stories")
+                .value("id", DatabaseValue::String(Uuid::new_v4().to_string()))
+                .value("game_id", DatabaseValue::String(game_id.to_string()))
+                .value("title", DatabaseValue::String(story.title.clone()))
+                .value(
+                    "story_key",
+                    story.key.as_ref().map_or(DatabaseValue::Null, |key| {
+                        DatabaseValue::String(key.clone())
+                    }),
+                )
+                .value("story_order", DatabaseValue::Number(next_order))
+                .execute(&**self.db)
+                .await?;
+            next_order += 1;
+            imported.push(story);
+        }
+
+        Ok(StoryImport {
+            added: imported.len(),
+            skipped,
+        })
+    }
+
+    async fn add_stories_batch(
+        &self,
+        game_id: Uuid,
+        stories: Vec<StoryInput>,
+    ) -> Result<Vec<Story>> {
+        tracing::info!(
+            "Adding a batch of {} stories to game {}",
+            stories.len(),
+            game_id
+        );
+
+        if let Err(errors) = StoryInput::validate_batch(&stories) {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            return Err(anyhow::anyhow!("Invalid stories: {}", errors.join("; ")));
+        }
+        if self.get_game(game_id).await?.is_none() {
+            return Err(anyhow::anyhow!("Game not found"));
+        }
+
+        let mut next_order = self
+            .get_game_stories(game_id)
+            .await?
+            .iter()
+            .map(|story| story.order)
+            .max()
+            .map_or(0, |order| order + 1);
+
+        let optional = |value: Option<String>| {
+            value
+                .filter(|value| !value.trim().is_empty())
+                .map_or(DatabaseValue::Null, DatabaseValue::String)
+        };
+        let mut ids = Vec::with_capacity(stories.len());
+        for story in stories {
+            let id = Uuid::new_v4();
+            self.db
+                .insert("stories")
+                .value("id", DatabaseValue::String(id.to_string()))
+                .value("game_id", DatabaseValue::String(game_id.to_string()))
+                .value(
+                    "title",
+                    DatabaseValue::String(story.title.trim().to_string()),
+                )
+                .value("description", optional(story.description))
+                .value("acceptance_criteria", optional(story.acceptance_criteria))
+                .value("story_order", DatabaseValue::Number(next_order))
+                .execute(&**self.db)
+                .await?;
+            next_order += 1;
+            ids.push(id);
+        }
+
+        Ok(self
+            .get_game_stories(game_id)
+            .await?
+            .into_iter()
+            .filter(|story| ids.contains(&story.id))
+            .collect())
+    }
+
+    async fn update_story_estimate(
+        &self,
+        game_id: Uuid,
+        story_id: Uuid,
+        estimate: String,
+    ) -> Result<()> {
+        tracing::info!(
+            "Recording estimate {} for story {} in game {}",
+            estimate,
+            story_id,
+            game_id
+        );
+
+        let stories = self.get_game_stories(game_id).await?;
+        if !stories.iter().any(|story| story.id == story_id) {
+            return Err(anyhow::anyhow!("Story not found in this game"));
+        }
+
+        self.db
+            .update("stories")
+            .value("final_estimate", DatabaseValue::String(estimate))
+            .value("completed_at", DatabaseValue::Now)
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .where_eq("id", DatabaseValue::String(story_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_completed_stories(&self, game_id: Uuid) -> Result<Vec<Story>> {
+        tracing::info!("Getting completed stories of game: {}", game_id);
+
+        let mut completed: Vec<Story> = self
+            .get_game_stories(game_id)
+            .await?
+            .into_iter()
+            .filter(|story| story.final_estimate.is_some())
+            .collect();
+        completed.sort_by_key(|story| story.completed_at);
+
+        Ok(completed)
+    }
+
+    async fn log_event(&self, entry: AuditLogEntry) -> Result<()> {
+        tracing::debug!(
+            "Recording {} by {} in the audit log of game {}",
+            entry.event_type,
+            entry.actor_id,
+            entry.game_id
+        );
+
+        self.db
+            .insert("audit_log")
+            .value("id", DatabaseValue::String(entry.id.to_string()))
+            .value("game_id", DatabaseValue::String(entry.game_id.to_string()))
+            .value(
+                "actor_id",
+                DatabaseValue::String(entry.actor_id.to_string()),
+            )
+            .value(
+                "event_type",
+                DatabaseValue::String(entry.event_type.to_string()),
+            )
+            .value("details", DatabaseValue::String(entry.details.to_string()))
+            .value(
+                "occurred_at",
+                DatabaseValue::DateTime(entry.occurred_at.naive_utc()),
+            )
+            .execute(&**self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_game_audit_log(&self, game_id: Uuid) -> Result<Vec<AuditLogEntry>> {
+        tracing::info!("Getting audit log of game: {}", game_id);
+
+        let rows = self
+            .db
+            .select("audit_log")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .sort("seq", SortDirection::Asc)
+            .execute(&**self.db)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                row.to_value_type()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert row to AuditLogEntry: {}", e))
+            })
+            .collect()
+    }
+
+    async fn create_session(&self, session: Session) -> Result<()> {
+        // TODO: Implement database insertion
+        tracing::info!("Creating session: {:?}", session);
+        Ok(())
+    }
+
+    async fn get_session(&self, connection_id: &str) -> Result<Option<Session>> {
+        // TODO: Implement database query
+        tracing::info!("Getting session: {}", connection_id);
+        Ok(None)
+    }
+
+    async fn update_session_last_seen(&self, connection_id: &str) -> Result<()> {
+        // TODO: Implement database update
+        tracing::info!("Updating session last seen: {}", connection_id);
+        Ok(())
+    }
+
+    async fn delete_session(&self, connection_id: &str) -> Result<()> {
+        // TODO: Implement database deletion
+        tracing::info!("Deleting session: {}", connection_id);
+        Ok(())
+    }
+
+    async fn cleanup_expired_sessions(&self) -> Result<()> {
+        tracing::info!("Cleaning up expired sessions");
+
+        let cutoff = Utc::now() - chrono::Duration::from_std(self.session_ttl)?;
+
+        self.db
+            .delete("sessions")
+            .where_lt("last_seen", DatabaseValue::DateTime(cutoff.naive_utc()))
+            .execute(&**self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn start_voting(&self, game_id: Uuid, story: String) -> Result<()> {
+        tracing::info!("Starting voting for game {} with story: {}", game_id, story);
+
+        self.open_voting(game_id, story, None).await
+    }
+
+    async fn start_voting_with_timer(
+        &self,
+        game_id: Uuid,
+        story: String,
+        duration: Duration,
+    ) -> Result<()> {
+        tracing::info!(
+            "Starting voting for game {} with story: {}, revealing in {:?}",
+            game_id,
+            story,
+            duration
+        );
+
+        let deadline = Utc::now() + chrono::Duration::from_std(duration)?;
+        self.open_voting(game_id, story, Some(deadline)).await
+    }
+
+    async fn reveal_votes(&self, game_id: Uuid) -> Result<()> {
+        tracing::info!("Revealing votes for game {}", game_id);
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        // Flip the state first so votes still in flight are rejected, then snapshot
+        // what made it in; nothing written to `votes` afterwards reaches the results
+        self.db
+            .update("games")
+            .value(
+                "state",
+                DatabaseValue::String(GameState::Revealed.to_string()),
+            )
+            .value("voting_deadline", DatabaseValue::Null)
+            .value("updated_at", DatabaseValue::Now)
+            .where_eq("id", DatabaseValue::String(game_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        self.snapshot_round(game_id, game.round_id, game.current_story.as_deref())
+            .await?;
+
+        self.metrics.increment(Metric::GamesRevealed);
+
+        let votes = self.get_round_votes(game_id, game.round_id).await?;
+        let mut recorded = None;
+        if let Some(story) = &game.current_story {
+            let players = self.get_game_players(game_id).await?;
+            let consensus = compute_consensus(
+                &counted_votes(&votes, &players),
+                &VotingSystem::from_string(&game.voting_system),
+            );
+            if let Some(value) = consensus.suggested.filter(|_| consensus.consensus) {
+                self.record_estimate(game_id, story.clone(), value.clone())
+                    .await?;
+                recorded = Some(value);
+            }
+        }
+
+        self.audit(
+            game_id,
+            game.owner_id,
+            AuditEventType::VotesRevealed,
+            serde_json::json!({
+                "story": game.current_story,
+                "vote_count": votes.len(),
+                "final_estimate": recorded,
+            }),
+        )
+        .await;
+        self.notify(WebhookPayload::VotesRevealed {
+            game_id,
+            story: game.current_story.clone(),
+            votes: votes.clone(),
+        })
+        .await;
+        self.notify_game(&game, RoundResultEvent::VotesRevealed, votes, recorded)
+            .await;
+
+        Ok(())
+    }
+
+    async fn reveal_if_expired(&self, game_id: Uuid) -> Result<bool> {
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        let expired = game.state == GameState::Voting
+            && game
+                .voting_deadline
+                .is_some_and(|deadline| deadline <= Utc::now());
+        if expired {
+            tracing::info!("Voting deadline passed for game {}", game_id);
+            self.reveal_votes(game_id).await?;
+        }
+
+        Ok(expired)
+    }
+
+    async fn reset_voting(&self, game_id: Uuid) -> Result<()> {
+        tracing::info!("Resetting voting for game {}", game_id);
+
+        self.ensure_not_ended(game_id).await?;
+        let game = self.get_game(game_id).await?;
+
+        // Clearing the votes and returning to Waiting happen in one
+        // transaction, so a failure part way can't leave a revealed game
+        // without its votes or a waiting game still holding them
+        let tx = self.db.begin_transaction().await?;
+        let reset = async {
+            tx.delete("votes")
+                .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+                .execute(&*tx)
+                .await?;
+
+            tx.update("games")
+                .value(
+                    "state",
+                    DatabaseValue::String(GameState::Waiting.to_string()),
+                )
+                .value("current_story", DatabaseValue::Null)
+                .value("voting_started_at", DatabaseValue::Null)
+                .value("voting_deadline", DatabaseValue::Null)
+                .value(
+                    "round_id",
+                    DatabaseValue::String(Uuid::new_v4().to_string()),
+                )
+                .value("updated_at", DatabaseValue::Now)
+                .where_eq("id", DatabaseValue::String(game_id.to_string()))
+                .execute(&*tx)
+                .await?;
+
+            Ok::<_, DatabaseError>(())
+        }
+        .await;
+
+        match reset {
+            Ok(()) => tx.commit().await?,
+            Err(e) => {
+                if let Err(rollback) = tx.rollback().await {
+                    warn!("Failed to roll back the reset of game {game_id}: {rollback}");
+                }
+                return Err(e.into());
+            }
+        }
+
+        self.metrics.increment(Metric::GamesReset);
+        if let Some(game) = game {
+            self.audit(
+                game_id,
+                game.owner_id,
+                AuditEventType::VotingReset,
+                serde_json::json!({ "story": game.current_story }),
+            )
+            .await;
+        }
+        self.notify(WebhookPayload::VotingReset { game_id }).await;
+
+        Ok(())
+    }
+
+    async fn revote(&self, game_id: Uuid) -> Result<()> {
+        tracing::info!(
+            "Restarting voting on the current story for game {}",
+            game_id
+        );
+
+        let game = self
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.state != GameState::Revealed {
+            return Err(anyhow::anyhow!(
+                "Revoting is only possible after votes are revealed"
+            ));
+        }
+
+        self.db
+            .delete("votes")
+            .where_eq("game_id", DatabaseValue::String(game_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        // Back to Voting in a new round, leaving current_story untouched
+        self.db
+            .update("games")
+            .value(
+                "state",
+                DatabaseValue::String(GameState::Voting.to_string()),
+            )
+            .value(
+                "round_id",
+                DatabaseValue::String(Uuid::new_v4().to_string()),
+            )
+            .value("voting_started_at", DatabaseValue::Now)
+            .value("voting_deadline", DatabaseValue::Null)
+            .value("updated_at", DatabaseValue::Now)
+            .where_eq("id", DatabaseValue::String(game_id.to_string()))
+            .execute(&**self.db)
+            .await?;
+
+        self.audit(
+            game_id,
+            game.owner_id,
+            AuditEventType::VotingStarted,
+            serde_json::json!({ "story": game.current_story, "revote": true }),
+        )
+        .await;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use planning_poker_models::{Confidence, TeamRole};
+
+    use super::*;
+    use crate::test_support::{database_session_manager, player, vote};
+
+    async fn revealed_game(manager: &DatabaseSessionManager, votes: &[(&Player, &str)]) -> Game {
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        for (player, _) in votes {
+            manager
+                .add_player_to_game(game.id, (*player).clone())
+                .await
+                .unwrap();
+        }
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        for (player, value) in votes {
+            manager
+                .cast_vote(game.id, vote(player, value))
+                .await
+                .unwrap();
+        }
+        manager.reveal_votes(game.id).await.unwrap();
+        game
+    }
+
+    #[tokio::test]
+    async fn test_discard_vote_removes_only_that_vote() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "89")]).await;
+
+        manager.discard_vote(game.id, bob.id).await.unwrap();
+
+        let votes = manager.get_game_votes(game.id).await.unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].player_id, alice.id);
+    }
+
+    #[tokio::test]
+    async fn test_discard_missing_vote_is_noop() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = revealed_game(&manager, &[(&alice, "5")]).await;
+
+        manager.discard_vote(game.id, Uuid::new_v4()).await.unwrap();
+
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_discard_vote_requires_revealed_state() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+
+        assert!(manager.discard_vote(game.id, alice.id).await.is_err());
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_discarded_player_can_only_revote_after_reset() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = revealed_game(&manager, &[(&alice, "89")]).await;
+
+        manager.discard_vote(game.id, alice.id).await.unwrap();
+        assert!(manager.cast_vote(game.id, vote(&alice, "5")).await.is_err());
+
+        manager.reset_voting(game.id).await.unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_revote_clears_votes_and_keeps_story() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = revealed_game(&manager, &[(&alice, "8")]).await;
+
+        manager.revote(game.id).await.unwrap();
+
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(game.state, GameState::Voting);
+        assert_eq!(game.current_story.as_deref(), Some("Story"));
+        assert!(manager.get_game_votes(game.id).await.unwrap().is_empty());
+
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reset_clears_the_story_that_revote_keeps() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = revealed_game(&manager, &[(&alice, "8")]).await;
+
+        manager.reset_voting(game.id).await.unwrap();
+
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(game.state, GameState::Waiting);
+        assert!(game.current_story.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_reset_keeps_the_votes_it_would_have_cleared() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = revealed_game(&manager, &[(&alice, "8")]).await;
+
+        // Make the second statement of the reset, the game update, fail
+        manager
+            .db
+            .exec_raw(
+                "CREATE TRIGGER fail_game_update BEFORE UPDATE ON games
+                 BEGIN SELECT RAISE(ABORT, 'simulated failure'); END",
+            )
+            .await
+            .unwrap();
+        assert!(manager.reset_voting(game.id).await.is_err());
+
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 1);
+        let unchanged = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(unchanged.state, GameState::Revealed);
+
+        manager
+            .db
+            .exec_raw("DROP TRIGGER fail_game_update")
+            .await
+            .unwrap();
+        manager.reset_voting(game.id).await.unwrap();
+        assert!(manager.get_game_votes(game.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revote_requires_revealed_state() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+
+        assert!(manager.revote(game.id).await.is_err());
+    }
+
+    async fn locked_voting_game(manager: &DatabaseSessionManager, alice: &Player) -> Game {
+        let game = manager
+            .create_game_with_settings(
+                "Game".to_string(),
+                "fibonacci".to_string(),
+                Uuid::new_v4(),
+                GameSettings {
+                    allow_vote_change: false,
+                    ..GameSettings::default()
+                },
+            )
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        game
+    }
+
+    #[tokio::test]
+    async fn test_vote_change_allowed_by_default() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+
+        manager.cast_vote(game.id, vote(&alice, "3")).await.unwrap();
+        manager.cast_vote(game.id, vote(&alice, "8")).await.unwrap();
+
+        let votes = manager.get_game_votes(game.id).await.unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].value, "8");
+    }
+
+    #[tokio::test]
+    async fn test_locked_game_rejects_second_vote() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = locked_voting_game(&manager, &alice).await;
+
+        let stored = manager.get_game(game.id).await.unwrap().unwrap();
+        assert!(!stored.settings.allow_vote_change);
+
+        manager.cast_vote(game.id, vote(&alice, "3")).await.unwrap();
+        let err = manager
+            .cast_vote(game.id, vote(&alice, "8"))
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("already voted"));
+        let votes = manager.get_game_votes(game.id).await.unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].value, "3");
+    }
+
+    #[tokio::test]
+    async fn test_locked_game_allows_new_vote_after_revote() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = locked_voting_game(&manager, &alice).await;
+
+        manager.cast_vote(game.id, vote(&alice, "3")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+        manager.revote(game.id).await.unwrap();
+        manager.cast_vote(game.id, vote(&alice, "8")).await.unwrap();
+
+        let votes = manager.get_game_votes(game.id).await.unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].value, "8");
+    }
+
+    #[tokio::test]
+    async fn test_count_game_players_counts_every_game_at_once() {
+        let manager = database_session_manager().await;
+        let mut games = Vec::new();
+        for name in ["Full", "Empty"] {
+            let game = manager
+                .create_game(name.to_string(), "fibonacci".to_string(), Uuid::new_v4())
+                .await
+                .unwrap();
+            games.push(game.id);
+        }
+        for name in ["Alice", "Bob"] {
+            manager
+                .add_player_to_game(games[0], player(name))
+                .await
+                .unwrap();
+        }
+
+        let counts = manager.count_game_players(&games).await.unwrap();
+
+        assert_eq!(counts, HashMap::from([(games[0], 2), (games[1], 0)]));
+        assert!(manager.count_game_players(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_player_rejects_joins_beyond_max_players() {
+        let manager = database_session_manager()
+            .await
+            .with_max_players_per_game(2);
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        manager
+            .add_player_to_game(game.id, player("Alice"))
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, player("Bob"))
+            .await
+            .unwrap();
+
+        assert!(manager
+            .add_player_to_game(game.id, player("Carol"))
+            .await
+            .is_err());
+        assert_eq!(manager.get_game_players(game.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_add_player_rejects_duplicate_names_case_insensitively() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        manager
+            .add_player_to_game(game.id, player("Alice"))
+            .await
+            .unwrap();
+
+        assert!(manager
+            .add_player_to_game(game.id, player(" alice "))
+            .await
+            .is_err());
+        assert_eq!(manager.get_game_players(game.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_name_error_names_the_taken_name() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, player("Alice"))
+            .await
+            .unwrap();
+
+        let error = manager
+            .add_player_to_game(game.id, player("Alice"))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<NameTakenError>(),
+            Some(&NameTakenError {
+                name: "Alice".to_string()
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "Player name 'Alice' is already taken in this game"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simultaneous_joins_with_the_same_name_add_one_player() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        let (first, second) = tokio::join!(
+            manager.add_player_to_game(game.id, player("Alice")),
+            manager.add_player_to_game(game.id, player("ALICE")),
+        );
+
+        assert!(first.is_ok() != second.is_ok());
+        assert_eq!(manager.get_game_players(game.id).await.unwrap().len(), 1);
+
+        // A name being joined under is held until that join settles
+        let held = manager.reserve_name(game.id, "Bob").unwrap();
+        assert!(manager.reserve_name(game.id, " bob ").is_err());
+        drop(held);
+        assert!(manager.reserve_name(game.id, "bob").unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_add_player_allows_duplicate_names_when_enabled() {
+        let manager = database_session_manager()
+            .await
+            .with_allow_duplicate_player_names(true);
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        manager
+            .add_player_to_game(game.id, player("Alice"))
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, player("ALICE"))
+            .await
+            .unwrap();
+
+        assert_eq!(manager.get_game_players(game.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_game_events() {
+        let metrics = Arc::new(MetricsRegistry::new());
+        let manager = database_session_manager()
+            .await
+            .with_metrics(metrics.clone());
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, bob.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "3")).await.unwrap();
+        manager.cast_vote(game.id, vote(&bob, "5")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+        manager.reset_voting(game.id).await.unwrap();
+
+        assert_eq!(metrics.get(Metric::GamesCreated), 1);
+        assert_eq!(metrics.get(Metric::PlayersJoined), 2);
+        assert_eq!(metrics.get(Metric::VotesCast), 2);
+        assert_eq!(metrics.get(Metric::GamesRevealed), 1);
+        assert_eq!(metrics.get(Metric::GamesReset), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_vote_is_not_counted() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+
+        assert!(manager.cast_vote(game.id, vote(&alice, "3")).await.is_err());
+        assert_eq!(manager.metrics().get(Metric::VotesCast), 0);
+    }
+
+    #[tokio::test]
+    async fn test_game_events_are_sent_to_webhooks() {
+        use planning_poker_config::{WebhookConfig, WebhookEvent};
+        use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let notifier = WebhookNotifier::new(vec![WebhookConfig {
+            url: server.uri(),
+            secret: None,
+            events: WebhookEvent::all(),
+        }]);
+        let manager = database_session_manager()
+            .await
+            .with_notifier(Arc::new(notifier));
+        let alice = player("Alice");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "3")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+        manager.reset_voting(game.id).await.unwrap();
+
+        let payloads: Vec<serde_json::Value> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.body_json().unwrap())
+            .collect();
+        let events: Vec<&str> = payloads
+            .iter()
+            .map(|payload| payload["event"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "game_created",
+                "voting_started",
+                "votes_revealed",
+                "voting_reset"
+            ]
+        );
+        assert_eq!(payloads[2]["story"], "Story");
+        assert_eq!(payloads[2]["votes"][0]["value"], "3");
+    }
+
+    #[tokio::test]
+    async fn test_round_results_are_sent_to_the_game_webhook() {
+        use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let manager = database_session_manager()
+            .await
+            .with_notifier(Arc::new(WebhookNotifier::new(Vec::new())));
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = manager
+            .create_game_with_settings(
+                "Sprint 12".to_string(),
+                "fibonacci".to_string(),
+                alice.id,
+                GameSettings {
+                    webhook_url: Some(server.uri()),
+                    ..GameSettings::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            manager
+                .get_game(game.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .settings
+                .webhook_url,
+            Some(server.uri())
+        );
+        for player in [&alice, &bob] {
+            manager
+                .add_player_to_game(game.id, player.clone())
+                .await
+                .unwrap();
+        }
+        manager
+            .start_voting(game.id, "Login page".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "1")).await.unwrap();
+        manager.cast_vote(game.id, vote(&bob, "8")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+        manager
+            .finalize_estimate(game.id, "5".to_string())
+            .await
+            .unwrap();
+
+        let payloads: Vec<RoundResultPayload> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.body_json().unwrap())
+            .collect();
+        assert_eq!(payloads.len(), 2);
+
+        let revealed = &payloads[0];
+        assert_eq!(revealed.event, RoundResultEvent::VotesRevealed);
+        assert_eq!(revealed.game_name, "Sprint 12");
+        assert_eq!(revealed.story.as_deref(), Some("Login page"));
+        assert_eq!(revealed.votes.len(), 2);
+        assert_eq!(
+            revealed.distribution,
+            vec![("1".to_string(), 1), ("8".to_string(), 1)]
+        );
+        assert_eq!(revealed.final_estimate, None);
+
+        let finalized = &payloads[1];
+        assert_eq!(finalized.event, RoundResultEvent::EstimateFinalized);
+        assert_eq!(finalized.final_estimate.as_deref(), Some("5"));
+    }
+
+    #[tokio::test]
+    async fn test_observer_cannot_vote() {
+        let manager = database_session_manager().await;
+        let alice = Player {
+            role: PlayerRole::Observer,
+            ..player("Alice")
+        };
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+
+        let error = manager
+            .cast_vote(game.id, vote(&alice, "3"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Observers can't vote");
+        assert!(manager.get_game_votes(game.id).await.unwrap().is_empty());
+
+        // They still show up as part of the game, just never as a voter
+        let players = manager.get_game_players(game.id).await.unwrap();
+        assert_eq!(players.len(), 1);
+        assert!(players[0].is_observer());
+    }
+
+    #[tokio::test]
+    async fn test_player_outside_the_game_cannot_vote() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = voting_game(&manager, "fibonacci", &alice).await;
+
+        let error = manager
+            .cast_vote(game, vote(&player("Mallory"), "3"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Player not found in this game");
+        assert!(manager.get_game_votes(game).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_team_role_round_trips_through_the_database() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        let team_roles = [
+            TeamRole::TechLead,
+            TeamRole::Qa,
+            TeamRole::Other("Scrum Master".to_string()),
+            TeamRole::default(),
+        ];
+        for (i, team_role) in team_roles.iter().enumerate() {
+            let player = Player {
+                team_role: team_role.clone(),
+                ..player(&format!("Player {i}"))
+            };
+            manager.add_player_to_game(game.id, player).await.unwrap();
+        }
+
+        let mut players = manager.get_game_players(game.id).await.unwrap();
+        players.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(
+            players
+                .iter()
+                .map(|p| p.team_role.clone())
+                .collect::<Vec<_>>(),
+            team_roles
+        );
+
+        let mut first = players.swap_remove(0);
+        first.team_role = TeamRole::Designer;
+        manager.update_player(game.id, &first).await.unwrap();
+        let first = manager
+            .get_player(game.id, first.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.team_role, TeamRole::Designer);
+    }
+
+    async fn voting_game(
+        manager: &DatabaseSessionManager,
+        voting_system: &str,
+        voter: &Player,
+    ) -> Uuid {
+        let game = manager
+            .create_game(
+                "Game".to_string(),
+                voting_system.to_string(),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, voter.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        game.id
+    }
+
+    #[tokio::test]
+    async fn test_votes_must_be_cards_in_the_games_deck() {
+        let manager = database_session_manager().await;
+
+        for (voting_system, valid, invalid) in [("fibonacci", "13", "4"), ("tshirt", "XL", "999")] {
+            let alice = player("Alice");
+            let game_id = voting_game(&manager, voting_system, &alice).await;
+
+            let error = manager
+                .cast_vote(game_id, vote(&alice, invalid))
+                .await
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("{invalid} is not a card in this game's deck")
+            );
+            assert!(manager.get_game_votes(game_id).await.unwrap().is_empty());
+
+            manager
+                .cast_vote(game_id, vote(&alice, valid))
+                .await
+                .unwrap();
+            let votes = manager.get_game_votes(game_id).await.unwrap();
+            assert_eq!(votes.len(), 1);
+            assert_eq!(votes[0].value, valid);
+
+            manager.cast_vote(game_id, vote(&alice, "?")).await.unwrap();
+            let votes = manager.get_game_votes(game_id).await.unwrap();
+            assert_eq!(votes.len(), 1);
+            assert_eq!(votes[0].value, "?");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vote_is_stored_in_the_decks_spelling() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game_id = voting_game(&manager, "tshirt", &alice).await;
+
+        manager
+            .cast_vote(game_id, vote(&alice, " xl "))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            manager.get_game_votes(game_id).await.unwrap()[0].value,
+            "XL"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_question_mark_is_allowed_in_a_custom_deck_without_one() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game_id = voting_game(&manager, "custom:S,M,L", &alice).await;
+
+        manager.cast_vote(game_id, vote(&alice, "?")).await.unwrap();
+
+        assert_eq!(manager.get_game_votes(game_id).await.unwrap()[0].value, "?");
+    }
+
+    #[tokio::test]
+    async fn test_advisor_vote_is_recorded() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = Player {
+            role: PlayerRole::Advisor,
+            ..player("Bob")
+        };
+
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "8")]).await;
+
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 2);
+        let players = manager.get_game_players(game.id).await.unwrap();
+        let bob = players.iter().find(|player| player.id == bob.id).unwrap();
+        assert_eq!(bob.role, PlayerRole::Advisor);
+    }
+
+    #[tokio::test]
+    async fn test_set_player_role() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+
+        manager
+            .set_player_role(game.id, alice.id, PlayerRole::Observer)
+            .await
+            .unwrap();
+
+        let players = manager.get_game_players(game.id).await.unwrap();
+        assert_eq!(players[0].role, PlayerRole::Observer);
+        assert!(players[0].is_observer());
+
+        assert!(manager
+            .set_player_role(game.id, Uuid::new_v4(), PlayerRole::Advisor)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_player_persists_name_and_observer_flag() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+
+        let updated = Player {
+            name: "Alicia".to_string(),
+            role: PlayerRole::Observer,
+            ..alice.clone()
+        };
+        manager.update_player(game.id, &updated).await.unwrap();
+
+        let fetched = manager
+            .get_player(game.id, alice.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.name, "Alicia");
+        assert!(fetched.is_observer());
+
+        let restored = Player {
+            role: PlayerRole::Voter,
+            ..fetched
+        };
+        manager.update_player(game.id, &restored).await.unwrap();
+
+        let fetched = manager
+            .get_player(game.id, alice.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.name, "Alicia");
+        assert!(!fetched.is_observer());
+    }
+
+    #[tokio::test]
+    async fn test_owner_kicks_player_and_their_vote() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "89")]).await;
+
+        manager
+            .kick_player(game.id, game.owner_id, bob.id)
+            .await
+            .unwrap();
+
+        let players = manager.get_game_players(game.id).await.unwrap();
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].id, alice.id);
+
+        let live_votes = manager.get_game_votes(game.id).await.unwrap();
+        let round_votes = manager
+            .get_round_votes(game.id, game.round_id)
+            .await
+            .unwrap();
+        for votes in [live_votes, round_votes] {
+            assert_eq!(votes.len(), 1);
+            assert_eq!(votes[0].player_id, alice.id);
+        }
+
+        assert!(manager
+            .kick_player(game.id, game.owner_id, bob.id)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_only_owner_can_kick_players() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "5")]).await;
+
+        let error = manager
+            .kick_player(game.id, alice.id, bob.id)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Only the game owner can kick players");
+        assert_eq!(manager.get_game_players(game.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_owner_cannot_kick_themselves() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+
+        assert!(manager
+            .kick_player(game.id, alice.id, alice.id)
+            .await
+            .is_err());
+        assert_eq!(manager.get_game_players(game.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_owner_leaving_hands_ownership_to_earliest_player() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+        let carol = player("Carol");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        for player in [&alice, &bob, &carol] {
+            manager
+                .add_player_to_game(game.id, player.clone())
+                .await
+                .unwrap();
+        }
+
+        manager
+            .remove_player_from_game(game.id, alice.id)
+            .await
+            .unwrap();
+
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(game.owner_id, bob.id);
+
+        // Owner-only actions are now Bob's
+        assert!(manager
+            .kick_player(game.id, alice.id, carol.id)
+            .await
+            .is_err());
+        manager
+            .kick_player(game.id, bob.id, carol.id)
+            .await
+            .unwrap();
+
+        // The last player leaving keeps the game's owner as is
+        manager
+            .remove_player_from_game(game.id, bob.id)
+            .await
+            .unwrap();
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(game.owner_id, bob.id);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_ownership_requires_a_player() {
+        let manager = database_session_manager().await;
+        let owner_id = Uuid::new_v4();
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), owner_id)
+            .await
+            .unwrap();
+
+        assert!(manager
+            .transfer_ownership(game.id, owner_id, Uuid::new_v4())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_only_the_current_owner_can_transfer_ownership() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        for player in [&alice, &bob] {
+            manager
+                .add_player_to_game(game.id, player.clone())
+                .await
+                .unwrap();
+        }
+
+        assert!(manager
+            .transfer_ownership(game.id, bob.id, bob.id)
+            .await
+            .is_err());
+        manager
+            .transfer_ownership(game.id, alice.id, bob.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_game(game.id).await.unwrap().unwrap().owner_id,
+            bob.id
+        );
+
+        // Alice gave up control, so she can't take it back
+        assert!(manager
+            .transfer_ownership(game.id, alice.id, alice.id)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_player_is_scoped_to_game() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        let other = manager
+            .create_game("Other".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+
+        assert!(manager
+            .get_player(game.id, alice.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(manager
+            .get_player(other.id, alice.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(manager.update_player(other.id, &alice).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_player_rejects_taken_name() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "5")]).await;
+
+        let renamed = Player {
+            name: "alice".to_string(),
+            ..bob
+        };
+        assert!(manager.update_player(game.id, &renamed).await.is_err());
+
+        // Changing only the case of your own name is fine
+        let recased = Player {
+            name: "ALICE".to_string(),
+            ..alice
+        };
+        manager.update_player(game.id, &recased).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_late_vote_does_not_change_revealed_round() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = revealed_game(&manager, &[(&alice, "3")]).await;
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+
+        // A vote that lost the race with reveal lands straight in the live table
+        manager
+            .db
+            .insert("votes")
+            .value("game_id", DatabaseValue::String(game.id.to_string()))
+            .value("player_id", DatabaseValue::String(bob.id.to_string()))
+            .value("player_name", DatabaseValue::String(bob.name.clone()))
+            .value("value", DatabaseValue::String("13".to_string()))
+            .value("cast_at", DatabaseValue::Now)
+            .execute(&**manager.db)
+            .await
+            .unwrap();
+
+        let revealed = manager
+            .get_round_votes(game.id, game.round_id)
+            .await
+            .unwrap();
+        assert_eq!(revealed.len(), 1);
+        assert_eq!(revealed[0].player_id, alice.id);
+        assert_eq!(revealed[0].value, "3");
+    }
+
+    #[tokio::test]
+    async fn test_revote_starts_a_new_round() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        let game = revealed_game(&manager, &[(&alice, "3")]).await;
+        let first_round = manager.get_game(game.id).await.unwrap().unwrap().round_id;
+
+        manager.revote(game.id).await.unwrap();
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+
+        assert_ne!(game.round_id, first_round);
+        assert!(manager
+            .get_round_votes(game.id, game.round_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            manager
+                .get_round_votes(game.id, first_round)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_voting_start_is_recorded_until_reset() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        let game = revealed_game(&manager, &[(&alice, "3")]).await;
+        let revealed = manager.get_game(game.id).await.unwrap().unwrap();
+        let started_at = revealed.voting_started_at.unwrap();
+        let votes = manager
+            .get_round_votes(game.id, revealed.round_id)
+            .await
+            .unwrap();
+        let timing = revealed.round_timing(&votes).unwrap();
+        assert!(timing.slowest_voter_seconds.unwrap() <= timing.round_seconds);
+
+        manager.revote(game.id).await.unwrap();
+        let revoting = manager.get_game(game.id).await.unwrap().unwrap();
+        assert!(revoting.voting_started_at.unwrap() >= started_at);
+        assert!(revoting.round_timing(&[]).is_none());
+
+        manager.reset_voting(game.id).await.unwrap();
+        let reset = manager.get_game(game.id).await.unwrap().unwrap();
+        assert!(reset.voting_started_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_export_game_history_covers_every_revealed_round() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "8")]).await;
+        manager.revote(game.id).await.unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+        manager.cast_vote(game.id, vote(&bob, "5")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+        manager.reset_voting(game.id).await.unwrap();
+        manager
+            .start_voting(game.id, "Next story".to_string())
+            .await
+            .unwrap();
+        manager
+            .cast_vote(game.id, vote(&alice, "13"))
+            .await
+            .unwrap();
+
+        let history = manager.export_game_history(game.id).await.unwrap();
+
+        assert!(history.in_progress);
+        assert_eq!(history.game.id, game.id);
+        let names: Vec<&str> = history
+            .participants
+            .iter()
+            .map(|player| player.name.as_str())
+            .collect();
+        assert_eq!(names, ["Alice", "Bob"]);
+
+        // The open round's votes are still hidden
+        assert_eq!(history.rounds.len(), 2);
+        let values: Vec<Vec<(&str, &str)>> = history
+            .rounds
+            .iter()
+            .map(|round| {
+                round
+                    .votes
+                    .iter()
+                    .map(|vote| (vote.player_name.as_str(), vote.value.as_str()))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [
+                [("Alice", "3"), ("Bob", "8")],
+                [("Alice", "5"), ("Bob", "5")]
+            ]
+        );
+        assert!(history
+            .rounds
+            .iter()
+            .all(|round| round.story.as_deref() == Some("Story")));
+
+        assert_eq!(
+            history.final_estimates,
+            [planning_poker_models::FinalEstimate {
+                story: "Story".to_string(),
+                estimate: Some("5".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_player_stats_cover_revealed_rounds() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "?")]).await;
+        manager.revote(game.id).await.unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+        manager.cast_vote(game.id, vote(&bob, "☕")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+
+        let stats = manager.get_player_stats(game.id).await.unwrap();
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].player_name, "Alice");
+        assert_eq!(stats[0].rounds_participated, 2);
+        assert_eq!(stats[0].matched_final_estimate, 1);
+        assert_eq!(stats[0].average_vote, Some(4.0));
+        assert_eq!(stats[1].player_name, "Bob");
+        assert_eq!(stats[1].average_vote, None);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_history_lists_stories_in_order() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = revealed_game(&manager, &[(&alice, "5"), (&bob, "5")]).await;
+        manager.reset_voting(game.id).await.unwrap();
+        manager
+            .start_voting(game.id, "Second story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "8")).await.unwrap();
+        manager.cast_vote(game.id, vote(&bob, "13")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+
+        // Without consensus the team picks the value themselves
+        manager
+            .record_estimate(game.id, "Second story".to_string(), "13".to_string())
+            .await
+            .unwrap();
+
+        let history = manager.get_estimate_history(game.id).await.unwrap();
+        let estimates: Vec<(&str, &str)> = history
+            .iter()
+            .map(|estimate| (estimate.story.as_str(), estimate.final_value.as_str()))
+            .collect();
+        assert_eq!(estimates, [("Story", "5"), ("Second story", "13")]);
+        assert!(history[0].recorded_at <= history[1].recorded_at);
+    }
+
+    #[tokio::test]
+    async fn test_timed_round_reveals_itself_once_the_deadline_passes() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+
+        manager
+            .start_voting_with_timer(game.id, "Story".to_string(), Duration::from_secs(1))
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+
+        let timed = manager.get_game(game.id).await.unwrap().unwrap();
+        assert!(timed
+            .voting_deadline
+            .is_some_and(|deadline| deadline > Utc::now()));
+        assert!(!manager.reveal_if_expired(game.id).await.unwrap());
+
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(manager.reveal_if_expired(game.id).await.unwrap());
+
+        let revealed = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(revealed.state, GameState::Revealed);
+        assert_eq!(revealed.voting_deadline, None);
+        assert_eq!(
+            manager
+                .get_round_votes(game.id, revealed.round_id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Already revealed, so the timer has nothing left to do
+        assert!(!manager.reveal_if_expired(game.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_finalize_estimate_records_value_and_closes_round() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "13")]).await;
+
+        assert!(manager
+            .finalize_estimate(game.id, "4".to_string())
+            .await
+            .is_err());
+        manager
+            .finalize_estimate(game.id, "8".to_string())
+            .await
+            .unwrap();
+
+        let finalized = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(finalized.state, GameState::Waiting);
+        assert_eq!(finalized.current_story, None);
+        assert!(manager
+            .finalize_estimate(game.id, "8".to_string())
+            .await
+            .is_err());
+
+        // Estimating the same story again replaces its estimate
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+        manager
+            .finalize_estimate(game.id, "5".to_string())
+            .await
+            .unwrap();
+
+        let history = manager.get_estimate_history(game.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].final_value, "5");
+
+        let exported = manager.export_game_history(game.id).await.unwrap();
+        assert_eq!(
+            exported.final_estimates,
+            [FinalEstimate {
+                story: "Story".to_string(),
+                estimate: Some("5".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_game_history_of_missing_game_fails() {
+        let manager = database_session_manager().await;
+
+        assert!(manager.export_game_history(Uuid::new_v4()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_games_matches_names_and_filters() {
+        let manager = database_session_manager().await;
+        let owner_id = Uuid::new_v4();
+        let create = |name: &str, voting_system: &str, owner_id: Uuid| {
+            manager.create_game(name.to_string(), voting_system.to_string(), owner_id)
+        };
+        let sprint_14 = create("Sprint 14 planning", "fibonacci", owner_id)
+            .await
+            .unwrap();
+        let tshirt = create("sprint 14 sizing", "tshirt", owner_id)
+            .await
+            .unwrap();
+        create("Sprint 15", "fibonacci", Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .start_voting(tshirt.id, "Story".to_string())
+            .await
+            .unwrap();
+        let page = PaginationParams {
+            page: 1,
+            page_size: 10,
+        };
+        let ids = |games: Vec<Game>| {
+            let mut ids: Vec<Uuid> = games.into_iter().map(|game| game.id).collect();
+            ids.sort();
+            ids
+        };
+
+        let (games, total) = manager
+            .search_games("SPRINT 14", None, None, None, page)
+            .await
+            .unwrap();
+        assert_eq!(total, 2);
+        let mut expected = vec![sprint_14.id, tshirt.id];
+        expected.sort();
+        assert_eq!(ids(games), expected);
+
+        let (games, total) = manager
+            .search_games("sprint", Some(GameState::Voting), None, None, page)
+            .await
+            .unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(ids(games), [tshirt.id]);
+
+        let (games, _) = manager
+            .search_games("sprint", None, Some("fibonacci"), Some(owner_id), page)
+            .await
+            .unwrap();
+        assert_eq!(ids(games), [sprint_14.id]);
+
+        let (first_page, total) = manager
+            .search_games(
+                "sprint",
+                None,
+                None,
+                None,
+                PaginationParams {
+                    page: 1,
+                    page_size: 2,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!((first_page.len(), total), (2, 3));
+
+        let (games, total) = manager
+            .search_games("retro", None, None, None, page)
+            .await
+            .unwrap();
+        assert!(games.is_empty());
+        assert_eq!(total, 0);
+
+        // Wildcards and quotes in the query match themselves
+        let discount = create("50% off O'Brien's", "fibonacci", owner_id)
+            .await
+            .unwrap();
+        for query in ["%", "0%", "o'brien", "_"] {
+            let (games, total) = manager
+                .search_games(query, None, None, None, page)
+                .await
+                .unwrap();
+            let expected: &[Uuid] = if query == "_" { &[] } else { &[discount.id] };
+            assert_eq!(ids(games), expected, "{query}");
+            assert_eq!(total, u64::try_from(expected.len()).unwrap(), "{query}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_games_reads_later_pages() {
+        let manager = database_session_manager().await;
+        for name in ["Sprint 1", "Sprint 2", "Sprint 3"] {
+            manager
+                .create_game(name.to_string(), "fibonacci".to_string(), Uuid::new_v4())
+                .await
+                .unwrap();
+        }
+        let page = |page| PaginationParams { page, page_size: 2 };
+
+        let (first, total) = manager
+            .search_games("sprint", None, None, None, page(1))
+            .await
+            .unwrap();
+        let (second, _) = manager
+            .search_games("sprint", None, None, None, page(2))
+            .await
+            .unwrap();
+
+        assert_eq!(total, 3);
+        assert_eq!((first.len(), second.len()), (2, 1));
+        assert!(first.iter().all(|game| game.id != second[0].id));
+    }
+
+    #[tokio::test]
+    async fn test_games_list_pages_and_hides_completed_games_by_default() {
+        let manager = database_session_manager().await;
+        let owner_id = Uuid::new_v4();
+        let mut ids = Vec::new();
+        for name in ["First", "Second", "Third"] {
+            let game = manager
+                .create_game(name.to_string(), "fibonacci".to_string(), owner_id)
+                .await
+                .unwrap();
+            ids.push(game.id);
+        }
+        manager.complete_game(ids[0]).await.unwrap();
+
+        let page = |page| PaginationParams { page, page_size: 1 };
+        let mut listed = Vec::new();
+        for number in 1..=3 {
+            let (games, total) = manager
+                .list_games_paginated(None, false, page(number))
+                .await
+                .unwrap();
+            assert_eq!(total, 2);
+            listed.extend(games.into_iter().map(|game| game.id));
+        }
+        listed.sort();
+        let mut open = vec![ids[1], ids[2]];
+        open.sort();
+        assert_eq!(listed, open);
+
+        let (games, total) = manager
+            .list_games_paginated(None, true, page(1))
+            .await
+            .unwrap();
+        assert_eq!((games.len(), total), (1, 3));
+
+        let (games, total) = manager
+            .list_games_paginated(Some(GameState::Completed), false, page(1))
+            .await
+            .unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(games[0].id, ids[0]);
+    }
+
+    #[tokio::test]
+    async fn test_archived_games_leave_active_listings() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = revealed_game(&manager, &[(&alice, "3")]).await;
+        let waiting = manager
+            .create_game("Other".to_string(), "fibonacci".to_string(), game.owner_id)
+            .await
+            .unwrap();
+
+        assert!(manager.archive_game(waiting.id).await.is_err());
+        manager.archive_game(game.id).await.unwrap();
+
+        let active = manager.list_games(None, 10).await.unwrap();
+        assert!(active.iter().all(|listed| listed.id != game.id));
+        assert!(active.iter().any(|listed| listed.id == waiting.id));
+        assert!(manager
+            .list_games(Some(GameState::Revealed), 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let archived = manager.get_archived_games(game.owner_id).await.unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].id, game.id);
+        assert_eq!(archived[0].state, GameState::Archived);
+        assert!(manager
+            .get_archived_games(Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // An archived game is over for good
+        assert!(manager
+            .start_voting(game.id, "Again".to_string())
+            .await
+            .is_err());
+        assert!(manager.reset_voting(game.id).await.is_err());
+        assert!(manager.archive_game(game.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_completed_game_rejects_joins_and_votes() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = revealed_game(&manager, &[(&alice, "3")]).await;
+
+        manager.complete_game(game.id).await.unwrap();
+
+        let completed = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(completed.state, GameState::Completed);
+        assert!(completed.completed_at.is_some());
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 1);
+
+        let error = manager
+            .add_player_to_game(game.id, player("Bob"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Game is completed");
+        let error = manager
+            .cast_vote(game.id, vote(&alice, "5"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Game is completed");
+        assert!(manager
+            .start_voting(game.id, "Again".to_string())
+            .await
+            .is_err());
+        assert!(manager.complete_game(game.id).await.is_err());
+        assert!(manager.archive_game(game.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_round_history_lists_latest_round_first() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "3")]).await;
+        manager
+            .finalize_estimate(game.id, "5".to_string())
+            .await
+            .unwrap();
+
+        manager
+            .start_voting(game.id, "Second".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "8")).await.unwrap();
+        manager.cast_vote(game.id, vote(&bob, "13")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+
+        // A round reset before its reveal is never kept
+        manager.reset_voting(game.id).await.unwrap();
+        manager
+            .start_voting(game.id, "Skipped".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "1")).await.unwrap();
+        manager.reset_voting(game.id).await.unwrap();
+
+        let history = manager.get_round_history(game.id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].story.as_deref(), Some("Second"));
+        assert_eq!(
+            history[0].distribution,
+            [("8".to_string(), 1), ("13".to_string(), 1)]
+        );
+        assert_eq!(history[1].story.as_deref(), Some("Story"));
+        assert_eq!(history[1].final_estimate.as_deref(), Some("5"));
+        assert_eq!(history[1].distribution, [("3".to_string(), 2)]);
+
+        assert!(manager.get_round_history(Uuid::new_v4()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discard_vote_removes_it_from_the_snapshot() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = revealed_game(&manager, &[(&alice, "3"), (&bob, "5")]).await;
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+
+        manager.discard_vote(game.id, bob.id).await.unwrap();
+
+        let revealed = manager
+            .get_round_votes(game.id, game.round_id)
+            .await
+            .unwrap();
+        assert_eq!(revealed.len(), 1);
+        assert_eq!(revealed[0].player_id, alice.id);
+    }
+
+    #[tokio::test]
+    async fn test_paginated_players_and_votes_follow_join_and_cast_order() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        let players = [player("Alice"), player("Bob"), player("Carol")];
+
+        // Inserted newest first so the order can only come from the timestamps
+        for (minutes, p) in players.iter().enumerate().rev() {
+            let at = (Utc::now() + chrono::Duration::minutes(i64::try_from(minutes).unwrap()))
+                .naive_utc();
+            manager
+                .db
+                .insert("players")
+                .value("id", DatabaseValue::String(p.id.to_string()))
+                .value("game_id", DatabaseValue::String(game.id.to_string()))
+                .value("name", DatabaseValue::String(p.name.clone()))
+                .value("role", DatabaseValue::String(p.role.to_string()))
+                .value("joined_at", DatabaseValue::DateTime(at))
+                .execute(&**manager.db)
+                .await
+                .unwrap();
+            manager
+                .db
+                .insert("votes")
+                .value("game_id", DatabaseValue::String(game.id.to_string()))
+                .value("player_id", DatabaseValue::String(p.id.to_string()))
+                .value("player_name", DatabaseValue::String(p.name.clone()))
+                .value("value", DatabaseValue::String("5".to_string()))
+                .value("cast_at", DatabaseValue::DateTime(at))
+                .execute(&**manager.db)
+                .await
+                .unwrap();
+        }
+
+        let params = PaginationParams {
+            page: 2,
+            page_size: 1,
+        };
+        let (page, total) = manager
+            .get_game_players_paginated(game.id, params)
+            .await
+            .unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, players[1].id);
+
+        let (page, total) = manager
+            .get_game_votes_paginated(game.id, params)
+            .await
+            .unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].player_id, players[1].id);
+
+        let (page, _) = manager
+            .get_game_players_paginated(
+                game.id,
+                PaginationParams {
+                    page: 4,
+                    page_size: 1,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(page.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_current_story_keeps_votes_and_state() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+
+        // Editing while waiting sets up the next round
+        manager
+            .update_current_story(game.id, "Logn page".to_string())
+            .await
+            .unwrap();
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(game.state, GameState::Waiting);
+        assert_eq!(game.current_story.as_deref(), Some("Logn page"));
+
+        manager
+            .start_voting(game.id, "Logn page".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+        manager
+            .update_current_story(game.id, "Login page".to_string())
+            .await
+            .unwrap();
+
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(game.state, GameState::Voting);
+        assert_eq!(game.current_story.as_deref(), Some("Login page"));
+        assert_eq!(manager.get_game_votes(game.id).await.unwrap().len(), 1);
+
+        manager
+            .update_current_story(game.id, "  ".to_string())
+            .await
+            .unwrap();
+        let game = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(game.current_story, None);
+
+        manager.reveal_votes(game.id).await.unwrap();
+        assert!(manager
+            .update_current_story(game.id, "Too late".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_stories_appends_new_stories_in_order() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        let stories = |lines: &[&str]| {
+            lines
+                .iter()
+                .filter_map(|line| NewStory::parse_line(line))
+                .collect::<Vec<_>>()
+        };
+
+        let first = manager
+            .import_stories(game.id, stories(&["KEY-1 | Login", "Search", "search"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            first,
+            StoryImport {
+                added: 2,
+                skipped: 1
+            }
+        );
+
+        let second = manager
+            .import_stories(game.id, stories(&["Search", "Checkout"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            second,
+            StoryImport {
+                added: 1,
+                skipped: 1
+            }
+        );
+
+        let snapshot = manager.get_game_snapshot(game.id).await.unwrap().unwrap();
+        let titles: Vec<_> = snapshot.stories.iter().map(ToString::to_string).collect();
+        assert_eq!(titles, ["KEY-1 Login", "Search", "Checkout"]);
+
+        assert!(manager
+            .import_stories(Uuid::new_v4(), stories(&["Login"]))
+            .await
+            .is_err());
+    }
+
+    fn story_input(title: &str) -> StoryInput {
+        StoryInput {
+            title: title.to_string(),
+            description: None,
+            acceptance_criteria: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_stories_batch_appends_after_the_backlog() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .import_stories(game.id, NewStory::parse_line("Login").into_iter().collect())
+            .await
+            .unwrap();
+
+        let added = manager
+            .add_stories_batch(
+                game.id,
+                vec![
+                    StoryInput {
+                        description: Some("Find games by name".to_string()),
+                        acceptance_criteria: Some("Matches ignore case".to_string()),
+                        ..story_input("Search")
+                    },
+                    story_input("Checkout"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let backlog = manager
+            .get_game_snapshot(game.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .stories;
+        assert_eq!(
+            backlog.iter().map(|story| story.order).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert_eq!(
+            added
+                .iter()
+                .map(|story| story.title.as_str())
+                .collect::<Vec<_>>(),
+            ["Search", "Checkout"]
+        );
+        assert_eq!(added[0].order, 1);
+        assert_eq!(added[0].description.as_deref(), Some("Find games by name"));
+        assert_eq!(
+            added[0].acceptance_criteria.as_deref(),
+            Some("Matches ignore case")
+        );
+        assert_eq!(added[1].description, None);
+
+        assert!(manager
+            .add_stories_batch(game.id, Vec::new())
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(manager
+            .add_stories_batch(Uuid::new_v4(), vec![story_input("Login")])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_add_stories_batch_adds_nothing_when_a_story_is_invalid() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        let result = manager
+            .add_stories_batch(
+                game.id,
+                vec![
+                    story_input("Login"),
+                    story_input(" "),
+                    story_input("Search"),
+                ],
+            )
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("Story 2"));
+        let backlog = manager
+            .get_game_snapshot(game.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .stories;
+        assert!(backlog.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_finalizing_a_backlog_story_records_its_estimate_and_completion() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .import_stories(
+                game.id,
+                ["KEY-1 | Login", "Search"]
+                    .into_iter()
+                    .filter_map(NewStory::parse_line)
+                    .collect(),
+            )
+            .await
+            .unwrap();
+        assert!(manager
+            .get_completed_stories(game.id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        manager
+            .start_voting(game.id, "KEY-1 Login".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+        manager.reveal_votes(game.id).await.unwrap();
+        manager
+            .finalize_estimate(game.id, "5".to_string())
+            .await
+            .unwrap();
+
+        let completed = manager.get_completed_stories(game.id).await.unwrap();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].title, "Login");
+        assert_eq!(completed[0].final_estimate.as_deref(), Some("5"));
+        assert!(completed[0].completed_at.is_some());
+
+        let search = manager
+            .get_game_snapshot(game.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .stories
+            .into_iter()
+            .find(|story| story.title == "Search")
+            .unwrap();
+        assert_eq!(search.final_estimate, None);
+        assert_eq!(search.completed_at, None);
+
+        manager
+            .update_story_estimate(game.id, search.id, "8".to_string())
+            .await
+            .unwrap();
+        let completed = manager.get_completed_stories(game.id).await.unwrap();
+        assert_eq!(completed.len(), 2);
+        assert!(completed
+            .iter()
+            .all(|story| story.final_estimate.is_some() && story.completed_at.is_some()));
+
+        assert!(manager
+            .update_story_estimate(game.id, Uuid::new_v4(), "8".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_game_snapshot_reads_game_players_votes_and_stories() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+
+        assert!(manager
+            .get_game_snapshot(Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_none());
+
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+        for (order, title) in [(1, "Second"), (0, "First")] {
+            manager
+                .db
+                .insert("stories")
+                .value("id", DatabaseValue::String(Uuid::new_v4().to_string()))
+                .value("game_id", DatabaseValue::String(game.id.to_string()))
+                .value("title", DatabaseValue::String(title.to_string()))
+                .value("story_order", DatabaseValue::Number(order))
+                .execute(&**manager.db)
+                .await
+                .unwrap();
+        }
+
+        let snapshot = manager.get_game_snapshot(game.id).await.unwrap().unwrap();
+
+        assert_eq!(snapshot.game.id, game.id);
+        assert_eq!(snapshot.game.state, GameState::Voting);
+        assert_eq!(snapshot.players.len(), 1);
+        assert_eq!(snapshot.votes.len(), 1);
+        let titles: Vec<_> = snapshot.stories.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["First", "Second"]);
+    }
+
+    #[tokio::test]
+    async fn test_game_snapshot_is_isolated_from_later_changes() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+
+        let game = revealed_game(&manager, &[(&alice, "3")]).await;
+        let before = manager.get_game_snapshot(game.id).await.unwrap().unwrap();
+
+        // A late vote and a new round after the snapshot was taken
+        manager
+            .db
+            .insert("votes")
+            .value("game_id", DatabaseValue::String(game.id.to_string()))
+            .value("player_id", DatabaseValue::String(bob.id.to_string()))
+            .value("player_name", DatabaseValue::String(bob.name.clone()))
+            .value("value", DatabaseValue::String("13".to_string()))
+            .value("cast_at", DatabaseValue::Now)
+            .execute(&**manager.db)
+            .await
+            .unwrap();
+        let revealed = manager.get_game_snapshot(game.id).await.unwrap().unwrap();
+        manager.reset_voting(game.id).await.unwrap();
+        let after = manager.get_game_snapshot(game.id).await.unwrap().unwrap();
+
+        assert_eq!(before.game.state, GameState::Revealed);
+        assert_eq!(before.votes.len(), 1);
+        assert_eq!(before.votes[0].value, "3");
+        // The revealed round still shows only the votes it was revealed with
+        assert_eq!(revealed.votes.len(), 1);
+        assert_eq!(after.game.state, GameState::Waiting);
+        assert_ne!(after.game.round_id, before.game.round_id);
+        assert!(after.votes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_custom_deck_is_persisted_with_the_game() {
+        let manager = database_session_manager().await;
+
+        let game = manager
+            .create_game(
+                "Game".to_string(),
+                "custom:XS,S,M".to_string(),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+
+        let stored = manager.get_game(game.id).await.unwrap().unwrap();
+        assert_eq!(stored.voting_system, "custom:XS,S,M");
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_records_each_action() {
+        let manager = database_session_manager().await;
+        let owner = player("Owner");
+        let (alice, bob) = (player("Alice"), player("Bob"));
+        let game = revealed_game(&manager, &[(&owner, "3"), (&alice, "5")]).await;
+        manager
+            .add_player_to_game(game.id, bob.clone())
+            .await
+            .unwrap();
+        manager.reset_voting(game.id).await.unwrap();
+        manager
+            .transfer_ownership(game.id, game.owner_id, owner.id)
+            .await
+            .unwrap();
+        manager
+            .kick_player(game.id, owner.id, bob.id)
+            .await
+            .unwrap();
+
+        let log = manager.get_game_audit_log(game.id).await.unwrap();
+        let events: Vec<(AuditEventType, Uuid)> = log
+            .iter()
+            .map(|entry| (entry.event_type, entry.actor_id))
+            .collect();
+
+        assert_eq!(
+            events,
+            [
+                (AuditEventType::GameCreated, game.owner_id),
+                (AuditEventType::PlayerJoined, owner.id),
+                (AuditEventType::PlayerJoined, alice.id),
+                (AuditEventType::VotingStarted, game.owner_id),
+                (AuditEventType::VoteCast, owner.id),
+                (AuditEventType::VoteCast, alice.id),
+                (AuditEventType::VotesRevealed, game.owner_id),
+                (AuditEventType::PlayerJoined, bob.id),
+                (AuditEventType::VotingReset, game.owner_id),
+                (AuditEventType::OwnershipTransferred, game.owner_id),
+                (AuditEventType::PlayerKicked, owner.id),
+            ]
+        );
+        assert!(log.iter().all(|entry| entry.game_id == game.id));
+        assert_eq!(log[3].details["story"], "Story");
+        assert!(log[4].details.get("value").is_none());
+        assert_eq!(log[10].details["player_id"], bob.id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_is_kept_per_game() {
+        let manager = database_session_manager().await;
+        let first = manager
+            .create_game("First".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        let second = manager
+            .create_game(
+                "Second".to_string(),
+                "fibonacci".to_string(),
+                Uuid::new_v4(),
+            )
+            .await
+            .unwrap();
+
+        let log = manager.get_game_audit_log(second.id).await.unwrap();
+
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].details["name"], "Second");
+        assert!(manager
+            .get_game_audit_log(first.id)
+            .await
+            .unwrap()
+            .iter()
+            .all(|entry| entry.game_id == first.id));
+    }
+
+    #[tokio::test]
+    async fn test_vote_confidence_round_trips() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let bob = player("Bob");
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        for player in [&alice, &bob] {
+            manager
+                .add_player_to_game(game.id, player.clone())
+                .await
+                .unwrap();
+        }
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+
+        let sure = Vote {
+            confidence: Some(Confidence::High),
+            ..vote(&alice, "5")
+        };
+        manager.cast_vote(game.id, sure).await.unwrap();
+        manager.cast_vote(game.id, vote(&bob, "8")).await.unwrap();
+
+        let votes = manager.get_game_votes(game.id).await.unwrap();
+        let confidence = |player: &Player| {
+            votes
+                .iter()
+                .find(|vote| vote.player_id == player.id)
+                .unwrap()
+                .confidence
+        };
+        assert_eq!(confidence(&alice), Some(Confidence::High));
+        assert_eq!(confidence(&bob), None);
+    }
+
+    #[tokio::test]
+    async fn test_votes_from_before_confidence_existed_read_as_none() {
+        let manager = database_session_manager().await;
+
+        // The votes table as it was before the confidence column was added
+        manager
+            .db
+            .exec_raw(
+                "CREATE TABLE legacy_votes (
+                    player_id TEXT NOT NULL,
+                    player_name TEXT NOT NULL,
+                    value TEXT NOT NULL,
+                    cast_at TEXT NOT NULL
+                )",
+            )
+            .await
+            .unwrap();
+        manager
+            .db
+            .insert("legacy_votes")
+            .value(
+                "player_id",
+                DatabaseValue::String(Uuid::new_v4().to_string()),
+            )
+            .value("player_name", DatabaseValue::String("Alice".to_string()))
+            .value("value", DatabaseValue::String("5".to_string()))
+            .value("cast_at", DatabaseValue::Now)
+            .execute(&**manager.db)
+            .await
+            .unwrap();
+
+        let rows = manager
+            .db
+            .select("legacy_votes")
+            .execute(&**manager.db)
+            .await
+            .unwrap();
+        let vote: Vote = (&rows[0]).to_value_type().unwrap();
+
+        assert_eq!(vote.value, "5");
+        assert_eq!(vote.confidence, None);
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_confidence_is_an_error() {
+        let manager = database_session_manager().await;
+        let alice = player("Alice");
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), alice.id)
+            .await
+            .unwrap();
+        manager
+            .add_player_to_game(game.id, alice.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager.cast_vote(game.id, vote(&alice, "5")).await.unwrap();
+
+        manager
+            .db
+            .exec_raw("UPDATE votes SET confidence = 'Certain'")
+            .await
+            .unwrap();
+
+        assert!(manager.get_game_votes(game.id).await.is_err());
+    }
 }
@@ -0,0 +1,60 @@
+//! Session managers for tests to run against
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use planning_poker_database::test_support::in_memory;
+use planning_poker_models::{Player, PlayerRole, TeamRole, Vote};
+use uuid::Uuid;
+
+use crate::{DatabaseSessionManager, SessionManager};
+
+/// A `DatabaseSessionManager` over a fresh in-memory database with every
+/// migration applied
+///
+/// Every call gets a database of its own.
+///
+/// # Panics
+///
+/// * If the database can't be opened or migrated
+pub async fn database_session_manager() -> DatabaseSessionManager {
+    let db = in_memory().await;
+    planning_poker_schema::SQLITE_MIGRATIONS
+        .run(&*db)
+        .await
+        .expect("Failed to migrate the in-memory database");
+    DatabaseSessionManager::new(db)
+}
+
+/// `database_session_manager` behind the `SessionManager` trait
+///
+/// # Panics
+///
+/// * If the database can't be opened or migrated
+pub async fn session() -> Arc<dyn SessionManager> {
+    Arc::new(database_session_manager().await)
+}
+
+/// A voter called `name` who has just joined
+#[must_use]
+pub fn player(name: &str) -> Player {
+    Player {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        role: PlayerRole::Voter,
+        team_role: TeamRole::default(),
+        joined_at: Utc::now(),
+    }
+}
+
+/// `player`'s vote for `value`, cast just now
+#[must_use]
+pub fn vote(player: &Player, value: &str) -> Vote {
+    Vote {
+        player_id: player.id,
+        player_name: player.name.clone(),
+        value: value.to_string(),
+        cast_at: Utc::now(),
+        confidence: None,
+    }
+}
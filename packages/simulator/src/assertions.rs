@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use planning_poker_models::GameState;
+use planning_poker_session::SessionManager;
+use uuid::Uuid;
+
+use crate::invariants;
+
+/// A correctness check evaluated against the server's session state
+#[async_trait]
+pub trait SimulationAssertion: std::fmt::Debug + Send + Sync {
+    /// Check the assertion, returning a description of the mismatch on failure
+    async fn verify(&self, session_manager: &dyn SessionManager) -> Result<(), String>;
+}
+
+/// Asserts how many votes have been cast in a game
+#[derive(Debug, Clone)]
+pub struct VoteCountAssertion {
+    pub game_id: Uuid,
+    pub expected_vote_count: usize,
+}
+
+#[async_trait]
+impl SimulationAssertion for VoteCountAssertion {
+    async fn verify(&self, session_manager: &dyn SessionManager) -> Result<(), String> {
+        let votes = session_manager
+            .get_game_votes(self.game_id)
+            .await
+            .map_err(|e| format!("Failed to get votes for game {}: {e}", self.game_id))?;
+
+        if votes.len() != self.expected_vote_count {
+            return Err(format!(
+                "Game {} has {} votes, expected {}",
+                self.game_id,
+                votes.len(),
+                self.expected_vote_count
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Asserts the state a game is in
+#[derive(Debug, Clone)]
+pub struct GameStateAssertion {
+    pub game_id: Uuid,
+    pub expected_state: GameState,
+}
+
+#[async_trait]
+impl SimulationAssertion for GameStateAssertion {
+    async fn verify(&self, session_manager: &dyn SessionManager) -> Result<(), String> {
+        let game = session_manager
+            .get_game(self.game_id)
+            .await
+            .map_err(|e| format!("Failed to get game {}: {e}", self.game_id))?
+            .ok_or_else(|| format!("Game {} not found", self.game_id))?;
+
+        if game.state != self.expected_state {
+            return Err(format!(
+                "Game {} is in state {:?}, expected {:?}",
+                self.game_id, game.state, self.expected_state
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Asserts how many players have joined a game
+#[derive(Debug, Clone)]
+pub struct PlayerCountAssertion {
+    pub game_id: Uuid,
+    pub expected_count: usize,
+}
+
+#[async_trait]
+impl SimulationAssertion for PlayerCountAssertion {
+    async fn verify(&self, session_manager: &dyn SessionManager) -> Result<(), String> {
+        let players = session_manager
+            .get_game_players(self.game_id)
+            .await
+            .map_err(|e| format!("Failed to get players for game {}: {e}", self.game_id))?;
+
+        if players.len() != self.expected_count {
+            return Err(format!(
+                "Game {} has {} players, expected {}",
+                self.game_id,
+                players.len(),
+                self.expected_count
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Upper bound on the games a consistency check loads; far above what a simulation creates
+const MAX_CHECKED_GAMES: usize = 10_000;
+
+/// Asserts that every game on the server satisfies the state invariants
+///
+/// Queued by each client once its plan finishes, so the games it touched are
+/// checked after it is done with them.
+#[derive(Debug, Clone, Copy)]
+pub struct StateConsistencyAssertion;
+
+#[async_trait]
+impl SimulationAssertion for StateConsistencyAssertion {
+    async fn verify(&self, session_manager: &dyn SessionManager) -> Result<(), String> {
+        let games = session_manager
+            .list_games(None, MAX_CHECKED_GAMES)
+            .await
+            .map_err(|e| format!("Failed to list games: {e}"))?;
+
+        let mut violations = vec![];
+        for game in games {
+            let players = session_manager
+                .get_game_players(game.id)
+                .await
+                .map_err(|e| format!("Failed to get players for game {}: {e}", game.id))?;
+            let votes = session_manager
+                .get_game_votes(game.id)
+                .await
+                .map_err(|e| format!("Failed to get votes for game {}: {e}", game.id))?;
+
+            if let Err(e) = invariants::check_game(&game, &players, &votes) {
+                violations.extend(e);
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations.join("; "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use planning_poker_session::{
+        test_support::{database_session_manager, player, vote},
+        DatabaseSessionManager,
+    };
+
+    /// Create a game in the voting state with one player who has voted
+    async fn voted_game(manager: &DatabaseSessionManager) -> Uuid {
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        let player = player("Alice");
+        manager
+            .add_player_to_game(game.id, player.clone())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+        manager
+            .cast_vote(game.id, vote(&player, "5"))
+            .await
+            .unwrap();
+        game.id
+    }
+
+    #[tokio::test]
+    async fn test_vote_count_assertion() {
+        let manager = database_session_manager().await;
+        let game_id = voted_game(&manager).await;
+
+        let passing = VoteCountAssertion {
+            game_id,
+            expected_vote_count: 1,
+        };
+        let failing = VoteCountAssertion {
+            game_id,
+            expected_vote_count: 3,
+        };
+
+        assert!(passing.verify(&manager).await.is_ok());
+        assert!(failing.verify(&manager).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_game_state_assertion() {
+        let manager = database_session_manager().await;
+        let game_id = voted_game(&manager).await;
+
+        let passing = GameStateAssertion {
+            game_id,
+            expected_state: GameState::Voting,
+        };
+        let failing = GameStateAssertion {
+            game_id,
+            expected_state: GameState::Revealed,
+        };
+        let missing = GameStateAssertion {
+            game_id: Uuid::new_v4(),
+            expected_state: GameState::Voting,
+        };
+
+        assert!(passing.verify(&manager).await.is_ok());
+        assert!(failing.verify(&manager).await.is_err());
+        assert!(missing.verify(&manager).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_player_count_assertion() {
+        let manager = database_session_manager().await;
+        let game_id = voted_game(&manager).await;
+
+        let passing = PlayerCountAssertion {
+            game_id,
+            expected_count: 1,
+        };
+        let failing = PlayerCountAssertion {
+            game_id,
+            expected_count: 0,
+        };
+
+        assert!(passing.verify(&manager).await.is_ok());
+        assert!(failing.verify(&manager).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_state_consistency_assertion() {
+        let manager = database_session_manager().await;
+        voted_game(&manager).await;
+
+        assert!(StateConsistencyAssertion.verify(&manager).await.is_ok());
+
+        let abandoned = manager
+            .create_game("Empty".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .start_voting(abandoned.id, "Story".to_string())
+            .await
+            .unwrap();
+
+        let error = StateConsistencyAssertion
+            .verify(&manager)
+            .await
+            .unwrap_err();
+        assert!(error.contains(&format!("Game {} is stuck in Voting", abandoned.id)));
+    }
+}
@@ -1,16 +1,10 @@
-use simvar::{
-    switchy::{
-        random::rng,
-        tcp::TcpStream,
-        unsync::{io::AsyncWriteExt, time::sleep},
-    },
-    Sim,
-};
+use planning_poker_models::GameState;
+use simvar::{switchy::unsync::time::sleep, Sim};
 use uuid::Uuid;
 
 use crate::{
-    host::server::PORT,
-    http::{parse_http_response, read_http_response},
+    host::server::PORT, http::make_http_request, queue_assertion, random, GameStateAssertion,
+    VoteCountAssertion,
 };
 
 pub fn start(sim: &mut impl Sim) {
@@ -18,17 +12,18 @@ pub fn start(sim: &mut impl Sim) {
     let player_name = "BasicGamePlayer".to_string();
 
     sim.client(player_name.clone(), async move {
-        run_basic_game_simulation(&server_addr, &player_name).await
+        super::finish(run_basic_game_simulation(&server_addr, &player_name).await)
     });
 }
 
-async fn run_basic_game_simulation(
+/// Creates a game, joins it, votes and reveals over HTTP
+pub(super) async fn run_basic_game_simulation(
     server_addr: &str,
     player_name: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send>> {
     let player_id = Uuid::new_v4();
     let mut game_id: Option<Uuid> = None;
-    log::info!("Starting basic game simulation for player: {player_name}");
+    tracing::info!("Starting basic game simulation for player: {player_name}");
 
     // Create a new game via HTTP POST
     let create_game_request = serde_json::json!({
@@ -57,11 +52,11 @@ async fn run_basic_game_simulation(
     if let Some(game_obj) = game_response.get("game") {
         if let Some(game_id_str) = game_obj.get("id").and_then(|v| v.as_str()) {
             game_id = Some(Uuid::parse_str(game_id_str).unwrap());
-            log::info!("Game created with ID: {game_id_str}");
+            tracing::info!("Game created with ID: {game_id_str}");
         }
     }
 
-    log::debug!("HTTP connection established for {player_name}");
+    tracing::debug!("HTTP connection established for {player_name}");
 
     let Some(game_id) = game_id else {
         return Err(Box::new(std::io::Error::other(
@@ -72,30 +67,17 @@ async fn run_basic_game_simulation(
     // Simulate other players joining
     sleep(std::time::Duration::from_millis(100)).await;
 
-    // Join the game as a player (simulate form submission)
-    let join_request = format!("game-id={game_id}&player-name={player_name}");
-    let (status, _body) = make_http_request(
-        server_addr,
-        "POST",
-        &format!("/games/{game_id}/join"),
-        Some(&join_request),
-        Some("application/x-www-form-urlencoded"),
-    )
-    .await?;
-
-    if status != 200 && status != 302 {
-        return Err(Box::new(std::io::Error::other(format!(
-            "Failed to join game: HTTP {status}"
-        ))));
-    }
+    // Join the game as a player and open voting on a story
+    super::join_game(server_addr, game_id, player_id, player_name).await?;
+    tracing::info!("{player_name} joined game {game_id}");
 
-    log::info!("{player_name} joined game {game_id}");
+    super::start_voting(server_addr, game_id, "Basic game story").await?;
 
     // Wait a bit then cast a vote
-    sleep(std::time::Duration::from_millis(rng().gen_range(100..500))).await;
+    sleep(random::delay(100..500)).await;
 
     let vote_values = ["1", "2", "3", "5", "8", "13", "21"];
-    let vote_value = vote_values[rng().gen_range(0..vote_values.len())];
+    let vote_value = *random::pick(&vote_values);
 
     // Cast vote via HTTP POST
     let vote_request = serde_json::json!({
@@ -112,15 +94,32 @@ async fn run_basic_game_simulation(
     )
     .await?;
 
-    if status == 200 || status == 201 {
-        log::info!("{player_name} cast vote: {vote_value}");
-    } else {
-        log::warn!("{player_name} failed to cast vote: HTTP {status}");
+    if status != 200 {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Failed to cast vote: HTTP {status}"
+        ))));
     }
 
+    tracing::info!("{player_name} cast vote: {vote_value}");
+
     // Wait for other votes and reveal
     sleep(std::time::Duration::from_millis(1000)).await;
 
+    let (status, _body) = make_http_request(
+        server_addr,
+        "POST",
+        &format!("/api/v1/games/{game_id}/reveal"),
+        None,
+        None,
+    )
+    .await?;
+
+    if status != 200 {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Failed to reveal votes: HTTP {status}"
+        ))));
+    }
+
     // Get final game state
     let (status, body) = make_http_request(
         server_addr,
@@ -132,51 +131,19 @@ async fn run_basic_game_simulation(
     .await?;
 
     if status == 200 {
-        log::info!("Final game state: {body}");
+        tracing::info!("Final game state: {body}");
     }
 
-    log::info!("Basic game simulation completed for player: {player_name}");
-    Ok(())
-}
-
-async fn make_http_request(
-    server_addr: &str,
-    method: &str,
-    path: &str,
-    body: Option<&str>,
-    content_type: Option<&str>,
-) -> Result<(u16, String), Box<dyn std::error::Error + Send>> {
-    let mut connection = TcpStream::connect(server_addr)
-        .await
-        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
-
-    let content_length = body.map_or(0, str::len);
-    let content_type_header =
-        content_type.map_or(String::new(), |ct| format!("Content-Type: {ct}\r\n"));
-
-    let request = format!(
-        "{method} {path} HTTP/1.1\r\n\
-         Host: {server_addr}\r\n\
-         {content_type_header}Content-Length: {content_length}\r\n\
-         Connection: close\r\n\
-         \r\n{body}",
-        body = body.unwrap_or("")
-    );
-
-    connection
-        .write_all(request.as_bytes())
-        .await
-        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+    // The single player voted once and the round was revealed
+    queue_assertion(VoteCountAssertion {
+        game_id,
+        expected_vote_count: 1,
+    });
+    queue_assertion(GameStateAssertion {
+        game_id,
+        expected_state: GameState::Revealed,
+    });
 
-    let mut response = String::new();
-    if let Some(response_data) = read_http_response(&mut response, Box::pin(&mut connection))
-        .await
-        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?
-    {
-        let (status, body) = parse_http_response(&response_data)
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
-        Ok((status, body))
-    } else {
-        Err(Box::new(std::io::Error::other("No HTTP response received")))
-    }
+    tracing::info!("Basic game simulation completed for player: {player_name}");
+    Ok(())
 }
@@ -1,26 +1,271 @@
-use simvar::{
-    switchy::{random::rng, unsync::time::sleep},
-    Sim,
+use std::{
+    collections::BTreeSet,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
 
-pub fn start(sim: &mut impl Sim) {
-    let player_name = "ConcurrentVotingPlayer".to_string();
-
-    sim.client(player_name.clone(), async move {
-        // Simplified concurrent voting simulation
-        log::info!("Starting concurrent voting simulation for player: {player_name}");
-
-        // Simulate concurrent voting behavior
-        for round in 0..3 {
-            sleep(std::time::Duration::from_millis(rng().gen_range(100..300))).await;
-            log::info!(
-                "{} simulating concurrent vote in round {}",
-                player_name,
-                round + 1
-            );
+use planning_poker_models::{GameState, Vote};
+use simvar::{switchy::unsync::time::sleep, Sim};
+use uuid::Uuid;
+
+use crate::{host::server::PORT, queue_assertion, random, VoteCountAssertion};
+
+/// Environment variable overriding how many voters race in the shared game
+pub const VOTERS_ENV_VAR: &str = "SIMULATOR_CONCURRENT_VOTERS";
+
+/// Voters racing in the shared game when `VOTERS_ENV_VAR` isn't set
+pub const DEFAULT_VOTER_COUNT: usize = 5;
+
+/// Votes each player fires back to back, so a player's own writes overlap too
+const VOTES_PER_PLAYER: usize = 2;
+
+/// How often a client asks the server whether the game has moved on
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+type ClientError = Box<dyn std::error::Error + Send>;
+
+/// Coordination between the host, the voters and the checker of one shared game
+#[derive(Default)]
+struct Round {
+    game_id: Mutex<Option<Uuid>>,
+    abandoned: AtomicBool,
+    /// Voters that finished joining, successfully or not
+    arrived: AtomicUsize,
+    /// Voters that are done voting, successfully or not
+    finished: AtomicUsize,
+}
+
+impl Round {
+    fn game_id(&self) -> Option<Uuid> {
+        *self.game_id.lock().unwrap()
+    }
+
+    async fn wait_for_game(&self) -> Option<Uuid> {
+        loop {
+            if self.abandoned.load(Ordering::SeqCst) {
+                return None;
+            }
+            if let Some(game_id) = self.game_id() {
+                return Some(game_id);
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    async fn wait_for(counter: &AtomicUsize, count: usize) {
+        while counter.load(Ordering::SeqCst) < count {
+            sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Polls the server until the game reaches `state`, returning what the
+    /// server reported then, or `None` if the host gave up first
+    async fn wait_for_state(
+        &self,
+        server_addr: &str,
+        game_id: Uuid,
+        state: &GameState,
+    ) -> Result<Option<serde_json::Value>, ClientError> {
+        loop {
+            if self.abandoned.load(Ordering::SeqCst) {
+                return Ok(None);
+            }
+
+            let game = super::get_game(server_addr, game_id).await?;
+            let current: GameState = serde_json::from_value(game["game"]["state"].clone())
+                .map_err(|e| Box::new(e) as ClientError)?;
+            if current == *state {
+                return Ok(Some(game));
+            }
+
+            sleep(POLL_INTERVAL).await;
         }
+    }
+}
+
+/// Number of voters set in `VOTERS_ENV_VAR`, or `DEFAULT_VOTER_COUNT`
+#[must_use]
+pub fn voter_count() -> usize {
+    std::env::var(VOTERS_ENV_VAR)
+        .ok()
+        .and_then(|count| count.trim().parse().ok())
+        .filter(|count| *count > 0)
+        .unwrap_or(DEFAULT_VOTER_COUNT)
+}
+
+pub fn start(sim: &mut impl Sim) {
+    let server_addr = format!("127.0.0.1:{PORT}");
+    let voters = voter_count();
+    let round = Arc::new(Round::default());
+
+    {
+        let server_addr = server_addr.clone();
+        let round = round.clone();
+        sim.client("ConcurrentVotingHost".to_string(), async move {
+            let result = run_host(&server_addr, voters, &round).await;
+            if result.is_err() {
+                round.abandoned.store(true, Ordering::SeqCst);
+            }
+            super::finish(result)
+        });
+    }
+
+    for i in 0..voters {
+        let server_addr = server_addr.clone();
+        let round = round.clone();
+        let player_name = format!("ConcurrentVoter{}", i + 1);
+        sim.client(player_name.clone(), async move {
+            let result = run_voter(&server_addr, &player_name, &round).await;
+            round.finished.fetch_add(1, Ordering::SeqCst);
+            super::finish(result)
+        });
+    }
+
+    sim.client("ConcurrentVotingChecker".to_string(), async move {
+        super::finish(run_checker(&server_addr, voters, &round).await)
+    });
+}
+
+/// Creates the shared game, opens voting once every voter has joined, checks
+/// that no vote went missing or doubled up, then reveals
+async fn run_host(server_addr: &str, voters: usize, round: &Round) -> Result<(), ClientError> {
+    tracing::info!("Starting concurrent voting simulation with {voters} voters");
 
-        log::info!("Concurrent voting simulation completed for player: {player_name}");
-        Ok::<(), Box<dyn std::error::Error + Send>>(())
+    let game_id = super::create_game(server_addr, "ConcurrentVotingHost").await?;
+    *round.game_id.lock().unwrap() = Some(game_id);
+
+    Round::wait_for(&round.arrived, voters).await;
+    super::start_voting(server_addr, game_id, "Concurrent story").await?;
+
+    Round::wait_for(&round.finished, voters).await;
+
+    let game = super::get_game(server_addr, game_id).await?;
+    let votes: Vec<Vote> =
+        serde_json::from_value(game["votes"].clone()).map_err(|e| Box::new(e) as ClientError)?;
+    check_one_vote_per_voter(game_id, &votes, voters)?;
+
+    super::reveal_votes(server_addr, game_id).await?;
+
+    queue_assertion(VoteCountAssertion {
+        game_id,
+        expected_vote_count: voters,
     });
+
+    tracing::info!("Concurrent voting simulation completed with {voters} voters");
+    Ok(())
+}
+
+/// Joins the shared game, waits for voting to open, then votes as fast as it can
+async fn run_voter(server_addr: &str, player_name: &str, round: &Round) -> Result<(), ClientError> {
+    let Some(game_id) = round.wait_for_game().await else {
+        round.arrived.fetch_add(1, Ordering::SeqCst);
+        return Err(Box::new(std::io::Error::other(format!(
+            "{player_name} had no game to join; the host gave up"
+        ))));
+    };
+
+    let player_id = Uuid::new_v4();
+    let joined = super::join_game(server_addr, game_id, player_id, player_name).await;
+    round.arrived.fetch_add(1, Ordering::SeqCst);
+    joined?;
+
+    if round
+        .wait_for_state(server_addr, game_id, &GameState::Voting)
+        .await?
+        .is_none()
+    {
+        return Err(Box::new(std::io::Error::other(format!(
+            "{player_name} never saw voting start; the host gave up"
+        ))));
+    }
+
+    let values = ["1", "2", "3", "5", "8", "13", "21"];
+    for _ in 0..VOTES_PER_PLAYER {
+        let value = *random::pick(&values);
+        super::cast_vote(server_addr, game_id, player_id, value).await?;
+    }
+
+    tracing::info!("{player_name} cast {VOTES_PER_PLAYER} overlapping votes in game {game_id}");
+    Ok(())
+}
+
+/// Waits for the shared game's votes to be revealed and checks that every
+/// voter has exactly one vote in it
+async fn run_checker(server_addr: &str, voters: usize, round: &Round) -> Result<(), ClientError> {
+    let Some(game_id) = round.wait_for_game().await else {
+        return Err(Box::new(std::io::Error::other(
+            "No game to check; the host gave up",
+        )));
+    };
+
+    let Some(game) = round
+        .wait_for_state(server_addr, game_id, &GameState::Revealed)
+        .await?
+    else {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Votes of game {game_id} were never revealed; the host gave up"
+        ))));
+    };
+
+    let votes: Vec<Vote> =
+        serde_json::from_value(game["votes"].clone()).map_err(|e| Box::new(e) as ClientError)?;
+    check_one_vote_per_voter(game_id, &votes, voters)?;
+
+    tracing::info!("Game {game_id} revealed one vote from each of its {voters} voters");
+    Ok(())
+}
+
+/// Fails unless `votes` holds exactly one vote from each of `voters` players
+fn check_one_vote_per_voter(
+    game_id: Uuid,
+    votes: &[Vote],
+    voters: usize,
+) -> Result<(), ClientError> {
+    let players: BTreeSet<Uuid> = votes.iter().map(|vote| vote.player_id).collect();
+
+    if votes.len() != players.len() {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Game {game_id} has {} votes from only {} players",
+            votes.len(),
+            players.len()
+        ))));
+    }
+    if players.len() != voters {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Game {game_id} has votes from {} players, expected {voters}",
+            players.len()
+        ))));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn vote(player_id: Uuid) -> Vote {
+        Vote {
+            player_id,
+            player_name: "Voter".to_string(),
+            value: "5".to_string(),
+            cast_at: Utc::now(),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_check_one_vote_per_voter_catches_missing_and_duplicate_votes() {
+        let game_id = Uuid::new_v4();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(check_one_vote_per_voter(game_id, &[vote(alice), vote(bob)], 2).is_ok());
+        assert!(check_one_vote_per_voter(game_id, &[vote(alice)], 2).is_err());
+        assert!(check_one_vote_per_voter(game_id, &[vote(alice), vote(alice)], 2).is_err());
+    }
 }
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use simvar::{switchy::time::now, Sim};
+
+use crate::{host::server::PORT, queue_set_latency};
+
+/// One-way delay injected into every client message while this run is active
+const LATENCY: Duration = Duration::from_millis(200);
+
+/// How long the basic game may take under `LATENCY` before the run fails
+const DEADLINE: Duration = Duration::from_secs(10);
+
+pub fn start(sim: &mut impl Sim) {
+    let server_addr = format!("127.0.0.1:{PORT}");
+    let player_name = "DegradedNetworkPlayer".to_string();
+
+    sim.client(player_name.clone(), async move {
+        super::finish(run_degraded_network_simulation(&server_addr, &player_name).await)
+    });
+}
+
+/// Plays the basic game with latency injected and checks it still finishes in time
+async fn run_degraded_network_simulation(
+    server_addr: &str,
+    player_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    tracing::info!(
+        "Starting degraded network simulation with {LATENCY:?} latency for {player_name}"
+    );
+
+    queue_set_latency(LATENCY);
+    let started = now();
+
+    let result = super::basic_game::run_basic_game_simulation(server_addr, player_name).await;
+
+    let elapsed = now().duration_since(started).unwrap_or_default();
+    queue_set_latency(Duration::ZERO);
+    result?;
+
+    if elapsed > DEADLINE {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Basic game took {elapsed:?} under {LATENCY:?} latency, deadline is {DEADLINE:?}"
+        ))));
+    }
+
+    tracing::info!("Degraded network simulation completed in {elapsed:?}");
+    Ok(())
+}
@@ -1,4 +1,196 @@
+use simvar::switchy::time::now;
+use uuid::Uuid;
+
+use crate::{
+    http::make_http_request, queue_assertion, record_game_created, record_vote_cast,
+    StateConsistencyAssertion,
+};
+
 pub mod basic_game;
 pub mod concurrent_voting;
+pub mod degraded_network;
 pub mod network_partition;
 pub mod player_churn;
+pub mod websocket_game;
+
+/// Ends a client plan, queueing a consistency check of the server state it left behind
+///
+/// Every client passes its final result through here so invariant violations
+/// surface as simulation failures.
+pub fn finish<T>(result: T) -> T {
+    queue_assertion(StateConsistencyAssertion);
+    result
+}
+
+/// Creates a Fibonacci game named after the player, returning its id
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response has no game id.
+pub async fn create_game(
+    server_addr: &str,
+    player_name: &str,
+) -> Result<Uuid, Box<dyn std::error::Error + Send>> {
+    let create_game_request = serde_json::json!({
+        "name": format!("{}'s Game", player_name),
+        "voting_system": "fibonacci"
+    });
+
+    let (status, body) = make_http_request(
+        server_addr,
+        "POST",
+        "/api/v1/games",
+        Some(&create_game_request.to_string()),
+        Some("application/json"),
+    )
+    .await?;
+
+    if status != 200 {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Failed to create game: HTTP {status}"
+        ))));
+    }
+
+    let game_response: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+
+    let game_id = game_response
+        .get("game")
+        .and_then(|game| game.get("id"))
+        .and_then(|id| id.as_str())
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(|| {
+            Box::new(std::io::Error::other("Failed to get game ID from response"))
+                as Box<dyn std::error::Error + Send>
+        })?;
+
+    record_game_created();
+    Ok(game_id)
+}
+
+/// Joins `game_id` as a voter named `player_name`
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the server turns the player away.
+pub async fn join_game(
+    server_addr: &str,
+    game_id: Uuid,
+    player_id: Uuid,
+    player_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    let join_request = serde_json::json!({
+        "player_id": player_id,
+        "name": player_name
+    });
+
+    post_json(
+        server_addr,
+        &format!("/api/v1/games/{game_id}/join"),
+        &join_request,
+    )
+    .await
+}
+
+/// Opens voting on `story` in `game_id`
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the game can't start a round.
+pub async fn start_voting(
+    server_addr: &str,
+    game_id: Uuid,
+    story: &str,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    post_json(
+        server_addr,
+        &format!("/api/v1/games/{game_id}/start"),
+        &serde_json::json!({ "story": story }),
+    )
+    .await
+}
+
+/// Casts `value` as `player_id`'s vote in `game_id`
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the server rejects the vote.
+pub async fn cast_vote(
+    server_addr: &str,
+    game_id: Uuid,
+    player_id: Uuid,
+    value: &str,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    let started = now();
+    post_json(
+        server_addr,
+        &format!("/api/v1/games/{game_id}/vote"),
+        &serde_json::json!({ "player_id": player_id, "vote": value }),
+    )
+    .await?;
+
+    let latency = now().duration_since(started).unwrap_or_default();
+    record_vote_cast(u64::try_from(latency.as_millis()).unwrap_or(u64::MAX));
+    Ok(())
+}
+
+/// Reveals the votes of the current round in `game_id`
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the game has no round to reveal.
+pub async fn reveal_votes(
+    server_addr: &str,
+    game_id: Uuid,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    post_json(
+        server_addr,
+        &format!("/api/v1/games/{game_id}/reveal"),
+        &serde_json::json!({}),
+    )
+    .await
+}
+
+/// The game, players and votes the server holds for `game_id`
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response isn't JSON.
+pub async fn get_game(
+    server_addr: &str,
+    game_id: Uuid,
+) -> Result<serde_json::Value, Box<dyn std::error::Error + Send>> {
+    let path = format!("/api/v1/games/{game_id}");
+    let (status, body) = make_http_request(server_addr, "GET", &path, None, None).await?;
+
+    if status != 200 {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Request to {path} failed: HTTP {status}"
+        ))));
+    }
+
+    serde_json::from_str(&body).map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)
+}
+
+async fn post_json(
+    server_addr: &str,
+    path: &str,
+    body: &serde_json::Value,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    let (status, _body) = make_http_request(
+        server_addr,
+        "POST",
+        path,
+        Some(&body.to_string()),
+        Some("application/json"),
+    )
+    .await?;
+
+    if status != 200 {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Request to {path} failed: HTTP {status}"
+        ))));
+    }
+
+    Ok(())
+}
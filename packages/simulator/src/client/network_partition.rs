@@ -1,40 +1,134 @@
-use simvar::{
-    switchy::{random::rng, unsync::time::sleep},
-    Sim,
+use simvar::{switchy::unsync::time::sleep, Sim};
+use uuid::Uuid;
+
+use crate::{
+    host::server::PORT, http::make_http_request, is_partitioned, queue_assertion,
+    queue_network_partition, queue_restore_network, random, VoteCountAssertion,
 };
 
-use crate::{queue_disconnect_player, queue_reconnect_player};
+/// A request held back while its sender was partitioned from the host
+struct HeldRequest {
+    path: String,
+    body: String,
+}
 
 pub fn start(sim: &mut impl Sim) {
+    let server_addr = format!("127.0.0.1:{PORT}");
     let player_name = "NetworkPartitionPlayer".to_string();
 
     sim.client(player_name.clone(), async move {
-        let player_id = uuid::Uuid::new_v4();
+        super::finish(run_network_partition_simulation(&server_addr, &player_name).await)
+    });
+}
+
+async fn run_network_partition_simulation(
+    server_addr: &str,
+    player_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    let player_id = Uuid::new_v4();
 
-        log::info!("Starting network partition simulation for player: {player_name}");
+    tracing::info!("Starting network partition simulation for player: {player_name}");
 
-        // Simulate normal operation
-        for i in 0..3 {
-            sleep(std::time::Duration::from_millis(rng().gen_range(100..300))).await;
-            log::info!("{} normal operation round {}", player_name, i + 1);
-        }
+    let game_id = super::create_game(server_addr, player_name).await?;
+    super::join_game(server_addr, game_id, player_id, player_name).await?;
+    super::start_voting(server_addr, game_id, "Partitioned story").await?;
 
-        // Simulate network partition
-        log::warn!("{player_name} experiencing network partition");
-        queue_disconnect_player(player_id);
+    // Simulate normal operation
+    for i in 0..3 {
+        sleep(random::delay(100..300)).await;
+        tracing::info!("{} normal operation round {}", player_name, i + 1);
+    }
 
-        // Wait during partition
-        sleep(std::time::Duration::from_millis(1000)).await;
+    // Simulate network partition
+    tracing::warn!("{player_name} experiencing network partition");
+    queue_network_partition(vec![player_id]);
+    wait_for_partition(player_id, true).await;
 
-        // Reconnect
-        log::info!("{player_name} attempting to reconnect after partition");
-        queue_reconnect_player(player_id);
+    // Traffic sent during the partition never reaches the host
+    let mut held = vec![];
+    send_or_hold(
+        server_addr,
+        player_id,
+        &mut held,
+        HeldRequest {
+            path: format!("/api/v1/games/{game_id}/vote"),
+            body: serde_json::json!({ "player_id": player_id, "vote": "5" }).to_string(),
+        },
+    )
+    .await?;
 
-        // Continue operation
-        sleep(std::time::Duration::from_millis(500)).await;
-        log::info!("{player_name} resumed normal operation");
+    queue_assertion(VoteCountAssertion {
+        game_id,
+        expected_vote_count: 0,
+    });
+
+    // Wait during partition
+    sleep(std::time::Duration::from_millis(1000)).await;
+
+    // Reconnect and flush everything held back during the partition
+    tracing::info!("{player_name} attempting to reconnect after partition");
+    queue_restore_network();
+    wait_for_partition(player_id, false).await;
 
-        log::info!("Network partition simulation completed for player: {player_name}");
-        Ok::<(), Box<dyn std::error::Error + Send>>(())
+    for request in held {
+        send_request(server_addr, &request).await?;
+    }
+
+    queue_assertion(VoteCountAssertion {
+        game_id,
+        expected_vote_count: 1,
     });
+
+    // Continue operation
+    sleep(std::time::Duration::from_millis(500)).await;
+    tracing::info!("{player_name} resumed normal operation");
+
+    tracing::info!("Network partition simulation completed for player: {player_name}");
+    Ok(())
+}
+
+/// Wait until the partition action for the player has been applied or lifted
+async fn wait_for_partition(player_id: Uuid, partitioned: bool) {
+    while is_partitioned(player_id) != partitioned {
+        sleep(std::time::Duration::from_millis(10)).await;
+    }
+}
+
+/// Send the request, or hold it back if the player is partitioned from the host
+async fn send_or_hold(
+    server_addr: &str,
+    player_id: Uuid,
+    held: &mut Vec<HeldRequest>,
+    request: HeldRequest,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    if is_partitioned(player_id) {
+        tracing::debug!("Holding {} while partitioned", request.path);
+        held.push(request);
+        return Ok(());
+    }
+
+    send_request(server_addr, &request).await
+}
+
+async fn send_request(
+    server_addr: &str,
+    request: &HeldRequest,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    let (status, _body) = make_http_request(
+        server_addr,
+        "POST",
+        &request.path,
+        Some(&request.body),
+        Some("application/json"),
+    )
+    .await?;
+
+    if status != 200 && status != 201 {
+        return Err(Box::new(std::io::Error::other(format!(
+            "Request to {} failed: HTTP {status}",
+            request.path
+        ))));
+    }
+
+    Ok(())
 }
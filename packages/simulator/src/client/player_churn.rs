@@ -1,32 +1,30 @@
-use simvar::{
-    switchy::{random::rng, unsync::time::sleep},
-    Sim,
-};
+use simvar::{switchy::unsync::time::sleep, Sim};
+
+use crate::random;
 
 pub fn start(sim: &mut impl Sim) {
     let player_name = "PlayerChurnSimulator".to_string();
 
     sim.client(player_name.clone(), async move {
-        log::info!("Starting player churn simulation for player: {player_name}");
+        tracing::info!("Starting player churn simulation for player: {player_name}");
 
         let churn_cycles = 5;
 
         for cycle in 0..churn_cycles {
-            log::info!("{} starting churn cycle {}", player_name, cycle + 1);
+            tracing::info!("{} starting churn cycle {}", player_name, cycle + 1);
 
             // Simulate joining
-            sleep(std::time::Duration::from_millis(rng().gen_range(100..300))).await;
-            log::info!("{} joined game (cycle {})", player_name, cycle + 1);
+            sleep(random::delay(100..300)).await;
+            tracing::info!("{} joined game (cycle {})", player_name, cycle + 1);
 
             // Participate briefly
-            let participation_time = rng().gen_range(200..800);
-            sleep(std::time::Duration::from_millis(participation_time)).await;
+            sleep(random::delay(200..800)).await;
 
             // Leave (gracefully or abruptly)
-            if rng().gen_bool(0.7) {
-                log::info!("{} left game gracefully (cycle {})", player_name, cycle + 1);
+            if random::chance(0.7) {
+                tracing::info!("{} left game gracefully (cycle {})", player_name, cycle + 1);
             } else {
-                log::info!(
+                tracing::info!(
                     "{} disconnected abruptly (cycle {})",
                     player_name,
                     cycle + 1
@@ -34,11 +32,10 @@ pub fn start(sim: &mut impl Sim) {
             }
 
             // Wait before next cycle
-            let wait_time = rng().gen_range(200..1000);
-            sleep(std::time::Duration::from_millis(wait_time)).await;
+            sleep(random::delay(200..1000)).await;
         }
 
-        log::info!("Player churn simulation completed for player: {player_name}");
-        Ok::<(), Box<dyn std::error::Error + Send>>(())
+        tracing::info!("Player churn simulation completed for player: {player_name}");
+        super::finish(Ok::<(), Box<dyn std::error::Error + Send>>(()))
     });
 }
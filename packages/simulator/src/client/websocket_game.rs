@@ -0,0 +1,107 @@
+use planning_poker_models::{ClientMessage, ServerMessage};
+use simvar::{switchy::unsync::time::sleep, Sim};
+use uuid::Uuid;
+
+use crate::{
+    host::server::PORT, is_partitioned, queue_assertion, websocket::WebSocketClient, Error,
+    VoteCountAssertion,
+};
+
+/// Attempts made to complete the join-and-vote exchange before giving up
+const MAX_ATTEMPTS: u32 = 5;
+
+pub fn start(sim: &mut impl Sim) {
+    let server_addr = format!("127.0.0.1:{PORT}");
+    let player_name = "WebSocketGamePlayer".to_string();
+
+    sim.client(player_name.clone(), async move {
+        super::finish(run_websocket_game_simulation(&server_addr, &player_name).await)
+    });
+}
+
+async fn run_websocket_game_simulation(
+    server_addr: &str,
+    player_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send>> {
+    let player_id = Uuid::new_v4();
+
+    tracing::info!("Starting WebSocket game simulation for player: {player_name}");
+
+    let game_id = super::create_game(server_addr, player_name).await?;
+
+    let mut attempt = 1;
+    loop {
+        // Don't dial out while cut off from the host; wait for the partition to lift
+        while is_partitioned(player_id) {
+            sleep(std::time::Duration::from_millis(50)).await;
+        }
+
+        match join_and_vote(server_addr, game_id, player_name).await {
+            Ok(()) => break,
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                tracing::warn!("{player_name} lost its WebSocket (attempt {attempt}): {e}");
+                attempt += 1;
+                sleep(std::time::Duration::from_millis(100 * u64::from(attempt))).await;
+            }
+            Err(e) => return Err(Box::new(e)),
+        }
+    }
+
+    queue_assertion(VoteCountAssertion {
+        game_id,
+        expected_vote_count: 1,
+    });
+
+    tracing::info!("WebSocket game simulation completed for player: {player_name}");
+    Ok(())
+}
+
+/// Joins the game over a fresh connection and casts a vote.
+///
+/// Safe to repeat after a dropped connection: re-joining re-attaches the player and a
+/// repeated vote replaces the earlier one.
+async fn join_and_vote(server_addr: &str, game_id: Uuid, player_name: &str) -> Result<(), Error> {
+    let mut ws = WebSocketClient::connect(server_addr).await?;
+
+    ws.send(&ClientMessage::JoinGame {
+        game_id,
+        player_name: player_name.to_string(),
+    })
+    .await?;
+
+    let me = ws
+        .expect("GameJoined", |message| match message {
+            ServerMessage::GameJoined { players, .. } => players
+                .iter()
+                .find(|player| player.name == player_name)
+                .map(|player| player.id),
+            _ => None,
+        })
+        .await?;
+
+    ws.send(&ClientMessage::StartVoting {
+        story: "WebSocket story".to_string(),
+    })
+    .await?;
+
+    ws.expect("VotingStarted", |message| {
+        matches!(message, ServerMessage::VotingStarted { .. }).then_some(())
+    })
+    .await?;
+
+    ws.send(&ClientMessage::CastVote {
+        value: "5".to_string(),
+    })
+    .await?;
+
+    ws.expect("VoteCast", |message| match message {
+        ServerMessage::VoteCast {
+            player_id,
+            has_voted: true,
+        } if *player_id == me => Some(()),
+        _ => None,
+    })
+    .await?;
+
+    ws.close().await
+}
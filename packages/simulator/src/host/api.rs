@@ -0,0 +1,355 @@
+//! The JSON API the simulated server answers over HTTP
+//!
+//! Each route calls straight into the session manager, so client simulations
+//! exercise the same game logic the real server runs.
+
+use chrono::Utc;
+use planning_poker_models::{Game, Player, PlayerRole, TeamRole, Vote};
+use planning_poker_session::SessionManager;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::http::HttpRequest;
+
+/// A request that could not be served: its HTTP status and the reason given
+type Failure = (u16, String);
+
+/// Routes `request` to the session manager, returning the response status and JSON body
+pub async fn handle_request(
+    session_manager: &dyn SessionManager,
+    request: &HttpRequest,
+) -> (u16, String) {
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let result = match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["api", "v1", "games"]) => create_game(session_manager, &request.body).await,
+        ("GET", ["api", "v1", "games", game_id]) => get_game(session_manager, game_id).await,
+        ("POST", ["api", "v1", "games", game_id, "join"]) => {
+            join_game(session_manager, game_id, &request.body).await
+        }
+        ("POST", ["api", "v1", "games", game_id, "start"]) => {
+            start_voting(session_manager, game_id, &request.body).await
+        }
+        ("POST", ["api", "v1", "games", game_id, "vote"]) => {
+            cast_vote(session_manager, game_id, &request.body).await
+        }
+        ("POST", ["api", "v1", "games", game_id, "reveal"]) => {
+            reveal_votes(session_manager, game_id).await
+        }
+        _ => Err((404, format!("No route for {} {path}", request.method))),
+    };
+
+    match result {
+        Ok(body) => (200, body.to_string()),
+        Err((status, message)) => {
+            tracing::debug!(
+                "{} {path} failed with HTTP {status}: {message}",
+                request.method
+            );
+            (status, json!({ "error": message }).to_string())
+        }
+    }
+}
+
+async fn create_game(session_manager: &dyn SessionManager, body: &str) -> Result<Value, Failure> {
+    let body = json_body(body)?;
+    let name = string_field(&body, "name")?;
+    let voting_system = body
+        .get("voting_system")
+        .and_then(Value::as_str)
+        .unwrap_or("fibonacci");
+    let owner_id = if body.get("owner_id").is_some() {
+        uuid_field(&body, "owner_id")?
+    } else {
+        Uuid::new_v4()
+    };
+
+    let game = session_manager
+        .create_game(name.to_string(), voting_system.to_string(), owner_id)
+        .await
+        .map_err(|e| (500, e.to_string()))?;
+
+    Ok(json!({ "game": game }))
+}
+
+async fn get_game(session_manager: &dyn SessionManager, game_id: &str) -> Result<Value, Failure> {
+    let game = existing_game(session_manager, game_id).await?;
+    let players = session_manager
+        .get_game_players(game.id)
+        .await
+        .map_err(|e| (500, e.to_string()))?;
+    let votes = session_manager
+        .get_game_votes(game.id)
+        .await
+        .map_err(|e| (500, e.to_string()))?;
+
+    Ok(json!({ "game": game, "players": players, "votes": votes }))
+}
+
+async fn join_game(
+    session_manager: &dyn SessionManager,
+    game_id: &str,
+    body: &str,
+) -> Result<Value, Failure> {
+    let game = existing_game(session_manager, game_id).await?;
+    let body = json_body(body)?;
+    let player = Player {
+        id: uuid_field(&body, "player_id")?,
+        name: string_field(&body, "name")?.to_string(),
+        role: PlayerRole::Voter,
+        team_role: TeamRole::default(),
+        joined_at: Utc::now(),
+    };
+
+    session_manager
+        .add_player_to_game(game.id, player.clone())
+        .await
+        .map_err(|e| (409, e.to_string()))?;
+
+    Ok(json!({ "player": player }))
+}
+
+async fn start_voting(
+    session_manager: &dyn SessionManager,
+    game_id: &str,
+    body: &str,
+) -> Result<Value, Failure> {
+    let game = existing_game(session_manager, game_id).await?;
+    let story = json_body(body)?
+        .get("story")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    session_manager
+        .start_voting(game.id, story)
+        .await
+        .map_err(|e| (409, e.to_string()))?;
+
+    Ok(json!({ "game_id": game.id }))
+}
+
+async fn cast_vote(
+    session_manager: &dyn SessionManager,
+    game_id: &str,
+    body: &str,
+) -> Result<Value, Failure> {
+    let game = existing_game(session_manager, game_id).await?;
+    let body = json_body(body)?;
+    let player_id = uuid_field(&body, "player_id")?;
+    let value = string_field(&body, "vote")?;
+
+    let player = session_manager
+        .get_player(game.id, player_id)
+        .await
+        .map_err(|e| (500, e.to_string()))?
+        .ok_or_else(|| (404, "Player not in game".to_string()))?;
+
+    session_manager
+        .cast_vote(
+            game.id,
+            Vote {
+                player_id,
+                player_name: player.name,
+                value: value.to_string(),
+                cast_at: Utc::now(),
+                confidence: None,
+            },
+        )
+        .await
+        .map_err(|e| (409, e.to_string()))?;
+
+    Ok(json!({ "player_id": player_id, "vote": value }))
+}
+
+async fn reveal_votes(
+    session_manager: &dyn SessionManager,
+    game_id: &str,
+) -> Result<Value, Failure> {
+    let game = existing_game(session_manager, game_id).await?;
+
+    session_manager
+        .reveal_votes(game.id)
+        .await
+        .map_err(|e| (409, e.to_string()))?;
+
+    Ok(json!({ "game_id": game.id }))
+}
+
+/// The game named by a path segment, failing with 400 or 404 if there is none
+async fn existing_game(
+    session_manager: &dyn SessionManager,
+    game_id: &str,
+) -> Result<Game, Failure> {
+    let game_id = Uuid::parse_str(game_id).map_err(|_| (400, "Invalid game id".to_string()))?;
+
+    session_manager
+        .get_game(game_id)
+        .await
+        .map_err(|e| (500, e.to_string()))?
+        .ok_or_else(|| (404, "Game not found".to_string()))
+}
+
+/// Parses a request body as JSON, treating an empty body as an empty object
+fn json_body(body: &str) -> Result<Value, Failure> {
+    if body.trim().is_empty() {
+        return Ok(json!({}));
+    }
+
+    serde_json::from_str(body).map_err(|e| (400, format!("Invalid JSON body: {e}")))
+}
+
+fn string_field<'a>(body: &'a Value, name: &str) -> Result<&'a str, Failure> {
+    body.get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| (400, format!("Missing {name}")))
+}
+
+fn uuid_field(body: &Value, name: &str) -> Result<Uuid, Failure> {
+    Uuid::parse_str(string_field(body, name)?).map_err(|_| (400, format!("Invalid {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        format_http_request, format_http_response, parse_http_request, parse_http_response,
+    };
+    use planning_poker_models::GameState;
+    use planning_poker_session::{test_support::database_session_manager, DatabaseSessionManager};
+
+    /// Sends a request the way the simulator's HTTP client writes it and
+    /// parses the response the way the client reads it
+    async fn send(
+        manager: &DatabaseSessionManager,
+        method: &str,
+        path: &str,
+        body: Option<Value>,
+    ) -> (u16, Value) {
+        let body = body.map(|body| body.to_string());
+        let raw = format_http_request(
+            "127.0.0.1:8080",
+            method,
+            path,
+            body.as_deref(),
+            body.as_ref().map(|_| "application/json"),
+        );
+
+        let request = parse_http_request(&raw).unwrap();
+        let (status, body) = handle_request(manager, &request).await;
+        let (status, body) = parse_http_response(&format_http_response(status, &body)).unwrap();
+
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_client_can_create_join_vote_and_reveal() {
+        let manager = database_session_manager().await;
+        let player_id = Uuid::new_v4();
+
+        let (status, body) = send(
+            &manager,
+            "POST",
+            "/api/v1/games",
+            Some(json!({ "name": "Sprint 12", "voting_system": "fibonacci" })),
+        )
+        .await;
+        assert_eq!(status, 200);
+        let game_id = body["game"]["id"].as_str().unwrap().to_string();
+
+        let (status, _) = send(
+            &manager,
+            "POST",
+            &format!("/api/v1/games/{game_id}/join"),
+            Some(json!({ "player_id": player_id, "name": "Alice" })),
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        let (status, _) = send(
+            &manager,
+            "POST",
+            &format!("/api/v1/games/{game_id}/start"),
+            Some(json!({ "story": "Login page" })),
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        let (status, _) = send(
+            &manager,
+            "POST",
+            &format!("/api/v1/games/{game_id}/vote"),
+            Some(json!({ "player_id": player_id, "vote": "5" })),
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        let (status, _) = send(
+            &manager,
+            "POST",
+            &format!("/api/v1/games/{game_id}/reveal"),
+            None,
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        let (status, body) = send(&manager, "GET", &format!("/api/v1/games/{game_id}"), None).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["game"]["state"], json!(GameState::Revealed));
+        assert_eq!(body["players"][0]["name"], "Alice");
+
+        let game_id = Uuid::parse_str(&game_id).unwrap();
+        let game = manager.get_game(game_id).await.unwrap().unwrap();
+        let votes = manager
+            .get_round_votes(game_id, game.round_id)
+            .await
+            .unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].value, "5");
+    }
+
+    #[tokio::test]
+    async fn test_vote_from_player_outside_the_game_is_rejected() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        manager
+            .start_voting(game.id, "Story".to_string())
+            .await
+            .unwrap();
+
+        let (status, body) = send(
+            &manager,
+            "POST",
+            &format!("/api/v1/games/{}/vote", game.id),
+            Some(json!({ "player_id": Uuid::new_v4(), "vote": "5" })),
+        )
+        .await;
+
+        assert_eq!(status, 404);
+        assert_eq!(body["error"], "Player not in game");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_routes_and_games_are_not_found() {
+        let manager = database_session_manager().await;
+
+        let (status, _) = send(&manager, "DELETE", "/api/v1/games", None).await;
+        assert_eq!(status, 404);
+
+        let (status, _) = send(
+            &manager,
+            "GET",
+            &format!("/api/v1/games/{}", Uuid::new_v4()),
+            None,
+        )
+        .await;
+        assert_eq!(status, 404);
+
+        let (status, _) = send(&manager, "GET", "/api/v1/games/not-a-uuid", None).await;
+        assert_eq!(status, 400);
+    }
+}
@@ -10,7 +10,7 @@ pub fn start(sim: &mut impl Sim) {
     sim.host(HOST, move || {
         let addr = addr.clone();
         async move {
-            log::debug!("starting Planning Poker server simulation");
+            tracing::debug!("starting Planning Poker server simulation");
 
             // Run the server simulation
             run_until_simulation_cancelled(run_server_simulation(&addr))
@@ -21,20 +21,20 @@ pub fn start(sim: &mut impl Sim) {
                         as Box<dyn std::error::Error + Send>
                 })?;
 
-            log::debug!("finished Planning Poker server simulation");
+            tracing::debug!("finished Planning Poker server simulation");
             Ok(())
         }
     });
 }
 
-async fn run_server_simulation(_addr: &str) -> Result<(), crate::Error> {
+async fn run_server_simulation(addr: &str) -> Result<(), crate::Error> {
     use planning_poker_database::{create_connection, DatabaseConfig};
     use planning_poker_session::{DatabaseSessionManager, SessionManager};
-    use simvar::switchy::unsync::time::sleep;
+    use simvar::switchy::{tcp::TcpListener, unsync::time::sleep};
     use std::sync::Arc;
     use switchy::unsync::sync::RwLock;
 
-    log::info!("Starting Planning Poker server simulation");
+    tracing::info!("Starting Planning Poker server simulation");
 
     // Initialize database and session manager
     let config = DatabaseConfig {
@@ -54,15 +54,36 @@ async fn run_server_simulation(_addr: &str) -> Result<(), crate::Error> {
 
     let session_manager = Arc::new(RwLock::new(session_manager));
 
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| std::io::Error::other(format!("Failed to bind {addr}: {e}")))?;
+    tracing::info!("Planning Poker server listening on {addr}");
+
     // Main server loop
     loop {
+        // Serve the next connection, or wake up to run maintenance
+        switchy::unsync::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    tracing::debug!("Accepted connection from {peer}");
+                    let session_manager_guard = session_manager.read().await;
+                    if let Err(e) = serve_connection(&*session_manager_guard, stream).await {
+                        tracing::warn!("Failed to serve connection from {peer}: {e}");
+                    }
+                }
+                Err(e) => tracing::error!("Failed to accept connection: {e}"),
+            },
+            () = sleep(std::time::Duration::from_millis(10)) => {}
+        }
+
         // Process any pending session updates
         let session_manager_guard = session_manager.read().await;
         session_manager_guard.cleanup_expired_sessions().await.ok();
-        drop(session_manager_guard);
 
-        // Simulate server processing time
-        sleep(std::time::Duration::from_millis(10)).await;
+        // Verify assertions queued by clients that finished their scenario
+        let verified = crate::verify_assertions(&*session_manager_guard).await;
+        drop(session_manager_guard);
+        verified?;
 
         // Check if simulation should continue
         if simvar::utils::is_simulator_cancelled() {
@@ -70,6 +91,43 @@ async fn run_server_simulation(_addr: &str) -> Result<(), crate::Error> {
         }
     }
 
-    log::info!("Planning Poker server simulation completed");
+    // Verify anything queued by clients that finished on the last tick
+    crate::verify_assertions(&*session_manager.read().await).await?;
+
+    tracing::info!("Planning Poker server simulation completed");
+    Ok(())
+}
+
+/// Reads one request from `stream` and answers it, letting the connection close
+/// unless the request upgrades it to a WebSocket
+async fn serve_connection(
+    session_manager: &dyn planning_poker_session::SessionManager,
+    mut stream: simvar::switchy::tcp::TcpStream,
+) -> Result<(), crate::Error> {
+    use simvar::switchy::unsync::io::AsyncWriteExt;
+
+    use crate::http::{format_http_response, parse_http_request, read_http_request};
+
+    let mut request = String::new();
+    let Some(request) = read_http_request(&mut request, Box::pin(&mut stream)).await? else {
+        return Ok(());
+    };
+
+    if super::websocket::is_upgrade_request(&request) {
+        return super::websocket::serve_websocket(session_manager, stream).await;
+    }
+
+    let (status, body) = match parse_http_request(&request) {
+        Ok(request) => super::api::handle_request(session_manager, &request).await,
+        Err(e) => (
+            400,
+            serde_json::json!({ "error": e.to_string() }).to_string(),
+        ),
+    };
+
+    stream
+        .write_all(format_http_response(status, &body).as_bytes())
+        .await?;
+
     Ok(())
 }
@@ -0,0 +1,455 @@
+//! The WebSocket endpoint the simulated server answers at [`WEBSOCKET_PATH`]
+//!
+//! It speaks the same `ClientMessage`/`ServerMessage` JSON protocol as the real
+//! server for the join, start, vote and reveal flow, calling straight into the
+//! session manager like the HTTP routes in [`super::api`].
+
+use chrono::Utc;
+use planning_poker_models::{
+    ClientMessage, ErrorCode, Player, PlayerRole, ServerMessage, TeamRole, Vote,
+};
+use planning_poker_session::SessionManager;
+use simvar::switchy::{
+    tcp::TcpStream,
+    unsync::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        time::sleep,
+    },
+};
+use uuid::Uuid;
+
+use crate::{
+    websocket::{
+        encode_server_frame, next_frame, CLOSE_MESSAGE_TOO_BIG, DEFAULT_MAX_FRAME_SIZE,
+        OPCODE_CLOSE, OPCODE_PING, OPCODE_PONG, OPCODE_TEXT, WEBSOCKET_PATH,
+    },
+    Error,
+};
+
+/// How long a connection may sit without sending anything before it is dropped
+///
+/// The host serves one connection at a time, so a stalled client must not hold
+/// it forever.
+const IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// The reply that switches the connection over to WebSocket frames.
+///
+/// The simulated client does not check `Sec-WebSocket-Accept`, so it is left out.
+const HANDSHAKE_RESPONSE: &str = "HTTP/1.1 101 Switching Protocols\r\n\
+                                  Upgrade: websocket\r\n\
+                                  Connection: Upgrade\r\n\
+                                  \r\n";
+
+/// Whether `request` asks to upgrade to a WebSocket at [`WEBSOCKET_PATH`]
+#[must_use]
+pub fn is_upgrade_request(request: &str) -> bool {
+    let mut lines = request.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+
+    request_line.next() == Some("GET")
+        && request_line.next() == Some(WEBSOCKET_PATH)
+        && lines.take_while(|line| !line.is_empty()).any(|line| {
+            line.split_once(':').is_some_and(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("upgrade")
+                    && value.trim().eq_ignore_ascii_case("websocket")
+            })
+        })
+}
+
+/// Completes the upgrade handshake and serves frames until the client closes,
+/// goes quiet for [`IDLE_TIMEOUT`] or the connection drops
+///
+/// # Errors
+///
+/// Returns an error if reading or writing the stream fails.
+pub async fn serve_websocket(
+    session_manager: &dyn SessionManager,
+    mut stream: TcpStream,
+) -> Result<(), Error> {
+    stream.write_all(HANDSHAKE_RESPONSE.as_bytes()).await?;
+
+    let mut connection = Connection::new(session_manager);
+    let mut buf = Vec::new();
+
+    loop {
+        let outgoing = connection.process(&mut buf).await?;
+        if !outgoing.bytes.is_empty() {
+            stream.write_all(&outgoing.bytes).await?;
+        }
+        if outgoing.close {
+            return Ok(());
+        }
+
+        let mut chunk = [0_u8; 4096];
+        let count = switchy::unsync::select! {
+            read = stream.read(&mut chunk) => read?,
+            () = sleep(IDLE_TIMEOUT) => {
+                tracing::debug!("Dropping WebSocket connection idle for {IDLE_TIMEOUT:?}");
+                return Ok(());
+            }
+        };
+        if count == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..count]);
+    }
+}
+
+/// Frames to write back after processing what was buffered
+#[derive(Debug, Default)]
+struct Outgoing {
+    bytes: Vec<u8>,
+    /// The connection is done once `bytes` is written
+    close: bool,
+}
+
+/// The game and player a WebSocket connection joined as
+struct Connection<'a> {
+    session_manager: &'a dyn SessionManager,
+    joined: Option<(Uuid, Uuid)>,
+}
+
+impl<'a> Connection<'a> {
+    fn new(session_manager: &'a dyn SessionManager) -> Self {
+        Self {
+            session_manager,
+            joined: None,
+        }
+    }
+
+    /// Handles every complete frame in `buf`, leaving a partial one for the next read
+    ///
+    /// A frame over [`DEFAULT_MAX_FRAME_SIZE`] is answered with a "message too big"
+    /// close, the same way the client treats one from the server.
+    async fn process(&mut self, buf: &mut Vec<u8>) -> Result<Outgoing, Error> {
+        let mut outgoing = Outgoing::default();
+
+        loop {
+            let frame = match next_frame(buf, DEFAULT_MAX_FRAME_SIZE) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("Rejecting inbound WebSocket frame: {e}");
+                    buf.clear();
+                    outgoing.bytes.extend(encode_server_frame(
+                        OPCODE_CLOSE,
+                        &CLOSE_MESSAGE_TOO_BIG.to_be_bytes(),
+                    ));
+                    outgoing.close = true;
+                    break;
+                }
+            };
+
+            match frame.opcode {
+                OPCODE_TEXT => {
+                    let reply = match serde_json::from_slice(&frame.payload) {
+                        Ok(message) => self.handle(message).await,
+                        Err(e) => error(ErrorCode::InvalidRequest, format!("Invalid message: {e}")),
+                    };
+                    let payload = serde_json::to_vec(&reply)?;
+                    outgoing
+                        .bytes
+                        .extend(encode_server_frame(OPCODE_TEXT, &payload));
+                }
+                OPCODE_PING => outgoing
+                    .bytes
+                    .extend(encode_server_frame(OPCODE_PONG, &frame.payload)),
+                OPCODE_CLOSE => {
+                    outgoing
+                        .bytes
+                        .extend(encode_server_frame(OPCODE_CLOSE, &frame.payload));
+                    outgoing.close = true;
+                    break;
+                }
+                opcode => tracing::trace!("Ignoring WebSocket frame with opcode {opcode:#x}"),
+            }
+        }
+
+        Ok(outgoing)
+    }
+
+    /// Applies one client message, returning the reply for this connection
+    async fn handle(&mut self, message: ClientMessage) -> ServerMessage {
+        let result = match message {
+            ClientMessage::JoinGame {
+                game_id,
+                player_name,
+            } => self.join(game_id, player_name).await,
+            ClientMessage::StartVoting { story } => self.start_voting(story).await,
+            ClientMessage::CastVote { value } => self.cast_vote(value).await,
+            ClientMessage::RevealVotes => self.reveal_votes().await,
+            message => Err(error(
+                ErrorCode::InvalidRequest,
+                format!("{message:?} is not supported by the simulated server"),
+            )),
+        };
+
+        result.unwrap_or_else(|reply| reply)
+    }
+
+    /// Joins `game_id` as a voter, re-attaching to a player of the same name
+    /// so a client can rejoin after a dropped connection
+    async fn join(
+        &mut self,
+        game_id: Uuid,
+        player_name: String,
+    ) -> Result<ServerMessage, ServerMessage> {
+        let game = self
+            .session_manager
+            .get_game(game_id)
+            .await
+            .map_err(|e| error(ErrorCode::Internal, e.to_string()))?
+            .ok_or_else(|| error(ErrorCode::GameNotFound, "Game not found".to_string()))?;
+
+        let mut players = self
+            .session_manager
+            .get_game_players(game_id)
+            .await
+            .map_err(|e| error(ErrorCode::Internal, e.to_string()))?;
+
+        let player_id = if let Some(player) = players.iter().find(|p| p.name == player_name) {
+            player.id
+        } else {
+            let player = Player {
+                id: Uuid::new_v4(),
+                name: player_name,
+                role: PlayerRole::Voter,
+                team_role: TeamRole::default(),
+                joined_at: Utc::now(),
+            };
+            self.session_manager
+                .add_player_to_game(game_id, player.clone())
+                .await
+                .map_err(|e| error(ErrorCode::InvalidState, e.to_string()))?;
+            let player_id = player.id;
+            players.push(player);
+            player_id
+        };
+
+        self.joined = Some((game_id, player_id));
+        Ok(ServerMessage::GameJoined { game, players })
+    }
+
+    async fn start_voting(&self, story: String) -> Result<ServerMessage, ServerMessage> {
+        let (game_id, _) = self.joined()?;
+
+        self.session_manager
+            .start_voting(game_id, story.clone())
+            .await
+            .map_err(|e| error(ErrorCode::InvalidState, e.to_string()))?;
+
+        Ok(ServerMessage::VotingStarted { story })
+    }
+
+    async fn cast_vote(&self, value: String) -> Result<ServerMessage, ServerMessage> {
+        let (game_id, player_id) = self.joined()?;
+
+        let player = self
+            .session_manager
+            .get_player(game_id, player_id)
+            .await
+            .map_err(|e| error(ErrorCode::Internal, e.to_string()))?
+            .ok_or_else(|| error(ErrorCode::PlayerNotInGame, "Player not in game".to_string()))?;
+
+        self.session_manager
+            .cast_vote(
+                game_id,
+                Vote {
+                    player_id,
+                    player_name: player.name,
+                    value,
+                    cast_at: Utc::now(),
+                    confidence: None,
+                },
+            )
+            .await
+            .map_err(|e| error(ErrorCode::InvalidState, e.to_string()))?;
+
+        Ok(ServerMessage::VoteCast {
+            player_id,
+            has_voted: true,
+        })
+    }
+
+    async fn reveal_votes(&self) -> Result<ServerMessage, ServerMessage> {
+        let (game_id, _) = self.joined()?;
+
+        self.session_manager
+            .reveal_votes(game_id)
+            .await
+            .map_err(|e| error(ErrorCode::InvalidState, e.to_string()))?;
+        let votes = self
+            .session_manager
+            .get_game_votes(game_id)
+            .await
+            .map_err(|e| error(ErrorCode::Internal, e.to_string()))?;
+
+        Ok(ServerMessage::VotesRevealed { votes })
+    }
+
+    /// The game and player this connection joined as, failing if it has not joined yet
+    fn joined(&self) -> Result<(Uuid, Uuid), ServerMessage> {
+        self.joined
+            .ok_or_else(|| error(ErrorCode::PlayerNotInGame, "Join a game first".to_string()))
+    }
+}
+
+fn error(code: ErrorCode, message: String) -> ServerMessage {
+    ServerMessage::Error { code, message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::{decode_frame, encode_frame};
+    use planning_poker_session::test_support::database_session_manager;
+
+    async fn send(connection: &mut Connection<'_>, message: &ClientMessage) -> ServerMessage {
+        let mut buf = encode_frame(OPCODE_TEXT, &serde_json::to_vec(message).unwrap(), [7; 4]);
+        let outgoing = connection.process(&mut buf).await.unwrap();
+        let (frame, used) = decode_frame(&outgoing.bytes).unwrap();
+
+        assert!(buf.is_empty());
+        assert_eq!(used, outgoing.bytes.len());
+        assert_eq!(frame.opcode, OPCODE_TEXT);
+        serde_json::from_slice(&frame.payload).unwrap()
+    }
+
+    #[test]
+    fn test_only_websocket_upgrades_of_the_ws_path_are_detected() {
+        let upgrade = format!(
+            "GET {WEBSOCKET_PATH} HTTP/1.1\r\nHost: h\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\r\n"
+        );
+
+        assert!(is_upgrade_request(&upgrade));
+        assert!(!is_upgrade_request(&upgrade.replace("GET", "POST")));
+        assert!(!is_upgrade_request(&format!(
+            "GET {WEBSOCKET_PATH} HTTP/1.1\r\nHost: h\r\n\r\n"
+        )));
+        assert!(!is_upgrade_request(
+            "GET /api/v1/games HTTP/1.1\r\nUpgrade: websocket\r\n\r\n"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_client_can_join_start_vote_and_reveal() {
+        let manager = database_session_manager().await;
+        let game = manager
+            .create_game("Game".to_string(), "fibonacci".to_string(), Uuid::new_v4())
+            .await
+            .unwrap();
+        let mut connection = Connection::new(&manager);
+
+        let join = ClientMessage::JoinGame {
+            game_id: game.id,
+            player_name: "Alice".to_string(),
+        };
+        let ServerMessage::GameJoined { players, .. } = send(&mut connection, &join).await else {
+            panic!("expected GameJoined");
+        };
+        assert_eq!(players.len(), 1);
+        let alice = players[0].id;
+
+        // Rejoining over a new connection re-attaches the same player
+        let mut connection = Connection::new(&manager);
+        let ServerMessage::GameJoined { players, .. } = send(&mut connection, &join).await else {
+            panic!("expected GameJoined");
+        };
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].id, alice);
+
+        let start = ClientMessage::StartVoting {
+            story: "Login page".to_string(),
+        };
+        assert!(matches!(
+            send(&mut connection, &start).await,
+            ServerMessage::VotingStarted { story } if story == "Login page"
+        ));
+
+        let vote = ClientMessage::CastVote {
+            value: "5".to_string(),
+        };
+        assert!(matches!(
+            send(&mut connection, &vote).await,
+            ServerMessage::VoteCast { player_id, has_voted: true } if player_id == alice
+        ));
+
+        let ServerMessage::VotesRevealed { votes } =
+            send(&mut connection, &ClientMessage::RevealVotes).await
+        else {
+            panic!("expected VotesRevealed");
+        };
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].value, "5");
+    }
+
+    #[tokio::test]
+    async fn test_messages_before_joining_and_unsupported_messages_are_errors() {
+        let manager = database_session_manager().await;
+        let mut connection = Connection::new(&manager);
+
+        let vote = ClientMessage::CastVote {
+            value: "5".to_string(),
+        };
+        assert!(matches!(
+            send(&mut connection, &vote).await,
+            ServerMessage::Error {
+                code: ErrorCode::PlayerNotInGame,
+                ..
+            }
+        ));
+        assert!(matches!(
+            send(&mut connection, &ClientMessage::LeaveGame).await,
+            ServerMessage::Error {
+                code: ErrorCode::InvalidRequest,
+                ..
+            }
+        ));
+
+        let join = ClientMessage::JoinGame {
+            game_id: Uuid::new_v4(),
+            player_name: "Alice".to_string(),
+        };
+        assert!(matches!(
+            send(&mut connection, &join).await,
+            ServerMessage::Error {
+                code: ErrorCode::GameNotFound,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_frame_is_answered_with_a_message_too_big_close() {
+        let manager = database_session_manager().await;
+        let mut connection = Connection::new(&manager);
+
+        // Only the header of a frame announcing 2^40 bytes has arrived
+        let mut buf = vec![0x80 | OPCODE_TEXT, 0x80 | 127];
+        buf.extend_from_slice(&(1_u64 << 40).to_be_bytes());
+        let outgoing = connection.process(&mut buf).await.unwrap();
+
+        let (close, _) = decode_frame(&outgoing.bytes).unwrap();
+        assert_eq!(close.opcode, OPCODE_CLOSE);
+        assert_eq!(close.payload, CLOSE_MESSAGE_TOO_BIG.to_be_bytes());
+        assert!(outgoing.close);
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pings_are_answered_and_close_ends_the_connection() {
+        let manager = database_session_manager().await;
+        let mut connection = Connection::new(&manager);
+
+        let mut buf = encode_frame(OPCODE_PING, b"hi", [1, 2, 3, 4]);
+        buf.extend(encode_frame(OPCODE_CLOSE, &[], [5, 6, 7, 8]));
+        let outgoing = connection.process(&mut buf).await.unwrap();
+
+        let (pong, used) = decode_frame(&outgoing.bytes).unwrap();
+        let (close, _) = decode_frame(&outgoing.bytes[used..]).unwrap();
+        assert_eq!(pong.opcode, OPCODE_PONG);
+        assert_eq!(pong.payload, b"hi");
+        assert_eq!(close.opcode, OPCODE_CLOSE);
+        assert!(outgoing.close);
+    }
+}
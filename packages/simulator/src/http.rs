@@ -1,8 +1,19 @@
 use std::pin::Pin;
 
-use simvar::switchy::unsync::io::AsyncReadExt;
+use simvar::switchy::{
+    tcp::TcpStream,
+    unsync::io::{AsyncReadExt, AsyncWriteExt},
+};
 
-use crate::Error;
+use crate::{network_delay, Error};
+
+/// An HTTP request as received by the simulated server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
 
 /// Reads an HTTP response from a stream until the complete response is received.
 ///
@@ -11,6 +22,26 @@ use crate::Error;
 /// Returns an error if reading from the stream fails or if the response is malformed.
 pub async fn read_http_response(
     response: &mut String,
+    stream: Pin<Box<impl AsyncReadExt>>,
+) -> Result<Option<String>, Error> {
+    read_http_message(response, stream).await
+}
+
+/// Reads an HTTP request from a stream until the complete request is received.
+///
+/// # Errors
+///
+/// Returns an error if reading from the stream fails or if the request is malformed.
+pub async fn read_http_request(
+    request: &mut String,
+    stream: Pin<Box<impl AsyncReadExt>>,
+) -> Result<Option<String>, Error> {
+    read_http_message(request, stream).await
+}
+
+/// Reads until `message` holds the headers and the body its `Content-Length` announces
+async fn read_http_message(
+    message: &mut String,
     mut stream: Pin<Box<impl AsyncReadExt>>,
 ) -> Result<Option<String>, Error> {
     let mut buf = [0_u8; 4096];
@@ -19,25 +50,90 @@ pub async fn read_http_response(
         let count = match stream.read(&mut buf).await {
             Ok(count) => count,
             Err(e) => {
-                log::error!("read_http_response: failed to read from stream: {e:?}");
+                tracing::error!("read_http_message: failed to read from stream: {e:?}");
                 break None;
             }
         };
         if count == 0 {
-            log::debug!("read_http_response: received empty response");
+            tracing::debug!("read_http_message: received empty message");
             break None;
         }
-        log::trace!("read count={count}");
+        tracing::trace!("read count={count}");
         let value = String::from_utf8_lossy(&buf[..count]).to_string();
-        response.push_str(&value);
+        message.push_str(&value);
 
-        // Look for end of HTTP response (double CRLF)
-        if response.contains("\r\n\r\n") {
-            break Some(response.clone());
+        if is_complete_message(message) {
+            break Some(message.clone());
         }
     })
 }
 
+/// Whether `message` holds its full headers (up to the double CRLF) and body
+fn is_complete_message(message: &str) -> bool {
+    let Some(header_end) = message.find("\r\n\r\n") else {
+        return false;
+    };
+    let content_length = message[..header_end]
+        .split("\r\n")
+        .skip(1)
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                value.trim().parse::<usize>().ok()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0);
+
+    message.len() >= header_end + 4 + content_length
+}
+
+/// Parses an HTTP request string into its method, path and body.
+///
+/// # Errors
+///
+/// Returns an error if the request line is missing its method or path.
+pub fn parse_http_request(request: &str) -> Result<HttpRequest, Error> {
+    let (head, body) = request.split_once("\r\n\r\n").unwrap_or((request, ""));
+    let request_line = head.split("\r\n").next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(Error::IO(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Invalid HTTP request line",
+        )));
+    };
+
+    Ok(HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        body: body.to_string(),
+    })
+}
+
+/// Formats a complete HTTP/1.1 response with a JSON body, closing the connection after it
+#[must_use]
+pub fn format_http_response(status: u16, body: &str) -> String {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        409 => "Conflict",
+        _ => "Internal Server Error",
+    };
+
+    format!(
+        "HTTP/1.1 {status} {reason}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {content_length}\r\n\
+         Connection: close\r\n\
+         \r\n{body}",
+        content_length = body.len()
+    )
+}
+
 /// Parses an HTTP response string and extracts the status code and body.
 ///
 /// # Errors
@@ -89,3 +185,66 @@ pub fn parse_http_response(response: &str) -> Result<(u16, String), Error> {
 
     Ok((status_code, body))
 }
+
+/// Formats a complete HTTP/1.1 request, closing the connection after its response
+#[must_use]
+pub fn format_http_request(
+    server_addr: &str,
+    method: &str,
+    path: &str,
+    body: Option<&str>,
+    content_type: Option<&str>,
+) -> String {
+    let content_length = body.map_or(0, str::len);
+    let content_type_header =
+        content_type.map_or(String::new(), |ct| format!("Content-Type: {ct}\r\n"));
+
+    format!(
+        "{method} {path} HTTP/1.1\r\n\
+         Host: {server_addr}\r\n\
+         {content_type_header}Content-Length: {content_length}\r\n\
+         Connection: close\r\n\
+         \r\n{body}",
+        body = body.unwrap_or("")
+    )
+}
+
+/// Sends a single HTTP request to `server_addr` and returns the response status and body.
+///
+/// # Errors
+///
+/// Returns an error if connecting, writing the request, or reading a valid response fails.
+pub async fn make_http_request(
+    server_addr: &str,
+    method: &str,
+    path: &str,
+    body: Option<&str>,
+    content_type: Option<&str>,
+) -> Result<(u16, String), Box<dyn std::error::Error + Send>> {
+    network_delay()
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+
+    let mut connection = TcpStream::connect(server_addr)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+
+    let request = format_http_request(server_addr, method, path, body, content_type);
+
+    connection
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+
+    let mut response = String::new();
+    if let Some(response_data) = read_http_response(&mut response, Box::pin(&mut connection))
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?
+    {
+        let (status, body) = parse_http_response(&response_data)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+        Ok((status, body))
+    } else {
+        Err(Box::new(std::io::Error::other("No HTTP response received")))
+    }
+}
@@ -0,0 +1,186 @@
+use planning_poker_models::{Game, GameState, Player, Vote};
+
+/// A game never holds more votes than it has players who are allowed to vote
+///
+/// # Errors
+///
+/// Returns a description of the violation.
+pub fn votes_within_voter_count(
+    game: &Game,
+    players: &[Player],
+    votes: &[Vote],
+) -> Result<(), String> {
+    let voters = players
+        .iter()
+        .filter(|player| player.role.can_vote())
+        .count();
+
+    if votes.len() > voters {
+        return Err(format!(
+            "Game {} has {} votes but only {voters} voting players",
+            game.id,
+            votes.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Every vote was cast by a player who is still in the game
+///
+/// # Errors
+///
+/// Returns a description of the violation.
+pub fn votes_reference_players(
+    game: &Game,
+    players: &[Player],
+    votes: &[Vote],
+) -> Result<(), String> {
+    for vote in votes {
+        if !players.iter().any(|player| player.id == vote.player_id) {
+            return Err(format!(
+                "Game {} has a vote from {} ({}) who is not a player",
+                game.id, vote.player_id, vote.player_name
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// A game is not left in the voting state after every player is gone
+///
+/// # Errors
+///
+/// Returns a description of the violation.
+pub fn voting_has_players(game: &Game, players: &[Player]) -> Result<(), String> {
+    if game.state == GameState::Voting && players.is_empty() {
+        return Err(format!(
+            "Game {} is stuck in Voting with no players",
+            game.id
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check every invariant for one game, collecting all violations
+///
+/// # Errors
+///
+/// Returns the descriptions of every violated invariant.
+pub fn check_game(game: &Game, players: &[Player], votes: &[Vote]) -> Result<(), Vec<String>> {
+    let violations: Vec<String> = [
+        votes_within_voter_count(game, players, votes),
+        votes_reference_players(game, players, votes),
+        voting_has_players(game, players),
+    ]
+    .into_iter()
+    .filter_map(Result::err)
+    .collect();
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use planning_poker_models::{GameSettings, PlayerRole, TeamRole};
+    use uuid::Uuid;
+
+    fn game(state: GameState) -> Game {
+        Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state,
+            current_story: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            settings: GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        }
+    }
+
+    fn player(name: &str, role: PlayerRole) -> Player {
+        Player {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            role,
+            team_role: TeamRole::default(),
+            joined_at: Utc::now(),
+        }
+    }
+
+    fn vote(player: &Player) -> Vote {
+        Vote {
+            player_id: player.id,
+            player_name: player.name.clone(),
+            value: "5".to_string(),
+            cast_at: Utc::now(),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_consistent_game_passes() {
+        let game = game(GameState::Voting);
+        let alice = player("Alice", PlayerRole::Voter);
+        let bob = player("Bob", PlayerRole::Observer);
+
+        assert!(check_game(&game, &[alice.clone(), bob], &[vote(&alice)]).is_ok());
+    }
+
+    #[test]
+    fn test_observers_do_not_count_as_voters() {
+        let game = game(GameState::Revealed);
+        let alice = player("Alice", PlayerRole::Voter);
+        let bob = player("Bob", PlayerRole::Observer);
+
+        let result = votes_within_voter_count(
+            &game,
+            &[alice.clone(), bob.clone()],
+            &[vote(&alice), vote(&bob)],
+        );
+
+        assert!(result
+            .unwrap_err()
+            .contains("2 votes but only 1 voting players"));
+    }
+
+    #[test]
+    fn test_vote_from_departed_player_is_rejected() {
+        let game = game(GameState::Revealed);
+        let alice = player("Alice", PlayerRole::Voter);
+        let ghost = player("Ghost", PlayerRole::Voter);
+
+        assert!(votes_reference_players(&game, &[alice.clone()], &[vote(&alice)]).is_ok());
+        assert!(votes_reference_players(&game, &[alice], &[vote(&ghost)]).is_err());
+    }
+
+    #[test]
+    fn test_empty_voting_game_is_stuck() {
+        assert!(voting_has_players(&game(GameState::Voting), &[]).is_err());
+        assert!(voting_has_players(&game(GameState::Waiting), &[]).is_ok());
+        assert!(voting_has_players(&game(GameState::Revealed), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_check_game_reports_every_violation() {
+        let game = game(GameState::Voting);
+        let ghost = player("Ghost", PlayerRole::Voter);
+
+        let violations = check_game(&game, &[], &[vote(&ghost)]).unwrap_err();
+
+        assert_eq!(violations.len(), 3);
+    }
+}
@@ -3,19 +3,53 @@
 #![allow(clippy::multiple_crate_versions)]
 
 use std::{
-    collections::VecDeque,
+    collections::{BTreeSet, VecDeque},
+    fmt::Write as _,
     sync::{Arc, LazyLock, Mutex},
+    time::Duration,
 };
 
-use simvar::Sim;
+use planning_poker_session::SessionManager;
+use simvar::{switchy::unsync::time::sleep, Sim};
 
+pub mod assertions;
 pub mod client;
 pub mod host;
 pub mod http;
+pub mod invariants;
+pub mod random;
+pub mod websocket;
+
+pub use assertions::{
+    GameStateAssertion, PlayerCountAssertion, SimulationAssertion, StateConsistencyAssertion,
+    VoteCountAssertion,
+};
 
 static ACTIONS: LazyLock<Arc<Mutex<VecDeque<Action>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(VecDeque::new())));
 
+/// Counts and timings gathered while the simulation runs
+static METRICS: LazyLock<Arc<Mutex<SimulationMetrics>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(SimulationMetrics::default())));
+
+/// Players currently cut off from the host by a network partition
+static PARTITIONED_PLAYERS: LazyLock<Arc<Mutex<BTreeSet<uuid::Uuid>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(BTreeSet::new())));
+
+/// Degraded network conditions applied to every message a client sends
+static NETWORK_CONDITIONS: LazyLock<Arc<Mutex<NetworkConditions>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(NetworkConditions::default())));
+
+/// Time a client waits before resending a lost message
+const RETRANSMIT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Resends attempted before a lost message is reported as a timeout
+const MAX_RETRANSMITS: usize = 5;
+
+/// Assertions collected by `handle_actions`, waiting to be verified by the server
+static ASSERTIONS: LazyLock<Arc<Mutex<Vec<Arc<dyn SimulationAssertion>>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(Vec::new())));
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
@@ -28,6 +62,143 @@ pub enum Error {
     Database(String),
     #[error("Game error: {0}")]
     Game(String),
+    #[error("Assertion failed: {0}")]
+    Assertion(String),
+}
+
+/// What a simulation run did, for the summary printed once it is over
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulationMetrics {
+    pub games_created: usize,
+    pub votes_cast: usize,
+    pub reconnections: usize,
+    pub disconnections: usize,
+    /// Mean round trip of the votes in `votes_cast`
+    pub average_vote_latency_ms: f64,
+    pub simulation_duration_ms: u64,
+}
+
+impl SimulationMetrics {
+    /// Counts a vote that took `latency_ms` to be accepted
+    pub fn record_vote(&mut self, latency_ms: u64) {
+        self.votes_cast += 1;
+        #[allow(clippy::cast_precision_loss)]
+        let (latency_ms, votes_cast) = (latency_ms as f64, self.votes_cast as f64);
+        self.average_vote_latency_ms += (latency_ms - self.average_vote_latency_ms) / votes_cast;
+    }
+
+    /// The metrics as a two-column table
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let rows = [
+            ("Games created", self.games_created.to_string()),
+            ("Votes cast", self.votes_cast.to_string()),
+            (
+                "Average vote latency",
+                format!("{:.1} ms", self.average_vote_latency_ms),
+            ),
+            ("Disconnections", self.disconnections.to_string()),
+            ("Reconnections", self.reconnections.to_string()),
+            (
+                "Simulation duration",
+                format!("{} ms", self.simulation_duration_ms),
+            ),
+        ];
+
+        let mut summary = format!("{:<24}{:>14}\n{}\n", "Metric", "Value", "-".repeat(38));
+        for (name, value) in rows {
+            let _ = writeln!(summary, "{name:<24}{value:>14}");
+        }
+        summary
+    }
+
+    /// Print the [`summary`](Self::summary) table to stdout
+    ///
+    /// This is the simulator's only direct output: the table is the run's
+    /// result, meant to be read or diffed whatever the log level. Everything
+    /// else goes through `tracing`.
+    pub fn print_summary(&self) {
+        println!("{}", self.summary());
+    }
+
+    /// Fails the run unless at least `n` votes were cast, showing that the
+    /// simulation really went through the voting path
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `n` votes were cast.
+    pub fn assert_minimum_votes(&self, n: usize) {
+        assert!(
+            self.votes_cast >= n,
+            "Expected the simulation to cast at least {n} votes, but only {} were cast",
+            self.votes_cast
+        );
+    }
+}
+
+/// The metrics gathered so far
+///
+/// # Panics
+///
+/// Panics if the global metrics mutex is poisoned.
+#[must_use]
+pub fn metrics() -> SimulationMetrics {
+    METRICS.lock().unwrap().clone()
+}
+
+/// Clears the metrics so the next run starts counting from zero
+///
+/// # Panics
+///
+/// Panics if the global metrics mutex is poisoned.
+pub fn reset_metrics() {
+    *METRICS.lock().unwrap() = SimulationMetrics::default();
+}
+
+/// Counts a game created by a client.
+///
+/// # Panics
+///
+/// Panics if the global metrics mutex is poisoned.
+pub fn record_game_created() {
+    METRICS.lock().unwrap().games_created += 1;
+}
+
+/// Counts a vote the server accepted after `latency_ms`.
+///
+/// # Panics
+///
+/// Panics if the global metrics mutex is poisoned.
+pub fn record_vote_cast(latency_ms: u64) {
+    METRICS.lock().unwrap().record_vote(latency_ms);
+}
+
+/// Counts `count` players cut off from the host.
+///
+/// # Panics
+///
+/// Panics if the global metrics mutex is poisoned.
+pub fn record_disconnections(count: usize) {
+    METRICS.lock().unwrap().disconnections += count;
+}
+
+/// Counts `count` players reconnected to the host.
+///
+/// # Panics
+///
+/// Panics if the global metrics mutex is poisoned.
+pub fn record_reconnections(count: usize) {
+    METRICS.lock().unwrap().reconnections += count;
+}
+
+/// Records how long the whole simulation took.
+///
+/// # Panics
+///
+/// Panics if the global metrics mutex is poisoned.
+pub fn record_simulation_duration(duration: Duration) {
+    METRICS.lock().unwrap().simulation_duration_ms =
+        u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
 }
 
 #[derive(Debug, Clone)]
@@ -36,6 +207,15 @@ enum Action {
     ReconnectPlayer(uuid::Uuid),
     NetworkPartition(Vec<uuid::Uuid>),
     RestoreNetwork,
+    SetLatency(Duration),
+    SetPacketLoss(f64),
+    Assert(Arc<dyn SimulationAssertion>),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct NetworkConditions {
+    latency: Duration,
+    packet_loss: f64,
 }
 
 /// Queues a player disconnection action for the next simulation step.
@@ -62,7 +242,8 @@ pub fn queue_reconnect_player(player_id: uuid::Uuid) {
         .push_back(Action::ReconnectPlayer(player_id));
 }
 
-/// Queues a network partition action that will disconnect the specified players.
+/// Queues a network partition action that cuts the specified players off from the host
+/// until `queue_restore_network` runs.
 ///
 /// # Panics
 ///
@@ -83,6 +264,124 @@ pub fn queue_restore_network() {
     ACTIONS.lock().unwrap().push_back(Action::RestoreNetwork);
 }
 
+/// Queues an action that delays every message clients send by `latency`.
+///
+/// Queue `Duration::ZERO` to remove the delay again.
+///
+/// # Panics
+///
+/// Panics if the global actions mutex is poisoned.
+pub fn queue_set_latency(latency: Duration) {
+    ACTIONS
+        .lock()
+        .unwrap()
+        .push_back(Action::SetLatency(latency));
+}
+
+/// Queues an action that loses each message clients send with probability `packet_loss`.
+///
+/// The probability is clamped to `0.0..=1.0`; queue `0.0` to stop losing messages.
+///
+/// # Panics
+///
+/// Panics if the global actions mutex is poisoned.
+pub fn queue_set_packet_loss(packet_loss: f64) {
+    ACTIONS
+        .lock()
+        .unwrap()
+        .push_back(Action::SetPacketLoss(packet_loss));
+}
+
+/// Whether the player is on the far side of an active network partition.
+///
+/// Clients must hold back any traffic to the host while this returns `true`.
+///
+/// # Panics
+///
+/// Panics if the global partition mutex is poisoned.
+#[must_use]
+pub fn is_partitioned(player_id: uuid::Uuid) -> bool {
+    PARTITIONED_PLAYERS.lock().unwrap().contains(&player_id)
+}
+
+/// Holds a client back as the current network conditions would hold back one message.
+///
+/// Every message pays the injected latency. A lost message is resent after
+/// `RETRANSMIT_TIMEOUT`, the way TCP would, so loss shows up as extra delay.
+///
+/// # Errors
+///
+/// Returns a `TimedOut` error if the message is lost `MAX_RETRANSMITS` times in a row.
+///
+/// # Panics
+///
+/// Panics if the global network conditions mutex is poisoned.
+pub async fn network_delay() -> std::io::Result<()> {
+    let conditions = *NETWORK_CONDITIONS.lock().unwrap();
+
+    if !conditions.latency.is_zero() {
+        sleep(conditions.latency).await;
+    }
+
+    // Only draw from the rng while loss is injected so clean runs keep their seeds
+    if conditions.packet_loss > 0.0 {
+        for _ in 0..MAX_RETRANSMITS {
+            if !random::chance(conditions.packet_loss) {
+                return Ok(());
+            }
+            tracing::trace!("Message lost, resending in {RETRANSMIT_TIMEOUT:?}");
+            sleep(RETRANSMIT_TIMEOUT).await;
+        }
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            format!("Message lost {MAX_RETRANSMITS} times in a row"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Queues an assertion to be verified against the server's session state.
+///
+/// # Panics
+///
+/// Panics if the global actions mutex is poisoned.
+pub fn queue_assertion(assertion: impl SimulationAssertion + 'static) {
+    ACTIONS
+        .lock()
+        .unwrap()
+        .push_back(Action::Assert(Arc::new(assertion)));
+}
+
+/// Verifies every collected assertion against the session manager.
+///
+/// Verified assertions are discarded, so each one is checked once.
+///
+/// # Errors
+///
+/// Returns `Error::Assertion` describing every assertion that failed.
+///
+/// # Panics
+///
+/// Panics if the global assertions mutex is poisoned.
+pub async fn verify_assertions(session_manager: &dyn SessionManager) -> Result<(), Error> {
+    let assertions = ASSERTIONS.lock().unwrap().drain(..).collect::<Vec<_>>();
+
+    let mut failures = vec![];
+    for assertion in assertions {
+        if let Err(e) = assertion.verify(session_manager).await {
+            tracing::error!("Simulation assertion {assertion:?} failed: {e}");
+            failures.push(e);
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Assertion(failures.join("; ")))
+    }
+}
+
 /// Processes all queued actions and applies them to the simulation.
 ///
 /// # Panics
@@ -93,23 +392,68 @@ pub fn handle_actions(sim: &mut impl Sim) {
     for action in actions {
         match action {
             Action::DisconnectPlayer(player_id) => {
-                log::debug!("Disconnecting player {player_id}");
+                tracing::debug!("Disconnecting player {player_id}");
+                record_disconnections(1);
                 sim.bounce(format!("player-{player_id}"));
             }
             Action::ReconnectPlayer(player_id) => {
-                log::debug!("Reconnecting player {player_id}");
+                tracing::debug!("Reconnecting player {player_id}");
+                record_reconnections(1);
                 // Reconnection is handled by client simulation plans
             }
             Action::NetworkPartition(player_ids) => {
-                log::debug!("Creating network partition for players: {player_ids:?}");
+                tracing::debug!("Creating network partition for players: {player_ids:?}");
+                record_disconnections(player_ids.len());
+                let mut partitioned = PARTITIONED_PLAYERS.lock().unwrap();
                 for player_id in player_ids {
+                    partitioned.insert(player_id);
+                    // Drop any connection that is already in flight
                     sim.bounce(format!("player-{player_id}"));
                 }
             }
             Action::RestoreNetwork => {
-                log::debug!("Restoring network connectivity");
-                // Network restoration is handled by reconnection logic
+                let restored = std::mem::take(&mut *PARTITIONED_PLAYERS.lock().unwrap());
+                tracing::debug!("Restoring network connectivity for players: {restored:?}");
+                record_reconnections(restored.len());
+                // Restored clients notice via `is_partitioned` and flush their held traffic
+            }
+            Action::SetLatency(latency) => {
+                tracing::debug!("Setting network latency to {latency:?}");
+                NETWORK_CONDITIONS.lock().unwrap().latency = latency;
+            }
+            Action::SetPacketLoss(packet_loss) => {
+                let packet_loss = packet_loss.clamp(0.0, 1.0);
+                tracing::debug!("Setting network packet loss to {packet_loss}");
+                NETWORK_CONDITIONS.lock().unwrap().packet_loss = packet_loss;
+            }
+            Action::Assert(assertion) => {
+                tracing::debug!("Collecting simulation assertion {assertion:?}");
+                ASSERTIONS.lock().unwrap().push(assertion);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_average_vote_latency() {
+        let mut metrics = SimulationMetrics::default();
+        for latency_ms in [10, 20, 60] {
+            metrics.record_vote(latency_ms);
+        }
+
+        assert_eq!(metrics.votes_cast, 3);
+        assert!((metrics.average_vote_latency_ms - 30.0).abs() < f64::EPSILON);
+        assert!(metrics.summary().contains("30.0 ms"));
+        metrics.assert_minimum_votes(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1 votes, but only 0 were cast")]
+    fn test_assert_minimum_votes_fails_without_votes() {
+        SimulationMetrics::default().assert_minimum_votes(1);
+    }
+}
@@ -2,15 +2,43 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
-use std::process::ExitCode;
+use std::{process::ExitCode, time::Instant};
 
-use planning_poker_simulator::{client, handle_actions, host};
+use planning_poker_simulator::{
+    client, handle_actions, host, metrics, random, record_simulation_duration, reset_metrics,
+};
 use simvar::{run_simulation, Sim, SimBootstrap, SimConfig};
 
-pub struct PlanningPokerSimulator;
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlanningPokerSimulator {
+    seed: Option<u64>,
+}
+
+impl PlanningPokerSimulator {
+    /// A simulator that replays the run `seed` produced
+    #[must_use]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { seed: Some(seed) }
+    }
+
+    /// The seed this simulator runs with, falling back to `SIMULATOR_SEED`
+    /// and then to a fresh one
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+            .or_else(random::seed_from_env)
+            .unwrap_or_else(random::fresh_seed)
+    }
+}
 
 impl SimBootstrap for PlanningPokerSimulator {
     fn build_sim(&self, mut config: SimConfig) -> SimConfig {
+        // Every sim simvar builds starts from the seed, so repeated runs
+        // replay the same draws instead of continuing the previous run's
+        if let Some(seed) = self.seed {
+            random::use_seed(seed);
+        }
+
         // Configure simulation parameters for WebSocket connections
         let tcp_capacity = 64; // Support multiple concurrent connections
         config.tcp_capacity(tcp_capacity);
@@ -18,7 +46,11 @@ impl SimBootstrap for PlanningPokerSimulator {
     }
 
     fn props(&self) -> Vec<(String, String)> {
-        vec![("simulation_type".to_string(), "planning_poker".to_string())]
+        let mut props = vec![("simulation_type".to_string(), "planning_poker".to_string())];
+        if let Some(seed) = self.seed {
+            props.push(("seed".to_string(), seed.to_string()));
+        }
+        props
     }
 
     fn on_start(&self, sim: &mut impl Sim) {
@@ -28,21 +60,122 @@ impl SimBootstrap for PlanningPokerSimulator {
         // Start client simulations
         client::basic_game::start(sim);
         client::concurrent_voting::start(sim);
+        client::degraded_network::start(sim);
         client::network_partition::start(sim);
         client::player_churn::start(sim);
+        client::websocket_game::start(sim);
     }
 
     fn on_step(&self, sim: &mut impl Sim) {
+        // Applies queued actions, counting disconnections and reconnections in
+        // the metrics, and collects queued assertions for the server to verify
         handle_actions(sim);
     }
 }
 
+/// Options read from the command line
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Args {
+    /// `--seed <u64>`: replay the run this seed produced
+    seed: Option<u64>,
+    /// `--execution-log`: print every random draw once the run is over
+    execution_log: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let seed = args.next().ok_or("--seed needs a value")?;
+                parsed.seed = Some(
+                    seed.parse()
+                        .map_err(|_| format!("Invalid seed '{seed}': expected a u64"))?,
+                );
+            }
+            "--execution-log" => parsed.execution_log = true,
+            _ => return Err(format!("Unknown argument '{arg}'")),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Votes a successful run must have cast to count as having exercised voting
+const MIN_VOTES_CAST: usize = 1;
+
+/// Runs one simulation with `seed`, returning whether it succeeded
+///
+/// The metrics are reset first, so afterwards they describe this run only.
+fn run_seeded(seed: u64) -> Result<bool, Box<dyn std::error::Error>> {
+    random::use_seed(seed);
+    reset_metrics();
+    let started = Instant::now();
+    let results = run_simulation(PlanningPokerSimulator::with_seed(seed))?;
+    record_simulation_duration(started.elapsed());
+
+    Ok(results.iter().all(|x| x.is_success()))
+}
+
 fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
-    let results = run_simulation(PlanningPokerSimulator)?;
+    let args = parse_args(std::env::args().skip(1))?;
+    let seed = PlanningPokerSimulator { seed: args.seed }.seed();
+    tracing::info!("Simulating with seed {seed}");
+
+    let succeeded = run_seeded(seed)?;
+
+    if args.execution_log {
+        for event in random::take_execution_log() {
+            tracing::info!("{event}");
+        }
+    }
+
+    let metrics = metrics();
+    metrics.print_summary();
 
-    if results.iter().any(|x| !x.is_success()) {
+    if !succeeded {
+        tracing::error!("Simulation failed with seed {seed}; replay it with --seed {seed}");
         return Ok(ExitCode::FAILURE);
     }
 
+    metrics.assert_minimum_votes(MIN_VOTES_CAST);
     Ok(ExitCode::SUCCESS)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn test_parse_args_reads_seed() {
+        assert_eq!(args(&[]), Ok(Args::default()));
+        assert_eq!(
+            args(&["--seed", "42", "--execution-log"]),
+            Ok(Args {
+                seed: Some(42),
+                execution_log: true,
+            })
+        );
+        assert!(args(&["--seed"]).is_err());
+        assert!(args(&["--seed", "-1"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_same_seed_replays_the_same_execution() {
+        let _ = random::take_execution_log();
+
+        run_seeded(7).unwrap();
+        let first = random::take_execution_log();
+        run_seeded(7).unwrap();
+        let second = random::take_execution_log();
+
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,96 @@
+//! The simulation's seeded source of randomness
+//!
+//! Every random choice a client makes is drawn here from switchy's simulator
+//! RNG, which is seeded from [`SEED_ENV_VAR`]. Each draw is also appended to
+//! the execution log, so two runs with the same seed can be compared draw by
+//! draw.
+
+use std::{
+    ops::Range,
+    sync::{LazyLock, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use simvar::switchy::random::rng;
+
+/// Environment variable the simulator RNG reads its seed from
+pub const SEED_ENV_VAR: &str = "SIMULATOR_SEED";
+
+/// Every random draw made so far, in the order it was made
+static EXECUTION_LOG: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// The seed set in [`SEED_ENV_VAR`], if any
+#[must_use]
+pub fn seed_from_env() -> Option<u64> {
+    std::env::var(SEED_ENV_VAR)
+        .ok()
+        .and_then(|seed| seed.trim().parse().ok())
+}
+
+/// A seed for a run nobody asked to replay, taken from the wall clock
+#[must_use]
+pub fn fresh_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            elapsed.as_secs() ^ u64::from(elapsed.subsec_nanos())
+        })
+}
+
+/// Seeds the simulator RNG with `seed`
+///
+/// Must be called before the simulation starts drawing random values.
+pub fn use_seed(seed: u64) {
+    std::env::set_var(SEED_ENV_VAR, seed.to_string());
+}
+
+/// A delay of a whole number of milliseconds in `range`
+///
+/// # Panics
+///
+/// Panics if the execution log mutex is poisoned.
+#[must_use]
+pub fn delay(range: Range<u64>) -> Duration {
+    let millis = rng().gen_range(range);
+    record(format!("delay {millis}ms"));
+    Duration::from_millis(millis)
+}
+
+/// One of `values`, picked uniformly
+///
+/// # Panics
+///
+/// Panics if `values` is empty or the execution log mutex is poisoned.
+#[must_use]
+pub fn pick<T>(values: &[T]) -> &T {
+    let index = rng().gen_range(0..values.len());
+    record(format!("pick {index} of {}", values.len()));
+    &values[index]
+}
+
+/// Whether an event with the given probability happens this time
+///
+/// # Panics
+///
+/// Panics if the execution log mutex is poisoned.
+#[must_use]
+pub fn chance(probability: f64) -> bool {
+    let happened = rng().gen_range(0.0..1.0) < probability;
+    record(format!("chance {probability}: {happened}"));
+    happened
+}
+
+/// Takes every draw recorded so far, leaving the log empty for the next run
+///
+/// # Panics
+///
+/// Panics if the execution log mutex is poisoned.
+#[must_use]
+pub fn take_execution_log() -> Vec<String> {
+    std::mem::take(&mut *EXECUTION_LOG.lock().unwrap())
+}
+
+fn record(event: String) {
+    tracing::trace!("random draw: {event}");
+    EXECUTION_LOG.lock().unwrap().push(event);
+}
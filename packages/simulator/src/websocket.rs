@@ -0,0 +1,401 @@
+use planning_poker_models::{ClientMessage, ServerMessage};
+use simvar::switchy::{
+    tcp::TcpStream,
+    unsync::io::{AsyncReadExt, AsyncWriteExt},
+};
+
+use crate::{http::read_http_response, network_delay, Error};
+
+/// Path of the server's WebSocket endpoint
+pub const WEBSOCKET_PATH: &str = "/api/v1/ws";
+
+/// Fixed handshake key; the simulation only needs a well-formed value
+const HANDSHAKE_KEY: &str = "cGxhbm5pbmctcG9rZXItc2lt";
+
+/// Largest frame payload accepted by default, the same 1 MiB cap the server applies
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 1 << 20;
+
+/// Close status sent when a frame is over the size limit ("message too big")
+pub(crate) const CLOSE_MESSAGE_TOO_BIG: u16 = 1009;
+
+pub(crate) const OPCODE_TEXT: u8 = 0x1;
+pub(crate) const OPCODE_CLOSE: u8 = 0x8;
+pub(crate) const OPCODE_PING: u8 = 0x9;
+pub(crate) const OPCODE_PONG: u8 = 0xA;
+
+/// A single decoded WebSocket frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+/// Encodes a final, masked client frame as required for client-to-server traffic.
+#[must_use]
+pub fn encode_frame(opcode: u8, payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+    let mut frame = frame_header(opcode, payload.len(), true);
+    frame.extend_from_slice(&mask);
+    frame.extend(
+        payload
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ mask[i % 4]),
+    );
+    frame
+}
+
+/// Encodes a final, unmasked frame as the server sends it.
+#[must_use]
+pub fn encode_server_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = frame_header(opcode, payload.len(), false);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// The opcode and length bytes of a final frame, with the mask bit set if `masked`
+fn frame_header(opcode: u8, len: usize, masked: bool) -> Vec<u8> {
+    let mask_bit = if masked { 0x80 } else { 0 };
+    let mut header = Vec::with_capacity(len + 14);
+    header.push(0x80 | opcode);
+
+    match len {
+        0..=125 => header.push(mask_bit | u8::try_from(len).unwrap()),
+        126..=0xFFFF => {
+            header.push(mask_bit | 126);
+            header.extend_from_slice(&u16::try_from(len).unwrap().to_be_bytes());
+        }
+        _ => {
+            header.push(mask_bit | 127);
+            header.extend_from_slice(&u64::try_from(len).unwrap().to_be_bytes());
+        }
+    }
+
+    header
+}
+
+/// Reads the payload length and the offset just past it from a frame header.
+///
+/// Returns `None` if `buf` does not hold the length bytes yet.
+fn payload_len(buf: &[u8]) -> Option<(u64, usize)> {
+    match *buf.get(1)? & 0x7F {
+        126 => {
+            let bytes = buf.get(2..4)?;
+            Some((u64::from(u16::from_be_bytes([bytes[0], bytes[1]])), 4))
+        }
+        127 => {
+            let bytes: [u8; 8] = buf.get(2..10)?.try_into().ok()?;
+            Some((u64::from_be_bytes(bytes), 10))
+        }
+        len => Some((u64::from(len), 2)),
+    }
+}
+
+/// Decodes one frame from the front of `buf`.
+///
+/// Returns the frame and the number of bytes it occupied, or `None` if `buf` does not
+/// hold a complete frame yet.
+#[must_use]
+pub fn decode_frame(buf: &[u8]) -> Option<(Frame, usize)> {
+    let opcode = buf.first()? & 0x0F;
+    let masked = buf.get(1)? & 0x80 != 0;
+    let (len, mut offset) = payload_len(buf)?;
+    let len = usize::try_from(len).ok()?;
+
+    let mask = if masked {
+        let mask: [u8; 4] = buf.get(offset..offset + 4)?.try_into().ok()?;
+        offset += 4;
+        Some(mask)
+    } else {
+        None
+    };
+
+    let end = offset.checked_add(len)?;
+    let payload = buf.get(offset..end)?;
+    let payload = mask.map_or_else(
+        || payload.to_vec(),
+        |mask| {
+            payload
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ mask[i % 4])
+                .collect()
+        },
+    );
+
+    Some((Frame { opcode, payload }, end))
+}
+
+/// Takes the next complete frame off the front of `buf`.
+///
+/// A frame whose header announces a payload over `max_frame_size` is rejected as soon
+/// as its length is known, so it is never buffered in full or parsed.
+///
+/// # Errors
+///
+/// Returns an error if the next frame is larger than `max_frame_size`.
+pub fn next_frame(buf: &mut Vec<u8>, max_frame_size: usize) -> Result<Option<Frame>, Error> {
+    if let Some((len, _)) = payload_len(buf) {
+        if usize::try_from(len)
+            .ok()
+            .is_none_or(|len| len > max_frame_size)
+        {
+            return Err(Error::WebSocket(format!(
+                "Frame of {len} bytes exceeds the {max_frame_size} byte limit"
+            )));
+        }
+    }
+
+    Ok(decode_frame(buf).map(|(frame, used)| {
+        buf.drain(..used);
+        frame
+    }))
+}
+
+/// A client-side WebSocket connection speaking the server's JSON message protocol
+pub struct WebSocketClient {
+    stream: TcpStream,
+    buf: Vec<u8>,
+    max_frame_size: usize,
+}
+
+impl WebSocketClient {
+    /// Opens a TCP connection to `server_addr` and performs the WebSocket upgrade handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if connecting fails or the server does not switch protocols.
+    pub async fn connect(server_addr: &str) -> Result<Self, Error> {
+        network_delay().await?;
+        let mut stream = TcpStream::connect(server_addr).await?;
+
+        let request = format!(
+            "GET {WEBSOCKET_PATH} HTTP/1.1\r\n\
+             Host: {server_addr}\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Key: {HANDSHAKE_KEY}\r\n\
+             Sec-WebSocket-Version: 13\r\n\
+             \r\n"
+        );
+        stream.write_all(request.as_bytes()).await?;
+
+        let mut response = String::new();
+        let response = read_http_response(&mut response, Box::pin(&mut stream))
+            .await?
+            .ok_or_else(|| Error::WebSocket("No handshake response received".to_string()))?;
+
+        let (head, rest) = response
+            .split_once("\r\n\r\n")
+            .unwrap_or((response.as_str(), ""));
+
+        if !head.starts_with("HTTP/1.1 101") {
+            return Err(Error::WebSocket(format!(
+                "Upgrade rejected: {}",
+                head.lines().next().unwrap_or_default()
+            )));
+        }
+
+        Ok(Self {
+            stream,
+            buf: rest.as_bytes().to_vec(),
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        })
+    }
+
+    /// Rejects inbound frames with a payload over `max_frame_size` bytes instead of
+    /// the default [`DEFAULT_MAX_FRAME_SIZE`].
+    #[must_use]
+    pub const fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Serializes and sends a message as a text frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing to the stream fails.
+    pub async fn send(&mut self, message: &ClientMessage) -> Result<(), Error> {
+        let payload = serde_json::to_vec(message)?;
+        let frame = encode_frame(OPCODE_TEXT, &payload, new_mask());
+        network_delay().await?;
+        self.stream.write_all(&frame).await?;
+        Ok(())
+    }
+
+    /// Waits for the next server message, answering pings along the way.
+    ///
+    /// Returns `None` once the server closes the connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails, a frame is over the size limit or a text frame
+    /// is not a valid `ServerMessage`. An oversized frame also closes the connection.
+    pub async fn recv(&mut self) -> Result<Option<ServerMessage>, Error> {
+        loop {
+            loop {
+                let frame = match next_frame(&mut self.buf, self.max_frame_size) {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!("Rejecting inbound WebSocket frame: {e}");
+                        self.buf.clear();
+                        let close = encode_frame(
+                            OPCODE_CLOSE,
+                            &CLOSE_MESSAGE_TOO_BIG.to_be_bytes(),
+                            new_mask(),
+                        );
+                        self.stream.write_all(&close).await.ok();
+                        return Err(e);
+                    }
+                };
+
+                match frame.opcode {
+                    OPCODE_TEXT => return Ok(Some(serde_json::from_slice(&frame.payload)?)),
+                    OPCODE_PING => {
+                        let pong = encode_frame(OPCODE_PONG, &frame.payload, new_mask());
+                        self.stream.write_all(&pong).await?;
+                    }
+                    OPCODE_CLOSE => return Ok(None),
+                    opcode => tracing::trace!("Ignoring WebSocket frame with opcode {opcode:#x}"),
+                }
+            }
+
+            let mut chunk = [0_u8; 4096];
+            let count = self.stream.read(&mut chunk).await?;
+            if count == 0 {
+                return Ok(None);
+            }
+            self.buf.extend_from_slice(&chunk[..count]);
+        }
+    }
+
+    /// Reads messages until `matcher` accepts one, skipping everything else.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on a server `Error` message, a closed connection or a failed read.
+    pub async fn expect<T>(
+        &mut self,
+        expected: &str,
+        matcher: impl Fn(&ServerMessage) -> Option<T>,
+    ) -> Result<T, Error> {
+        loop {
+            match self.recv().await? {
+                Some(ServerMessage::Error { code, message }) => {
+                    return Err(Error::WebSocket(format!(
+                        "Server error ({code:?}) while waiting for {expected}: {message}"
+                    )));
+                }
+                Some(message) => {
+                    if let Some(value) = matcher(&message) {
+                        return Ok(value);
+                    }
+                    tracing::debug!("Skipping {message:?} while waiting for {expected}");
+                }
+                None => {
+                    return Err(Error::WebSocket(format!(
+                        "Connection closed while waiting for {expected}"
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Sends a close frame; the connection should not be used afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the stream fails.
+    pub async fn close(mut self) -> Result<(), Error> {
+        let frame = encode_frame(OPCODE_CLOSE, &[], new_mask());
+        self.stream.write_all(&frame).await?;
+        Ok(())
+    }
+}
+
+fn new_mask() -> [u8; 4] {
+    let bytes = uuid::Uuid::new_v4().into_bytes();
+    [bytes[0], bytes[1], bytes[2], bytes[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_frame_round_trips() {
+        let payload = br#"{"type":"RevealVotes"}"#;
+        let encoded = encode_frame(OPCODE_TEXT, payload, [1, 2, 3, 4]);
+
+        let (frame, used) = decode_frame(&encoded).unwrap();
+
+        assert_eq!(used, encoded.len());
+        assert_eq!(frame.opcode, OPCODE_TEXT);
+        assert_eq!(frame.payload, payload);
+    }
+
+    #[test]
+    fn test_extended_length_frame_round_trips() {
+        let payload = vec![b'x'; 300];
+        let encoded = encode_frame(OPCODE_TEXT, &payload, [9, 8, 7, 6]);
+
+        assert_eq!(encoded[1] & 0x7F, 126);
+        assert_eq!(decode_frame(&encoded).unwrap().0.payload, payload);
+    }
+
+    #[test]
+    fn test_server_frame_is_unmasked() {
+        let payload = vec![b'x'; 300];
+        let encoded = encode_server_frame(OPCODE_TEXT, &payload);
+
+        assert_eq!(encoded[1], 126);
+        assert_eq!(encoded.len(), payload.len() + 4);
+        assert_eq!(decode_frame(&encoded).unwrap().0.payload, payload);
+    }
+
+    #[test]
+    fn test_decode_waits_for_complete_frame() {
+        let encoded = encode_server_frame(OPCODE_TEXT, b"hello");
+
+        assert!(decode_frame(&encoded[..encoded.len() - 1]).is_none());
+        assert!(decode_frame(&encoded[..1]).is_none());
+    }
+
+    #[test]
+    fn test_oversized_frame_is_rejected_before_it_arrives() {
+        // Only the header of a frame announcing 2^40 bytes has arrived
+        let mut buf = vec![0x80 | OPCODE_TEXT, 127];
+        buf.extend_from_slice(&(1_u64 << 40).to_be_bytes());
+
+        let result = next_frame(&mut buf, DEFAULT_MAX_FRAME_SIZE);
+
+        assert!(
+            matches!(result, Err(Error::WebSocket(message)) if message.contains("exceeds the 1048576 byte limit"))
+        );
+    }
+
+    #[test]
+    fn test_frame_at_the_size_limit_is_accepted() {
+        let payload = vec![b'x'; 300];
+        let mut buf = encode_server_frame(OPCODE_TEXT, &payload);
+        buf.extend(encode_server_frame(OPCODE_TEXT, &payload));
+
+        assert!(next_frame(&mut buf.clone(), 299).is_err());
+        assert_eq!(next_frame(&mut buf, 300).unwrap().unwrap().payload, payload);
+        assert_eq!(next_frame(&mut buf, 300).unwrap().unwrap().payload, payload);
+        assert!(next_frame(&mut buf, 300).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decode_consecutive_frames() {
+        let mut buf = encode_server_frame(OPCODE_TEXT, b"first");
+        buf.extend(encode_server_frame(OPCODE_CLOSE, b""));
+
+        let (first, used) = decode_frame(&buf).unwrap();
+        let (second, _) = decode_frame(&buf[used..]).unwrap();
+
+        assert_eq!(first.payload, b"first");
+        assert_eq!(second.opcode, OPCODE_CLOSE);
+    }
+}
@@ -2,12 +2,21 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
-use std::sync::{Arc, OnceLock};
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use planning_poker_config::Config;
 use planning_poker_database::{create_connection, DatabaseConfig};
-pub use planning_poker_session::{DatabaseSessionManager, SessionManager};
+use planning_poker_metrics::MetricsRegistry;
+use planning_poker_models::GameState;
+use planning_poker_notifications::WebhookNotifier;
+pub use planning_poker_session::{
+    CachedSessionManager, DatabaseSessionManager, SessionManager, DEFAULT_CACHE_TTL,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -16,11 +25,22 @@ pub enum StateError {
     Database(#[from] planning_poker_database::DatabaseError),
     #[error("Session error: {0}")]
     Session(#[from] anyhow::Error),
+    #[error("Config error: {0}")]
+    Config(#[from] planning_poker_config::ConfigError),
 }
 
-/// Planning Poker application state with lazy database initialization
+/// How often revealed games are checked for automatic archival
+pub const AUTO_ARCHIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How many revealed games one archival pass looks at; any beyond that are
+/// left for a later pass
+const AUTO_ARCHIVE_BATCH_SIZE: usize = 1000;
+
+/// Planning Poker application state with lazy config and database initialization
 pub struct PlanningPokerState {
+    config: OnceLock<Config>,
     session_manager: OnceLock<Arc<dyn SessionManager>>,
+    metrics: OnceLock<Arc<MetricsRegistry>>,
 }
 
 impl PlanningPokerState {
@@ -28,10 +48,32 @@ impl PlanningPokerState {
     #[must_use]
     pub const fn new() -> Self {
         Self {
+            config: OnceLock::new(),
             session_manager: OnceLock::new(),
+            metrics: OnceLock::new(),
         }
     }
 
+    /// Get the metrics registry shared with the session manager
+    pub fn metrics(&self) -> &Arc<MetricsRegistry> {
+        self.metrics
+            .get_or_init(|| Arc::new(MetricsRegistry::new()))
+    }
+
+    /// Get the application configuration, loading and validating it on first access
+    ///
+    /// # Errors
+    ///
+    /// Returns `StateError::Config` if the configuration cannot be loaded or is invalid
+    pub fn config(&self) -> Result<&Config, StateError> {
+        if let Some(config) = self.config.get() {
+            return Ok(config);
+        }
+
+        let config = Config::load()?;
+        Ok(self.config.get_or_init(|| config))
+    }
+
     /// Get the session manager, initializing the database connection on first access
     ///
     /// # Errors
@@ -51,12 +93,15 @@ impl PlanningPokerState {
         // Lazy initialization - only happens on first access
         tracing::info!("Initializing database connection (lazy initialization)");
 
-        let manager = Arc::new(self.setup_database().await?);
+        // Route handlers look the game up on nearly every request, so keep games cached briefly
+        let database: Arc<dyn SessionManager> = Arc::new(self.setup_database().await?);
+        let manager = Arc::new(CachedSessionManager::new(database, DEFAULT_CACHE_TTL));
 
         // Store the session manager (this can only happen once due to OnceLock)
-        match self.session_manager.set(manager) {
+        match self.session_manager.set(manager.clone()) {
             Ok(()) => {
                 tracing::info!("Database connection initialized successfully");
+                switchy::unsync::task::spawn(run_auto_archival(manager, AUTO_ARCHIVE_INTERVAL));
                 Ok(self.session_manager.get().unwrap())
             }
             Err(_) => {
@@ -69,20 +114,34 @@ impl PlanningPokerState {
     /// Set up database connection and initialize schema
     async fn setup_database(&self) -> Result<DatabaseSessionManager, StateError> {
         // Set up database connection
-        let config = Config::from_env();
+        let config = self.config()?;
         let database_url = config
             .database_url
+            .clone()
             .unwrap_or_else(|| "sqlite://planning_poker.db".to_string());
 
         let db_config = DatabaseConfig {
             database_url,
             max_connections: 10,
             connection_timeout: std::time::Duration::from_secs(30),
+            ..DatabaseConfig::default()
         };
 
         // Create database connection and session manager
         let db = create_connection(db_config).await?;
-        let session_manager = DatabaseSessionManager::new(db);
+        let mut session_manager = DatabaseSessionManager::new(db)
+            .with_metrics(self.metrics().clone())
+            .with_max_players_per_game(config.game.max_players_per_game)
+            .with_allow_duplicate_player_names(config.game.allow_duplicate_player_names)
+            .with_session_ttl(std::time::Duration::from_secs(
+                config.game.session_ttl_seconds,
+            ));
+        // Games may bring their own webhook, so the notifier is needed even
+        // when none are configured
+        if config.webhooks_enabled {
+            session_manager = session_manager
+                .with_notifier(Arc::new(WebhookNotifier::new(config.webhooks.clone())));
+        }
 
         // Initialize database schema
         session_manager.init_schema().await?;
@@ -91,6 +150,45 @@ impl PlanningPokerState {
     }
 }
 
+/// Archive every revealed game whose `auto_archive_after` delay has passed by
+/// `now`, returning how many were archived
+///
+/// # Errors
+///
+/// * If listing the revealed games fails
+pub async fn archive_due_games(
+    session_manager: &dyn SessionManager,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let games = session_manager
+        .list_games(Some(GameState::Revealed), AUTO_ARCHIVE_BATCH_SIZE)
+        .await?;
+
+    let mut archived = 0;
+    for game in games.iter().filter(|game| game.is_due_for_archival(now)) {
+        match session_manager.archive_game(game.id).await {
+            Ok(()) => archived += 1,
+            // Someone may have started another round since the game was listed
+            Err(e) => tracing::warn!("Failed to archive game {}: {}", game.id, e),
+        }
+    }
+
+    Ok(archived)
+}
+
+/// Archive due games every `interval` for as long as the app runs
+async fn run_auto_archival(session_manager: Arc<dyn SessionManager>, interval: Duration) {
+    loop {
+        switchy::unsync::time::sleep(interval).await;
+
+        match archive_due_games(session_manager.as_ref(), Utc::now()).await {
+            Ok(0) => {}
+            Ok(archived) => tracing::info!("Archived {} idle games", archived),
+            Err(e) => tracing::error!("Failed to archive idle games: {}", e),
+        }
+    }
+}
+
 impl Default for PlanningPokerState {
     fn default() -> Self {
         Self::new()
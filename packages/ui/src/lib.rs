@@ -2,12 +2,12 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
-use hyperchad::{
-    renderer::View,
-    router::{RouteRequest, Router},
-    template::{self as hyperchad_template, container, Containers},
+use hyperchad::template::{self as hyperchad_template, container, Containers};
+use planning_poker_models::{
+    Confidence, Game, GameState, PaginationParams, Player, PlayerRole, PlayerStats, RoundSummary,
+    RoundTiming, Story, StoryImport, TeamRole, Vote,
 };
-use planning_poker_models::{Game, GameState, Player, Vote};
+use planning_poker_poker::{ConsensusResult, VotingSystem};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -73,36 +73,23 @@ impl PlanningPokerApp {
     }
 }
 
-#[must_use]
-pub fn create_router() -> Router {
-    Router::new()
-        .with_route_result("/", |_request: RouteRequest| {
-            let content = app_layout();
-            let container = content.into_iter().next().unwrap_or_default();
-            async move {
-                Ok::<View, anyhow::Error>(View {
-                    immediate: container,
-                    future: None,
-                })
-            }
-        })
-        .with_route_result("/home", |_request: RouteRequest| {
-            let content = home_content();
-            let container = content.into_iter().next().unwrap_or_default();
-            async move {
-                Ok::<View, anyhow::Error>(View {
-                    immediate: container,
-                    future: None,
-                })
-            }
-        })
-}
-
 #[must_use]
 pub fn page_layout(content: &Containers) -> Containers {
     tracing::info!("page_layout called, wrapping content with main-content div");
     container! {
         div id="main-content" width=100% height=100% padding=20 overflow-y="auto" {
+            div id="toast-area" {}
+            (content)
+        }
+    }
+}
+
+/// Full-screen layout for a board shared on a screen, with larger text and
+/// no navigation
+#[must_use]
+pub fn presentation_layout(content: &Containers) -> Containers {
+    container! {
+        div id="main-content" width=100% height=100% padding=40 overflow-y="auto" font-size=20 {
             (content)
         }
     }
@@ -116,6 +103,24 @@ pub fn app_layout() -> Containers {
 
 #[must_use]
 pub fn home_content() -> Containers {
+    home_content_with_default(
+        &VotingSystem::Fibonacci.key(),
+        &VotingSystem::Fibonacci,
+        None,
+    )
+}
+
+/// Home page content with `default_voting_system` pre-selected in the create form
+///
+/// `default_deck` is what `default_voting_system` resolves to, which may be a
+/// named deck from the configuration. `join_game_id` pre-fills the join form,
+/// for visitors following an invite link.
+#[must_use]
+pub fn home_content_with_default(
+    default_voting_system: &str,
+    default_deck: &VotingSystem,
+    join_game_id: Option<&str>,
+) -> Containers {
     container! {
         h1 { "Planning Poker" }
         div { "Welcome to Planning Poker!" }
@@ -125,12 +130,20 @@ pub fn home_content() -> Containers {
             form hx-post="/join-game" {
                 div margin-bottom=10 {
                     span { "Game ID:" }
-                    input type="text" name="game-id" placeholder="Enter game ID" margin-left=10 required;
+                    input type="text" name="game-id" value=(join_game_id.unwrap_or_default()) placeholder="Enter game ID" margin-left=10 required;
                 }
                 div margin-bottom=10 {
                     span { "Your Name:" }
                     input type="text" name="player-name" placeholder="Enter your name" margin-left=10 required;
                 }
+                div margin-bottom=10 {
+                    span { "Role:" }
+                    input type="text" name="role" value=(PlayerRole::Voter.to_string()) placeholder="Voter, Advisor or Observer" margin-left=10;
+                }
+                div margin-bottom=10 {
+                    span { "Team Role:" }
+                    input type="text" name="team-role" placeholder="Developer, Designer, Product Manager, Tech Lead, QA or your own" margin-left=10;
+                }
                 button type="submit" margin-top=10 padding=10 background="#007bff" color="#fff" border="none" border-radius=5 {
                     "Join Game"
                 }
@@ -146,7 +159,26 @@ pub fn home_content() -> Containers {
                 }
                 div margin-bottom=10 {
                     span { "Voting System:" }
-                    input type="text" name="voting_system" value="fibonacci" placeholder="fibonacci, tshirt, or powers_of_2" margin-left=10 required;
+                    input type="text" name="voting_system" value=(default_voting_system) placeholder=(voting_system_placeholder()) margin-left=10 required hx-get="/api/voting-systems" hx-trigger="change" hx-target="#deck-preview";
+                }
+                div margin-bottom=10 {
+                    span { "Custom Deck:" }
+                    input type="text" name="custom_deck" placeholder="Comma-separated cards, e.g. XS, S, M" margin-left=10;
+                }
+                div id="deck-preview" margin-bottom=10 {
+                    (deck_preview_for_system(default_deck))
+                }
+                div margin-bottom=10 {
+                    input type="checkbox" name="lock_votes" value="true";
+                    span margin-left=5 { "First answer counts (votes can't be changed once cast)" }
+                }
+                div margin-bottom=10 {
+                    span { "Archive after:" }
+                    input type="number" name="auto_archive_hours" placeholder="Hours once revealed (optional)" margin-left=10;
+                }
+                div margin-bottom=10 {
+                    span { "Webhook URL:" }
+                    input type="url" name="webhook_url" placeholder="Posts each round's results (optional)" margin-left=10;
                 }
                 button type="submit" margin-top=10 padding=10 background="#28a745" color="#fff" border="none" border-radius=5 {
                     "Create Game"
@@ -156,6 +188,319 @@ pub fn home_content() -> Containers {
     }
 }
 
+/// Placeholder for the voting system input listing every built-in system
+fn voting_system_placeholder() -> String {
+    let keys: Vec<String> = VotingSystem::builtin()
+        .iter()
+        .map(VotingSystem::key)
+        .collect();
+    format!("{}, or custom:1,2,3", keys.join(", "))
+}
+
+/// Preview of the cards a voting system deals, rendered as small chips
+#[must_use]
+pub fn deck_preview_content(voting_system: &str) -> Containers {
+    deck_preview_for_system(&VotingSystem::from_string(voting_system))
+}
+
+/// Preview of the cards of an already resolved voting system
+#[must_use]
+pub fn deck_preview_for_system(system: &VotingSystem) -> Containers {
+    let options = system.get_voting_options();
+
+    container! {
+        div color="#666" font-size=12 margin-bottom=5 {
+            (format!("{} deck:", system.display_name()))
+        }
+        div direction=row flex-wrap=wrap gap=5 {
+            @for value in &options {
+                span padding-x=8 padding-y=3 background="#e9ecef" border-radius=10 font-size=12 {
+                    (value)
+                }
+            }
+        }
+    }
+}
+
+/// Page shown when the requested resource does not exist
+#[must_use]
+pub fn page_not_found_content(resource: &str) -> Containers {
+    error_page("404 - Not Found", resource, "/")
+}
+
+/// Page shown when the requester is not allowed to perform an action
+#[must_use]
+pub fn forbidden_content(reason: &str) -> Containers {
+    error_page("403 - Forbidden", reason, "/")
+}
+
+/// Page shown when a request can't be served, explaining why in `message`
+/// and linking back to `back_link`
+#[must_use]
+pub fn error_page(title: &str, message: &str, back_link: &str) -> Containers {
+    container! {
+        div id="error-page" padding=20 {
+            h1 { (title) }
+            div color="#666" margin-top=10 { (message) }
+            div margin-top=20 {
+                anchor href=(back_link) padding=10 background="#6c757d" color="#fff" text-decoration="none" border-radius=5 {
+                    @if back_link == "/" { "Back to Home" } @else { "Go Back" }
+                }
+            }
+        }
+    }
+}
+
+/// Page shown when a submitted form field fails validation, naming the field
+#[must_use]
+pub fn validation_error_content(field: &str, message: &str) -> Containers {
+    container! {
+        div id="validation-error" padding=20 {
+            h1 { "400 - Bad Request" }
+            div margin-top=10 {
+                span { "Check the " }
+                span font-weight="bold" background="#fff3cd" padding=2 { (field) }
+                span { " field" }
+            }
+            div color="#666" margin-top=10 { (message) }
+            div margin-top=20 {
+                anchor href="/" padding=10 background="#6c757d" color="#fff" text-decoration="none" border-radius=5 {
+                    "Back to Home"
+                }
+            }
+        }
+    }
+}
+
+/// Fallback page shown while the client is sent on to `url`
+#[must_use]
+pub fn redirect_page(url: &str) -> Containers {
+    container! {
+        div id="redirect" padding=20 {
+            h2 { "Redirecting..." }
+            div margin-top=10 {
+                anchor href=(url) {
+                    "Continue to your game"
+                }
+            }
+        }
+    }
+}
+
+/// Home page content with a lobby listing the games that are open to join,
+/// followed by the visitor's archived games if they have any
+#[must_use]
+pub fn home_content_with_games(
+    games: &[Game],
+    player_counts: &HashMap<Uuid, usize>,
+    archived_games: &[Game],
+    default_voting_system: &str,
+    default_deck: &VotingSystem,
+    join_game_id: Option<&str>,
+) -> Containers {
+    container! {
+        (home_content_with_default(default_voting_system, default_deck, join_game_id))
+        (games_lobby_content(games, player_counts))
+        @if !archived_games.is_empty() {
+            (archived_games_content(archived_games))
+        }
+    }
+}
+
+/// Archived games, each linking to the summary of how it went
+#[must_use]
+pub fn archived_games_content(games: &[Game]) -> Containers {
+    container! {
+        div id="archived-games" margin-top=30 {
+            h2 { "Archived Games" }
+            @if games.is_empty() {
+                div color="#666" { "No archived games" }
+            } @else {
+                @for game in games {
+                    div padding=5 border-bottom="1px solid #eee" {
+                        anchor href=(format!("/game/{}/summary", game.id)) { (game.name) }
+                        span margin-left=10 color="#666" { (game.voting_system) }
+                        span margin-left=10 color="#999" { (format!("archived {}", game.updated_at.format("%Y-%m-%d %H:%M"))) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One page of the games list at `/games`, each linking into its game
+///
+/// Completed games are only on the page when `include_completed` is set, and
+/// then listed apart from the ones still in play.
+#[must_use]
+pub fn games_list_content(
+    games: &[Game],
+    player_counts: &HashMap<Uuid, usize>,
+    state: Option<GameState>,
+    params: PaginationParams,
+    total: u64,
+    include_completed: bool,
+) -> Containers {
+    let (completed, active): (Vec<&Game>, Vec<&Game>) = games
+        .iter()
+        .partition(|game| game.state == GameState::Completed);
+    let page_url = |page: u32, include_completed: bool| {
+        let mut url = format!("/games?page={page}&page_size={}", params.page_size);
+        if let Some(state) = state {
+            url.push_str(&format!("&state={state}"));
+        }
+        if include_completed {
+            url.push_str("&completed=1");
+        }
+        url
+    };
+    let has_previous = params.page > 1;
+    let has_next = u64::try_from(params.end()).is_ok_and(|end| end < total);
+    let toggle_label = if include_completed {
+        "Hide completed games"
+    } else {
+        "Show completed games"
+    };
+
+    container! {
+        div id="games-list" {
+            h1 { "Games" }
+            div margin-bottom=10 {
+                span color="#666" { (format!("{total} games")) }
+                anchor href=(page_url(1, !include_completed)) margin-left=10 {
+                    (toggle_label)
+                }
+            }
+            @if games.is_empty() {
+                div color="#666" padding=10 background="#f8f9fa" border-radius=5 { "No games to show" }
+            } @else {
+                (games_table(&active, player_counts))
+                @if !completed.is_empty() {
+                    h2 margin-top=20 color="#666" { "Completed" }
+                    (games_table(&completed, player_counts))
+                }
+            }
+            div id="games-pagination" margin-top=20 {
+                @if has_previous {
+                    anchor href=(page_url(params.page - 1, include_completed)) margin-right=10 { "← Previous" }
+                }
+                span color="#666" { (format!("Page {}", params.page.max(1))) }
+                @if has_next {
+                    anchor href=(page_url(params.page.max(1) + 1, include_completed)) margin-left=10 { "Next →" }
+                }
+            }
+        }
+    }
+}
+
+/// A table of games with their short code, state, player count and when
+/// they were last active
+fn games_table(games: &[&Game], player_counts: &HashMap<Uuid, usize>) -> Containers {
+    container! {
+        table {
+            thead {
+                tr {
+                    th { "Game" }
+                    th { "Code" }
+                    th { "State" }
+                    th { "Players" }
+                    th { "Last Activity" }
+                }
+            }
+            tbody {
+                @for game in games {
+                    tr {
+                        td padding=5 {
+                            anchor href=(format!("/game/{}", game.id)) { (game.name) }
+                        }
+                        td padding=5 color="#666" { (short_code(game.id)) }
+                        td padding=5 { (game_state_badge(&game.state)) }
+                        td padding=5 { (player_counts.get(&game.id).copied().unwrap_or_default().to_string()) }
+                        td padding=5 { (game.updated_at.format("%Y-%m-%d %H:%M").to_string()) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The first block of a game's id, enough to tell games apart at a glance
+fn short_code(game_id: Uuid) -> String {
+    game_id.simple().to_string()[..8].to_uppercase()
+}
+
+fn game_state_badge(state: &GameState) -> Containers {
+    let (background, color) = match state {
+        GameState::Waiting => ("#e9ecef", "#495057"),
+        GameState::Voting => ("#cce5ff", "#004085"),
+        GameState::Revealed => ("#d4edda", "#155724"),
+        GameState::Archived | GameState::Completed => ("#f8f9fa", "#6c757d"),
+    };
+
+    container! {
+        span padding-x=8 padding-y=3 background=(background) color=(color) border-radius=10 font-size=12 {
+            (state.to_string())
+        }
+    }
+}
+
+#[must_use]
+pub fn games_lobby_content(games: &[Game], player_counts: &HashMap<Uuid, usize>) -> Containers {
+    let joinable: Vec<&Game> = games
+        .iter()
+        .filter(|game| game.state == GameState::Waiting)
+        .collect();
+
+    container! {
+        div id="games-lobby" margin-top=30 {
+            h2 { "Open Games" }
+            @if joinable.is_empty() {
+                (lobbies_empty_content())
+            } @else {
+                table {
+                    thead {
+                        tr {
+                            th { "Game" }
+                            th { "Voting System" }
+                            th { "Players" }
+                            th { "Created" }
+                            th {}
+                        }
+                    }
+                    tbody {
+                        @for game in joinable {
+                            tr {
+                                td padding=5 { (game.name) }
+                                td padding=5 { (game.voting_system) }
+                                td padding=5 { (player_counts.get(&game.id).copied().unwrap_or_default().to_string()) }
+                                td padding=5 { (game.created_at.format("%Y-%m-%d %H:%M").to_string()) }
+                                td padding=5 {
+                                    form hx-post="/join-game" {
+                                        input type="hidden" name="game-id" value=(game.id.to_string());
+                                        input type="text" name="player-name" placeholder="Your name" required;
+                                        button type="submit" margin-left=5 padding=5 background="#007bff" color="#fff" border="none" border-radius=3 {
+                                            "Join"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn lobbies_empty_content() -> Containers {
+    container! {
+        div color="#666" padding=10 background="#f8f9fa" border-radius=5 {
+            "No games are waiting for players. Create a new game to get started!"
+        }
+    }
+}
+
 // UI Component Functions
 
 #[must_use]
@@ -182,8 +527,8 @@ pub fn players_section(players: &[Player]) -> Containers {
                     @for player in players {
                         div padding=5 border-bottom="1px solid #eee" {
                             span { (player.name) }
-                            @if player.is_observer {
-                                span margin-left=10 color="#666" { "(Observer)" }
+                            @if player.role != PlayerRole::Voter {
+                                span margin-left=10 color="#666" { (format!("({})", player.role)) }
                             }
                             span margin-left=10 color="#999" { (format!("joined {}", player.joined_at.format("%H:%M"))) }
                         }
@@ -194,40 +539,111 @@ pub fn players_section(players: &[Player]) -> Containers {
     }
 }
 
+/// The voting area of the game page: its timer and the vote buttons
+///
+/// `locked_vote` is the acting player's vote when their game doesn't allow
+/// changing it, and shows their cards disabled.
 #[must_use]
-pub fn voting_section(game_id: &str, game: &Game, voting_active: bool) -> Containers {
-    let start_voting_url = format!("/api/games/{game_id}/start-voting");
+pub fn voting_section(
+    game_id: &str,
+    game: &Game,
+    voting_active: bool,
+    locked_vote: Option<&str>,
+) -> Containers {
+    let remaining_secs = game
+        .voting_deadline
+        .filter(|_| voting_active)
+        .map(|deadline| remaining_secs(deadline, chrono::Utc::now()));
 
     container! {
         div id="voting-section" margin-top=20 {
             h2 { "Voting" }
 
-            // Story input section
-            div id="story-input" margin-bottom=15 {
-                form hx-post=(start_voting_url) {
-                    span { "Story:" }
-                    input type="text" name="story" placeholder="Enter story to vote on" margin-left=10 required;
-                    button type="submit" margin-left=10 padding=5 background="#007bff" color="#fff" border="none" border-radius=3 {
-                        "Start Voting"
-                    }
-                }
+            div id="voting-timer" {
+                (voting_timer_content(remaining_secs))
             }
 
             // Vote buttons section
             div id="vote-buttons" margin-top=15 {
-                @if voting_active {
-                    (vote_buttons(game_id, game))
+                (vote_buttons_content(game_id, game, voting_active, locked_vote))
+            }
+        }
+    }
+}
+
+/// What goes in the `vote-buttons` area: the deck while voting, disabled
+/// once a `locked_vote` is cast, otherwise a note that voting hasn't started
+#[must_use]
+pub fn vote_buttons_content(
+    game_id: &str,
+    game: &Game,
+    voting_active: bool,
+    locked_vote: Option<&str>,
+) -> Containers {
+    container! {
+        @if let (true, Some(cast_value)) = (voting_active, locked_vote) {
+            (locked_vote_buttons(game, cast_value))
+        } @else if voting_active {
+            (vote_buttons(game_id, game))
+        } @else {
+            div color="#666" {
+                "Voting not active. Click 'Start Voting' to begin."
+            }
+        }
+    }
+}
+
+/// Whole seconds left until `deadline`, rounded up so a countdown only shows
+/// zero once the deadline has passed
+#[must_use]
+pub fn remaining_secs(
+    deadline: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> u64 {
+    u64::try_from((deadline - now).num_milliseconds())
+        .unwrap_or(0)
+        .div_ceil(1000)
+}
+
+/// Countdown of a timed round, empty for untimed rounds
+#[must_use]
+pub fn voting_timer_content(remaining_secs: Option<u64>) -> Containers {
+    container! {
+        @if let Some(remaining_secs) = remaining_secs {
+            div color="#dc3545" font-weight=bold {
+                (format!("⏱ {}:{:02} left", remaining_secs / 60, remaining_secs % 60))
+            }
+        }
+    }
+}
+
+/// Vote buttons for a player whose vote is final for this round
+///
+/// Rendered once a vote is cast in a game that doesn't allow changing votes.
+#[must_use]
+pub fn locked_vote_buttons(game: &Game, cast_value: &str) -> Containers {
+    let voting_system = VotingSystem::from_string(&game.voting_system);
+    let vote_values = voting_system.get_voting_options();
+
+    container! {
+        span { "Your Vote:" }
+        div margin-top=10 {
+            @for value in &vote_values {
+                @if value == cast_value {
+                    button type="button" disabled background="#007bff" color="#fff" border="1, #007bff" border-radius=5 width="60" height="90" font-size=28 font-weight=bold {
+                        (value)
+                    }
                 } @else {
-                    div color="#666" {
-                        "Voting not active. Click 'Start Voting' to begin."
+                    button type="button" disabled background="#f8f9fa" color="#adb5bd" border="1, #dee2e6" border-radius=5 width="60" height="90" font-size=28 font-weight=bold {
+                        (value)
                     }
                 }
             }
         }
+        div color="#666" margin-top=5 { "You already voted this round" }
     }
 }
 
-#[must_use]
 fn get_card_display(value: &str) -> Containers {
     container! {
         button
@@ -249,124 +665,489 @@ fn get_card_display(value: &str) -> Containers {
 
 #[must_use]
 pub fn vote_buttons(game_id: &str, game: &Game) -> Containers {
-    let voting_system = planning_poker_poker::VotingSystem::from_string(&game.voting_system);
+    let voting_system = VotingSystem::from_string(&game.voting_system);
     let vote_values = voting_system.get_voting_options();
 
     container! {
         span { "Your Vote:" }
         div margin-top=10 {
-            @for value in vote_values {
-                form hx-post=(format!("/api/games/{game_id}/vote")) {
+            @for value in &vote_values {
+                form hx-post=(format!("/api/games/{game_id}/vote")) hx-target="#toast-area" {
                     input type="hidden" name="vote" value=(value);
-                    (get_card_display(&value))
+                    (get_card_display(value))
                 }
             }
         }
+        (confidence_selector(game_id, &vote_values))
     }
 }
 
+/// Small row of buttons beneath the cards for casting a vote flagged as low confidence
 #[must_use]
-pub fn results_section(game_id: &str, votes: &[Vote], votes_revealed: bool) -> Containers {
-    let reveal_url = format!("/api/games/{game_id}/reveal");
-    let reset_url = format!("/api/games/{game_id}/reset");
+fn confidence_selector(game_id: &str, vote_values: &[String]) -> Containers {
+    let low_confidence = Confidence::Low.to_string();
 
     container! {
-        div id="results-section" margin-top=20 {
-            h2 { "Results" }
-            div id="vote-results" {
-                @if votes.is_empty() {
-                    div color="#666" { "No votes cast yet" }
-                } @else if votes_revealed {
-                    div {
-                        h3 { "Vote Results:" }
-                        @for vote in votes {
-                            div padding=5 border-bottom="1px solid #eee" {
-                                span { (format!("{}: {}", vote.player_name, vote.value)) }
-                                span margin-left=10 color="#999" { (format!("cast at {}", vote.cast_at.format("%H:%M:%S"))) }
-                            }
+        div id="confidence-selector" margin-top=10 {
+            span color="#666" font-size=12 { "Not sure? Vote with low confidence:" }
+            div margin-top=5 {
+                @for value in vote_values {
+                    form hx-post=(format!("/api/games/{game_id}/vote")) hx-target="#toast-area" {
+                        input type="hidden" name="vote" value=(value);
+                        input type="hidden" name="confidence" value=(low_confidence);
+                        button
+                            type="submit"
+                            margin=2
+                            padding=4
+                            background="#fff"
+                            color="#856404"
+                            border="1, #ffc107"
+                            border-radius=3
+                            font-size=12
+                        {
+                            (value)
                         }
                     }
-                } @else {
-                    div {
-                        span { (format!("{} votes cast", votes.len())) }
-                        span margin-left=10 color="#666" { "(hidden until revealed)" }
-                    }
-                }
-            }
-
-            // Game action buttons
-            div id="game-actions" margin-top=15 {
-                button hx-post=(reveal_url) margin=5 padding=10 background="#dc3545" color="#fff" border="none" border-radius=5 {
-                    "Reveal Votes"
-                }
-                button hx-post=(reset_url) margin=5 padding=10 background="#ffc107" color="#000" border="none" border-radius=5 {
-                    "Reset Voting"
                 }
             }
         }
     }
 }
-// Partial update UI functions for SSE
+
 #[must_use]
-pub fn players_list_content(players: &[Player]) -> Containers {
+pub fn results_section(
+    votes: &[Vote],
+    players: &[Player],
+    votes_revealed: bool,
+    voting_system: &VotingSystem,
+    timing: Option<&RoundTiming>,
+) -> Containers {
     container! {
-        @if players.is_empty() {
-            div color="#666" { "No players yet" }
-        } @else {
-            @for player in players {
-                div padding=5 border-bottom="1px solid #eee" {
-                    span { (player.name) }
-                    @if player.is_observer {
-                        span margin-left=10 color="#666" { "(Observer)" }
-                    }
-                    span margin-left=10 color="#999" { (format!("joined {}", player.joined_at.format("%H:%M"))) }
-                }
+        div id="results-section" margin-top=20 {
+            h2 { "Results" }
+            div id="vote-results" {
+                (vote_results_content(votes, players, votes_revealed, voting_system))
+            }
+            @if let Some(timing) = timing {
+                div id="round-timing" margin-top=10 color="#666" { (timing.to_string()) }
             }
         }
     }
 }
 
+/// Controls for running rounds, only rendered for the game owner
+///
+/// These live outside the voting and results sections because those are
+/// re-rendered for every player, while the `story-input` and `game-actions`
+/// partials only land on the page of the owner, who is the only one with
+/// those targets.
 #[must_use]
-pub fn vote_results_content(votes: &[Vote], revealed: bool) -> Containers {
+pub fn owner_controls_section(game_id: &str, game: &Game, vote_count: usize) -> Containers {
+    let voting_active = matches!(game.state, GameState::Voting);
+
     container! {
-        @if votes.is_empty() {
-            div color="#666" { "No votes cast yet" }
-        } @else if revealed {
-            div {
-                h3 { "Vote Results:" }
-                @for vote in votes {
-                    div padding=5 border-bottom="1px solid #eee" {
-                        span { (format!("{}: {}", vote.player_name, vote.value)) }
-                        span margin-left=10 color="#999" { (format!("cast at {}", vote.cast_at.format("%H:%M:%S"))) }
-                    }
-                }
+        div id="owner-controls" margin-top=20 padding=15 background="#f8f9fa" border-radius=5 {
+            h2 { "Game Controls" }
+            div id="story-input" margin-bottom=15 {
+                (story_input_content(game_id, voting_active, &game.current_story))
             }
-        } @else {
-            div {
-                span { (format!("{} votes cast", votes.len())) }
-                span margin-left=10 color="#666" { "(hidden until revealed)" }
+            div id="game-actions" margin-top=15 {
+                (game_actions_content(game_id, &game.state, &VotingSystem::from_string(&game.voting_system), vote_count))
             }
+            (story_import_form(game_id))
         }
     }
 }
 
+/// A box for pasting one story per line, optionally as `KEY-123 | title`,
+/// into the backlog
 #[must_use]
-pub fn game_status_content(status: &str) -> Containers {
+pub fn story_import_form(game_id: &str) -> Containers {
+    let import_url = format!("/api/games/{game_id}/stories/import");
+
     container! {
-        div padding=10 background="#f0f0f0" border-radius=5 {
-            span { "Status: " }
-            span { (status) }
+        div id="story-import" margin-top=15 {
+            form hx-post=(import_url) hx-target="#story-import-result" {
+                span { "Import stories (one per line, optionally \"KEY-123 | title\"):" }
+                textarea name="stories" placeholder="KEY-123 | Login page" margin-top=5;
+                button type="submit" margin-top=5 padding=5 background="#007bff" color="#fff" border="none" border-radius=3 {
+                    "Import stories"
+                }
+            }
+            div id="story-import-result" margin-top=5 {}
         }
     }
 }
 
+/// How many pasted stories made it into the backlog
 #[must_use]
-pub fn current_story_section(current_story: &Option<String>, voting_active: bool) -> Containers {
+pub fn story_import_result_content(result: StoryImport) -> Containers {
+    container! {
+        div color="#155724" {
+            (format!("Added {} stories, skipped {} duplicates", result.added, result.skipped))
+        }
+    }
+}
+
+#[must_use]
+pub fn story_backlog_section(stories: &[Story]) -> Containers {
+    container! {
+        div id="story-backlog" margin-top=20 {
+            (story_backlog_content(stories))
+        }
+    }
+}
+
+/// The game's stories in backlog order, each prefixed with its ticket key if it has one
+#[must_use]
+pub fn story_backlog_content(stories: &[Story]) -> Containers {
+    container! {
+        h2 { "Backlog" }
+        @if stories.is_empty() {
+            div color="#666" { "No stories in the backlog yet" }
+        } @else {
+            @for story in stories {
+                div padding=5 border-bottom="1px solid #eee" {
+                    @if let Some(key) = &story.key {
+                        span font-weight="bold" margin-right=10 { (key) }
+                    }
+                    span { (story.title) }
+                }
+            }
+        }
+    }
+}
+
+/// The stories the team has finished estimating, each with the estimate it
+/// was settled on
+#[must_use]
+pub fn completed_stories_section(stories: &[Story]) -> Containers {
+    container! {
+        div id="completed-stories" margin-top=20 {
+            (completed_stories_content(stories))
+        }
+    }
+}
+
+#[must_use]
+pub fn completed_stories_content(stories: &[Story]) -> Containers {
+    container! {
+        h2 { "Completed Stories" }
+        @if stories.is_empty() {
+            div color="#666" { "No stories completed yet" }
+        } @else {
+            @for story in stories {
+                div padding=5 border-bottom="1px solid #eee" {
+                    span { (story.to_string()) }
+                    span font-weight="bold" margin-left=10 {
+                        (story.final_estimate.as_deref().unwrap_or("-"))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reveal, reset and revote buttons for the current state of the round
+///
+/// While voting, the reveal button stays disabled until `vote_count` shows
+/// at least one vote, so an empty round can't be revealed. Once votes are
+/// revealed the cards of `voting_system` are offered as buttons for
+/// recording the story's final estimate, next to archiving the game.
+#[must_use]
+pub fn game_actions_content(
+    game_id: &str,
+    game_state: &GameState,
+    voting_system: &VotingSystem,
+    vote_count: usize,
+) -> Containers {
+    let reveal_url = format!("/api/games/{game_id}/reveal");
+    let reset_url = format!("/api/games/{game_id}/reset");
+    let revote_url = format!("/api/games/{game_id}/revote");
+    let finalize_url = format!("/api/games/{game_id}/finalize");
+    let archive_url = format!("/api/games/{game_id}/archive");
+    let complete_url = format!("/api/games/{game_id}/complete");
+
+    container! {
+        @if matches!(game_state, GameState::Archived) {
+            div color="#666" {
+                "This game is archived"
+            }
+        } @else if matches!(game_state, GameState::Completed) {
+            div color="#666" {
+                "This game is completed"
+            }
+        } @else if matches!(game_state, GameState::Revealed) {
+            button hx-post=(reveal_url) hx-target="#toast-area" margin=5 padding=10 background="#6c757d" color="#fff" border="none" border-radius=5 disabled {
+                "Votes Revealed"
+            }
+            button hx-post=(reset_url) hx-target="#toast-area" margin=5 padding=10 background="#ffc107" color="#000" border="none" border-radius=5 {
+                "Reset Voting"
+            }
+            button hx-post=(revote_url) hx-target="#toast-area" margin=5 padding=10 background="#17a2b8" color="#fff" border="none" border-radius=5 {
+                "Revote"
+            }
+            button hx-post=(archive_url) hx-target="#toast-area" margin=5 padding=10 background="#343a40" color="#fff" border="none" border-radius=5 {
+                "Archive Game"
+            }
+            button hx-post=(complete_url) hx-target="#toast-area" margin=5 padding=10 background="#28a745" color="#fff" border="none" border-radius=5 {
+                "Complete Game"
+            }
+            div id="finalize-estimate" margin-top=10 {
+                span { "Record final estimate:" }
+                @for value in &voting_system.get_voting_options() {
+                    form hx-post=(finalize_url) hx-target="#toast-area" {
+                        input type="hidden" name="value" value=(value);
+                        button type="submit" margin=2 padding=5 background="#28a745" color="#fff" border="none" border-radius=3 {
+                            (value)
+                        }
+                    }
+                }
+            }
+        } @else if matches!(game_state, GameState::Voting) {
+            @if vote_count == 0 {
+                button hx-post=(reveal_url) hx-target="#toast-area" margin=5 padding=10 background="#6c757d" color="#fff" border="none" border-radius=5 disabled {
+                    "Reveal Votes"
+                }
+            } @else {
+                button hx-post=(reveal_url) hx-target="#toast-area" margin=5 padding=10 background="#dc3545" color="#fff" border="none" border-radius=5 {
+                    "Reveal Votes"
+                }
+            }
+            button hx-post=(reset_url) hx-target="#toast-area" margin=5 padding=10 background="#ffc107" color="#000" border="none" border-radius=5 {
+                "Reset Voting"
+            }
+            @if vote_count == 0 {
+                div color="#666" font-size=12 {
+                    "Votes can be revealed once someone has voted"
+                }
+            }
+        } @else {
+            // Waiting state - no votes to reveal yet, no need for reset
+            div color="#666" {
+                "Start voting to see action buttons"
+            }
+            button hx-post=(complete_url) hx-target="#toast-area" margin=5 padding=10 background="#28a745" color="#fff" border="none" border-radius=5 {
+                "Complete Game"
+            }
+        }
+    }
+}
+// Partial update UI functions for SSE
+#[must_use]
+pub fn players_list_content(players: &[Player]) -> Containers {
+    container! {
+        @if players.is_empty() {
+            div color="#666" { "No players yet" }
+        } @else {
+            @for player in players {
+                div padding=5 border-bottom="1px solid #eee" {
+                    span { (player.name) }
+                    @if player.team_role.is_set() {
+                        span margin-left=10 padding-x=6 background=(team_role_color(&player.team_role)) color="#fff" border-radius=3 {
+                            (player.team_role.to_string())
+                        }
+                    }
+                    @if player.role != PlayerRole::Voter {
+                        span margin-left=10 color="#666" { (format!("({})", player.role)) }
+                    }
+                    span margin-left=10 color="#999" { (format!("joined {}", player.joined_at.format("%H:%M"))) }
+                }
+            }
+        }
+    }
+}
+
+/// Background of a team role's badge in the players list
+const fn team_role_color(team_role: &TeamRole) -> &'static str {
+    match team_role {
+        TeamRole::Developer => "#007bff",
+        TeamRole::Designer => "#e83e8c",
+        TeamRole::ProductManager => "#fd7e14",
+        TeamRole::TechLead => "#6f42c1",
+        TeamRole::Qa => "#20c997",
+        TeamRole::Other(_) => "#6c757d",
+    }
+}
+
+/// Vote results, with advisory votes listed apart from the counted ones once revealed
+#[must_use]
+/// Standard deviations from the mean beyond which a revealed vote is flagged
+const OUTLIER_THRESHOLD: f64 = 1.5;
+
+pub fn vote_results_content(
+    votes: &[Vote],
+    players: &[Player],
+    revealed: bool,
+    voting_system: &VotingSystem,
+) -> Containers {
+    let mut counted = planning_poker_poker::counted_votes(votes, players);
+    let mut advisory: Vec<Vote> = votes
+        .iter()
+        .filter(|vote| !counted.iter().any(|kept| kept.player_id == vote.player_id))
+        .cloned()
+        .collect();
+    if revealed {
+        planning_poker_poker::sort_votes(&mut counted, voting_system);
+        planning_poker_poker::sort_votes(&mut advisory, voting_system);
+    }
+    let consensus = planning_poker_poker::compute_consensus(&counted, voting_system);
+    let outliers: Vec<Uuid> =
+        planning_poker_poker::vote_outliers(&counted, voting_system, OUTLIER_THRESHOLD)
+            .into_iter()
+            .map(|(player_id, _)| player_id)
+            .collect();
+
+    container! {
+        @if votes.is_empty() {
+            div color="#666" { "No votes cast yet" }
+        } @else if revealed {
+            div {
+                h3 { "Vote Results:" }
+                (average_banner(&counted, voting_system))
+                (consensus_banner(&consensus))
+                (distribution_chart(&counted, voting_system))
+                @for vote in &counted {
+                    (revealed_vote_row(vote, outliers.contains(&vote.player_id)))
+                }
+                @if !advisory.is_empty() {
+                    h3 margin-top=10 { "Advisory votes" }
+                    @for vote in &advisory {
+                        (revealed_vote_row(vote, false))
+                    }
+                }
+            }
+        } @else {
+            div {
+                span { (format!("{} votes cast", votes.len())) }
+                span margin-left=10 color="#666" { "(hidden until revealed)" }
+            }
+        }
+    }
+}
+
+/// Whether the counted votes converged, with the estimate to settle on if they did
+fn consensus_banner(consensus: &ConsensusResult) -> Containers {
+    let (summary, background, color) = match (&consensus.suggested, &consensus.spread) {
+        (Some(suggested), _) if consensus.consensus => {
+            (format!("Consensus: {suggested} ✅"), "#d4edda", "#155724")
+        }
+        (_, Some((lowest, highest))) => (
+            format!("No consensus — spread from {lowest} to {highest}"),
+            "#f8d7da",
+            "#721c24",
+        ),
+        _ => (
+            "No consensus — no estimates".to_string(),
+            "#f8d7da",
+            "#721c24",
+        ),
+    };
+
+    container! {
+        div id="consensus" padding=10 margin-bottom=10 background=(background) color=(color) border-radius=5 {
+            span { (summary) }
+            @if consensus.excluded > 0 {
+                span margin-left=10 color="#666" { (format!("({} without an estimate)", consensus.excluded)) }
+            }
+        }
+    }
+}
+
+/// The revealed round's average at a glance, with a badge when every
+/// numeric vote is the same
+///
+/// A round without numeric votes, such as all `?`, shows no average.
+fn average_banner(votes: &[Vote], voting_system: &VotingSystem) -> Containers {
+    let average = planning_poker_poker::numeric_average(votes, voting_system);
+    let agreed = planning_poker_poker::has_consensus(votes, voting_system);
+
+    container! {
+        div id="vote-average" margin-bottom=10 font-size=18 {
+            @if let Some(average) = average {
+                span font-weight=bold { (format!("Average: {average:.1}")) }
+            }
+            @if agreed {
+                span margin-left=10 padding-x=8 padding-y=3 background="#28a745" color="#fff" border-radius=10 font-size=14 {
+                    "Consensus!"
+                }
+            }
+        }
+    }
+}
+
+/// Bars sized by how many counted votes went to each card, in deck order
+///
+/// The bar of a value the whole round agreed on is drawn in green.
+fn distribution_chart(votes: &[Vote], voting_system: &VotingSystem) -> Containers {
+    let distribution = planning_poker_poker::vote_distribution(votes);
+    let agreed = planning_poker_poker::consensus_recommendation(votes);
+    let most = distribution.values().copied().max().unwrap_or(1);
+
+    let mut bars: Vec<(String, usize)> = distribution.into_iter().collect();
+    bars.sort_by(|(a, _), (b, _)| {
+        let position = |value: &str| voting_system.ordinal_of(value).unwrap_or(usize::MAX);
+        position(a).cmp(&position(b)).then_with(|| a.cmp(b))
+    });
+    let bars: Vec<(String, usize, String, &str)> = bars
+        .into_iter()
+        .map(|(value, count)| {
+            let color = if agreed.as_ref() == Some(&value) {
+                "#28a745"
+            } else {
+                "#007bff"
+            };
+            (value, count, format!("{}%", count * 100 / most), color)
+        })
+        .collect();
+
+    container! {
+        div id="vote-distribution" margin-bottom=10 {
+            @for (value, count, width, color) in bars {
+                div direction=row align-items=center margin-bottom=3 {
+                    span width=40 { (value) }
+                    div width=200 background="#f1f3f5" border-radius=3 {
+                        div width=(width) height=16 background=(color) border-radius=3 {}
+                    }
+                    span margin-left=10 color="#666" { (count.to_string()) }
+                }
+            }
+        }
+    }
+}
+
+fn revealed_vote_row(vote: &Vote, outlier: bool) -> Containers {
+    container! {
+        div padding=5 border-bottom="1px solid #eee" {
+            span { (format!("{}: {}", vote.player_name, vote.value)) }
+            @if outlier {
+                span margin-left=10 padding=2 background="#f8d7da" color="#721c24" border-radius=3 font-size=12 { "outlier" }
+            }
+            @if vote.is_low_confidence() {
+                span margin-left=10 padding=2 background="#fff3cd" color="#856404" border-radius=3 font-size=12 { "low confidence" }
+            }
+            span margin-left=10 color="#999" { (format!("cast at {}", vote.cast_at.format("%H:%M:%S"))) }
+        }
+    }
+}
+
+#[must_use]
+pub fn game_status_content(status: &str) -> Containers {
+    container! {
+        div padding=10 background="#f0f0f0" border-radius=5 {
+            span { "Status: " }
+            span { (status) }
+        }
+    }
+}
+
+#[must_use]
+pub fn current_story_section(current_story: &Option<String>, voting_active: bool) -> Containers {
     container! {
         div id="current-story" margin-bottom=15 {
             @if let Some(story) = current_story {
                 h3 { "Current Story" }
-                div padding=15 background="#e3f2fd" border-left="4px solid #2196f3" border-radius=5 margin-bottom=10 {
+                div padding=15 background="#e3f2fd" border-left="4px solid #2196f3" border-radius=5 margin-bottom=10 font-size=20 font-weight=bold {
                     (story)
                 }
             } @else if voting_active {
@@ -385,22 +1166,26 @@ pub fn story_input_content(
     current_story: &Option<String>,
 ) -> Containers {
     let start_voting_url = format!("/api/games/{game_id}/start-voting");
+    let update_story_url = format!("/api/games/{game_id}/story");
+    let story = current_story.as_deref().unwrap_or("");
 
+    // While voting the story can still be corrected, without restarting the round
     if voting_active {
         container! {
-            form hx-post=(start_voting_url) {
+            form hx-post=(update_story_url) hx-target="#toast-area" {
                 span { "Story:" }
-                input type="text" name="story" value=(current_story.as_deref().unwrap_or("")) margin-left=10 readonly;
-                button type="submit" margin-left=10 padding=5 background="#6c757d" color="#fff" border="none" border-radius=3 disabled {
-                    "Voting Active"
+                input type="text" name="story" value=(story) placeholder="Leave blank to clear the story" margin-left=10;
+                button type="submit" margin-left=10 padding=5 background="#17a2b8" color="#fff" border="none" border-radius=3 {
+                    "Update Story"
                 }
             }
         }
     } else {
         container! {
-            form hx-post=(start_voting_url) {
+            form hx-post=(start_voting_url) hx-target="#toast-area" {
                 span { "Story:" }
-                input type="text" name="story" placeholder="Enter story to vote on" margin-left=10 required;
+                input type="text" name="story" value=(story) placeholder="Enter story to vote on (optional)" margin-left=10;
+                input type="number" name="timer_seconds" placeholder="Timer in seconds (optional)" margin-left=10;
                 button type="submit" margin-left=10 padding=5 background="#007bff" color="#fff" border="none" border-radius=3 {
                     "Start Voting"
                 }
@@ -409,44 +1194,415 @@ pub fn story_input_content(
     }
 }
 
+/// The full game page, topped by a `flash` notice when the visit follows
+/// creating or joining the game
+#[allow(clippy::too_many_arguments)]
 pub fn game_page_with_data(
     game_id: &str,
     game: &Game,
     players: &[Player],
     votes: &[Vote],
+    stories: &[Story],
+    my_player_id: Option<Uuid>,
+    invite_url: &str,
+    flash: Option<&str>,
 ) -> Containers {
     tracing::info!("game_page_with_data called, wrapping with page_layout");
-    let content = game_content_with_data(game_id, game, players, votes);
+    let mut content = flash.map(flash_toast).unwrap_or_default();
+    content.extend(game_content_with_data(
+        game_id,
+        game,
+        players,
+        votes,
+        stories,
+        my_player_id,
+        invite_url,
+    ));
     page_layout(&content)
 }
 
+/// Whether a toast confirms what the player did or reports why it didn't
+/// happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Error,
+}
+
+/// A short notice answering something the player just did, shown in the
+/// `toast-area` at the top of every page
+#[must_use]
+pub fn toast_content(kind: ToastKind, message: &str) -> Containers {
+    let (id, background, color, border) = match kind {
+        ToastKind::Success => ("toast-success", "#d4edda", "#155724", "1px solid #c3e6cb"),
+        ToastKind::Error => ("toast-error", "#f8d7da", "#721c24", "1px solid #f5c6cb"),
+    };
+
+    container! {
+        div id=(id) padding=10 margin-bottom=10 background=(background) color=(color) border=(border) border-radius=5 {
+            (message)
+        }
+    }
+}
+
+/// A one-off notice shown at the top of a page, such as "Joined as Alice"
+#[must_use]
+pub fn flash_toast(message: &str) -> Containers {
+    container! {
+        div id="flash-toast" padding=10 margin-bottom=10 background="#d4edda" color="#155724" border="1px solid #c3e6cb" border-radius=5 {
+            (message)
+        }
+    }
+}
+
+/// Tells an open page that the server is shutting down, pushed into its
+/// `toast-area`
+#[must_use]
+pub fn shutdown_notice(estimated_seconds: u64) -> Containers {
+    container! {
+        div id="shutdown-notice" padding=10 margin-bottom=10 background="#fff3cd" color="#856404" border="1px solid #ffeeba" border-radius=5 {
+            (format!("The server is shutting down within {estimated_seconds} seconds, reload the page once it is back"))
+        }
+    }
+}
+
+/// The game page as seen by `my_player_id`, who gets the round controls if
+/// they own the game, with `invite_url` offered for sharing
 #[must_use]
 pub fn game_content_with_data(
     game_id: &str,
     game: &Game,
     players: &[Player],
     votes: &[Vote],
+    stories: &[Story],
+    my_player_id: Option<Uuid>,
+    invite_url: &str,
 ) -> Containers {
     let game_id_display = format!("Game ID: {game_id}");
-    let status_text = match game.state {
-        GameState::Waiting => "Waiting for players",
-        GameState::Voting => "Voting in progress",
-        GameState::Revealed => "Votes revealed",
-    };
+    let status_text = game.state.description();
     let voting_active = matches!(game.state, GameState::Voting);
     let votes_revealed = matches!(game.state, GameState::Revealed);
+    let locked_vote = my_player_id
+        .filter(|_| !game.settings.allow_vote_change)
+        .and_then(|id| votes.iter().find(|vote| vote.player_id == id))
+        .map(|vote| vote.value.as_str());
+    let timing = game.round_timing(votes);
+    let summary_url = format!("/game/{game_id}/summary");
+    let is_owner = my_player_id == Some(game.owner_id);
+    let leave_url = format!("/api/games/{game_id}/leave");
+    let mut completed_stories: Vec<Story> = stories
+        .iter()
+        .filter(|story| story.final_estimate.is_some())
+        .cloned()
+        .collect();
+    completed_stories.sort_by_key(|story| story.completed_at);
 
     container! {
         h1 { "Planning Poker Game" }
         div { (game_id_display) }
         div { (format!("Game: {}", game.name)) }
+        (invite_section(invite_url))
 
         (game_status_section(&status_text))
         (current_story_section(&game.current_story, voting_active))
+        (story_backlog_section(stories))
+        (completed_stories_section(&completed_stories))
         (players_section(&players))
-        (voting_section(&game_id, game, voting_active))
-        (results_section(&game_id, &votes, votes_revealed))
+        @if is_owner {
+            (owner_controls_section(&game_id, game, votes.len()))
+        }
+        (voting_section(&game_id, game, voting_active, locked_vote))
+        (results_section(&votes, &players, votes_revealed, &VotingSystem::from_string(&game.voting_system), timing.as_ref()))
+
+        @if votes_revealed {
+            div margin-top=20 {
+                anchor href=(summary_url) { "Session summary →" }
+            }
+            (export_links(game_id))
+        }
+
+        div margin-top=30 {
+            anchor href="/" {
+                "← Back to Home"
+            }
+            @if my_player_id.is_some() {
+                button hx-post=(leave_url) hx-target="#toast-area" margin-left=20 padding=5 background="#dc3545" color="#fff" border="none" border-radius=3 {
+                    "Leave game"
+                }
+            }
+        }
+
+        div id="round-history" margin-top=30 {
+            (round_history_collapsed(&game_id))
+        }
+    }
+}
+
+/// The game page as an observer sees it: the round, the players and the vote
+/// counts, but no voting section
+///
+/// Leaving out the `voting-section` element also keeps the vote buttons
+/// pushed to voters by partial updates off this page.
+#[must_use]
+pub fn observer_game_page(
+    game_id: &str,
+    game: &Game,
+    players: &[Player],
+    votes: &[Vote],
+) -> Containers {
+    let status_text = game.state.description();
+    let voting_active = matches!(game.state, GameState::Voting);
+    let votes_revealed = matches!(game.state, GameState::Revealed);
+    let timing = game.round_timing(votes);
+    let leave_url = format!("/api/games/{game_id}/leave");
+
+    let content = container! {
+        h1 { "Planning Poker Game" }
+        div { (format!("Game ID: {game_id}")) }
+        div { (format!("Game: {}", game.name)) }
+        div id="observer-notice" margin-top=10 color="#666" {
+            "You are observing this game and can't vote"
+        }
+
+        (game_status_section(&status_text))
+        (current_story_section(&game.current_story, voting_active))
+        (players_section(players))
+        (results_section(votes, players, votes_revealed, &VotingSystem::from_string(&game.voting_system), timing.as_ref()))
+
+        div margin-top=30 {
+            anchor href="/" {
+                "← Back to Home"
+            }
+            button hx-post=(leave_url) hx-target="#toast-area" margin-left=20 padding=5 background="#dc3545" color="#fff" border="none" border-radius=3 {
+                "Leave game"
+            }
+        }
+    };
+    page_layout(&content)
+}
+
+/// Read-only board for a facilitator to put on a shared screen: the round's
+/// story, who has voted and, once revealed, the results
+///
+/// It has no vote buttons or owner actions, but keeps the ids of the
+/// sections the game page's partial updates target, so it follows the game
+/// as it is played.
+#[must_use]
+pub fn presentation_content(game: &Game, players: &[Player], votes: &[Vote]) -> Containers {
+    let voting_active = matches!(game.state, GameState::Voting);
+    let votes_revealed = matches!(game.state, GameState::Revealed);
+    let timing = game.round_timing(votes);
+
+    container! {
+        h1 { (game.name) }
+        (game_status_section(game.state.description()))
+        (current_story_section(&game.current_story, voting_active))
+        (players_section(players))
+        (results_section(votes, players, votes_revealed, &VotingSystem::from_string(&game.voting_system), timing.as_ref()))
+    }
+}
+
+/// The link players can be sent to join the game, in a field so it can be
+/// selected and copied
+fn invite_section(invite_url: &str) -> Containers {
+    container! {
+        div id="invite" margin-top=10 padding=10 background="#f8f9fa" border="1px solid #dee2e6" border-radius=5 {
+            h3 { "Invite" }
+            div color="#666" { "Share this link to invite players:" }
+            input type="text" name="invite-url" value=(invite_url) margin-top=5 width="100%";
+            div margin-top=5 {
+                anchor href=(invite_url) { (invite_url) }
+            }
+        }
+    }
+}
+
+/// The name prompt an invite link for `/join/{game_id}` lands on, which
+/// joins the game as a voter
+#[must_use]
+pub fn join_prompt_content(game_id: &str, game_name: &str) -> Containers {
+    container! {
+        h1 { (format!("Join {game_name}")) }
+        form hx-post="/join-game" margin-top=20 {
+            input type="hidden" name="game-id" value=(game_id);
+            div margin-bottom=10 {
+                span { "Your Name:" }
+                input type="text" name="player-name" placeholder="Enter your name" margin-left=10 required;
+            }
+            button type="submit" margin-top=10 padding=10 background="#007bff" color="#fff" border="none" border-radius=5 {
+                "Join Game"
+            }
+        }
+    }
+}
+
+/// Form for joining a game as an observer, who follows the round without
+/// voting
+#[must_use]
+pub fn observe_game_content(game_id: &str) -> Containers {
+    let observe_url = format!("/game/{game_id}/observe");
+
+    container! {
+        h1 { "Observe a Game" }
+        div color="#666" { "Observers see the round and the votes but don't vote themselves" }
+        form hx-post=(observe_url) margin-top=20 {
+            input type="hidden" name="game-id" value=(game_id);
+            div margin-bottom=10 {
+                span { "Your Name:" }
+                input type="text" name="observer-name" placeholder="Enter your name" margin-left=10 required;
+            }
+            button type="submit" margin-top=10 padding=10 background="#6c757d" color="#fff" border="none" border-radius=5 {
+                "Observe Game"
+            }
+        }
+    }
+}
+
+/// The history section before it is expanded, so the rounds are only
+/// loaded when someone asks for them
+#[must_use]
+pub fn round_history_collapsed(game_id: &str) -> Containers {
+    let rounds_url = format!("/api/games/{game_id}/rounds");
+
+    container! {
+        button hx-get=(rounds_url) hx-target="#round-history" padding=5 background="#6c757d" color="#fff" border="none" border-radius=3 {
+            "Show history"
+        }
+    }
+}
+
+/// One page of a game's completed rounds, latest first
+///
+/// The first page (`offset` 0) replaces the collapsed history section; later
+/// pages fill the placeholder left by the "Load more" button of the page
+/// before them. `next_offset` is `None` once there are no more rounds.
+#[must_use]
+pub fn round_history_content(
+    game_id: &str,
+    rounds: &[RoundSummary],
+    offset: usize,
+    next_offset: Option<usize>,
+) -> Containers {
+    let collapse_url = format!("/api/games/{game_id}/rounds?collapsed=true");
+
+    container! {
+        @if offset == 0 {
+            h2 { "History" }
+            button hx-get=(collapse_url) hx-target="#round-history" margin-bottom=10 padding=5 background="#6c757d" color="#fff" border="none" border-radius=3 {
+                "Hide history"
+            }
+            @if rounds.is_empty() {
+                div color="#666" { "No rounds have been completed yet" }
+            } @else {
+                (export_links(game_id))
+            }
+        }
+        @for round in rounds {
+            (round_summary_row(round))
+        }
+        @if let Some(next_offset) = next_offset {
+            div id=(format!("round-history-page-{next_offset}")) {
+                button hx-get=(format!("/api/games/{game_id}/rounds?offset={next_offset}")) hx-target=(format!("#round-history-page-{next_offset}")) margin-top=10 padding=5 background="#007bff" color="#fff" border="none" border-radius=3 {
+                    "Load more"
+                }
+            }
+        }
+    }
+}
+
+/// Download links for the game's completed rounds, for pasting into a tracker
+fn export_links(game_id: &str) -> Containers {
+    container! {
+        div margin-top=10 margin-bottom=10 {
+            anchor href=(format!("/api/games/{game_id}/export?format=csv")) { "Export CSV" }
+            anchor href=(format!("/api/games/{game_id}/export?format=md")) margin-left=10 { "Export Markdown" }
+        }
+    }
+}
+
+fn round_summary_row(round: &RoundSummary) -> Containers {
+    let story = round.story.as_deref().unwrap_or("Untitled story");
+    let estimate = round.final_estimate.as_deref().map_or_else(
+        || "no estimate".to_string(),
+        |estimate| format!("estimate {estimate}"),
+    );
+    let distribution = round
+        .distribution
+        .iter()
+        .map(|(value, count)| format!("{value} ×{count}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let timestamps = format!(
+        "{} – {}",
+        round.first_vote_at.format("%Y-%m-%d %H:%M"),
+        round.last_vote_at.format("%H:%M")
+    );
+
+    container! {
+        div padding=5 border-bottom="1px solid #eee" {
+            span font-weight="bold" { (story) }
+            span margin-left=10 { (estimate) }
+            span margin-left=10 color="#666" { (distribution) }
+            span margin-left=10 color="#999" { (timestamps) }
+        }
+    }
+}
+
+/// The read-only page of a completed game: its totals and every round it
+/// played, latest first, in place of the voting controls
+#[must_use]
+pub fn completed_game_content(
+    game_id: &str,
+    game: &Game,
+    players: &[Player],
+    rounds: &[RoundSummary],
+) -> Containers {
+    let completed = game.completed_at.map_or_else(
+        || "Game completed".to_string(),
+        |completed_at| format!("Completed {}", completed_at.format("%Y-%m-%d %H:%M")),
+    );
+    let votes_cast: usize = rounds
+        .iter()
+        .flat_map(|round| &round.distribution)
+        .map(|(_, count)| count)
+        .sum();
+    let mut estimated: Vec<&str> = rounds
+        .iter()
+        .filter(|round| round.final_estimate.is_some())
+        .filter_map(|round| round.story.as_deref())
+        .collect();
+    estimated.sort_unstable();
+    estimated.dedup();
+    let summary_url = format!("/game/{game_id}/summary");
+
+    container! {
+        h1 { "Planning Poker Game" }
+        div { (format!("Game: {}", game.name)) }
+        div color="#666" { (completed) }
+
+        div id="completed-totals" margin-top=20 {
+            h2 { "Totals" }
+            div { (format!("Rounds played: {}", rounds.len())) }
+            div { (format!("Stories estimated: {}", estimated.len())) }
+            div { (format!("Votes cast: {votes_cast}")) }
+            div { (format!("Players: {}", players.len())) }
+        }
+
+        div id="completed-rounds" margin-top=20 {
+            h2 { "Rounds" }
+            @if rounds.is_empty() {
+                div color="#666" { "No rounds were played" }
+            } @else {
+                (export_links(game_id))
+                @for round in rounds {
+                    (round_summary_row(round))
+                }
+            }
+        }
 
+        div margin-top=20 {
+            anchor href=(summary_url) { "Session summary →" }
+        }
         div margin-top=30 {
             anchor href="/" {
                 "← Back to Home"
@@ -454,3 +1610,1070 @@ pub fn game_content_with_data(
         }
     }
 }
+
+/// How every player took part across the game's revealed rounds
+#[must_use]
+pub fn session_summary_content(game_id: &str, game: &Game, stats: &[PlayerStats]) -> Containers {
+    let game_url = format!("/game/{game_id}");
+
+    container! {
+        h1 { "Session summary" }
+        div { (format!("Game: {}", game.name)) }
+
+        div id="session-summary" margin-top=20 {
+            @if stats.is_empty() {
+                div color="#666" { "No rounds have been revealed yet" }
+            } @else {
+                @for player in stats {
+                    (player_stats_row(player))
+                }
+            }
+        }
+
+        div margin-top=30 {
+            anchor href=(game_url) {
+                "← Back to Game"
+            }
+        }
+    }
+}
+
+fn player_stats_row(stats: &PlayerStats) -> Containers {
+    let average = stats.average_vote.map_or_else(
+        || "no numeric votes".to_string(),
+        |average| format!("average {average:.1}"),
+    );
+
+    container! {
+        div padding=5 border-bottom="1px solid #eee" {
+            span font-weight="bold" { (stats.player_name) }
+            span margin-left=10 { (format!("{} rounds", stats.rounds_participated)) }
+            span margin-left=10 { (format!("matched the final estimate {} times", stats.matched_final_estimate)) }
+            span margin-left=10 color="#666" { (average) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_games_lobby_shows_empty_state_without_games() {
+        let content = games_lobby_content(&[], &HashMap::new());
+        let rendered = format!("{content:?}");
+
+        assert!(rendered.contains("No games are waiting for players"));
+    }
+
+    #[test]
+    fn test_archived_games_link_to_their_summary() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Sprint 12".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Archived,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+
+        let rendered = format!("{:?}", archived_games_content(&[game.clone()]));
+        assert!(rendered.contains("Sprint 12"));
+        assert!(rendered.contains(&format!("/game/{}/summary", game.id)));
+
+        let rendered = format!(
+            "{:?}",
+            home_content_with_games(
+                &[game],
+                &HashMap::new(),
+                &[],
+                "fibonacci",
+                &VotingSystem::Fibonacci,
+                None
+            )
+        );
+        assert!(!rendered.contains("Archived Games"));
+        assert!(!rendered.contains("Sprint 12"));
+    }
+
+    #[test]
+    fn test_games_list_separates_completed_games_and_links_pages() {
+        let games: Vec<Game> = [
+            ("Sprint 12", GameState::Voting),
+            ("Sprint 11", GameState::Completed),
+        ]
+        .into_iter()
+        .map(|(name, state)| Game {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        })
+        .collect();
+        let player_counts = HashMap::from([(games[0].id, 4)]);
+        let params = PaginationParams {
+            page: 2,
+            page_size: 2,
+        };
+
+        let rendered = format!(
+            "{:?}",
+            games_list_content(&games, &player_counts, None, params, 7, true)
+        );
+
+        assert!(rendered.contains(&format!("/game/{}", games[0].id)));
+        assert!(rendered.contains(&short_code(games[0].id)));
+        assert!(rendered.contains("Completed"));
+        assert!(rendered.contains("Hide completed games"));
+        assert!(rendered.contains("/games?page=1&page_size=2&completed=1"));
+        assert!(rendered.contains("/games?page=3&page_size=2&completed=1"));
+
+        let last_page = PaginationParams {
+            page: 4,
+            page_size: 2,
+        };
+        let rendered = format!(
+            "{:?}",
+            games_list_content(
+                &games[..1],
+                &player_counts,
+                Some(GameState::Voting),
+                last_page,
+                7,
+                false
+            )
+        );
+        assert!(rendered.contains("Show completed games"));
+        assert!(rendered.contains("/games?page=3&page_size=2&state=Voting"));
+        assert!(!rendered.contains("Next"));
+    }
+
+    #[test]
+    fn test_presentation_view_has_no_controls_and_hides_votes_until_revealed() {
+        let alice = Player {
+            id: Uuid::new_v4(),
+            name: "Alice".to_string(),
+            role: PlayerRole::Voter,
+            team_role: TeamRole::default(),
+            joined_at: chrono::Utc::now(),
+        };
+        let votes = [Vote {
+            player_id: alice.id,
+            player_name: alice.name.clone(),
+            value: "XXL".to_string(),
+            cast_at: chrono::Utc::now(),
+            confidence: None,
+        }];
+        let mut game = Game {
+            id: Uuid::new_v4(),
+            name: "Sprint 12".to_string(),
+            owner_id: alice.id,
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Voting,
+            current_story: Some("Login page".to_string()),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let players = [alice];
+
+        let rendered = format!(
+            "{:?}",
+            presentation_layout(&presentation_content(&game, &players, &votes))
+        );
+        assert!(rendered.contains("Login page"));
+        assert!(rendered.contains("vote-results"));
+        assert!(!rendered.contains("/api/games/"));
+        assert!(!rendered.contains("XXL"));
+
+        game.state = GameState::Revealed;
+        let rendered = format!("{:?}", presentation_content(&game, &players, &votes));
+        assert!(rendered.contains("XXL"));
+        assert!(!rendered.contains("/api/games/"));
+    }
+
+    fn revealed_results(values: &[&str]) -> String {
+        let votes: Vec<Vote> = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Vote {
+                player_id: Uuid::new_v4(),
+                player_name: format!("Player {i}"),
+                value: (*value).to_string(),
+                cast_at: chrono::Utc::now(),
+                confidence: None,
+            })
+            .collect();
+
+        format!(
+            "{:?}",
+            vote_results_content(&votes, &[], true, &VotingSystem::Fibonacci)
+        )
+    }
+
+    #[test]
+    fn test_consensus_round_shows_average_and_badge() {
+        let rendered = revealed_results(&["5", "5", "5"]);
+
+        assert!(rendered.contains("Average: 5.0"));
+        assert!(rendered.contains("Consensus!"));
+    }
+
+    #[test]
+    fn test_split_round_shows_average_without_badge() {
+        let rendered = revealed_results(&["3", "5", "8"]);
+
+        assert!(rendered.contains("Average: 5.3"));
+        assert!(!rendered.contains("Consensus!"));
+    }
+
+    #[test]
+    fn test_unsure_round_shows_neither_average_nor_badge() {
+        let rendered = revealed_results(&["?", "?"]);
+
+        assert!(!rendered.contains("Average"));
+        assert!(!rendered.contains("Consensus!"));
+    }
+
+    #[test]
+    fn test_average_banner_waits_for_the_reveal() {
+        let vote = Vote {
+            player_id: Uuid::new_v4(),
+            player_name: "Alice".to_string(),
+            value: "5".to_string(),
+            cast_at: chrono::Utc::now(),
+            confidence: None,
+        };
+        let rendered = format!(
+            "{:?}",
+            vote_results_content(&[vote], &[], false, &VotingSystem::Fibonacci)
+        );
+
+        assert!(!rendered.contains("vote-average"));
+        assert!(!rendered.contains("Consensus!"));
+    }
+
+    #[test]
+    fn test_lobbies_empty_content_renders_message() {
+        let rendered = format!("{:?}", lobbies_empty_content());
+
+        assert!(rendered.contains("No games are waiting for players"));
+    }
+
+    #[test]
+    fn test_observer_page_shows_vote_counts_but_no_vote_buttons() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Sprint".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Voting,
+            current_story: Some("Login page".to_string()),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let players: Vec<Player> = [("Alice", PlayerRole::Voter), ("Bob", PlayerRole::Observer)]
+            .into_iter()
+            .map(|(name, role)| Player {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                role,
+                team_role: TeamRole::default(),
+                joined_at: chrono::Utc::now(),
+            })
+            .collect();
+        let votes = [Vote {
+            player_id: players[0].id,
+            player_name: "Alice".to_string(),
+            value: "5".to_string(),
+            cast_at: chrono::Utc::now(),
+            confidence: None,
+        }];
+
+        let observed = format!("{:?}", observer_game_page("game", &game, &players, &votes));
+        assert!(observed.contains("You are observing this game"));
+        assert!(observed.contains("Login page"));
+        assert!(observed.contains("results-section"));
+        assert!(!observed.contains("voting-section"));
+        assert!(!observed.contains("/api/games/game/vote"));
+
+        let voted = format!(
+            "{:?}",
+            game_content_with_data(
+                "game",
+                &game,
+                &players,
+                &votes,
+                &[],
+                None,
+                "https://poker.example.com/?game=game"
+            )
+        );
+        assert!(voted.contains("/api/games/game/vote"));
+        assert!(voted.contains("https://poker.example.com/?game=game"));
+        assert!(!observed.contains("https://poker.example.com"));
+    }
+
+    #[test]
+    fn test_game_page_shows_flash_toast_only_when_given() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Sprint".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Waiting,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let page = |flash| {
+            format!(
+                "{:?}",
+                game_page_with_data("game", &game, &[], &[], &[], None, "/", flash)
+            )
+        };
+
+        let flashed = page(Some("Joined as Alice"));
+        assert!(flashed.contains("flash-toast"));
+        assert!(flashed.contains("Joined as Alice"));
+        assert!(!page(None).contains("flash-toast"));
+    }
+
+    #[test]
+    fn test_redirect_page_links_to_target() {
+        let rendered = format!("{:?}", redirect_page("/game/some-game"));
+
+        assert!(rendered.contains("/game/some-game"));
+    }
+
+    #[test]
+    fn test_revealed_votes_are_sorted_by_deck_order() {
+        let votes: Vec<Vote> = [("Alice", "XL"), ("Bob", "?"), ("Carol", "S")]
+            .into_iter()
+            .map(|(name, value)| Vote {
+                player_id: Uuid::new_v4(),
+                player_name: name.to_string(),
+                value: value.to_string(),
+                cast_at: chrono::Utc::now(),
+                confidence: None,
+            })
+            .collect();
+
+        let rendered = format!(
+            "{:?}",
+            vote_results_content(&votes, &[], true, &VotingSystem::TShirtSizes)
+        );
+
+        let carol = rendered.find("Carol: S").unwrap();
+        let alice = rendered.find("Alice: XL").unwrap();
+        let bob = rendered.find("Bob: ?").unwrap();
+        assert!(carol < alice && alice < bob);
+    }
+
+    #[test]
+    fn test_players_list_shows_team_role_badges() {
+        let players: Vec<Player> = [
+            ("Alice", TeamRole::TechLead),
+            ("Bob", TeamRole::Other("Scrum Master".to_string())),
+            ("Carol", TeamRole::default()),
+        ]
+        .into_iter()
+        .map(|(name, team_role)| Player {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            role: PlayerRole::Voter,
+            team_role,
+            joined_at: chrono::Utc::now(),
+        })
+        .collect();
+
+        let rendered = format!("{:?}", players_list_content(&players));
+
+        assert!(rendered.contains("Tech Lead"));
+        assert!(rendered.contains("Scrum Master"));
+        assert!(!rendered.contains("Other"));
+    }
+
+    #[test]
+    fn test_advisory_votes_are_listed_separately() {
+        let players: Vec<Player> = [("Alice", PlayerRole::Voter), ("Bob", PlayerRole::Advisor)]
+            .into_iter()
+            .map(|(name, role)| Player {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                role,
+                team_role: TeamRole::default(),
+                joined_at: chrono::Utc::now(),
+            })
+            .collect();
+        let votes: Vec<Vote> = players
+            .iter()
+            .map(|player| Vote {
+                player_id: player.id,
+                player_name: player.name.clone(),
+                value: "5".to_string(),
+                cast_at: chrono::Utc::now(),
+                confidence: None,
+            })
+            .collect();
+
+        let rendered = format!(
+            "{:?}",
+            vote_results_content(&votes, &players, true, &VotingSystem::Fibonacci)
+        );
+
+        let heading = rendered.find("Advisory votes").unwrap();
+        assert!(rendered.find("Alice: 5").unwrap() < heading);
+        assert!(rendered.find("Bob: 5").unwrap() > heading);
+    }
+
+    #[test]
+    fn test_consensus_banner_reports_agreement_and_spread() {
+        let votes = |values: &[&str]| -> Vec<Vote> {
+            values
+                .iter()
+                .map(|value| Vote {
+                    player_id: Uuid::new_v4(),
+                    player_name: "Player".to_string(),
+                    value: (*value).to_string(),
+                    cast_at: chrono::Utc::now(),
+                    confidence: None,
+                })
+                .collect()
+        };
+        let deck = VotingSystem::Fibonacci;
+
+        let agreed = format!(
+            "{:?}",
+            vote_results_content(&votes(&["5", "5", "?"]), &[], true, &deck)
+        );
+        let split = format!(
+            "{:?}",
+            vote_results_content(&votes(&["2", "13"]), &[], true, &deck)
+        );
+        let hidden = format!(
+            "{:?}",
+            vote_results_content(&votes(&["2", "13"]), &[], false, &deck)
+        );
+
+        assert!(agreed.contains("Consensus: 5 ✅"));
+        assert!(agreed.contains("(1 without an estimate)"));
+        assert!(split.contains("No consensus — spread from 2 to 13"));
+        assert!(!hidden.contains("consensus"));
+    }
+
+    #[test]
+    fn test_revealed_votes_show_distribution_and_outliers() {
+        let votes: Vec<Vote> = [
+            ("Alice", "3"),
+            ("Bob", "3"),
+            ("Carol", "3"),
+            ("Dave", "3"),
+            ("Erin", "21"),
+        ]
+        .into_iter()
+        .map(|(name, value)| Vote {
+            player_id: Uuid::new_v4(),
+            player_name: name.to_string(),
+            value: value.to_string(),
+            cast_at: chrono::Utc::now(),
+            confidence: None,
+        })
+        .collect();
+
+        let rendered = format!(
+            "{:?}",
+            vote_results_content(&votes, &[], true, &VotingSystem::Fibonacci)
+        );
+
+        assert!(rendered.contains("vote-distribution"));
+        assert!(rendered.contains("100%"));
+        assert!(rendered.contains("25%"));
+        let erin = rendered.find("Erin: 21").unwrap();
+        let flag = rendered.find("outlier").unwrap();
+        assert!(flag > erin);
+        assert_eq!(rendered.matches("outlier").count(), 1);
+    }
+
+    #[test]
+    fn test_game_actions_offer_revote_only_when_revealed() {
+        let deck = VotingSystem::Fibonacci;
+        let revealed = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Revealed, &deck, 3)
+        );
+        let hidden = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Voting, &deck, 3)
+        );
+
+        assert!(revealed.contains("/api/games/game/revote"));
+        assert!(!hidden.contains("/api/games/game/revote"));
+    }
+
+    #[test]
+    fn test_reveal_is_disabled_until_someone_votes() {
+        let deck = VotingSystem::Fibonacci;
+        let no_votes = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Voting, &deck, 0)
+        );
+        let with_votes = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Voting, &deck, 1)
+        );
+
+        assert!(no_votes.contains("/api/games/game/reveal"));
+        assert!(no_votes.contains("Votes can be revealed once someone has voted"));
+        assert!(with_votes.contains("/api/games/game/reveal"));
+        assert!(!with_votes.contains("Votes can be revealed once someone has voted"));
+    }
+
+    #[test]
+    fn test_game_actions_offer_deck_as_final_estimates_once_revealed() {
+        let deck = VotingSystem::TShirtSizes;
+        let revealed = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Revealed, &deck, 3)
+        );
+        let voting = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Voting, &deck, 3)
+        );
+
+        assert!(revealed.contains("/api/games/game/finalize"));
+        for card in deck.get_voting_options() {
+            assert!(revealed.contains(&format!("\"{card}\"")), "missing {card}");
+        }
+        assert!(!voting.contains("/api/games/game/finalize"));
+    }
+
+    #[test]
+    fn test_results_section_shows_round_timing() {
+        let deck = VotingSystem::Fibonacci;
+        let timing = RoundTiming {
+            round_seconds: 134,
+            slowest_voter_seconds: Some(110),
+        };
+
+        let timed = format!(
+            "{:?}",
+            results_section(&[], &[], true, &deck, Some(&timing))
+        );
+        let untimed = format!("{:?}", results_section(&[], &[], true, &deck, None));
+
+        assert!(timed.contains("round took 2m 14s, slowest voter 1m 50s"));
+        assert!(!untimed.contains("round-timing"));
+    }
+
+    #[test]
+    fn test_current_story_section_shows_story() {
+        let story = Some("Login page".to_string());
+
+        let rendered = format!("{:?}", current_story_section(&story, true));
+        let waiting = format!("{:?}", current_story_section(&None, false));
+
+        assert!(rendered.contains("Current Story"));
+        assert!(rendered.contains("Login page"));
+        assert!(!waiting.contains("Current Story"));
+        assert!(!waiting.contains("No story specified"));
+    }
+
+    #[test]
+    fn test_story_input_edits_the_story_while_voting() {
+        let story = Some("Logn page".to_string());
+
+        let voting = format!("{:?}", story_input_content("game", true, &story));
+        let waiting = format!("{:?}", story_input_content("game", false, &story));
+
+        assert!(voting.contains("/api/games/game/story"));
+        assert!(voting.contains("Update Story"));
+        assert!(voting.contains("Logn page"));
+        assert!(waiting.contains("/api/games/game/start-voting"));
+        assert!(waiting.contains("Logn page"));
+    }
+
+    #[test]
+    fn test_session_summary_lists_player_stats() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Sprint 12".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Revealed,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let stats = [
+            PlayerStats {
+                player_id: Uuid::new_v4(),
+                player_name: "Alice".to_string(),
+                rounds_participated: 3,
+                matched_final_estimate: 2,
+                average_vote: Some(16.0 / 3.0),
+            },
+            PlayerStats {
+                player_id: Uuid::new_v4(),
+                player_name: "Bob".to_string(),
+                rounds_participated: 1,
+                matched_final_estimate: 0,
+                average_vote: None,
+            },
+        ];
+
+        let rendered = format!("{:?}", session_summary_content("game", &game, &stats));
+        let empty = format!("{:?}", session_summary_content("game", &game, &[]));
+
+        assert!(rendered.contains("Session summary"));
+        assert!(rendered.contains("matched the final estimate 2 times"));
+        assert!(rendered.contains("average 5.3"));
+        assert!(rendered.contains("no numeric votes"));
+        assert!(!rendered.contains("NaN"));
+        assert!(empty.contains("No rounds have been revealed yet"));
+    }
+
+    #[test]
+    fn test_page_not_found_content_names_resource() {
+        let rendered = format!("{:?}", page_not_found_content("Game not found"));
+
+        assert!(rendered.contains("404 - Not Found"));
+        assert!(rendered.contains("Game not found"));
+    }
+
+    #[test]
+    fn test_error_page_links_back() {
+        let home = format!(
+            "{:?}",
+            error_page(
+                "400 - Bad Request",
+                "That game link doesn't look right",
+                "/"
+            )
+        );
+        assert!(home.contains("400 - Bad Request"));
+        assert!(home.contains("That game link doesn't look right"));
+        assert!(home.contains("Back to Home"));
+
+        let game = format!(
+            "{:?}",
+            error_page("404 - Not Found", "Round not found", "/game/abc")
+        );
+        assert!(game.contains("/game/abc"));
+        assert!(game.contains("Go Back"));
+    }
+
+    #[test]
+    fn test_forbidden_content_shows_reason() {
+        let rendered = format!("{:?}", forbidden_content("Only the game owner can do that"));
+
+        assert!(rendered.contains("403 - Forbidden"));
+        assert!(rendered.contains("Only the game owner can do that"));
+    }
+
+    #[test]
+    fn test_deck_preview_shows_every_card() {
+        let rendered = format!("{:?}", deck_preview_content("tshirt"));
+
+        for value in VotingSystem::TShirtSizes.get_voting_options() {
+            assert!(rendered.contains(&format!("{value:?}")));
+        }
+        assert!(rendered.contains("T-Shirt Sizes deck:"));
+    }
+
+    #[test]
+    fn test_deck_preview_supports_custom_decks() {
+        let rendered = format!("{:?}", deck_preview_content("custom:tiny,huge"));
+
+        assert!(rendered.contains("tiny"));
+        assert!(rendered.contains("huge"));
+    }
+
+    #[test]
+    fn test_home_content_preselects_default_voting_system() {
+        let deck = VotingSystem::Custom(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        let rendered = format!("{:?}", home_content_with_default("myteam", &deck, None));
+
+        assert!(rendered.contains("myteam"));
+        assert!(rendered.contains("Custom deck:"));
+    }
+
+    #[test]
+    fn test_invite_link_prefills_the_join_form() {
+        let rendered = format!(
+            "{:?}",
+            home_content_with_default("fibonacci", &VotingSystem::Fibonacci, Some("abc-123"))
+        );
+        assert!(rendered.contains("abc-123"));
+
+        let rendered = format!("{:?}", join_prompt_content("abc-123", "Sprint 12"));
+        assert!(rendered.contains("Join Sprint 12"));
+        assert!(rendered.contains("abc-123"));
+        assert!(rendered.contains("player-name"));
+        assert!(!rendered.contains("team-role"));
+    }
+
+    #[test]
+    fn test_toasts_show_their_message_under_their_kind() {
+        let success = format!("{:?}", toast_content(ToastKind::Success, "Votes revealed"));
+        assert!(success.contains("Votes revealed"));
+        assert!(success.contains("toast-success"));
+
+        let error = format!(
+            "{:?}",
+            toast_content(ToastKind::Error, "Only the game owner can do that")
+        );
+        assert!(error.contains("Only the game owner can do that"));
+        assert!(error.contains("toast-error"));
+        assert!(!error.contains("toast-success"));
+    }
+
+    #[test]
+    fn test_shutdown_notice_says_how_long_until_the_server_stops() {
+        let notice = format!("{:?}", shutdown_notice(30));
+        assert!(notice.contains("shutdown-notice"));
+        assert!(notice.contains("within 30 seconds"));
+    }
+
+    #[test]
+    fn test_every_page_has_a_toast_area_and_owner_actions_target_it() {
+        let page = format!("{:?}", page_layout(&home_content()));
+        assert!(page.contains("toast-area"));
+
+        let actions = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Voting, &VotingSystem::Fibonacci, 1)
+        );
+        assert_eq!(actions.matches("toast-area").count(), 2);
+
+        // Reveal, reset, revote, archive, complete and one finalize form per card
+        let revealed = format!(
+            "{:?}",
+            game_actions_content("game", &GameState::Revealed, &VotingSystem::Fibonacci, 1)
+        );
+        assert_eq!(
+            revealed.matches("toast-area").count(),
+            5 + VotingSystem::Fibonacci.get_voting_options().len()
+        );
+    }
+
+    #[test]
+    fn test_locked_vote_buttons_disable_every_card() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "tshirt".to_string(),
+            state: GameState::Voting,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings {
+                allow_vote_change: false,
+                ..Default::default()
+            },
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+
+        let rendered = format!("{:?}", locked_vote_buttons(&game, "M"));
+
+        assert!(rendered.contains("You already voted this round"));
+        assert!(!rendered.contains("/vote"));
+    }
+
+    #[test]
+    fn test_game_page_locks_the_cards_of_a_player_who_voted_in_a_locked_round() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "tshirt".to_string(),
+            state: GameState::Voting,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings {
+                allow_vote_change: false,
+                ..Default::default()
+            },
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let voter = Uuid::new_v4();
+        let votes = [Vote {
+            player_id: voter,
+            player_name: "Alice".to_string(),
+            value: "M".to_string(),
+            cast_at: chrono::Utc::now(),
+            confidence: None,
+        }];
+        let page = |viewer| {
+            format!(
+                "{:?}",
+                game_content_with_data("game", &game, &[], &votes, &[], Some(viewer), "/")
+            )
+        };
+
+        assert!(page(voter).contains("You already voted this round"));
+        assert!(!page(voter).contains("/api/games/game/vote"));
+        assert!(page(Uuid::new_v4()).contains("/api/games/game/vote"));
+    }
+
+    #[test]
+    fn test_vote_buttons_content_posts_votes_only_while_voting() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Voting,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let game_id = game.id.to_string();
+
+        let active = format!("{:?}", vote_buttons_content(&game_id, &game, true, None));
+        let inactive = format!("{:?}", vote_buttons_content(&game_id, &game, false, None));
+
+        assert!(active.contains(&format!("/api/games/{game_id}/vote")));
+        assert!(inactive.contains("Voting not active"));
+        assert!(!inactive.contains("/vote"));
+    }
+
+    #[test]
+    fn test_vote_buttons_render_custom_deck() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "custom:XS,S,M".to_string(),
+            state: GameState::Voting,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+
+        let rendered = format!("{:?}", vote_buttons("game", &game));
+
+        for card in ["\"XS\"", "\"S\"", "\"M\""] {
+            assert!(rendered.contains(card), "missing {card}");
+        }
+        assert!(!rendered.contains("\"13\""));
+        assert!(!rendered.contains("\"?\""));
+    }
+
+    #[test]
+    fn test_voting_timer_counts_down_in_minutes_and_seconds() {
+        let now = chrono::Utc::now();
+
+        assert_eq!(
+            remaining_secs(now + chrono::Duration::milliseconds(1500), now),
+            2
+        );
+        assert_eq!(remaining_secs(now - chrono::Duration::seconds(5), now), 0);
+        assert!(format!("{:?}", voting_timer_content(Some(75))).contains("1:15 left"));
+        assert!(!format!("{:?}", voting_timer_content(None)).contains("left"));
+    }
+
+    #[test]
+    fn test_only_the_owner_sees_round_controls() {
+        let owner_id = Uuid::new_v4();
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            owner_id,
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Waiting,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: None,
+        };
+        let page = |viewer| {
+            format!(
+                "{:?}",
+                game_content_with_data("game", &game, &[], &[], &[], viewer, "/")
+            )
+        };
+
+        assert!(page(Some(owner_id)).contains("/api/games/game/start-voting"));
+        assert!(!page(Some(Uuid::new_v4())).contains("/api/games/game/start-voting"));
+        assert!(!page(None).contains("/api/games/game/start-voting"));
+    }
+
+    #[test]
+    fn test_completed_game_shows_totals_instead_of_controls() {
+        let game = Game {
+            id: Uuid::new_v4(),
+            name: "Sprint 12".to_string(),
+            owner_id: Uuid::new_v4(),
+            voting_system: "fibonacci".to_string(),
+            state: GameState::Completed,
+            current_story: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            settings: planning_poker_models::GameSettings::default(),
+            round_id: Uuid::new_v4(),
+            voting_started_at: None,
+            voting_deadline: None,
+            completed_at: Some(chrono::Utc::now()),
+        };
+        let round = |story: &str, final_estimate: Option<&str>| RoundSummary {
+            round_id: Uuid::new_v4(),
+            story: Some(story.to_string()),
+            final_estimate: final_estimate.map(ToString::to_string),
+            distribution: vec![("3".to_string(), 1), ("5".to_string(), 2)],
+            first_vote_at: chrono::Utc::now(),
+            last_vote_at: chrono::Utc::now(),
+        };
+        let rounds = [
+            round("Login", Some("5")),
+            round("Login", Some("3")),
+            round("Search", None),
+        ];
+
+        let rendered = format!("{:?}", completed_game_content("game", &game, &[], &rounds));
+
+        assert!(rendered.contains("Rounds played: 3"));
+        assert!(rendered.contains("Stories estimated: 1"));
+        assert!(rendered.contains("Votes cast: 9"));
+        assert!(rendered.contains("/api/games/game/export?format=csv"));
+        assert!(!rendered.contains("/api/games/game/start-voting"));
+        assert!(!rendered.contains("/api/games/game/vote"));
+    }
+
+    #[test]
+    fn test_round_history_links_to_the_next_page() {
+        let round = RoundSummary {
+            round_id: Uuid::new_v4(),
+            story: Some("Login".to_string()),
+            final_estimate: Some("5".to_string()),
+            distribution: vec![("3".to_string(), 1), ("5".to_string(), 2)],
+            first_vote_at: chrono::Utc::now(),
+            last_vote_at: chrono::Utc::now(),
+        };
+
+        let first_page = format!(
+            "{:?}",
+            round_history_content("game", &[round.clone()], 0, Some(50))
+        );
+        assert!(first_page.contains("Login"));
+        assert!(first_page.contains("3 ×1, 5 ×2"));
+        assert!(first_page.contains("Hide history"));
+        assert!(first_page.contains("/api/games/game/rounds?offset=50"));
+        assert!(first_page.contains("/api/games/game/export?format=csv"));
+        assert!(first_page.contains("/api/games/game/export?format=md"));
+
+        let empty = format!("{:?}", round_history_content("game", &[], 0, None));
+        assert!(!empty.contains("Export CSV"));
+
+        let last_page = format!("{:?}", round_history_content("game", &[round], 50, None));
+        assert!(!last_page.contains("Hide history"));
+        assert!(!last_page.contains("Load more"));
+    }
+
+    #[test]
+    fn test_story_backlog_prefixes_ticket_keys() {
+        let story = |key: Option<&str>, title: &str| Story {
+            id: Uuid::new_v4(),
+            game_id: Uuid::new_v4(),
+            title: title.to_string(),
+            key: key.map(ToString::to_string),
+            description: None,
+            acceptance_criteria: None,
+            final_estimate: None,
+            completed_at: None,
+            order: 0,
+            created_at: chrono::Utc::now(),
+        };
+
+        let rendered = format!(
+            "{:?}",
+            story_backlog_content(&[story(Some("KEY-123"), "Login"), story(None, "Search")])
+        );
+        assert!(rendered.contains("KEY-123"));
+        assert!(rendered.contains("Login"));
+        assert!(rendered.contains("Search"));
+
+        let empty = format!("{:?}", story_backlog_content(&[]));
+        assert!(empty.contains("No stories in the backlog yet"));
+    }
+
+    #[test]
+    fn test_completed_stories_show_their_final_estimates() {
+        let story = Story {
+            id: Uuid::new_v4(),
+            game_id: Uuid::new_v4(),
+            title: "Login".to_string(),
+            key: Some("KEY-1".to_string()),
+            description: None,
+            acceptance_criteria: Some("Users can sign in".to_string()),
+            final_estimate: Some("13".to_string()),
+            completed_at: Some(chrono::Utc::now()),
+            order: 0,
+            created_at: chrono::Utc::now(),
+        };
+
+        let rendered = format!("{:?}", completed_stories_content(&[story]));
+        assert!(rendered.contains("KEY-1 Login"));
+        assert!(rendered.contains("13"));
+
+        let empty = format!("{:?}", completed_stories_content(&[]));
+        assert!(empty.contains("No stories completed yet"));
+    }
+}
@@ -16,6 +16,7 @@ resource "kubernetes_secret" "planning_poker_secrets" {
 
   data = {
     database-url = var.database_url != null ? var.database_url : env("DATABASE_URL")
+    auth-secret  = var.auth_secret != null ? var.auth_secret : env("PLANNING_POKER_AUTH_SECRET")
   }
 
   type = "Opaque"
@@ -111,6 +112,16 @@ resource "kubernetes_deployment" "planning_poker" {
             }
           }
 
+          env {
+            name = "PLANNING_POKER_AUTH_SECRET"
+            value_from {
+              secret_key_ref {
+                name = kubernetes_secret.planning_poker_secrets.metadata[0].name
+                key  = "auth-secret"
+              }
+            }
+          }
+
           # Add any additional environment variables
           dynamic "env" {
             for_each = var.k8s_environment_variables
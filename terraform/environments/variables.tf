@@ -56,6 +56,13 @@ variable "database_url" {
   default     = null
 }
 
+variable "auth_secret" {
+  description = "Key auth tokens are signed with - uses PLANNING_POKER_AUTH_SECRET env var if not provided"
+  type        = string
+  sensitive   = true
+  default     = null
+}
+
 variable "enable_debug_logging" {
   description = "Enable debug logging for the application"
   type        = bool